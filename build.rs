@@ -0,0 +1,26 @@
+use std::env;
+use std::process::Command;
+
+// Records the git commit being built for `/health`, unless given with
+// POKEDEX_COMMIT, such as when building outside of a checkout.
+fn main() {
+    println!("cargo:rerun-if-env-changed=POKEDEX_COMMIT");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+
+    if env::var("POKEDEX_COMMIT").is_ok() {
+        return;
+    }
+
+    if let Ok(output) = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+    {
+        if output.status.success() {
+            println!(
+                "cargo:rustc-env=POKEDEX_COMMIT={}",
+                String::from_utf8_lossy(&output.stdout).trim()
+            );
+        }
+    }
+}
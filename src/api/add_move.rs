@@ -0,0 +1,46 @@
+use crate::api::fetch_moves;
+use crate::api::Status;
+use crate::domain::add_move;
+use crate::repositories::moves::Repository as MovesRepository;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    name: String,
+    #[serde(rename = "type")]
+    move_type: String,
+    power: Option<u8>,
+    accuracy: Option<u8>,
+    pp: u8,
+    level: u8,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    moves_repo: Arc<dyn MovesRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => add_move::Request {
+            number,
+            name: req.name,
+            move_type: req.move_type,
+            power: req.power,
+            accuracy: req.accuracy,
+            pp: req.pp,
+            level: req.level,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.add_move(moves_repo, req) {
+        Ok(learned) => rouille::Response::json(&fetch_moves::Response::from(learned)),
+        Err(add_move::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(add_move::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(add_move::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(add_move::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,168 @@
+use crate::api::fetch_pokemon::{Ability, Evolution};
+use crate::api::{fetch_all_pokemons, Status};
+use crate::domain::{clear_pokemons, ingest_pokemons};
+use crate::repositories::pokemon::Repository;
+use crate::service::PokedexService;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+/// A Pokemon as the dump gives it back.
+#[derive(Deserialize)]
+struct RestoreRequest {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    #[serde(default)]
+    evolutions: Option<Vec<Evolution>>,
+    #[serde(default)]
+    abilities: Option<Vec<Ability>>,
+    #[serde(default)]
+    height: Option<f64>,
+    #[serde(default)]
+    weight: Option<f64>,
+    #[serde(default)]
+    sprite: Option<String>,
+    #[serde(default)]
+    class: Option<String>,
+}
+
+#[derive(Serialize)]
+struct RestoreResponse {
+    restored: usize,
+    skipped: usize,
+}
+
+#[derive(Serialize)]
+struct ResetResponse {
+    deleted: usize,
+}
+
+#[derive(Deserialize)]
+struct ReadOnlyRequest {
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+struct ReadOnlyResponse {
+    enabled: bool,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    read_only: Arc<AtomicBool>,
+    admin_token: Option<&str>,
+    req: &rouille::Request,
+) -> rouille::Response {
+    match authorize(admin_token, req) {
+        Ok(()) => {}
+        Err(res) => return res,
+    }
+
+    router!(req,
+        (GET) (/dump) => {
+            fetch_all_pokemons::serve(service.clone())
+        },
+        (POST) (/restore) => {
+            // Restores bypass the service: they are not Pokemons being caught.
+            restore(service.repo(), req)
+        },
+        (POST) (/reset) => {
+            reset(service.clone(), req)
+        },
+        (GET) (/read-only) => {
+            rouille::Response::json(&ReadOnlyResponse {
+                enabled: read_only.load(Ordering::SeqCst),
+            })
+        },
+        (PUT) (/read-only) => {
+            set_read_only(read_only.clone(), req)
+        },
+        _ => {
+            rouille::Response::from(Status::NotFound)
+        }
+    )
+}
+
+/// Lets the request through if it bears the admin token. Without a token
+/// configured, the admin routes don't exist.
+pub fn authorize(
+    admin_token: Option<&str>,
+    req: &rouille::Request,
+) -> Result<(), rouille::Response> {
+    let admin_token = match admin_token {
+        Some(admin_token) => admin_token,
+        None => return Err(rouille::Response::from(Status::NotFound)),
+    };
+
+    // Compared in constant time, so that the time taken doesn't tell how
+    // much of the token was right.
+    let expected = format!("Bearer {}", admin_token);
+    match req.header("Authorization") {
+        Some(header) if bool::from(header.as_bytes().ct_eq(expected.as_bytes())) => Ok(()),
+        _ => Err(rouille::Response::from(Status::Unauthorized)),
+    }
+}
+
+fn restore(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let pokemons = match rouille::input::json_input::<Vec<RestoreRequest>>(req) {
+        Ok(pokemons) => pokemons,
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    // The taken numbers and the invalid records are skipped, the others
+    // being restored with all their fields.
+    let reqs = pokemons
+        .into_iter()
+        .map(|pokemon| ingest_pokemons::Request {
+            number: pokemon.number,
+            name: pokemon.name,
+            types: pokemon.types,
+            details: ingest_pokemons::Details {
+                evolutions: pokemon
+                    .evolutions
+                    .map(|evolutions| evolutions.into_iter().map(From::from).collect()),
+                abilities: pokemon
+                    .abilities
+                    .map(|abilities| abilities.into_iter().map(From::from).collect()),
+                height: pokemon.height,
+                weight: pokemon.weight,
+                sprite: pokemon.sprite,
+                class: pokemon.class,
+            },
+        })
+        .collect::<Vec<ingest_pokemons::Request>>();
+
+    match ingest_pokemons::execute(repo, ingest_pokemons::Policy::Skip, reqs) {
+        Ok(res) => rouille::Response::json(&RestoreResponse {
+            restored: res.inserted,
+            skipped: res.conflicts + res.invalid,
+        }),
+        _ => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+/// Deletes every Pokemon, with `confirm=true` only.
+fn reset(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
+    let req = clear_pokemons::Request {
+        confirmed: req.get_param("confirm").as_deref() == Some("true"),
+    };
+    match service.clear_pokemons(req) {
+        Ok(res) => rouille::Response::json(&ResetResponse {
+            deleted: res.numbers.len(),
+        }),
+        Err(clear_pokemons::Error::NotConfirmed) => rouille::Response::from(Status::BadRequest),
+        Err(clear_pokemons::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+fn set_read_only(read_only: Arc<AtomicBool>, req: &rouille::Request) -> rouille::Response {
+    match rouille::input::json_input::<ReadOnlyRequest>(req) {
+        Ok(ReadOnlyRequest { enabled }) => {
+            read_only.store(enabled, Ordering::SeqCst);
+            rouille::Response::json(&ReadOnlyResponse { enabled })
+        }
+        _ => rouille::Response::from(Status::BadRequest),
+    }
+}
@@ -0,0 +1,32 @@
+use crate::api::Status;
+use crate::domain::check_name;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    name: String,
+    valid: bool,
+    available: bool,
+    suggestions: Vec<String>,
+}
+
+pub fn serve(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
+    let name = match req.get_param("name") {
+        Some(name) => name,
+        None => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let req = check_name::Request { name: name.clone() };
+    match service.check_name(req) {
+        Ok(res) => rouille::Response::json(&Response {
+            name,
+            valid: res.valid,
+            available: res.available,
+            suggestions: res.suggestions,
+        }),
+        Err(check_name::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
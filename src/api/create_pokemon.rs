@@ -1,25 +1,26 @@
+use crate::api::validate_pokemon;
 use crate::api::Status;
 use crate::domain::create_pokemon;
-use crate::repositories::pokemon::Repository;
-use rouille;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-#[derive(Deserialize)]
-struct Request {
-    number: u16,
-    name: String,
-    types: Vec<String>,
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
 }
 
-#[derive(Serialize)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+pub fn serve(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
     let req = match rouille::input::json_input::<Request>(req) {
         Ok(req) => create_pokemon::Request {
             number: req.number,
@@ -28,7 +29,7 @@ pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Resp
         },
         _ => return rouille::Response::from(Status::BadRequest),
     };
-    match create_pokemon::execute(repo, req) {
+    match service.create_pokemon(req) {
         Ok(create_pokemon::Response {
             number,
             name,
@@ -38,7 +39,7 @@ pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Resp
             name,
             types,
         }),
-        Err(create_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(create_pokemon::Error::BadRequest(errors)) => validate_pokemon::bad_request(errors),
         Err(create_pokemon::Error::Conflict) => rouille::Response::from(Status::Conflict),
         Err(create_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
     }
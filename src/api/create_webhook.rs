@@ -0,0 +1,33 @@
+use crate::api::Status;
+use crate::domain::create_webhook;
+use crate::repositories::webhook::Repository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    url: String,
+    secret: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    id: u32,
+    url: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_webhook::Request {
+            url: req.url,
+            secret: req.secret,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match create_webhook::execute(repo, req) {
+        Ok(create_webhook::Response { id, url }) => rouille::Response::json(&Response { id, url }),
+        Err(create_webhook::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(create_webhook::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
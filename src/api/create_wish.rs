@@ -0,0 +1,40 @@
+use crate::api::Status;
+use crate::domain::create_wish;
+use crate::repositories::wishlist::Repository;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    number: u16,
+    name: String,
+    note: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    number: u16,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_wish::Request {
+            number: req.number,
+            name: req.name,
+            note: req.note,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match create_wish::execute(repo, req) {
+        Ok(create_wish::Response { number, name, note }) => {
+            rouille::Response::json(&Response { number, name, note })
+        }
+        Err(create_wish::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(create_wish::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(create_wish::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
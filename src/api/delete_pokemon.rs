@@ -1,12 +1,11 @@
 use crate::api::Status;
 use crate::domain::delete_pokemon;
-use crate::repositories::pokemon::Repository;
-use rouille;
+use crate::service::PokedexService;
 use std::sync::Arc;
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+pub fn serve(service: Arc<PokedexService>, number: u16) -> rouille::Response {
     let req = delete_pokemon::Request { number };
-    match delete_pokemon::execute(repo, req) {
+    match service.delete_pokemon(req) {
         Ok(()) => rouille::Response::from(Status::Ok),
         Err(delete_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(delete_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
@@ -0,0 +1,42 @@
+use crate::api::pokemon::Pokemon;
+use crate::api::Status;
+use crate::domain::delete_pokemons_by_type;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response<'a> {
+    deleted: usize,
+    pokemons: Vec<Pokemon<'a>>,
+}
+
+/// Requires `type` and `confirm=true`: without them nothing is deleted.
+pub fn serve(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
+    let pokemon_type = match req.get_param("type") {
+        Some(pokemon_type) => pokemon_type,
+        None => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let req = delete_pokemons_by_type::Request {
+        pokemon_type,
+        confirmed: req.get_param("confirm").as_deref() == Some("true"),
+    };
+    match service.delete_pokemons_by_type(req) {
+        Ok(res) => rouille::Response::json(&Response {
+            deleted: res.len(),
+            pokemons: res
+                .iter()
+                .map(|p| Pokemon::new(p.number, &p.name, &p.types))
+                .collect::<Vec<Pokemon>>(),
+        }),
+        Err(delete_pokemons_by_type::Error::BadRequest)
+        | Err(delete_pokemons_by_type::Error::NotConfirmed) => {
+            rouille::Response::from(Status::BadRequest)
+        }
+        Err(delete_pokemons_by_type::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
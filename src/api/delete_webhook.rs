@@ -0,0 +1,14 @@
+use crate::api::Status;
+use crate::domain::delete_webhook;
+use crate::repositories::webhook::Repository;
+use std::sync::Arc;
+
+pub fn serve(repo: Arc<dyn Repository>, id: u32) -> rouille::Response {
+    let req = delete_webhook::Request { id };
+    match delete_webhook::execute(repo, req) {
+        Ok(()) => rouille::Response::from(Status::Ok),
+        Err(delete_webhook::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(delete_webhook::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(delete_webhook::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,14 @@
+use crate::api::Status;
+use crate::domain::delete_wish;
+use crate::repositories::wishlist::Repository;
+use std::sync::Arc;
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    let req = delete_wish::Request { number };
+    match delete_wish::execute(repo, req) {
+        Ok(()) => rouille::Response::from(Status::Ok),
+        Err(delete_wish::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(delete_wish::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(delete_wish::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,17 @@
+use crate::api::Status;
+use crate::export::{self, Error};
+use crate::repositories::pokemon::Repository;
+use std::sync::Arc;
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let format = req.get_param("format").unwrap_or_default();
+    match export::export(repo, &format) {
+        Ok((format, bytes)) => rouille::Response::from_data(format.content_type(), bytes)
+            .with_additional_header(
+                "Content-Disposition",
+                format!("attachment; filename=\"pokedex.{}\"", format.extension()),
+            ),
+        Err(Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
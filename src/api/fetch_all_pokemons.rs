@@ -1,30 +1,273 @@
+use crate::api::fetch_pokemon::{Ability, Evolution};
+use crate::api::pokemon::Pokemon;
 use crate::api::Status;
-use crate::domain::fetch_all_pokemons;
-use crate::repositories::pokemon::Repository;
-use rouille;
-use serde::Serialize;
-use std::sync::Arc;
-
-#[derive(Serialize)]
-struct Response {
+use crate::clock;
+use crate::domain::entities::PokemonClass;
+use crate::domain::{fetch_all_pokemons, fetch_collection_version};
+use crate::repositories::change::Repository as ChangeRepository;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Owned counterpart of `Pokemon`, for clients reading the list back.
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[schemars(rename = "Pokemon")]
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evolutions: Option<Vec<Evolution>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abilities: Option<Vec<Ability>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprite: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+}
+
+/// A stored record left out of the listing because it isn't a valid
+/// Pokemon.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Skipped {
     number: u16,
-    name: String,
-    types: Vec<String>,
+    reason: String,
+}
+
+/// Last serialized collection along with the version it was read at.
+/// `epoch`, the time the server started at, goes in the ETags along with the
+/// version: the in-memory change log counts the versions from zero again
+/// after a restart.
+pub struct Cache {
+    epoch: u64,
+    cached: Mutex<Option<Cached>>,
+}
+
+struct Cached {
+    version: u64,
+    body: Vec<u8>,
+    skipped: usize,
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self {
+            epoch: clock::now(),
+            cached: Mutex::new(None),
+        }
+    }
 }
 
-pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
-    match fetch_all_pokemons::execute(repo) {
+pub fn serve(service: Arc<PokedexService>) -> rouille::Response {
+    let req = fetch_all_pokemons::Request {
+        sort_by: fetch_all_pokemons::SortBy::NumberAscending,
+        offset: 0,
+        limit: None,
+        class: None,
+    };
+    serve_page(service, req)
+}
+
+fn serve_page(service: Arc<PokedexService>, req: fetch_all_pokemons::Request) -> rouille::Response {
+    match service.fetch_all_pokemons(req) {
+        Ok(res) => with_skipped(rouille::Response::json(&borrow(&res)), res.warnings.len()),
+        Err(fetch_all_pokemons::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_all_pokemons::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
+
+/// Only the whole collection in the default order is cached, the pages
+/// asked for with `offset` and `limit`, sorted otherwise with `sort` or
+/// filtered with `class` are read from the repository every time. Each of
+/// them has its own ETag, made of the collection version and of the query.
+pub fn serve_cached(
+    service: Arc<PokedexService>,
+    change_repo: Arc<dyn ChangeRepository>,
+    cache: Arc<Cache>,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let page = match page(req) {
+        Ok(page) => page,
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let version = match fetch_collection_version::execute(change_repo) {
+        Ok(res) => res.version,
+        Err(fetch_collection_version::Error::Unknown) => return serve_page(service, page),
+    };
+    let etag = match variant(&page) {
+        Ok(Some(variant)) => format!("\"{}-{}-{}\"", cache.epoch, version, variant),
+        Ok(None) => format!("\"{}-{}\"", cache.epoch, version),
+        Err(()) => return rouille::Response::from(Status::BadRequest),
+    };
+
+    if req
+        .header("If-None-Match")
+        .is_some_and(|tags| matches_any(tags, &etag))
+    {
+        return rouille::Response::from(Status::NotModified).with_additional_header("ETag", etag);
+    }
+
+    if page.offset != 0
+        || page.limit.is_some()
+        || page.class.is_some()
+        || !matches!(page.sort_by, fetch_all_pokemons::SortBy::NumberAscending)
+    {
+        let res = serve_page(service, page);
+        return match res.status_code {
+            200 => res.with_additional_header("ETag", etag),
+            _ => res,
+        };
+    }
+
+    let mut lock = match cache.cached.lock() {
+        Ok(lock) => lock,
+        _ => return rouille::Response::from(Status::InternalServerError),
+    };
+
+    let (body, skipped) = match lock.as_ref() {
+        Some(cached) if cached.version == version => (cached.body.clone(), cached.skipped),
+        _ => {
+            let (body, skipped) = match service
+                .fetch_all_pokemons(page)
+                .map(|res| (serde_json::to_vec(&borrow(&res)), res.warnings.len()))
+            {
+                Ok((Ok(body), skipped)) => (body, skipped),
+                _ => return rouille::Response::from(Status::InternalServerError),
+            };
+            *lock = Some(Cached {
+                version,
+                body: body.clone(),
+                skipped,
+            });
+            (body, skipped)
+        }
+    };
+
+    with_skipped(
+        rouille::Response::from_data("application/json", body),
+        skipped,
+    )
+    .with_additional_header("ETag", etag)
+}
+
+/// `sort` is one of `number`, `-number`, `name` and `type`, the number
+/// ascending being the default. `class` keeps the normal, legendary or
+/// mythical Pokemons only.
+fn page(req: &rouille::Request) -> Result<fetch_all_pokemons::Request, ()> {
+    let sort_by = match req.get_param("sort").as_deref() {
+        None | Some("number") => fetch_all_pokemons::SortBy::NumberAscending,
+        Some("-number") => fetch_all_pokemons::SortBy::NumberDescending,
+        Some("name") => fetch_all_pokemons::SortBy::Name,
+        Some("type") => fetch_all_pokemons::SortBy::Type,
+        _ => return Err(()),
+    };
+
+    match (
+        req.get_param("offset")
+            .map(|offset| offset.parse::<u32>())
+            .transpose(),
+        req.get_param("limit")
+            .map(|limit| limit.parse::<u32>())
+            .transpose(),
+    ) {
+        (Ok(offset), Ok(limit)) => Ok(fetch_all_pokemons::Request {
+            sort_by,
+            offset: offset.unwrap_or(0),
+            limit,
+            class: req.get_param("class"),
+        }),
+        _ => Err(()),
+    }
+}
+
+/// The query written the same way whatever the order of its parameters or
+/// the case of the class, none being the whole collection in the default
+/// order.
+fn variant(page: &fetch_all_pokemons::Request) -> Result<Option<String>, ()> {
+    let sort = match page.sort_by {
+        fetch_all_pokemons::SortBy::NumberAscending => "number",
+        fetch_all_pokemons::SortBy::NumberDescending => "-number",
+        fetch_all_pokemons::SortBy::Name => "name",
+        fetch_all_pokemons::SortBy::Type => "type",
+    };
+    let class = match page.class.clone().map(PokemonClass::try_from).transpose() {
+        Ok(class) => class.map_or("", |class| class.as_str()),
+        Err(()) => return Err(()),
+    };
+    let limit = page
+        .limit
+        .map(|limit| limit.to_string())
+        .unwrap_or_default();
+
+    match (sort, page.offset, limit.as_str(), class) {
+        ("number", 0, "", "") => Ok(None),
+        _ => Ok(Some(format!(
+            "sort={}&offset={}&limit={}&class={}",
+            sort, page.offset, limit, class
+        ))),
+    }
+}
+
+/// Whether one of the entity tags of an `If-None-Match` header is `*` or
+/// the current one, the weak ones comparing the same.
+fn matches_any(tags: &str, etag: &str) -> bool {
+    tags.split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
+
+fn borrow(res: &fetch_all_pokemons::Response) -> Vec<Pokemon<'_>> {
+    res.pokemons
+        .iter()
+        .map(|p| {
+            Pokemon::new(p.number, &p.name, &p.types)
+                .with_evolutions(&p.evolutions)
+                .with_abilities(&p.abilities)
+                .with_measurements(p.height, p.weight)
+                .with_sprite(p.sprite.as_deref())
+                .with_class(&p.class)
+        })
+        .collect::<Vec<Pokemon>>()
+}
+
+/// The records left out of the listing, each with the reason.
+pub fn serve_skipped(service: Arc<PokedexService>) -> rouille::Response {
+    let req = fetch_all_pokemons::Request {
+        sort_by: fetch_all_pokemons::SortBy::NumberAscending,
+        offset: 0,
+        limit: None,
+        class: None,
+    };
+    match service.fetch_all_pokemons(req) {
         Ok(res) => rouille::Response::json(
-            &res.into_iter()
-                .map(|p| Response {
-                    number: p.number,
-                    name: p.name,
-                    types: p.types,
+            &res.warnings
+                .into_iter()
+                .map(|w| Skipped {
+                    number: w.number,
+                    reason: w.reason,
                 })
-                .collect::<Vec<Response>>(),
+                .collect::<Vec<Skipped>>(),
         ),
+        Err(fetch_all_pokemons::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(fetch_all_pokemons::Error::Unknown) => {
             rouille::Response::from(Status::InternalServerError)
         }
     }
 }
+
+// The body stays a plain list of Pokemons: the number of records left out
+// of it is told by a header, and `/pokemons/skipped` lists them.
+fn with_skipped(res: rouille::Response, skipped: usize) -> rouille::Response {
+    match skipped {
+        0 => res,
+        skipped => res.with_additional_header("X-Pokedex-Skipped", skipped.to_string()),
+    }
+}
@@ -0,0 +1,29 @@
+use crate::api::Status;
+use crate::domain::fetch_all_webhooks;
+use crate::repositories::webhook::Repository;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Webhook")]
+pub(super) struct Response {
+    id: u32,
+    url: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_all_webhooks::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|w| Response {
+                    id: w.id,
+                    url: w.url,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_all_webhooks::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::api::fetch_pokemon::{Ability, Evolution};
+use crate::api::Status;
+use crate::domain::fetch_changes;
+use crate::repositories::change::Repository;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+const DEFAULT_LIMIT: u32 = 100;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    changes: Vec<Change>,
+    cursor: Option<String>,
+}
+
+/// The Pokemon as the change left it, its fields being left out once it is
+/// deleted.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Change {
+    cursor: String,
+    kind: String,
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    types: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    evolutions: Option<Vec<Evolution>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    abilities: Option<Vec<Ability>>,
+    /// In meters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<f64>,
+    /// In kilograms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sprite: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<String>,
+    timestamp: u64,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let limit = match req.get_param("limit").map(|limit| limit.parse::<u32>()) {
+        Some(Ok(limit)) => limit,
+        Some(Err(_)) => return rouille::Response::from(Status::BadRequest),
+        None => DEFAULT_LIMIT,
+    };
+
+    let req = fetch_changes::Request {
+        since: req.get_param("since"),
+        limit,
+    };
+    match fetch_changes::execute(repo, req) {
+        Ok(fetch_changes::Response { changes, cursor }) => rouille::Response::json(&Response {
+            changes: changes
+                .into_iter()
+                .map(Change::from)
+                .collect::<Vec<Change>>(),
+            cursor,
+        }),
+        Err(fetch_changes::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_changes::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+impl From<fetch_changes::Change> for Change {
+    fn from(change: fetch_changes::Change) -> Self {
+        let mut res = Self {
+            cursor: change.cursor,
+            kind: change.kind,
+            number: change.number,
+            name: None,
+            types: None,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+            timestamp: change.timestamp,
+        };
+        if let Some(pokemon) = change.pokemon {
+            res.name = Some(pokemon.name);
+            res.types = Some(pokemon.types);
+            res.evolutions = Some(pokemon.evolutions.iter().map(Evolution::from).collect());
+            res.abilities = Some(pokemon.abilities.iter().map(Ability::from).collect());
+            res.height = pokemon.height;
+            res.weight = pokemon.weight;
+            res.sprite = pokemon.sprite;
+            res.class = Some(pokemon.class);
+        }
+        res
+    }
+}
@@ -0,0 +1,26 @@
+use crate::api::pokemon::Pokemon;
+use crate::api::Status;
+use crate::domain::fetch_index;
+use crate::service::PokedexService;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub fn serve(service: Arc<PokedexService>) -> rouille::Response {
+    match service.fetch_index() {
+        Ok(res) => rouille::Response::json(
+            &res.iter()
+                .map(|group| {
+                    (
+                        group.letter.as_str(),
+                        group
+                            .pokemons
+                            .iter()
+                            .map(|p| Pokemon::new(p.number, &p.name, &p.types))
+                            .collect::<Vec<Pokemon>>(),
+                    )
+                })
+                .collect::<BTreeMap<&str, Vec<Pokemon>>>(),
+        ),
+        Err(fetch_index::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
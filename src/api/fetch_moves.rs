@@ -0,0 +1,50 @@
+use crate::api::Status;
+use crate::domain::fetch_moves;
+use crate::repositories::moves::Repository as MovesRepository;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    name: String,
+    #[serde(rename = "type")]
+    move_type: String,
+    power: Option<u8>,
+    accuracy: Option<u8>,
+    pp: u8,
+    level: u8,
+}
+
+impl From<fetch_moves::LearnedMove> for Response {
+    fn from(learned: fetch_moves::LearnedMove) -> Self {
+        Self {
+            name: learned.name,
+            move_type: learned.move_type,
+            power: learned.power,
+            accuracy: learned.accuracy,
+            pp: learned.pp,
+            level: learned.level,
+        }
+    }
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    moves_repo: Arc<dyn MovesRepository>,
+    number: u16,
+) -> rouille::Response {
+    let req = fetch_moves::Request { number };
+    match service.fetch_moves(moves_repo, req) {
+        Ok(learnset) => rouille::Response::json(
+            &learnset
+                .into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_moves::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_moves::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_moves::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
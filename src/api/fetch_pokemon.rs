@@ -1,28 +1,114 @@
 use crate::api::Status;
 use crate::domain::fetch_pokemon;
-use crate::repositories::pokemon::Repository;
-use rouille;
-use serde::Serialize;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-#[derive(Serialize)]
-struct Response {
-    number: u16,
-    name: String,
-    types: Vec<String>,
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    /// Left out by the endpoints which don't read the evolutions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evolutions: Option<Vec<Evolution>>,
+    /// Left out by the endpoints which don't read the abilities.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abilities: Option<Vec<Ability>>,
+    /// In meters, left out until it is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    /// In kilograms, left out until it is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// The URL of the image, left out until it is set. The image itself is
+    /// served by `/pokemons/{number}/sprite`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprite: Option<String>,
+    /// Normal, Legendary or Mythical, left out by the endpoints which don't
+    /// read it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+/// `method` is one of `level`, `item`, `trade`, `friendship` and `other`.
+#[derive(Deserialize, Serialize, JsonSchema, Clone)]
+pub struct Evolution {
+    pub target: u16,
+    pub method: String,
+    #[serde(default)]
+    pub level: Option<u8>,
+}
+
+impl From<&fetch_pokemon::Evolution> for Evolution {
+    fn from(evolution: &fetch_pokemon::Evolution) -> Self {
+        Self {
+            target: evolution.target,
+            method: evolution.method.clone(),
+            level: evolution.level,
+        }
+    }
+}
+
+impl From<Evolution> for fetch_pokemon::Evolution {
+    fn from(evolution: Evolution) -> Self {
+        Self {
+            target: evolution.target,
+            method: evolution.method,
+            level: evolution.level,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Clone)]
+pub struct Ability {
+    pub name: String,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl From<&fetch_pokemon::Ability> for Ability {
+    fn from(ability: &fetch_pokemon::Ability) -> Self {
+        Self {
+            name: ability.name.clone(),
+            hidden: ability.hidden,
+        }
+    }
+}
+
+impl From<Ability> for fetch_pokemon::Ability {
+    fn from(ability: Ability) -> Self {
+        Self {
+            name: ability.name,
+            hidden: ability.hidden,
+        }
+    }
+}
+
+pub fn serve(service: Arc<PokedexService>, number: u16) -> rouille::Response {
     let req = fetch_pokemon::Request { number };
-    match fetch_pokemon::execute(repo, req) {
+    match service.fetch_pokemon(req) {
         Ok(fetch_pokemon::Response {
             number,
             name,
             types,
+            evolutions,
+            abilities,
+            height,
+            weight,
+            sprite,
+            class,
         }) => rouille::Response::json(&Response {
             number,
             name,
             types,
+            evolutions: Some(evolutions.iter().map(Evolution::from).collect()),
+            abilities: Some(abilities.iter().map(Ability::from).collect()),
+            height,
+            weight,
+            sprite,
+            class: Some(class),
         }),
         Err(fetch_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(fetch_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
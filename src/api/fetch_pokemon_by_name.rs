@@ -0,0 +1,26 @@
+use crate::api::pokemon::Pokemon;
+use crate::api::Status;
+use crate::domain::fetch_pokemon_by_name;
+use crate::service::PokedexService;
+use std::sync::Arc;
+
+pub fn serve(service: Arc<PokedexService>, name: String) -> rouille::Response {
+    let req = fetch_pokemon_by_name::Request { name };
+    match service.fetch_pokemon_by_name(req) {
+        Ok(res) => rouille::Response::json(
+            &Pokemon::new(res.number, &res.name, &res.types)
+                .with_evolutions(&res.evolutions)
+                .with_abilities(&res.abilities)
+                .with_measurements(res.height, res.weight)
+                .with_sprite(res.sprite.as_deref())
+                .with_class(&res.class),
+        ),
+        Err(fetch_pokemon_by_name::Error::BadRequest) => {
+            rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_pokemon_by_name::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_pokemon_by_name::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
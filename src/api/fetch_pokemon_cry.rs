@@ -0,0 +1,80 @@
+use crate::api::Status;
+use crate::domain::fetch_pokemon_cry;
+use crate::repositories::cry::Repository as CryRepository;
+use crate::service::PokedexService;
+use std::sync::Arc;
+
+/// Serves the cry whole, or the single byte range asked for by a `Range`
+/// header, so that browsers can seek through it.
+pub fn serve(
+    service: Arc<PokedexService>,
+    cry_repo: Arc<dyn CryRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let cry = match service.fetch_pokemon_cry(cry_repo, fetch_pokemon_cry::Request { number }) {
+        Ok(cry) => cry,
+        Err(fetch_pokemon_cry::Error::BadRequest) => {
+            return rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_pokemon_cry::Error::NotFound) => {
+            return rouille::Response::from(Status::NotFound)
+        }
+        Err(fetch_pokemon_cry::Error::Unknown) => {
+            return rouille::Response::from(Status::InternalServerError)
+        }
+    };
+
+    let size = cry.bytes.len();
+    let res = match range(req.header("Range"), size) {
+        Ok(Some((start, end))) => {
+            rouille::Response::from_data(cry.content_type, cry.bytes[start..=end].to_vec())
+                .with_status_code(206)
+                .with_additional_header(
+                    "Content-Range",
+                    format!("bytes {}-{}/{}", start, end, size),
+                )
+        }
+        Ok(None) => rouille::Response::from_data(cry.content_type, cry.bytes),
+        Err(()) => rouille::Response::from(Status::RangeNotSatisfiable)
+            .with_additional_header("Content-Range", format!("bytes */{}", size)),
+    };
+    res.with_additional_header("Accept-Ranges", "bytes")
+}
+
+/// The first and the last byte of the range, none when the whole content is
+/// to be served. Several ranges at once aren't supported, so the whole
+/// content is served instead, which the clients have to accept.
+fn range(header: Option<&str>, size: usize) -> Result<Option<(usize, usize)>, ()> {
+    let spec = match header.map(|h| h.trim().strip_prefix("bytes=")) {
+        Some(Some(spec)) if !spec.contains(',') => spec,
+        _ => return Ok(None),
+    };
+
+    let bounds = match spec.split_once('-') {
+        Some((start, end)) => (start.trim(), end.trim()),
+        None => return Ok(None),
+    };
+
+    let (start, end) = match bounds {
+        // The last bytes, as many as told.
+        ("", suffix) => match suffix.parse::<usize>() {
+            Ok(0) => return Err(()),
+            Ok(suffix) => (size.saturating_sub(suffix), size.saturating_sub(1)),
+            _ => return Ok(None),
+        },
+        (start, "") => match start.parse::<usize>() {
+            Ok(start) => (start, size.saturating_sub(1)),
+            _ => return Ok(None),
+        },
+        (start, end) => match (start.parse::<usize>(), end.parse::<usize>()) {
+            (Ok(start), Ok(end)) if start <= end => (start, end.min(size.saturating_sub(1))),
+            _ => return Ok(None),
+        },
+    };
+
+    match start < size {
+        true => Ok(Some((start, end))),
+        false => Err(()),
+    }
+}
@@ -0,0 +1,20 @@
+use crate::api::pokemon::Pokemon;
+use crate::api::Status;
+use crate::domain::fetch_pokemon_range;
+use crate::service::PokedexService;
+use std::sync::Arc;
+
+pub fn serve(service: Arc<PokedexService>, from: u16, to: u16) -> rouille::Response {
+    let req = fetch_pokemon_range::Request { from, to };
+    match service.fetch_pokemon_range(req) {
+        Ok(res) => rouille::Response::json(
+            &res.iter()
+                .map(|p| Pokemon::new(p.number, &p.name, &p.types))
+                .collect::<Vec<Pokemon>>(),
+        ),
+        Err(fetch_pokemon_range::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_pokemon_range::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
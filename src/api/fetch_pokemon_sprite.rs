@@ -0,0 +1,30 @@
+use crate::api::Status;
+use crate::domain::fetch_pokemon;
+use crate::service::PokedexService;
+use crate::sprite;
+use std::sync::Arc;
+
+/// Serves the image found at the sprite URL of the Pokemon, so that clients
+/// don't have to reach the host it lives on.
+pub fn serve(service: Arc<PokedexService>, number: u16) -> rouille::Response {
+    let req = fetch_pokemon::Request { number };
+    let url = match service.fetch_pokemon(req) {
+        Ok(fetch_pokemon::Response {
+            sprite: Some(url), ..
+        }) => url,
+        Ok(_) | Err(fetch_pokemon::Error::NotFound) => {
+            return rouille::Response::from(Status::NotFound)
+        }
+        Err(fetch_pokemon::Error::BadRequest) => {
+            return rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_pokemon::Error::Unknown) => {
+            return rouille::Response::from(Status::InternalServerError)
+        }
+    };
+
+    match sprite::download(&url) {
+        Ok(image) => rouille::Response::from_data(image.content_type, image.bytes),
+        _ => rouille::Response::from(Status::BadGateway),
+    }
+}
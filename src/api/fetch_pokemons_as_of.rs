@@ -0,0 +1,49 @@
+use crate::api::fetch_all_pokemons::Response as Pokemon;
+use crate::api::fetch_pokemon::{Ability, Evolution};
+use crate::api::Status;
+use crate::domain::fetch_pokemons_as_of;
+use crate::repositories::change::Repository;
+use std::sync::Arc;
+
+/// Answers with the Pokemon of `number`, or the list of all of them, as
+/// they were at `as_of`, a timestamp in seconds like the ones of
+/// `/changes`.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: Option<u16>,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let as_of = match req.get_param("as_of").map(|as_of| as_of.parse::<u64>()) {
+        Some(Ok(as_of)) => as_of,
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let req = fetch_pokemons_as_of::Request { as_of, number };
+    match fetch_pokemons_as_of::execute(repo, req) {
+        Ok(fetch_pokemons_as_of::Response { pokemons }) => {
+            let pokemons = pokemons
+                .into_iter()
+                .map(|p| Pokemon {
+                    number: p.number,
+                    name: p.name,
+                    types: p.types,
+                    evolutions: Some(p.evolutions.iter().map(Evolution::from).collect()),
+                    abilities: Some(p.abilities.iter().map(Ability::from).collect()),
+                    height: p.height,
+                    weight: p.weight,
+                    sprite: p.sprite,
+                    class: Some(p.class),
+                })
+                .collect::<Vec<Pokemon>>();
+            match (number, &pokemons[..]) {
+                (Some(_), [pokemon]) => rouille::Response::json(pokemon),
+                _ => rouille::Response::json(&pokemons),
+            }
+        }
+        Err(fetch_pokemons_as_of::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_pokemons_as_of::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_pokemons_as_of::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::api::pokemon::Pokemon;
+use crate::api::Status;
+use crate::domain::fetch_pokemons_by_type;
+use crate::service::PokedexService;
+use std::sync::Arc;
+
+pub fn serve(service: Arc<PokedexService>, pokemon_type: String) -> rouille::Response {
+    let req = fetch_pokemons_by_type::Request { pokemon_type };
+    match service.fetch_pokemons_by_type(req) {
+        Ok(res) => rouille::Response::json(
+            &res.iter()
+                .map(|p| Pokemon::new(p.number, &p.name, &p.types))
+                .collect::<Vec<Pokemon>>(),
+        ),
+        Err(fetch_pokemons_by_type::Error::BadRequest) => {
+            rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_pokemons_by_type::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::api::Status;
+use crate::domain::fetch_progress;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    target: u16,
+    registered: u32,
+    remaining: u32,
+    percentage: f64,
+    missing: Vec<u16>,
+}
+
+pub fn serve(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
+    let target = match req.get_param("target").map(|target| target.parse::<u16>()) {
+        Some(Ok(target)) => Some(target),
+        Some(Err(_)) => return rouille::Response::from(Status::BadRequest),
+        None => None,
+    };
+
+    let req = fetch_progress::Request { target };
+    match service.fetch_progress(req) {
+        Ok(res) => rouille::Response::json(&Response {
+            target: res.target,
+            registered: res.registered,
+            remaining: res.missing.len() as u32,
+            percentage: res.percentage,
+            missing: res.missing,
+        }),
+        Err(fetch_progress::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_progress::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
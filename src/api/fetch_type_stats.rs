@@ -0,0 +1,39 @@
+use crate::api::Status;
+use crate::domain::fetch_type_stats;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    total: u32,
+    types: Vec<TypeStat>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct TypeStat {
+    name: String,
+    count: u32,
+    percentage: f64,
+}
+
+pub fn serve(service: Arc<PokedexService>) -> rouille::Response {
+    match service.fetch_type_stats() {
+        Ok(res) => rouille::Response::json(&Response {
+            total: res.total,
+            types: res
+                .types
+                .into_iter()
+                .map(|t| TypeStat {
+                    name: t.name,
+                    count: t.count,
+                    percentage: t.percentage,
+                })
+                .collect::<Vec<TypeStat>>(),
+        }),
+        Err(fetch_type_stats::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::api::Status;
+use crate::domain::fetch_types;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "PokemonType")]
+pub(super) struct Response {
+    name: String,
+    color: String,
+    emoji: String,
+    description: String,
+    strong_against: Vec<String>,
+    weak_against: Vec<String>,
+}
+
+/// `generation` picks the type chart, the latest one by default.
+pub fn serve(req: &rouille::Request) -> rouille::Response {
+    let generation = match req
+        .get_param("generation")
+        .map(|generation| generation.parse::<u8>())
+    {
+        Some(Ok(generation)) => Some(generation),
+        Some(Err(_)) => return rouille::Response::from(Status::BadRequest),
+        None => None,
+    };
+
+    match fetch_types::execute(fetch_types::Request { generation }) {
+        Ok(types) => rouille::Response::json(
+            &types
+                .into_iter()
+                .map(|t| Response {
+                    name: t.name,
+                    color: t.color,
+                    emoji: t.emoji,
+                    description: t.description,
+                    strong_against: t.strong_against,
+                    weak_against: t.weak_against,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_types::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+    }
+}
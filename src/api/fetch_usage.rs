@@ -0,0 +1,48 @@
+use crate::api::quota::{issued, today};
+use crate::api::Status;
+use crate::domain::fetch_usage;
+use crate::repositories::usage::Repository;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    used: u32,
+    limit: u32,
+    remaining: u32,
+    reset: u64,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    limit: Option<u32>,
+    api_keys: &[String],
+    req: &rouille::Request,
+) -> rouille::Response {
+    let limit = match limit {
+        Some(limit) => limit,
+        None => return rouille::Response::from(Status::NotFound),
+    };
+    let key = match req.header("X-Api-Key") {
+        Some(key) if issued(api_keys, key) => String::from(key),
+        _ => return rouille::Response::from(Status::Unauthorized),
+    };
+    let (day, reset) = today();
+
+    let req = fetch_usage::Request { key, limit, day };
+    match fetch_usage::execute(repo, req) {
+        Ok(fetch_usage::Response {
+            used,
+            limit,
+            remaining,
+        }) => rouille::Response::json(&Response {
+            used,
+            limit,
+            remaining,
+            reset,
+        }),
+        Err(fetch_usage::Error::BadRequest) => rouille::Response::from(Status::Unauthorized),
+        Err(fetch_usage::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
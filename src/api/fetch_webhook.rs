@@ -0,0 +1,22 @@
+use crate::api::Status;
+use crate::domain::fetch_webhook;
+use crate::repositories::webhook::Repository;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    id: u32,
+    url: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, id: u32) -> rouille::Response {
+    let req = fetch_webhook::Request { id };
+    match fetch_webhook::execute(repo, req) {
+        Ok(fetch_webhook::Response { id, url }) => rouille::Response::json(&Response { id, url }),
+        Err(fetch_webhook::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_webhook::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_webhook::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,38 @@
+use crate::api::Status;
+use crate::domain::fetch_webhook_deliveries;
+use crate::repositories::webhook::Repository;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Delivery")]
+pub(super) struct Response {
+    event: String,
+    attempt: u32,
+    status_code: Option<u16>,
+    timestamp: u64,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, id: u32) -> rouille::Response {
+    let req = fetch_webhook_deliveries::Request { id };
+    match fetch_webhook_deliveries::execute(repo, req) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|d| Response {
+                    event: d.event,
+                    attempt: d.attempt,
+                    status_code: d.status_code,
+                    timestamp: d.timestamp,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_webhook_deliveries::Error::BadRequest) => {
+            rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_webhook_deliveries::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_webhook_deliveries::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::api::Status;
+use crate::domain::fetch_wishlist;
+use crate::repositories::wishlist::Repository;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Wish")]
+pub(super) struct Response {
+    number: u16,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_wishlist::execute(repo) {
+        Ok(res) => rouille::Response::json(
+            &res.into_iter()
+                .map(|w| Response {
+                    number: w.number,
+                    name: w.name,
+                    note: w.note,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_wishlist::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
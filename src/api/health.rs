@@ -1,13 +1,61 @@
-use rouille;
+use crate::repositories::pokemon::Repository;
+use crate::repositories::write_queue::Repository as WriteQueueRepository;
+use schemars::JsonSchema;
 use serde::Serialize;
+use std::sync::Arc;
+use std::time::Instant;
 
-#[derive(Serialize)]
-struct Response {
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
     message: String,
+    /// The writes waiting for the remote backend to be back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    queued_writes: Option<u32>,
+    backend: Backend,
+    build: Build,
 }
 
-pub fn serve() -> rouille::Response {
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Backend {
+    kind: String,
+    reachable: bool,
+    /// How long counting the Pokemons took, in milliseconds.
+    latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_version: Option<u32>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Build {
+    version: &'static str,
+    /// The git commit the server was built from, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<&'static str>,
+}
+
+/// Counts the Pokemons as a round trip to the backend, so that operators see
+/// what a deployment runs and how its backend responds.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    write_queue: Option<Arc<dyn WriteQueueRepository>>,
+) -> rouille::Response {
+    let start = Instant::now();
+    let reachable = repo.count().is_ok();
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let backend = repo.backend();
+
     rouille::Response::json(&Response {
         message: String::from("Gotta catch them all!"),
+        queued_writes: write_queue.and_then(|queue| queue.depth().ok()),
+        backend: Backend {
+            kind: backend.kind,
+            reachable,
+            latency_ms,
+            schema_version: backend.schema_version,
+        },
+        build: Build {
+            version: env!("CARGO_PKG_VERSION"),
+            commit: option_env!("POKEDEX_COMMIT"),
+        },
     })
 }
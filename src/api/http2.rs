@@ -0,0 +1,205 @@
+use super::listener;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper::service::service_fn;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto;
+use std::convert::Infallible;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::Arc;
+use std::thread;
+use tokio_rustls::rustls::crypto::ring;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+/// PEM files of the certificate chain and of its private key.
+pub struct Tls {
+    pub certificate: String,
+    pub private_key: String,
+}
+
+/// Headers which only make sense on an HTTP/1 connection and that HTTP/2
+/// forbids.
+const CONNECTION_HEADERS: [&str; 5] = [
+    "Connection",
+    "Content-Length",
+    "Keep-Alive",
+    "Transfer-Encoding",
+    "Upgrade",
+];
+
+/// Serves `handler` over HTTP/1.1 with keep-alive and HTTP/2, which rouille
+/// cannot speak: in cleartext when clients use HTTP/2 with prior knowledge,
+/// or negotiated with ALPN over TLS. The handler stays blocking and runs on
+/// up to `workers` threads.
+pub(super) fn run<F>(socket: TcpListener, handler: F, workers: Option<usize>, tls: Option<Tls>)
+where
+    F: Fn(&rouille::Request) -> rouille::Response + Send + Sync + 'static,
+{
+    let acceptor = match tls.map(acceptor) {
+        Some(Ok(acceptor)) => Some(acceptor),
+        Some(Err(message)) => panic!("Error while starting the server: {}", message),
+        None => None,
+    };
+    let workers =
+        workers.unwrap_or_else(|| 8 * thread::available_parallelism().map_or(1, |cpus| cpus.get()));
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .max_blocking_threads(workers)
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        _ => panic!("Error while starting the server"),
+    };
+    let handler = Arc::new(handler);
+
+    runtime.block_on(async move {
+        let socket = match socket
+            .set_nonblocking(true)
+            .and_then(|_| tokio::net::TcpListener::from_std(socket))
+        {
+            Ok(socket) => socket,
+            _ => panic!("Error while starting the server"),
+        };
+
+        loop {
+            let (stream, remote_addr) = match socket.accept().await {
+                Ok(connection) => connection,
+                _ => continue,
+            };
+            let handler = handler.clone();
+            let acceptor = acceptor.clone();
+
+            tokio::spawn(async move {
+                let service = service_fn(move |req| respond(req, remote_addr, handler.clone()));
+                let mut builder = auto::Builder::new(TokioExecutor::new());
+                builder.http1().timer(TokioTimer::new());
+                builder.http2().timer(TokioTimer::new());
+
+                match acceptor {
+                    Some(acceptor) => match acceptor.accept(stream).await {
+                        Ok(stream) => {
+                            builder
+                                .serve_connection(TokioIo::new(stream), service)
+                                .await
+                        }
+                        _ => Ok(()),
+                    },
+                    None => {
+                        builder
+                            .serve_connection(TokioIo::new(stream), service)
+                            .await
+                    }
+                }
+                .ok();
+            });
+        }
+    });
+}
+
+fn acceptor(tls: Tls) -> Result<TlsAcceptor, String> {
+    let certificates = match File::open(&tls.certificate)
+        .map(BufReader::new)
+        .and_then(|mut file| rustls_pemfile::certs(&mut file).collect::<Result<Vec<_>, _>>())
+    {
+        Ok(certificates) if !certificates.is_empty() => certificates,
+        _ => return Err(format!("No certificate in {}", tls.certificate)),
+    };
+    let private_key = match File::open(&tls.private_key)
+        .map(BufReader::new)
+        .and_then(|mut file| rustls_pemfile::private_key(&mut file))
+    {
+        Ok(Some(private_key)) => private_key,
+        _ => return Err(format!("No private key in {}", tls.private_key)),
+    };
+
+    let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .and_then(|config| {
+            config
+                .with_no_client_auth()
+                .with_single_cert(certificates, private_key)
+        });
+    match config {
+        Ok(mut config) => {
+            config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+            Ok(TlsAcceptor::from(Arc::new(config)))
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+async fn respond<F>(
+    req: hyper::Request<Incoming>,
+    remote_addr: SocketAddr,
+    handler: Arc<F>,
+) -> Result<hyper::Response<Full<Bytes>>, Infallible>
+where
+    F: Fn(&rouille::Request) -> rouille::Response + Send + Sync + 'static,
+{
+    let (parts, body) = req.into_parts();
+    let body = match body.collect().await {
+        Ok(body) => body.to_bytes().to_vec(),
+        _ => return Ok(status(400)),
+    };
+
+    let url = parts
+        .uri
+        .path_and_query()
+        .map_or("/", |path| path.as_str())
+        .to_owned();
+    let mut headers = parts
+        .headers
+        .iter()
+        .filter_map(|(key, value)| match value.to_str() {
+            Ok(value) => Some((key.to_string(), value.to_owned())),
+            _ => None,
+        })
+        .collect::<Vec<(String, String)>>();
+    // HTTP/2 moves the host to the `:authority` pseudo-header.
+    if let (None, Some(authority)) = (parts.headers.get("Host"), parts.uri.authority()) {
+        headers.push((String::from("Host"), authority.to_string()));
+    }
+    let method = parts.method.to_string();
+
+    let res = tokio::task::spawn_blocking(move || {
+        let req = rouille::Request::fake_http_from(remote_addr, method, url, headers, body);
+        let res = listener::call(handler.as_ref(), &req);
+        let (mut data, _) = res.data.into_reader_and_size();
+        let mut body = vec![];
+        data.read_to_end(&mut body)
+            .map(|_| (res.status_code, res.headers, body))
+    })
+    .await;
+
+    let (status_code, headers, body) = match res {
+        Ok(Ok(res)) => res,
+        _ => return Ok(status(500)),
+    };
+    let builder = headers
+        .iter()
+        .filter(|(key, _)| {
+            !CONNECTION_HEADERS
+                .iter()
+                .any(|h| key.eq_ignore_ascii_case(h))
+        })
+        .fold(
+            hyper::Response::builder().status(status_code),
+            |builder, (key, value)| builder.header(key.as_ref(), value.as_ref()),
+        );
+    match builder.body(Full::new(Bytes::from(body))) {
+        Ok(res) => Ok(res),
+        _ => Ok(status(500)),
+    }
+}
+
+fn status(code: u16) -> hyper::Response<Full<Bytes>> {
+    let mut res = hyper::Response::new(Full::new(Bytes::new()));
+    if let Ok(code) = hyper::StatusCode::from_u16(code) {
+        *res.status_mut() = code;
+    }
+    res
+}
@@ -0,0 +1,260 @@
+use crate::api;
+use crate::api::validate_pokemon::FieldError;
+use crate::domain::ingest_pokemons;
+use crate::service::PokedexService;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use schemars::{schema_for, JsonSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+const MAX_RECORDS: usize = 1000;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ImportedPokemon {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    inserted: usize,
+    overwritten: usize,
+    conflicts: usize,
+    invalid: usize,
+    outcomes: Vec<Outcome>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Outcome {
+    number: u16,
+    status: Status,
+    /// Why an `invalid` Pokemon is invalid.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<FieldError>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Status {
+    Inserted,
+    Overwritten,
+    Skipped,
+    Invalid,
+}
+
+/// Every problem found in the payload, each located by a JSON pointer such
+/// as `/3/types/0`.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Problems {
+    errors: Vec<Problem>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Problem {
+    pointer: String,
+    message: String,
+}
+
+/// Checks the whole payload against the schema of the records published by
+/// the `schema` subcommand before any of them reaches the domain.
+pub fn serve(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
+    let policy = match policy(req) {
+        Ok(policy) => policy,
+        _ => return rouille::Response::from(api::Status::BadRequest),
+    };
+
+    let body = match rouille::input::json_input::<Value>(req) {
+        Ok(body) => body,
+        _ => return problems(vec![Problem::new("", "must be a JSON document")]),
+    };
+
+    let found = validate(&body);
+    if !found.is_empty() {
+        return problems(found);
+    }
+
+    let reqs = match serde_json::from_value::<Vec<ImportedPokemon>>(body) {
+        Ok(pokemons) => pokemons
+            .into_iter()
+            .map(|pokemon| ingest_pokemons::Request {
+                number: pokemon.number,
+                name: pokemon.name,
+                types: pokemon.types,
+                details: ingest_pokemons::Details::default(),
+            })
+            .collect::<Vec<ingest_pokemons::Request>>(),
+        _ => return rouille::Response::from(api::Status::BadRequest),
+    };
+
+    match service.ingest_pokemons(policy, reqs) {
+        Ok(ingest_pokemons::Response {
+            inserted,
+            overwritten,
+            conflicts,
+            invalid,
+            outcomes,
+        }) => rouille::Response::json(&Response {
+            inserted,
+            overwritten,
+            conflicts,
+            invalid,
+            outcomes: outcomes
+                .into_iter()
+                .map(|outcome| Outcome {
+                    number: outcome.number,
+                    status: match outcome.status {
+                        ingest_pokemons::Status::Inserted => Status::Inserted,
+                        ingest_pokemons::Status::Overwritten => Status::Overwritten,
+                        ingest_pokemons::Status::Skipped => Status::Skipped,
+                        ingest_pokemons::Status::Invalid => Status::Invalid,
+                    },
+                    errors: outcome.errors.into_iter().map(FieldError::from).collect(),
+                })
+                .collect::<Vec<Outcome>>(),
+        }),
+        Err(ingest_pokemons::Error::Conflict) => rouille::Response::from(api::Status::Conflict),
+        Err(ingest_pokemons::Error::Unknown) => {
+            rouille::Response::from(api::Status::InternalServerError)
+        }
+    }
+}
+
+/// The `on_conflict` query parameter: `skip` by default, `overwrite` or `fail`.
+pub(super) fn policy(req: &rouille::Request) -> Result<ingest_pokemons::Policy, ()> {
+    match req.get_param("on_conflict").as_deref() {
+        None | Some("skip") => Ok(ingest_pokemons::Policy::Skip),
+        Some("overwrite") => Ok(ingest_pokemons::Policy::Overwrite),
+        Some("fail") => Ok(ingest_pokemons::Policy::Fail),
+        _ => Err(()),
+    }
+}
+
+fn problems(errors: Vec<Problem>) -> rouille::Response {
+    rouille::Response::json(&Problems { errors }).with_status_code(400)
+}
+
+fn validate(body: &Value) -> Vec<Problem> {
+    let records = match body {
+        Value::Array(records) => records,
+        _ => return vec![Problem::new("", "must be an array")],
+    };
+
+    let mut found = vec![];
+    if records.len() > MAX_RECORDS {
+        found.push(Problem::new(
+            "",
+            &format!("must contain at most {} records", MAX_RECORDS),
+        ));
+    }
+
+    let schema = schema_for!(ImportedPokemon).schema;
+    for (index, record) in records.iter().enumerate() {
+        found.extend(check(record, &schema, &format!("/{}", index)));
+    }
+    found
+}
+
+fn check(value: &Value, schema: &SchemaObject, pointer: &str) -> Vec<Problem> {
+    if let Some(instance_type) = &schema.instance_type {
+        if !has_type(value, instance_type) {
+            return vec![Problem::new(
+                pointer,
+                &format!("must be of type {}", describe(instance_type)),
+            )];
+        }
+    }
+
+    let mut found = vec![];
+
+    if let (Some(format), Some(n)) = (schema.format.as_deref(), value.as_u64()) {
+        if format == "uint16" && n > u64::from(u16::MAX) {
+            found.push(Problem::new(
+                pointer,
+                &format!("must be at most {}", u16::MAX),
+            ));
+        }
+    }
+
+    if let (Some(object), Value::Object(fields)) = (&schema.object, value) {
+        for key in object.required.iter() {
+            if !fields.contains_key(key) {
+                found.push(Problem::new(&format!("{}/{}", pointer, key), "is required"));
+            }
+        }
+        for (key, field) in fields.iter() {
+            let field_pointer = format!("{}/{}", pointer, key);
+            match (
+                object.properties.get(key),
+                object.additional_properties.as_deref(),
+            ) {
+                (Some(Schema::Object(property)), _) => {
+                    found.extend(check(field, property, &field_pointer))
+                }
+                (None, Some(Schema::Bool(false))) => {
+                    found.push(Problem::new(&field_pointer, "is not allowed"))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let (Some(array), Value::Array(items)) = (&schema.array, value) {
+        if let Some(SingleOrVec::Single(item_schema)) = &array.items {
+            if let Schema::Object(item_schema) = item_schema.as_ref() {
+                for (index, item) in items.iter().enumerate() {
+                    found.extend(check(item, item_schema, &format!("{}/{}", pointer, index)));
+                }
+            }
+        }
+    }
+
+    found
+}
+
+fn has_type(value: &Value, instance_type: &SingleOrVec<InstanceType>) -> bool {
+    let is = |instance_type: &InstanceType| match instance_type {
+        InstanceType::Null => value.is_null(),
+        InstanceType::Boolean => value.is_boolean(),
+        InstanceType::Object => value.is_object(),
+        InstanceType::Array => value.is_array(),
+        InstanceType::Number => value.is_number(),
+        InstanceType::String => value.is_string(),
+        InstanceType::Integer => value.is_u64() || value.is_i64(),
+    };
+    match instance_type {
+        SingleOrVec::Single(instance_type) => is(instance_type),
+        SingleOrVec::Vec(instance_types) => instance_types.iter().any(is),
+    }
+}
+
+fn describe(instance_type: &SingleOrVec<InstanceType>) -> String {
+    let name = |instance_type: &InstanceType| match instance_type {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "boolean",
+        InstanceType::Object => "object",
+        InstanceType::Array => "array",
+        InstanceType::Number => "number",
+        InstanceType::String => "string",
+        InstanceType::Integer => "integer",
+    };
+    match instance_type {
+        SingleOrVec::Single(instance_type) => String::from(name(instance_type)),
+        SingleOrVec::Vec(instance_types) => instance_types
+            .iter()
+            .map(name)
+            .collect::<Vec<&str>>()
+            .join(" or "),
+    }
+}
+
+impl Problem {
+    fn new(pointer: &str, message: &str) -> Self {
+        Self {
+            pointer: String::from(pointer),
+            message: String::from(message),
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use crate::api::{import_pokemons, Status};
+use crate::domain::ingest_pokemons;
+use crate::service::PokedexService;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Cursor, Read, Write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Arc;
+use std::thread;
+
+const DEFAULT_BATCH_SIZE: usize = 100;
+const MAX_BATCH_SIZE: usize = 1000;
+/// Longer lines are counted as invalid without being read whole.
+const MAX_LINE_BYTES: u64 = 64 * 1024;
+/// Bigger bodies are refused, so that one of them can't fill the disk.
+const MAX_BODY_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Numbers the temporary files of the ingestions running at once.
+static SPOOLS: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Deserialize)]
+struct Request {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Progress {
+    processed: usize,
+    inserted: usize,
+    overwritten: usize,
+    conflicts: usize,
+    invalid: usize,
+    /// Why the ingesting stopped early, on the last line only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+/// The body copied to a temporary file, removed once ingested.
+struct Spool {
+    path: PathBuf,
+    too_long: usize,
+    /// False when the body could not be read to its end.
+    complete: bool,
+}
+
+enum SpoolError {
+    TooLarge,
+    Unknown,
+}
+
+impl From<io::Error> for SpoolError {
+    fn from(_: io::Error) -> Self {
+        Self::Unknown
+    }
+}
+
+/// Reads the progress lines as the ingesting thread sends them.
+struct Report {
+    lines: Receiver<Vec<u8>>,
+    pending: Cursor<Vec<u8>>,
+}
+
+/// Copies the newline-delimited JSON body to a temporary file, then ingests
+/// it in batches on another thread while the response streams the progress
+/// after each batch, as newline-delimited JSON too. Memory stays bounded by
+/// the batch size, and lines longer than `MAX_LINE_BYTES` are counted as
+/// invalid. Bodies of more than `MAX_BODY_BYTES` are answered with a 413.
+///
+/// The status being sent before ingesting, a failure is told by the `error`
+/// of the last line: `read` when the body was cut short, the lines before
+/// being ingested, `conflict` when the `fail` policy met a taken number, or
+/// `unknown`. The batches before a failure stay committed.
+pub fn serve(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
+    let policy = match import_pokemons::policy(req) {
+        Ok(policy) => policy,
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let batch_size = match req
+        .get_param("batch_size")
+        .map(|size| size.parse::<usize>())
+    {
+        Some(Ok(size)) if size > 0 && size <= MAX_BATCH_SIZE => size,
+        Some(_) => return rouille::Response::from(Status::BadRequest),
+        None => DEFAULT_BATCH_SIZE,
+    };
+
+    match req
+        .header("Content-Length")
+        .map(|length| length.parse::<u64>())
+    {
+        Some(Ok(length)) if length > MAX_BODY_BYTES => {
+            return rouille::Response::from(Status::PayloadTooLarge)
+        }
+        _ => {}
+    }
+
+    let body = match req.data() {
+        Some(body) => body,
+        None => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let spool = match spool(body) {
+        Ok(spool) => spool,
+        Err(SpoolError::TooLarge) => return rouille::Response::from(Status::PayloadTooLarge),
+        Err(SpoolError::Unknown) => return rouille::Response::from(Status::InternalServerError),
+    };
+
+    let (sender, lines) = mpsc::sync_channel(1);
+    thread::spawn(move || {
+        ingest(service, policy, batch_size, &spool, sender);
+        fs::remove_file(&spool.path).ok();
+    });
+
+    rouille::Response {
+        status_code: 200,
+        headers: vec![("Content-Type".into(), "application/x-ndjson".into())],
+        data: rouille::ResponseBody::from_reader(Report {
+            lines,
+            pending: Cursor::new(vec![]),
+        }),
+        upgrade: None,
+    }
+}
+
+fn spool(body: impl Read) -> Result<Spool, SpoolError> {
+    let path = env::temp_dir().join(format!(
+        "pokedex-ingest-{}-{}.ndjson",
+        process::id(),
+        SPOOLS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut file = BufWriter::new(File::create(&path)?);
+    // Reading one byte more than allowed tells the body is too large.
+    let mut body = BufReader::new(body.take(MAX_BODY_BYTES + 1));
+    let mut spool = Spool {
+        path,
+        too_long: 0,
+        complete: true,
+    };
+
+    loop {
+        let mut line = vec![];
+        match body
+            .by_ref()
+            .take(MAX_LINE_BYTES + 1)
+            .read_until(b'\n', &mut line)
+        {
+            Ok(0) => break,
+            Ok(read) if read as u64 > MAX_LINE_BYTES && !line.ends_with(b"\n") => {
+                spool.too_long += 1;
+                if body.skip_until(b'\n').is_err() {
+                    spool.complete = false;
+                    break;
+                }
+            }
+            Ok(_) => {
+                file.write_all(&line)?;
+                if !line.ends_with(b"\n") {
+                    file.write_all(b"\n")?;
+                }
+            }
+            Err(_) => {
+                spool.complete = false;
+                break;
+            }
+        }
+    }
+
+    file.flush()?;
+    drop(file);
+
+    if body.get_ref().limit() == 0 {
+        fs::remove_file(&spool.path).ok();
+        return Err(SpoolError::TooLarge);
+    }
+
+    Ok(spool)
+}
+
+fn ingest(
+    service: Arc<PokedexService>,
+    policy: ingest_pokemons::Policy,
+    batch_size: usize,
+    spool: &Spool,
+    sender: SyncSender<Vec<u8>>,
+) {
+    let mut progress = Progress {
+        processed: spool.too_long,
+        inserted: 0,
+        overwritten: 0,
+        conflicts: 0,
+        invalid: spool.too_long,
+        error: None,
+    };
+    let mut sent = false;
+    let mut batch = vec![];
+
+    let file = match File::open(&spool.path) {
+        Ok(file) => file,
+        _ => {
+            progress.error = Some("unknown");
+            send(&sender, &progress);
+            return;
+        }
+    };
+
+    for line in BufReader::new(file).lines() {
+        let line = match line {
+            Ok(line) => line,
+            _ => {
+                progress.error = Some("unknown");
+                send(&sender, &progress);
+                return;
+            }
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<Request>(&line) {
+            Ok(req) => batch.push(ingest_pokemons::Request {
+                number: req.number,
+                name: req.name,
+                types: req.types,
+                details: ingest_pokemons::Details::default(),
+            }),
+            _ => {
+                progress.processed += 1;
+                progress.invalid += 1;
+            }
+        }
+
+        if batch.len() == batch_size {
+            if let Err(err) = commit(service.clone(), policy, &mut batch, &mut progress) {
+                progress.error = Some(failure(err));
+                send(&sender, &progress);
+                return;
+            }
+            if !send(&sender, &progress) {
+                return;
+            }
+            sent = true;
+        }
+    }
+
+    if !batch.is_empty() || !sent || !spool.complete {
+        if let Err(err) = commit(service, policy, &mut batch, &mut progress) {
+            progress.error = Some(failure(err));
+        } else if !spool.complete {
+            progress.error = Some("read");
+        }
+        send(&sender, &progress);
+    }
+}
+
+fn commit(
+    service: Arc<PokedexService>,
+    policy: ingest_pokemons::Policy,
+    batch: &mut Vec<ingest_pokemons::Request>,
+    progress: &mut Progress,
+) -> Result<(), ingest_pokemons::Error> {
+    let size = batch.len();
+    match service.ingest_pokemons(policy, std::mem::take(batch)) {
+        Ok(ingest_pokemons::Response {
+            inserted,
+            overwritten,
+            conflicts,
+            invalid,
+            ..
+        }) => {
+            progress.processed += size;
+            progress.inserted += inserted;
+            progress.overwritten += overwritten;
+            progress.conflicts += conflicts;
+            progress.invalid += invalid;
+            Ok(())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// False once the client is gone, so that the ingesting stops.
+fn send(sender: &SyncSender<Vec<u8>>, progress: &Progress) -> bool {
+    match serde_json::to_vec(progress) {
+        Ok(mut line) => {
+            line.push(b'\n');
+            sender.send(line).is_ok()
+        }
+        _ => true,
+    }
+}
+
+fn failure(err: ingest_pokemons::Error) -> &'static str {
+    match err {
+        ingest_pokemons::Error::Conflict => "conflict",
+        ingest_pokemons::Error::Unknown => "unknown",
+    }
+}
+
+impl Read for Report {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.pending.read(buf) {
+                Ok(0) if !buf.is_empty() => match self.lines.recv() {
+                    Ok(line) => self.pending = Cursor::new(line),
+                    Err(_) => return Ok(0),
+                },
+                res => return res,
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// An operation of an `application/json-patch+json` document (RFC 6902).
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub(super) enum Operation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+pub(super) enum Error {
+    /// A path which is malformed or doesn't exist in the document.
+    Unprocessable,
+    TestFailed,
+}
+
+/// Applies the operations in order to a copy of the document, which is
+/// returned only when all of them succeed.
+pub(super) fn apply(document: &Value, operations: Vec<Operation>) -> Result<Value, Error> {
+    operations
+        .into_iter()
+        .try_fold(document.clone(), |mut document, operation| {
+            let res = match operation {
+                Operation::Add { path, value } => add(&mut document, &path, value),
+                Operation::Remove { path } => remove(&mut document, &path).map(|_| ()),
+                Operation::Replace { path, value } => match get_mut(&mut document, &path) {
+                    Some(target) => {
+                        *target = value;
+                        Ok(())
+                    }
+                    None => Err(Error::Unprocessable),
+                },
+                // A value can't be moved into itself.
+                Operation::Move { from, path } if path.starts_with(&format!("{}/", from)) => {
+                    Err(Error::Unprocessable)
+                }
+                Operation::Move { from, path } => match remove(&mut document, &from) {
+                    Ok(value) => add(&mut document, &path, value),
+                    Err(err) => Err(err),
+                },
+                Operation::Copy { from, path } => match get_mut(&mut document, &from) {
+                    Some(value) => {
+                        let value = value.clone();
+                        add(&mut document, &path, value)
+                    }
+                    None => Err(Error::Unprocessable),
+                },
+                Operation::Test { path, value } => match get_mut(&mut document, &path) {
+                    Some(target) if *target == value => Ok(()),
+                    _ => Err(Error::TestFailed),
+                },
+            };
+            res.map(|()| document)
+        })
+}
+
+/// The reference tokens of a JSON pointer (RFC 6901), the empty pointer
+/// being the whole document.
+fn tokens(pointer: &str) -> Result<Vec<String>, Error> {
+    match pointer {
+        "" => Ok(vec![]),
+        _ if pointer.starts_with('/') => Ok(pointer[1..]
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect()),
+        _ => Err(Error::Unprocessable),
+    }
+}
+
+/// An array index, without leading zeros.
+fn index(token: &str) -> Option<usize> {
+    match token {
+        "0" => Some(0),
+        _ if token.starts_with('0') => None,
+        _ => token.parse::<usize>().ok(),
+    }
+}
+
+fn get_mut<'a>(document: &'a mut Value, pointer: &str) -> Option<&'a mut Value> {
+    let tokens = match tokens(pointer) {
+        Ok(tokens) => tokens,
+        _ => return None,
+    };
+
+    tokens
+        .iter()
+        .try_fold(document, |value, token| match value {
+            Value::Object(fields) => fields.get_mut(token),
+            Value::Array(items) => index(token).and_then(move |i| items.get_mut(i)),
+            _ => None,
+        })
+}
+
+/// Splits the pointer into the one of the parent and the last token.
+fn parent(pointer: &str) -> Result<(&str, String), Error> {
+    match (pointer.rfind('/'), tokens(pointer)) {
+        (Some(at), Ok(mut tokens)) => match tokens.pop() {
+            Some(last) => Ok((&pointer[..at], last)),
+            None => Err(Error::Unprocessable),
+        },
+        _ => Err(Error::Unprocessable),
+    }
+}
+
+fn add(document: &mut Value, pointer: &str, value: Value) -> Result<(), Error> {
+    if pointer.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+
+    let (parent, last) = match parent(pointer) {
+        Ok(split) => split,
+        _ => return Err(Error::Unprocessable),
+    };
+    match get_mut(document, parent) {
+        Some(Value::Object(fields)) => {
+            fields.insert(last, value);
+            Ok(())
+        }
+        Some(Value::Array(items)) if last == "-" => {
+            items.push(value);
+            Ok(())
+        }
+        Some(Value::Array(items)) => match index(&last) {
+            Some(i) if i <= items.len() => {
+                items.insert(i, value);
+                Ok(())
+            }
+            _ => Err(Error::Unprocessable),
+        },
+        _ => Err(Error::Unprocessable),
+    }
+}
+
+fn remove(document: &mut Value, pointer: &str) -> Result<Value, Error> {
+    let (parent, last) = match parent(pointer) {
+        Ok(split) => split,
+        _ => return Err(Error::Unprocessable),
+    };
+    match get_mut(document, parent) {
+        Some(Value::Object(fields)) => match fields.remove(&last) {
+            Some(value) => Ok(value),
+            None => Err(Error::Unprocessable),
+        },
+        Some(Value::Array(items)) => match index(&last) {
+            Some(i) if i < items.len() => Ok(items.remove(i)),
+            _ => Err(Error::Unprocessable),
+        },
+        _ => Err(Error::Unprocessable),
+    }
+}
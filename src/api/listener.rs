@@ -0,0 +1,88 @@
+use std::net::TcpListener;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread;
+
+/// Serves `handler` on a socket which is already listening, which rouille
+/// cannot do, by translating the requests and responses of tiny_http, the
+/// server underneath rouille.
+pub(super) fn run<F>(listener: TcpListener, handler: F, workers: Option<usize>)
+where
+    F: Fn(&rouille::Request) -> rouille::Response + Send + Sync + 'static,
+{
+    let server = match tiny_http::Server::from_listener(listener, None) {
+        Ok(server) => Arc::new(server),
+        _ => panic!("Error while starting the server"),
+    };
+    let handler = Arc::new(handler);
+    let workers =
+        workers.unwrap_or_else(|| 8 * thread::available_parallelism().map_or(1, |cpus| cpus.get()));
+
+    let threads = (0..workers)
+        .map(|_| {
+            let server = server.clone();
+            let handler = handler.clone();
+            thread::spawn(move || {
+                while let Ok(req) = server.recv() {
+                    respond(req, handler.as_ref());
+                }
+            })
+        })
+        .collect::<Vec<thread::JoinHandle<()>>>();
+
+    for thread in threads {
+        thread.join().ok();
+    }
+}
+
+fn respond<F>(mut req: tiny_http::Request, handler: &F)
+where
+    F: Fn(&rouille::Request) -> rouille::Response,
+{
+    let mut body = vec![];
+    if req.as_reader().read_to_end(&mut body).is_err() {
+        return;
+    }
+    let rouille_req = rouille::Request::fake_http_from(
+        *req.remote_addr(),
+        req.method().as_str(),
+        req.url(),
+        req.headers()
+            .iter()
+            .map(|h| (h.field.to_string(), h.value.to_string()))
+            .collect(),
+        body,
+    );
+
+    let res = call(handler, &rouille_req);
+
+    let headers = res
+        .headers
+        .iter()
+        .filter(|(key, _)| !key.eq_ignore_ascii_case("Content-Length"))
+        .filter_map(|(key, value)| {
+            tiny_http::Header::from_bytes(key.as_bytes(), value.as_bytes()).ok()
+        })
+        .collect::<Vec<tiny_http::Header>>();
+    let (data, length) = res.data.into_reader_and_size();
+
+    req.respond(tiny_http::Response::new(
+        tiny_http::StatusCode(res.status_code),
+        headers,
+        data,
+        length,
+        None,
+    ))
+    .ok();
+}
+
+/// Answers 500 when the handler panics, as rouille does.
+pub(super) fn call<F>(handler: &F, req: &rouille::Request) -> rouille::Response
+where
+    F: Fn(&rouille::Request) -> rouille::Response,
+{
+    match panic::catch_unwind(AssertUnwindSafe(|| handler(req))) {
+        Ok(res) => res,
+        Err(_) => rouille::Response::text("Internal Server Error").with_status_code(500),
+    }
+}
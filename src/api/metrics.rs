@@ -0,0 +1,32 @@
+use crate::api::panics;
+use crate::repositories::write_queue::Repository as WriteQueueRepository;
+use std::sync::Arc;
+
+/// The counters of the server in the Prometheus text format.
+pub fn serve(write_queue: Option<Arc<dyn WriteQueueRepository>>) -> rouille::Response {
+    let mut metrics = vec![(
+        "pokedex_panics_total",
+        "counter",
+        "Requests whose handler panicked.",
+        panics::count(),
+    )];
+    if let Some(Ok(depth)) = write_queue.map(|queue| queue.depth()) {
+        metrics.push((
+            "pokedex_queued_writes",
+            "gauge",
+            "Writes waiting for the remote backend to be back.",
+            u64::from(depth),
+        ));
+    }
+
+    let body = metrics
+        .iter()
+        .map(|(name, kind, help, value)| {
+            format!(
+                "# HELP {} {}\n# TYPE {} {}\n{} {}\n",
+                name, help, name, kind, name, value
+            )
+        })
+        .collect::<String>();
+    rouille::Response::from_data("text/plain; version=0.0.4", body)
+}
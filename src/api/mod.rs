@@ -1,53 +1,578 @@
-mod create_pokemon;
+// The expansion of rouille's `router!` macro strips url prefixes by hand.
+#![allow(clippy::manual_strip)]
+
+mod add_move;
+mod admin;
+mod check_name;
+pub mod create_pokemon;
+mod create_webhook;
+mod create_wish;
 mod delete_pokemon;
-mod fetch_all_pokemons;
-mod fetch_pokemon;
+mod delete_pokemons_by_type;
+mod delete_webhook;
+mod delete_wish;
+mod export;
+pub mod fetch_all_pokemons;
+mod fetch_all_webhooks;
+mod fetch_changes;
+mod fetch_index;
+mod fetch_moves;
+pub mod fetch_pokemon;
+mod fetch_pokemon_by_name;
+mod fetch_pokemon_cry;
+mod fetch_pokemon_range;
+mod fetch_pokemon_sprite;
+mod fetch_pokemons_as_of;
+mod fetch_pokemons_by_type;
+mod fetch_progress;
+mod fetch_type_stats;
+mod fetch_types;
+mod fetch_usage;
+mod fetch_webhook;
+mod fetch_webhook_deliveries;
+mod fetch_wishlist;
 mod health;
+pub mod http2;
+mod import_pokemons;
+mod ingest_pokemons;
+mod json_patch;
+mod listener;
+mod metrics;
+mod panics;
+mod patch_pokemon;
+mod pokemon;
+mod promote_wish;
+mod quota;
+mod remove_move;
+pub mod schema;
+mod update_pokemon;
+mod update_pokemon_abilities;
+mod update_pokemon_class;
+mod update_pokemon_cry;
+mod update_pokemon_evolutions;
+mod update_pokemon_measurements;
+mod update_pokemon_sprite;
+mod update_pokemon_types;
+mod update_pokemons;
+mod validate_pokemon;
 
+use crate::cancellation::Cancellation;
+use crate::domain::hooks::Validator;
+use crate::domain::middleware::Middleware;
+use crate::notifiers::email::EmailNotifier;
+use crate::notifiers::webhook::WebhookNotifier;
+use crate::notifiers::{EventBus, Subscriber};
+use crate::outbox;
+use crate::repositories::change::Repository as ChangeRepository;
+use crate::repositories::cry::Repository as CryRepository;
+use crate::repositories::moves::Repository as MovesRepository;
+use crate::repositories::outbox::Repository as OutboxRepository;
 use crate::repositories::pokemon::Repository;
+use crate::repositories::usage::Repository as UsageRepository;
+use crate::repositories::webhook::Repository as WebhookRepository;
+use crate::repositories::wishlist::Repository as WishlistRepository;
+use crate::repositories::write_queue::Repository as WriteQueueRepository;
+use crate::service::PokedexService;
+use crate::systemd;
+use crate::trace;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+
+/// The routes served without an API key under `--daily-quota`: the usage of
+/// the key itself, and those of the load balancers and the monitoring.
+const UNMETERED: [&str; 3] = ["/usage", "/health", "/metrics"];
+
+pub struct Config {
+    pub admin_token: Option<String>,
+    pub daily_quota: Option<u32>,
+    /// The keys accepted in `X-Api-Key` under `daily_quota`.
+    pub api_keys: Vec<String>,
+    pub read_only: bool,
+    pub workers: Option<usize>,
+    /// How long a request may run before its use cases stop calling the
+    /// backend. Clients can ask for less with `X-Request-Timeout`.
+    pub request_timeout: Option<Duration>,
+    pub email_notifier: Option<EmailNotifier>,
+    pub middlewares: Vec<Arc<dyn Middleware>>,
+    /// Run before the writes, such as the scripts of `--hooks`.
+    pub validators: Vec<Arc<dyn Validator>>,
+    /// Told about every event, besides the webhooks and the emails.
+    pub subscribers: Vec<Arc<dyn Subscriber>>,
+    /// Serves HTTP/2 besides HTTP/1.1, over TLS when given.
+    pub http2: bool,
+    pub tls: Option<http2::Tls>,
+    /// The writes waiting for the remote backend, counted by `/health` and
+    /// `/metrics`.
+    pub write_queue: Option<Arc<dyn WriteQueueRepository>>,
+    /// The learnsets of the Pokemons.
+    pub moves_repo: Arc<dyn MovesRepository>,
+    pub cry_repo: Arc<dyn CryRepository>,
+    /// The events the backend stores along with its writes, delivered from
+    /// there in the background rather than published by the service.
+    pub outbox: Option<Arc<dyn OutboxRepository>>,
+}
+
+/// Where the server takes its connections from.
+pub enum Listener {
+    Address(String),
+    /// A socket already listening, such as the one systemd passes with
+    /// socket activation.
+    Inherited(TcpListener),
+}
+
+pub fn serve(
+    listener: Listener,
+    repo: Arc<dyn Repository>,
+    usage_repo: Arc<dyn UsageRepository>,
+    webhook_repo: Arc<dyn WebhookRepository>,
+    change_repo: Arc<dyn ChangeRepository>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    config: Config,
+) {
+    let healthy = repo.count().is_ok();
+    let read_only = Arc::new(AtomicBool::new(config.read_only));
+    let mut subscribers: Vec<Arc<dyn Subscriber>> =
+        vec![Arc::new(WebhookNotifier::new(webhook_repo.clone()))];
+    if let Some(email_notifier) = config.email_notifier {
+        subscribers.push(Arc::new(email_notifier));
+    }
+    subscribers.extend(config.subscribers);
+    let events = Arc::new(EventBus::new(subscribers));
+    let service = match config.outbox {
+        Some(outbox_repo) => {
+            outbox::spawn(outbox_repo, events);
+            PokedexService::new(repo)
+        }
+        None => PokedexService::new(repo).with_events(events),
+    };
+    let service = service.with_cries(config.cry_repo.clone());
+    let service = config
+        .validators
+        .into_iter()
+        .fold(service, |service, validator| {
+            service.with_validator(validator)
+        });
+    let service = config
+        .middlewares
+        .into_iter()
+        .fold(service, |service, middleware| {
+            service.with_middleware(middleware)
+        });
+    let service = Arc::new(service);
+    let collection_cache = Arc::new(fetch_all_pokemons::Cache::new());
+
+    let workers = config.workers;
+    let (http2, tls) = (config.http2, config.tls);
+    let write_queue = config.write_queue;
+    let moves_repo = config.moves_repo;
+    let cry_repo = config.cry_repo;
+
+    let handler = move |req: &rouille::Request| {
+        let cancellation = match cancellation(config.request_timeout, req) {
+            Ok(cancellation) => cancellation,
+            _ => return rouille::Response::from(Status::BadRequest),
+        };
+        let service = Arc::new(service.with_cancellation(cancellation.clone()));
+
+        if let Some(admin_req) = req.remove_prefix("/admin") {
+            return admin::serve(
+                service.clone(),
+                read_only.clone(),
+                config.admin_token.as_deref(),
+                &admin_req,
+            );
+        }
+
+        // Webhooks make the server post to the URLs they are given, so only
+        // the admin manages them.
+        if req.url().starts_with("/webhooks") {
+            match admin::authorize(config.admin_token.as_deref(), req) {
+                Ok(()) => {}
+                Err(res) => return res,
+            }
+        }
 
-pub fn serve(url: &str, repo: Arc<dyn Repository>) {
-    rouille::start_server(url, move |req| {
-        router!(req,
+        if req.method() != "GET"
+            && req.url() != "/pokemons/validate"
+            && req.url() != "/validate"
+            && read_only.load(Ordering::SeqCst)
+        {
+            return rouille::Response::from(Status::ServiceUnavailable);
+        }
+
+        let quota = match config.daily_quota {
+            Some(limit) if !UNMETERED.contains(&req.url().as_str()) => {
+                match quota::consume(usage_repo.clone(), limit, &config.api_keys, req) {
+                    Ok(quota) => Some(quota),
+                    Err(res) => return res,
+                }
+            }
+            _ => None,
+        };
+
+        let res = router!(req,
+            (GET) (/pokemons) => {
+                match req.get_param("as_of") {
+                    Some(_) => fetch_pokemons_as_of::serve(change_repo.clone(), None, req),
+                    None => fetch_all_pokemons::serve_cached(
+                        service.clone(),
+                        change_repo.clone(),
+                        collection_cache.clone(),
+                        req,
+                    ),
+                }
+            },
+            (GET) (/pokemons/index) => {
+                fetch_index::serve(service.clone())
+            },
+            (GET) (/pokemons/skipped) => {
+                fetch_all_pokemons::serve_skipped(service.clone())
+            },
+            (GET) (/pokemons/range/{from: u16}/{to: u16}) => {
+                fetch_pokemon_range::serve(service.clone(), from, to)
+            },
+            (GET) (/pokemons/by-name/{name: String}) => {
+                fetch_pokemon_by_name::serve(service.clone(), name)
+            },
+            (GET) (/pokemons/{number: u16}) => {
+                match req.get_param("as_of") {
+                    Some(_) => fetch_pokemons_as_of::serve(change_repo.clone(), Some(number), req),
+                    None => fetch_pokemon::serve(service.clone(), number),
+                }
+            },
+            (POST) (/pokemons) => {
+                create_pokemon::serve(service.clone(), req)
+            },
+            (PUT) (/pokemons/{number: u16}) => {
+                update_pokemon::serve(service.clone(), number, req)
+            },
+            (POST) (/pokemons/import) => {
+                import_pokemons::serve(service.clone(), req)
+            },
+            (POST) (/pokemons/ingest) => {
+                ingest_pokemons::serve(service.clone(), req)
+            },
+            (POST) (/pokemons/validate) => {
+                validate_pokemon::serve(req)
+            },
+            (PATCH) (/pokemons/batch) => {
+                update_pokemons::serve(service.clone(), req)
+            },
+            (PATCH) (/pokemons/{number: u16}) => {
+                patch_pokemon::serve(service.clone(), number, req)
+            },
+            (PATCH) (/pokemons/{number: u16}/types) => {
+                update_pokemon_types::serve(service.clone(), number, req)
+            },
+            (PUT) (/pokemons/{number: u16}/evolutions) => {
+                update_pokemon_evolutions::serve(service.clone(), number, req)
+            },
+            (PUT) (/pokemons/{number: u16}/abilities) => {
+                update_pokemon_abilities::serve(service.clone(), number, req)
+            },
+            (PUT) (/pokemons/{number: u16}/measurements) => {
+                update_pokemon_measurements::serve(service.clone(), number, req)
+            },
+            (GET) (/pokemons/{number: u16}/sprite) => {
+                fetch_pokemon_sprite::serve(service.clone(), number)
+            },
+            (PUT) (/pokemons/{number: u16}/sprite) => {
+                update_pokemon_sprite::serve(service.clone(), number, req)
+            },
+            (PUT) (/pokemons/{number: u16}/class) => {
+                update_pokemon_class::serve(service.clone(), number, req)
+            },
+            (GET) (/pokemons/{number: u16}/cry) => {
+                fetch_pokemon_cry::serve(service.clone(), cry_repo.clone(), number, req)
+            },
+            (PUT) (/pokemons/{number: u16}/cry) => {
+                update_pokemon_cry::serve(service.clone(), cry_repo.clone(), number, req)
+            },
+            (GET) (/pokemons/{number: u16}/moves) => {
+                fetch_moves::serve(service.clone(), moves_repo.clone(), number)
+            },
+            (POST) (/pokemons/{number: u16}/moves) => {
+                add_move::serve(service.clone(), moves_repo.clone(), number, req)
+            },
+            (DELETE) (/pokemons/{number: u16}/moves/{name: String}) => {
+                remove_move::serve(service.clone(), moves_repo.clone(), number, name)
+            },
+            (DELETE) (/pokemons) => {
+                delete_pokemons_by_type::serve(service.clone(), req)
+            },
+            (DELETE) (/pokemons/{number: u16}) => {
+                delete_pokemon::serve(service.clone(), number)
+            },
+            (GET) (/changes) => {
+                fetch_changes::serve(change_repo.clone(), req)
+            },
+            (GET) (/export) => {
+                export::serve(service.repo(), req)
+            },
+            (GET) (/health) => {
+                health::serve(service.repo(), write_queue.clone())
+            },
+            (GET) (/metrics) => {
+                metrics::serve(write_queue.clone())
+            },
+            (GET) (/progress) => {
+                fetch_progress::serve(service.clone(), req)
+            },
+            (GET) (/names/check) => {
+                check_name::serve(service.clone(), req)
+            },
+            (GET) (/types) => {
+                fetch_types::serve(req)
+            },
+            (GET) (/types/stats) => {
+                fetch_type_stats::serve(service.clone())
+            },
+            (GET) (/types/{pokemon_type: String}/pokemons) => {
+                fetch_pokemons_by_type::serve(service.clone(), pokemon_type)
+            },
+            (GET) (/usage) => {
+                fetch_usage::serve(
+                    usage_repo.clone(),
+                    config.daily_quota,
+                    &config.api_keys,
+                    req,
+                )
+            },
+            (GET) (/webhooks) => {
+                fetch_all_webhooks::serve(webhook_repo.clone())
+            },
+            (GET) (/webhooks/{id: u32}) => {
+                fetch_webhook::serve(webhook_repo.clone(), id)
+            },
+            (GET) (/webhooks/{id: u32}/deliveries) => {
+                fetch_webhook_deliveries::serve(webhook_repo.clone(), id)
+            },
+            (POST) (/webhooks) => {
+                create_webhook::serve(webhook_repo.clone(), req)
+            },
+            (DELETE) (/webhooks/{id: u32}) => {
+                delete_webhook::serve(webhook_repo.clone(), id)
+            },
+            (GET) (/wishlist) => {
+                fetch_wishlist::serve(wishlist_repo.clone())
+            },
+            (POST) (/wishlist) => {
+                create_wish::serve(wishlist_repo.clone(), req)
+            },
+            (POST) (/wishlist/{number: u16}/promote) => {
+                promote_wish::serve(
+                    service.clone(),
+                    wishlist_repo.clone(),
+                    number,
+                    req,
+                )
+            },
+            (DELETE) (/wishlist/{number: u16}) => {
+                delete_wish::serve(wishlist_repo.clone(), number)
+            },
+            // Deprecated aliases of the /pokemons routes.
             (GET) (/) => {
-                fetch_all_pokemons::serve(repo.clone())
+                let res = match req.get_param("as_of") {
+                    Some(_) => fetch_pokemons_as_of::serve(change_repo.clone(), None, req),
+                    None => fetch_all_pokemons::serve_cached(
+                        service.clone(),
+                        change_repo.clone(),
+                        collection_cache.clone(),
+                        req,
+                    ),
+                };
+                deprecated(res, req)
+            },
+            (GET) (/index) => {
+                deprecated(fetch_index::serve(service.clone()), req)
+            },
+            (GET) (/range/{from: u16}/{to: u16}) => {
+                deprecated(fetch_pokemon_range::serve(service.clone(), from, to), req)
             },
             (GET) (/{number: u16}) => {
-                fetch_pokemon::serve(repo.clone(), number)
+                let res = match req.get_param("as_of") {
+                    Some(_) => fetch_pokemons_as_of::serve(change_repo.clone(), Some(number), req),
+                    None => fetch_pokemon::serve(service.clone(), number),
+                };
+                deprecated(res, req)
             },
-            (GET) (/health) => {
-                health::serve()
+            (GET) (/{number: u16}/moves) => {
+                deprecated(
+                    fetch_moves::serve(service.clone(), moves_repo.clone(), number),
+                    req,
+                )
             },
             (POST) (/) => {
-                create_pokemon::serve(repo.clone(), req)
+                deprecated(create_pokemon::serve(service.clone(), req), req)
+            },
+            (POST) (/ingest) => {
+                deprecated(ingest_pokemons::serve(service.clone(), req), req)
+            },
+            (POST) (/validate) => {
+                deprecated(validate_pokemon::serve(req), req)
+            },
+            (PATCH) (/{number: u16}/types) => {
+                deprecated(
+                    update_pokemon_types::serve(service.clone(), number, req),
+                    req,
+                )
+            },
+            (DELETE) (/) => {
+                deprecated(delete_pokemons_by_type::serve(service.clone(), req), req)
             },
             (DELETE) (/{number: u16}) => {
-                delete_pokemon::serve(repo.clone(), number)
+                deprecated(delete_pokemon::serve(service.clone(), number), req)
             },
             _ => {
                 rouille::Response::from(Status::NotFound)
             }
+        );
+
+        // The use cases fail without knowing why once cancelled.
+        let res = match res.status_code {
+            500.. if cancellation.is_cancelled() => rouille::Response::from(Status::GatewayTimeout),
+            _ => res,
+        };
+
+        match quota {
+            Some(quota) => quota::with_headers(res, &quota),
+            None => res,
+        }
+    };
+    let handler = traced(panics::catch(handler));
+
+    if http2 {
+        let socket = match listener {
+            Listener::Address(url) => match TcpListener::bind(url) {
+                Ok(socket) => socket,
+                _ => panic!("Error while starting the server"),
+            },
+            Listener::Inherited(socket) => socket,
+        };
+        ready(healthy);
+        return http2::run(socket, handler, workers, tls);
+    }
+
+    match listener {
+        Listener::Address(url) => {
+            let server = match (rouille::Server::new(url, handler), workers) {
+                (Ok(server), Some(workers)) => server.pool_size(workers),
+                (Ok(server), None) => server,
+                _ => panic!("Error while starting the server"),
+            };
+            ready(healthy);
+            server.run();
+        }
+        Listener::Inherited(socket) => {
+            ready(healthy);
+            listener::run(socket, handler, workers);
+        }
+    }
+}
+
+/// Tells systemd the server accepts requests, unless the repository could
+/// not be reached when starting: systemd then restarts the server after its
+/// start timeout and the previous one keeps serving meanwhile.
+fn ready(healthy: bool) {
+    match healthy {
+        true => systemd::notify("READY=1"),
+        false => {
+            eprintln!("The repository cannot be reached, not notifying systemd");
+            systemd::notify("STATUS=The repository cannot be reached");
+        }
+    }
+}
+
+/// The earliest of the server timeout and the one the client asks for, in
+/// seconds, with the `X-Request-Timeout` header.
+fn cancellation(
+    request_timeout: Option<Duration>,
+    req: &rouille::Request,
+) -> Result<Cancellation, ()> {
+    let mut cancellation = Cancellation::new();
+    if let Some(timeout) = request_timeout {
+        cancellation = cancellation.with_timeout(timeout);
+    }
+    match req.header("X-Request-Timeout").map(str::parse::<f64>) {
+        Some(Ok(seconds)) => match Duration::try_from_secs_f64(seconds) {
+            Ok(timeout) => Ok(cancellation.with_timeout(timeout)),
+            _ => Err(()),
+        },
+        Some(Err(_)) => Err(()),
+        None => Ok(cancellation),
+    }
+}
+
+/// Serves the request within the trace of its `traceparent` or B3 headers,
+/// or a new one, and tells its id in `X-Trace-Id` when it fails.
+fn traced<F>(handler: F) -> impl Fn(&rouille::Request) -> rouille::Response
+where
+    F: Fn(&rouille::Request) -> rouille::Response,
+{
+    move |req| {
+        let context = trace::Context::from_headers(|name| req.header(name));
+        let trace_id = String::from(context.trace_id());
+
+        let res = trace::scope(context, || handler(req));
+        match res.status_code {
+            400.. => res.with_additional_header("X-Trace-Id", trace_id),
+            _ => res,
+        }
+    }
+}
+
+fn deprecated(res: rouille::Response, req: &rouille::Request) -> rouille::Response {
+    let successor = match req.url().as_str() {
+        "/" => String::from("/pokemons"),
+        url => format!("/pokemons{}", url),
+    };
+    res.with_additional_header("Deprecation", "true")
+        .with_additional_header(
+            "Link",
+            format!("<{}>; rel=\"successor-version\"", successor),
         )
-    });
 }
 
 enum Status {
     Ok,
+    NotModified,
     BadRequest,
+    Unauthorized,
     NotFound,
     Conflict,
+    PreconditionFailed,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    RangeNotSatisfiable,
+    UnprocessableEntity,
+    TooManyRequests,
     InternalServerError,
+    BadGateway,
+    ServiceUnavailable,
+    GatewayTimeout,
 }
 
 impl From<Status> for rouille::Response {
     fn from(status: Status) -> Self {
         let status_code = match status {
             Status::Ok => 200,
+            Status::NotModified => 304,
             Status::BadRequest => 400,
+            Status::Unauthorized => 401,
             Status::NotFound => 404,
             Status::Conflict => 409,
+            Status::PreconditionFailed => 412,
+            Status::PayloadTooLarge => 413,
+            Status::UnsupportedMediaType => 415,
+            Status::RangeNotSatisfiable => 416,
+            Status::UnprocessableEntity => 422,
+            Status::TooManyRequests => 429,
             Status::InternalServerError => 500,
+            Status::BadGateway => 502,
+            Status::ServiceUnavailable => 503,
+            Status::GatewayTimeout => 504,
         };
         Self {
             status_code,
@@ -0,0 +1,92 @@
+use crate::clock;
+use crate::trace;
+use serde::Serialize;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Once;
+
+static PANICS: AtomicU64 = AtomicU64::new(0);
+static REQUESTS: AtomicU64 = AtomicU64::new(0);
+static HOOK: Once = Once::new();
+
+thread_local! {
+    /// Whether the thread is running a handler, whose panics are reported
+    /// by `catch` instead of the default hook.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static REPORT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Serialize)]
+struct Response {
+    message: String,
+    request_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<String>,
+}
+
+/// How many requests panicked since the server started.
+pub fn count() -> u64 {
+    PANICS.load(Ordering::Relaxed)
+}
+
+/// Turns the panics of `handler` into 500 responses carrying an id, which
+/// is logged along with the backtrace so that a report can be matched with
+/// the logs.
+pub(super) fn catch<F>(handler: F) -> impl Fn(&rouille::Request) -> rouille::Response
+where
+    F: Fn(&rouille::Request) -> rouille::Response,
+{
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| match CATCHING.with(Cell::get) {
+            true => REPORT.with(|report| {
+                report.replace(Some(format!("{}\n{}", info, Backtrace::force_capture())));
+            }),
+            false => default(info),
+        }));
+    });
+
+    move |req| {
+        CATCHING.with(|catching| catching.set(true));
+        let res = panic::catch_unwind(AssertUnwindSafe(|| handler(req)));
+        CATCHING.with(|catching| catching.set(false));
+
+        match res {
+            Ok(res) => res,
+            Err(_) => {
+                PANICS.fetch_add(1, Ordering::Relaxed);
+                let request_id = request_id();
+                let trace_id = trace::current().map(|context| String::from(context.trace_id()));
+                let report = REPORT.with(|report| report.take()).unwrap_or_default();
+                eprintln!(
+                    "Request {} ({} {}) trace {} {}",
+                    request_id,
+                    req.method(),
+                    req.raw_url(),
+                    trace_id.as_deref().unwrap_or("-"),
+                    report
+                );
+                rouille::Response::json(&Response {
+                    message: String::from("Internal Server Error"),
+                    request_id: request_id.clone(),
+                    trace_id,
+                })
+                .with_status_code(500)
+                .with_additional_header("X-Request-Id", request_id)
+            }
+        }
+    }
+}
+
+/// Unique among the runs of the server thanks to the time and the PID.
+fn request_id() -> String {
+    format!(
+        "{:x}-{:x}-{:x}",
+        clock::now(),
+        process::id(),
+        REQUESTS.fetch_add(1, Ordering::Relaxed)
+    )
+}
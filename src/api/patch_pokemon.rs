@@ -0,0 +1,171 @@
+use crate::api::json_patch::{self, Operation};
+use crate::api::{fetch_pokemon, validate_pokemon, Status};
+use crate::domain::{self, patch_pokemon, update_pokemon};
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+const JSON_PATCH: &str = "application/json-patch+json";
+const MERGE_PATCH: &str = "application/merge-patch+json";
+
+/// The fields to change, the others keep their value.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Request {
+    name: Option<String>,
+    types: Option<Vec<String>>,
+}
+
+/// The Pokemon as the operations see it. Any other field makes the patch
+/// unprocessable.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Document {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+/// Changes the fields of a JSON object, sent as a merge patch or as plain
+/// JSON, or applies a JSON Patch. Either way the result is checked against
+/// the domain rules and answers 422 when it breaks them.
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let content_type = req
+        .header("Content-Type")
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim);
+
+    match content_type {
+        Some(JSON_PATCH) => serve_json_patch(service, number, req),
+        Some(MERGE_PATCH) | Some("application/json") => serve_merge_patch(service, number, req),
+        _ => rouille::Response::from(Status::UnsupportedMediaType)
+            .with_additional_header("Accept-Patch", format!("{}, {}", JSON_PATCH, MERGE_PATCH)),
+    }
+}
+
+fn serve_merge_patch(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match req.data().map(serde_json::from_reader::<_, Request>) {
+        Some(Ok(req)) => patch_pokemon::Request {
+            number,
+            name: req.name,
+            types: req.types,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.patch_pokemon(req) {
+        Ok(patch_pokemon::Response {
+            number,
+            name,
+            types,
+        }) => rouille::Response::json(&fetch_pokemon::Response {
+            number,
+            name,
+            types,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+        }),
+        Err(patch_pokemon::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors).with_status_code(422)
+        }
+        Err(patch_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(patch_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
+
+/// A `test` operation which fails answers 409.
+fn serve_json_patch(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let operations = match rouille::input::json_input::<Vec<Operation>>(req) {
+        Ok(operations) => operations,
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let pokemon = match service.fetch_pokemon(domain::fetch_pokemon::Request { number }) {
+        Ok(pokemon) => pokemon,
+        Err(domain::fetch_pokemon::Error::BadRequest) => {
+            return rouille::Response::from(Status::BadRequest)
+        }
+        Err(domain::fetch_pokemon::Error::NotFound) => {
+            return rouille::Response::from(Status::NotFound)
+        }
+        Err(domain::fetch_pokemon::Error::Unknown) => {
+            return rouille::Response::from(Status::InternalServerError)
+        }
+    };
+
+    let document = json!({
+        "number": pokemon.number,
+        "name": pokemon.name,
+        "types": pokemon.types,
+    });
+    let patched =
+        match json_patch::apply(&document, operations).map(serde_json::from_value::<Document>) {
+            Ok(Ok(patched)) => patched,
+            Ok(Err(_)) | Err(json_patch::Error::Unprocessable) => {
+                return rouille::Response::from(Status::UnprocessableEntity)
+            }
+            Err(json_patch::Error::TestFailed) => return rouille::Response::from(Status::Conflict),
+        };
+
+    if patched.number != pokemon.number {
+        return rouille::Response::from(Status::UnprocessableEntity);
+    }
+    if patched.name == pokemon.name && patched.types == pokemon.types {
+        return rouille::Response::json(&fetch_pokemon::Response {
+            number: pokemon.number,
+            name: pokemon.name,
+            types: pokemon.types,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+        });
+    }
+
+    let req = update_pokemon::Request {
+        number,
+        name: patched.name,
+        types: patched.types,
+    };
+    match service.update_pokemon(req) {
+        Ok(update_pokemon::Response {
+            number,
+            name,
+            types,
+        }) => rouille::Response::json(&fetch_pokemon::Response {
+            number,
+            name,
+            types,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+        }),
+        Err(update_pokemon::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors).with_status_code(422)
+        }
+        Err(update_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,82 @@
+use crate::api::fetch_pokemon::{Ability, Evolution};
+use crate::domain::fetch_pokemon;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Pokemon as the list endpoints return it. It borrows from the use case
+/// responses, so a list is serialized without copying names and types.
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "Pokemon")]
+pub(super) struct Pokemon<'a> {
+    number: u16,
+    name: &'a str,
+    types: &'a [String],
+    /// Left out by the endpoints which don't read the evolutions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    evolutions: Option<Vec<Evolution>>,
+    /// Left out by the endpoints which don't read the abilities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    abilities: Option<Vec<Ability>>,
+    /// In meters, left out until it is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<f64>,
+    /// In kilograms, left out until it is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f64>,
+    /// The URL of the image, left out until it is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sprite: Option<&'a str>,
+    /// Normal, Legendary or Mythical, left out by the endpoints which don't
+    /// read it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<&'a str>,
+}
+
+impl<'a> Pokemon<'a> {
+    pub(super) fn new(number: u16, name: &'a str, types: &'a [String]) -> Self {
+        Self {
+            number,
+            name,
+            types,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+        }
+    }
+
+    pub(super) fn with_evolutions(self, evolutions: &[fetch_pokemon::Evolution]) -> Self {
+        Self {
+            evolutions: Some(evolutions.iter().map(Evolution::from).collect()),
+            ..self
+        }
+    }
+
+    pub(super) fn with_abilities(self, abilities: &[fetch_pokemon::Ability]) -> Self {
+        Self {
+            abilities: Some(abilities.iter().map(Ability::from).collect()),
+            ..self
+        }
+    }
+
+    pub(super) fn with_measurements(self, height: Option<f64>, weight: Option<f64>) -> Self {
+        Self {
+            height,
+            weight,
+            ..self
+        }
+    }
+
+    pub(super) fn with_sprite(self, sprite: Option<&'a str>) -> Self {
+        Self { sprite, ..self }
+    }
+
+    pub(super) fn with_class(self, class: &'a str) -> Self {
+        Self {
+            class: Some(class),
+            ..self
+        }
+    }
+}
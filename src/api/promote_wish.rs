@@ -0,0 +1,42 @@
+use crate::api::{create_pokemon, validate_pokemon, Status};
+use crate::domain::promote_wish;
+use crate::repositories::wishlist::Repository as WishlistRepository;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    types: Vec<String>,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => promote_wish::Request {
+            number,
+            types: req.types,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.promote_wish(wishlist_repo, req) {
+        Ok(promote_wish::Response {
+            number,
+            name,
+            types,
+        }) => rouille::Response::json(&create_pokemon::Response {
+            number,
+            name,
+            types,
+        }),
+        Err(promote_wish::Error::BadRequest(errors)) => validate_pokemon::bad_request(errors),
+        Err(promote_wish::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(promote_wish::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(promote_wish::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
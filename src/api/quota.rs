@@ -0,0 +1,72 @@
+use crate::api::Status;
+use crate::clock::now;
+use crate::domain::consume_quota;
+use crate::repositories::usage::Repository;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub struct Quota {
+    limit: u32,
+    remaining: u32,
+    reset: u64,
+}
+
+pub fn consume(
+    repo: Arc<dyn Repository>,
+    limit: u32,
+    api_keys: &[String],
+    req: &rouille::Request,
+) -> Result<Quota, rouille::Response> {
+    let key = match req.header("X-Api-Key") {
+        Some(key) if issued(api_keys, key) => String::from(key),
+        _ => return Err(rouille::Response::from(Status::Unauthorized)),
+    };
+    let (day, reset) = today();
+
+    let req = consume_quota::Request { key, limit, day };
+    match consume_quota::execute(repo, req) {
+        Ok(consume_quota::Response { limit, remaining }) => Ok(Quota {
+            limit,
+            remaining,
+            reset,
+        }),
+        Err(consume_quota::Error::BadRequest) => Err(rouille::Response::from(Status::Unauthorized)),
+        Err(consume_quota::Error::QuotaExceeded) => Err(with_headers(
+            rouille::Response::from(Status::TooManyRequests),
+            &Quota {
+                limit,
+                remaining: 0,
+                reset,
+            },
+        )),
+        Err(consume_quota::Error::Unknown) => {
+            Err(rouille::Response::from(Status::InternalServerError))
+        }
+    }
+}
+
+pub fn with_headers(res: rouille::Response, quota: &Quota) -> rouille::Response {
+    res.with_additional_header("X-RateLimit-Limit", quota.limit.to_string())
+        .with_additional_header("X-RateLimit-Remaining", quota.remaining.to_string())
+        .with_additional_header("X-RateLimit-Reset", quota.reset.to_string())
+}
+
+/// Whether the key is one of the issued ones. Every key is compared in
+/// constant time, so that the time taken doesn't tell how much of one was
+/// right.
+pub fn issued(api_keys: &[String], key: &str) -> bool {
+    api_keys.iter().fold(false, |found, issued| {
+        found | bool::from(issued.as_bytes().ct_eq(key.as_bytes()))
+    })
+}
+
+/// Returns the current UTC day and the number of seconds until it ends.
+pub fn today() -> (u64, u64) {
+    let now = now();
+    (
+        now / SECONDS_PER_DAY,
+        SECONDS_PER_DAY - now % SECONDS_PER_DAY,
+    )
+}
@@ -0,0 +1,20 @@
+use crate::api::Status;
+use crate::domain::remove_move;
+use crate::repositories::moves::Repository as MovesRepository;
+use crate::service::PokedexService;
+use std::sync::Arc;
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    moves_repo: Arc<dyn MovesRepository>,
+    number: u16,
+    name: String,
+) -> rouille::Response {
+    let req = remove_move::Request { number, name };
+    match service.remove_move(moves_repo, req) {
+        Ok(()) => rouille::Response::from(Status::Ok),
+        Err(remove_move::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(remove_move::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(remove_move::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+    }
+}
@@ -0,0 +1,291 @@
+use crate::api::{
+    add_move, check_name, create_pokemon, create_webhook, create_wish, delete_pokemons_by_type,
+    fetch_all_pokemons, fetch_all_webhooks, fetch_changes, fetch_moves, fetch_pokemon,
+    fetch_progress, fetch_type_stats, fetch_types, fetch_usage, fetch_webhook,
+    fetch_webhook_deliveries, fetch_wishlist, health, import_pokemons, patch_pokemon, pokemon,
+    promote_wish, update_pokemon, update_pokemon_abilities, update_pokemon_class,
+    update_pokemon_cry, update_pokemon_evolutions, update_pokemon_measurements,
+    update_pokemon_sprite, update_pokemon_types, update_pokemons, validate_pokemon,
+};
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use schemars::schema_for;
+use std::collections::BTreeMap;
+
+fn schemas() -> Vec<(&'static str, RootSchema)> {
+    let schemas = vec![
+        ("CreatePokemonRequest", schema_for!(create_pokemon::Request)),
+        (
+            "CreatePokemonResponse",
+            schema_for!(create_pokemon::Response),
+        ),
+        ("CheckNameResponse", schema_for!(check_name::Response)),
+        ("FetchPokemonResponse", schema_for!(fetch_pokemon::Response)),
+        ("FetchPokemonByNameResponse", schema_for!(pokemon::Pokemon)),
+        (
+            "FetchAllPokemonsResponse",
+            schema_for!(Vec<fetch_all_pokemons::Response>),
+        ),
+        (
+            "FetchSkippedPokemonsResponse",
+            schema_for!(Vec<fetch_all_pokemons::Skipped>),
+        ),
+        (
+            "FetchPokemonRangeResponse",
+            schema_for!(Vec<pokemon::Pokemon>),
+        ),
+        (
+            "ImportPokemonsRequest",
+            schema_for!(Vec<import_pokemons::ImportedPokemon>),
+        ),
+        (
+            "ImportPokemonsResponse",
+            schema_for!(import_pokemons::Response),
+        ),
+        (
+            "ImportPokemonsProblems",
+            schema_for!(import_pokemons::Problems),
+        ),
+        (
+            "DeletePokemonsByTypeResponse",
+            schema_for!(delete_pokemons_by_type::Response),
+        ),
+        ("UpdatePokemonRequest", schema_for!(update_pokemon::Request)),
+        (
+            "UpdatePokemonResponse",
+            schema_for!(update_pokemon::Response),
+        ),
+        ("PatchPokemonRequest", schema_for!(patch_pokemon::Request)),
+        (
+            "UpdatePokemonTypesRequest",
+            schema_for!(update_pokemon_types::Request),
+        ),
+        (
+            "UpdatePokemonTypesResponse",
+            schema_for!(update_pokemon_types::Response),
+        ),
+        (
+            "UpdatePokemonEvolutionsRequest",
+            schema_for!(update_pokemon_evolutions::Request),
+        ),
+        (
+            "UpdatePokemonEvolutionsResponse",
+            schema_for!(fetch_pokemon::Response),
+        ),
+        (
+            "UpdatePokemonAbilitiesRequest",
+            schema_for!(update_pokemon_abilities::Request),
+        ),
+        (
+            "UpdatePokemonAbilitiesResponse",
+            schema_for!(fetch_pokemon::Response),
+        ),
+        (
+            "UpdatePokemonMeasurementsRequest",
+            schema_for!(update_pokemon_measurements::Request),
+        ),
+        (
+            "UpdatePokemonMeasurementsResponse",
+            schema_for!(fetch_pokemon::Response),
+        ),
+        (
+            "UpdatePokemonSpriteRequest",
+            schema_for!(update_pokemon_sprite::Request),
+        ),
+        (
+            "UpdatePokemonSpriteResponse",
+            schema_for!(fetch_pokemon::Response),
+        ),
+        (
+            "UpdatePokemonClassRequest",
+            schema_for!(update_pokemon_class::Request),
+        ),
+        (
+            "UpdatePokemonClassResponse",
+            schema_for!(fetch_pokemon::Response),
+        ),
+        (
+            "UpdatePokemonCryResponse",
+            schema_for!(update_pokemon_cry::Response),
+        ),
+        (
+            "FetchMovesResponse",
+            schema_for!(Vec<fetch_moves::Response>),
+        ),
+        ("AddMoveRequest", schema_for!(add_move::Request)),
+        ("AddMoveResponse", schema_for!(fetch_moves::Response)),
+        (
+            "UpdatePokemonsRequest",
+            schema_for!(Vec<update_pokemons::Request>),
+        ),
+        (
+            "UpdatePokemonsResponse",
+            schema_for!(update_pokemons::Response),
+        ),
+        (
+            "ValidatePokemonRequest",
+            schema_for!(validate_pokemon::Request),
+        ),
+        (
+            "ValidatePokemonResponse",
+            schema_for!(validate_pokemon::Response),
+        ),
+        (
+            "InvalidFieldsResponse",
+            schema_for!(validate_pokemon::Errors),
+        ),
+        ("FetchChangesResponse", schema_for!(fetch_changes::Response)),
+        (
+            "FetchProgressResponse",
+            schema_for!(fetch_progress::Response),
+        ),
+        (
+            "FetchIndexResponse",
+            schema_for!(std::collections::BTreeMap<String, Vec<pokemon::Pokemon>>),
+        ),
+        (
+            "FetchPokemonsByTypeResponse",
+            schema_for!(Vec<pokemon::Pokemon>),
+        ),
+        (
+            "FetchTypeStatsResponse",
+            schema_for!(fetch_type_stats::Response),
+        ),
+        (
+            "FetchTypesResponse",
+            schema_for!(Vec<fetch_types::Response>),
+        ),
+        ("FetchUsageResponse", schema_for!(fetch_usage::Response)),
+        ("CreateWebhookRequest", schema_for!(create_webhook::Request)),
+        (
+            "CreateWebhookResponse",
+            schema_for!(create_webhook::Response),
+        ),
+        ("FetchWebhookResponse", schema_for!(fetch_webhook::Response)),
+        (
+            "FetchAllWebhooksResponse",
+            schema_for!(Vec<fetch_all_webhooks::Response>),
+        ),
+        (
+            "FetchWebhookDeliveriesResponse",
+            schema_for!(Vec<fetch_webhook_deliveries::Response>),
+        ),
+        (
+            "FetchWishlistResponse",
+            schema_for!(Vec<fetch_wishlist::Response>),
+        ),
+        ("CreateWishRequest", schema_for!(create_wish::Request)),
+        ("CreateWishResponse", schema_for!(create_wish::Response)),
+        ("PromoteWishRequest", schema_for!(promote_wish::Request)),
+        ("HealthResponse", schema_for!(health::Response)),
+    ];
+
+    schemas
+        .into_iter()
+        .map(|(name, mut root)| {
+            root.schema.metadata().title = Some(String::from(name));
+            (name, root)
+        })
+        .collect()
+}
+
+pub fn json_schema() -> String {
+    let schemas = schemas()
+        .into_iter()
+        .collect::<BTreeMap<&str, RootSchema>>();
+    match serde_json::to_string_pretty(&schemas) {
+        Ok(json) => json,
+        _ => panic!("Error while serializing the api schemas"),
+    }
+}
+
+pub fn typescript() -> String {
+    let mut definitions = BTreeMap::new();
+    let mut declarations = vec![];
+
+    for (name, root) in schemas() {
+        declarations.push(declaration(name, &root.schema));
+        definitions.extend(root.definitions);
+    }
+
+    for (name, schema) in definitions {
+        if let Schema::Object(schema) = schema {
+            declarations.push(declaration(&name, &schema));
+        }
+    }
+
+    declarations.join("\n")
+}
+
+fn declaration(name: &str, schema: &SchemaObject) -> String {
+    match &schema.object {
+        Some(object)
+            if matches!(
+                object.additional_properties.as_deref(),
+                None | Some(Schema::Bool(false))
+            ) =>
+        {
+            let mut lines = vec![format!("export interface {} {{", name)];
+            for (field, property) in &object.properties {
+                let optional = if object.required.contains(field) {
+                    ""
+                } else {
+                    "?"
+                };
+                lines.push(format!("  {}{}: {};", field, optional, type_of(property)));
+            }
+            lines.push(String::from("}\n"));
+            lines.join("\n")
+        }
+        _ => format!("export type {} = {};\n", name, type_of_object(schema)),
+    }
+}
+
+fn type_of(schema: &Schema) -> String {
+    match schema {
+        Schema::Object(schema) => type_of_object(schema),
+        Schema::Bool(_) => String::from("unknown"),
+    }
+}
+
+fn type_of_object(schema: &SchemaObject) -> String {
+    if let Some(reference) = &schema.reference {
+        return reference.trim_start_matches("#/definitions/").to_string();
+    }
+
+    let instance_types = match &schema.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => vec![**instance_type],
+        Some(SingleOrVec::Vec(instance_types)) => instance_types.clone(),
+        None => return String::from("unknown"),
+    };
+
+    instance_types
+        .into_iter()
+        .map(|instance_type| match instance_type {
+            InstanceType::Boolean => String::from("boolean"),
+            InstanceType::Integer | InstanceType::Number => String::from("number"),
+            InstanceType::String => String::from("string"),
+            InstanceType::Null => String::from("null"),
+            InstanceType::Object => match schema
+                .object
+                .as_ref()
+                .and_then(|o| o.additional_properties.as_ref())
+            {
+                Some(values) => format!("Record<string, {}>", type_of(values)),
+                None => String::from("Record<string, unknown>"),
+            },
+            InstanceType::Array => match schema.array.as_ref().and_then(|a| a.items.as_ref()) {
+                Some(SingleOrVec::Single(items)) => format!("{}[]", wrap(type_of(items))),
+                _ => String::from("unknown[]"),
+            },
+        })
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+fn wrap(ty: String) -> String {
+    if ty.contains(' ') {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
@@ -0,0 +1,104 @@
+use crate::api::fetch_pokemon::{Ability, Evolution};
+use crate::api::validate_pokemon;
+use crate::api::Status;
+use crate::domain::replace_pokemon;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::sync::Arc;
+
+/// The other fields are optional: the ones left out keep their value, and
+/// `null` removes a measurement or the sprite.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    name: String,
+    types: Vec<String>,
+    #[serde(default)]
+    evolutions: Option<Vec<Evolution>>,
+    #[serde(default)]
+    abilities: Option<Vec<Ability>>,
+    /// In meters.
+    #[serde(default, deserialize_with = "present")]
+    #[schemars(with = "Option<f64>")]
+    height: Option<Option<f64>>,
+    /// In kilograms.
+    #[serde(default, deserialize_with = "present")]
+    #[schemars(with = "Option<f64>")]
+    weight: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "present")]
+    #[schemars(with = "Option<String>")]
+    sprite: Option<Option<String>>,
+    #[serde(default)]
+    class: Option<String>,
+}
+
+// A field given as `null` is `Some(None)`, a field left out being `None`.
+fn present<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+/// Replaces the Pokemon. With `If-None-Match: *` the Pokemon is created
+/// instead, only when its number is free, so that automation can retry it
+/// safely: 201 when created, 412 when the number is taken.
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let create = req.header("If-None-Match").map(str::trim) == Some("*");
+
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => replace_pokemon::Request {
+            number,
+            name: req.name,
+            types: req.types,
+            create,
+            evolutions: req
+                .evolutions
+                .map(|evolutions| evolutions.into_iter().map(From::from).collect()),
+            abilities: req
+                .abilities
+                .map(|abilities| abilities.into_iter().map(From::from).collect()),
+            height: req.height,
+            weight: req.weight,
+            sprite: req.sprite,
+            class: req.class,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    match service.replace_pokemon(req) {
+        Ok(replace_pokemon::Response {
+            number,
+            name,
+            types,
+        }) => {
+            let res = rouille::Response::json(&Response {
+                number,
+                name,
+                types,
+            });
+            match create {
+                true => res.with_status_code(201),
+                false => res,
+            }
+        }
+        Err(replace_pokemon::Error::BadRequest(errors)) => validate_pokemon::bad_request(errors),
+        Err(replace_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(replace_pokemon::Error::Conflict) => {
+            rouille::Response::from(Status::PreconditionFailed)
+        }
+        Err(replace_pokemon::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::api::fetch_pokemon::{self, Ability};
+use crate::api::validate_pokemon;
+use crate::api::Status;
+use crate::domain::update_pokemon_abilities;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    abilities: Vec<Ability>,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon_abilities::Request {
+            number,
+            abilities: req.abilities.into_iter().map(Into::into).collect(),
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.update_pokemon_abilities(req) {
+        Ok(update_pokemon_abilities::Response {
+            number,
+            name,
+            types,
+            abilities,
+        }) => rouille::Response::json(&fetch_pokemon::Response {
+            number,
+            name,
+            types,
+            evolutions: None,
+            abilities: Some(abilities.iter().map(Ability::from).collect()),
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+        }),
+        Err(update_pokemon_abilities::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors)
+        }
+        Err(update_pokemon_abilities::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon_abilities::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::api::fetch_pokemon;
+use crate::api::validate_pokemon;
+use crate::api::Status;
+use crate::domain::update_pokemon_class;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// `class` is one of `Normal`, `Legendary` and `Mythical`, whatever the
+/// case.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    class: String,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon_class::Request {
+            number,
+            class: req.class,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.update_pokemon_class(req) {
+        Ok(update_pokemon_class::Response {
+            number,
+            name,
+            types,
+            class,
+        }) => rouille::Response::json(&fetch_pokemon::Response {
+            number,
+            name,
+            types,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: Some(class),
+        }),
+        Err(update_pokemon_class::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors)
+        }
+        Err(update_pokemon_class::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon_class::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::api::Status;
+use crate::domain::entities::Cry;
+use crate::domain::update_pokemon_cry;
+use crate::repositories::cry::Repository as CryRepository;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::io::Read;
+use std::sync::Arc;
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    number: u16,
+    content_type: String,
+    /// In bytes.
+    size: usize,
+}
+
+/// The body is the clip itself, its `Content-Type` being an audio one such
+/// as `audio/ogg`.
+pub fn serve(
+    service: Arc<PokedexService>,
+    cry_repo: Arc<dyn CryRepository>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let content_type = match req.header("Content-Type") {
+        Some(content_type) => String::from(content_type),
+        None => return rouille::Response::from(Status::UnsupportedMediaType),
+    };
+
+    // One byte over the limit is enough for the clip to be refused.
+    let mut bytes = vec![];
+    match req
+        .data()
+        .map(|body| body.take(Cry::MAX_SIZE as u64 + 1).read_to_end(&mut bytes))
+    {
+        Some(Ok(_)) => {}
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let req = update_pokemon_cry::Request {
+        number,
+        content_type,
+        bytes,
+    };
+    match service.update_pokemon_cry(cry_repo, req) {
+        Ok(update_pokemon_cry::Response {
+            number,
+            content_type,
+            size,
+        }) => rouille::Response::json(&Response {
+            number,
+            content_type,
+            size,
+        }),
+        Err(update_pokemon_cry::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(update_pokemon_cry::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon_cry::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::api::fetch_pokemon::{self, Evolution};
+use crate::api::validate_pokemon;
+use crate::api::Status;
+use crate::domain::update_pokemon_evolutions;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    evolutions: Vec<Evolution>,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon_evolutions::Request {
+            number,
+            evolutions: req.evolutions.into_iter().map(Into::into).collect(),
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.update_pokemon_evolutions(req) {
+        Ok(update_pokemon_evolutions::Response {
+            number,
+            name,
+            types,
+            evolutions,
+        }) => rouille::Response::json(&fetch_pokemon::Response {
+            number,
+            name,
+            types,
+            evolutions: Some(evolutions.iter().map(Evolution::from).collect()),
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+        }),
+        Err(update_pokemon_evolutions::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors)
+        }
+        Err(update_pokemon_evolutions::Error::NotFound) => {
+            rouille::Response::from(Status::NotFound)
+        }
+        Err(update_pokemon_evolutions::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::api::fetch_pokemon;
+use crate::api::validate_pokemon;
+use crate::api::Status;
+use crate::domain::update_pokemon_measurements;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// The height in meters and the weight in kilograms, a missing one being
+/// removed.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    #[serde(default)]
+    height: Option<f64>,
+    #[serde(default)]
+    weight: Option<f64>,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon_measurements::Request {
+            number,
+            height: req.height,
+            weight: req.weight,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.update_pokemon_measurements(req) {
+        Ok(update_pokemon_measurements::Response {
+            number,
+            name,
+            types,
+            height,
+            weight,
+        }) => rouille::Response::json(&fetch_pokemon::Response {
+            number,
+            name,
+            types,
+            evolutions: None,
+            abilities: None,
+            height,
+            weight,
+            sprite: None,
+            class: None,
+        }),
+        Err(update_pokemon_measurements::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors)
+        }
+        Err(update_pokemon_measurements::Error::NotFound) => {
+            rouille::Response::from(Status::NotFound)
+        }
+        Err(update_pokemon_measurements::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
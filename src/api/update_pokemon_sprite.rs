@@ -0,0 +1,54 @@
+use crate::api::fetch_pokemon;
+use crate::api::validate_pokemon;
+use crate::api::Status;
+use crate::domain::update_pokemon_sprite;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// The http or https URL of the image, a missing one removing it.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    #[serde(default)]
+    url: Option<String>,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon_sprite::Request {
+            number,
+            url: req.url,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.update_pokemon_sprite(req) {
+        Ok(update_pokemon_sprite::Response {
+            number,
+            name,
+            types,
+            sprite,
+        }) => rouille::Response::json(&fetch_pokemon::Response {
+            number,
+            name,
+            types,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite,
+            class: None,
+        }),
+        Err(update_pokemon_sprite::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors)
+        }
+        Err(update_pokemon_sprite::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon_sprite::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::api::validate_pokemon;
+use crate::api::Status;
+use crate::domain::update_pokemon_types;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    add: Option<String>,
+    remove: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+pub fn serve(
+    service: Arc<PokedexService>,
+    number: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(Request {
+            add: Some(pokemon_type),
+            remove: None,
+        }) => update_pokemon_types::Request {
+            number,
+            operation: update_pokemon_types::Operation::Add,
+            pokemon_type,
+        },
+        Ok(Request {
+            add: None,
+            remove: Some(pokemon_type),
+        }) => update_pokemon_types::Request {
+            number,
+            operation: update_pokemon_types::Operation::Remove,
+            pokemon_type,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    match service.update_pokemon_types(req) {
+        Ok(update_pokemon_types::Response {
+            number,
+            name,
+            types,
+        }) => rouille::Response::json(&Response {
+            number,
+            name,
+            types,
+        }),
+        Err(update_pokemon_types::Error::BadRequest(errors)) => {
+            validate_pokemon::bad_request(errors)
+        }
+        Err(update_pokemon_types::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon_types::Error::LastType) => rouille::Response::from(Status::Conflict),
+        Err(update_pokemon_types::Error::TooManyTypes) => rouille::Response::from(Status::Conflict),
+        Err(update_pokemon_types::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
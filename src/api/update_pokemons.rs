@@ -0,0 +1,112 @@
+use crate::api::validate_pokemon::{self, FieldError};
+use crate::api::Status;
+use crate::domain::update_pokemons;
+use crate::service::PokedexService;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    number: u16,
+    changes: Changes,
+}
+
+/// Exactly one of the fields, the others make the change invalid.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Changes {
+    types: Option<Vec<String>>,
+    add: Option<String>,
+    remove: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    results: Vec<Outcome>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Outcome {
+    number: u16,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    types: Option<Vec<String>>,
+    /// Why an `invalid` change is invalid.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<FieldError>,
+}
+
+/// Applies every change or none of them. When one fails, answers 409 with
+/// the result of each change, `not_applied` for the valid ones.
+pub fn serve(service: Arc<PokedexService>, req: &rouille::Request) -> rouille::Response {
+    let reqs = match rouille::input::json_input::<Vec<Request>>(req) {
+        Ok(reqs) => reqs,
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+
+    let mut changes = vec![];
+    for req in reqs {
+        let change = match req.changes {
+            Changes {
+                types: Some(types),
+                add: None,
+                remove: None,
+            } => update_pokemons::Change::SetTypes(types),
+            Changes {
+                types: None,
+                add: Some(pokemon_type),
+                remove: None,
+            } => update_pokemons::Change::AddType(pokemon_type),
+            Changes {
+                types: None,
+                add: None,
+                remove: Some(pokemon_type),
+            } => update_pokemons::Change::RemoveType(pokemon_type),
+            // No types is invalid too, so the change is reported along with
+            // the others rather than failing the whole request.
+            _ => update_pokemons::Change::SetTypes(vec![]),
+        };
+        changes.push(update_pokemons::Request {
+            number: req.number,
+            change,
+        });
+    }
+
+    match service.update_pokemons(changes) {
+        Ok(outcomes) => rouille::Response::json(&Response {
+            results: outcomes.into_iter().map(Outcome::from).collect(),
+        }),
+        Err(update_pokemons::Error::Rejected(outcomes)) => rouille::Response::json(&Response {
+            results: outcomes.into_iter().map(Outcome::from).collect(),
+        })
+        .with_status_code(409),
+        Err(update_pokemons::Error::BadRequest(errors)) => validate_pokemon::bad_request(errors),
+        Err(update_pokemons::Error::Unknown) => {
+            rouille::Response::from(Status::InternalServerError)
+        }
+    }
+}
+
+impl From<update_pokemons::Outcome> for Outcome {
+    fn from(outcome: update_pokemons::Outcome) -> Self {
+        let (status, name, types, errors) = match outcome.status {
+            update_pokemons::Status::Updated { name, types } => {
+                ("updated", Some(name), Some(types), vec![])
+            }
+            update_pokemons::Status::NotApplied => ("not_applied", None, None, vec![]),
+            update_pokemons::Status::Invalid(errors) => ("invalid", None, None, errors),
+            update_pokemons::Status::NotFound => ("not_found", None, None, vec![]),
+            update_pokemons::Status::LastType => ("last_type", None, None, vec![]),
+            update_pokemons::Status::TooManyTypes => ("too_many_types", None, None, vec![]),
+        };
+        Self {
+            number: outcome.number,
+            status,
+            name,
+            types,
+            errors: errors.into_iter().map(FieldError::from).collect(),
+        }
+    }
+}
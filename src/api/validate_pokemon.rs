@@ -0,0 +1,68 @@
+use crate::api::Status;
+use crate::domain::validate_pokemon;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct Request {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Response {
+    valid: bool,
+    errors: Vec<FieldError>,
+}
+
+/// The body of the bad requests refused because of some fields.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct Errors {
+    errors: Vec<FieldError>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(super) struct FieldError {
+    field: String,
+    message: String,
+}
+
+impl From<validate_pokemon::FieldError> for FieldError {
+    fn from(error: validate_pokemon::FieldError) -> Self {
+        Self {
+            field: error.field,
+            message: error.message,
+        }
+    }
+}
+
+pub fn serve(req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => validate_pokemon::Request {
+            number: req.number,
+            name: req.name,
+            types: req.types,
+        },
+        _ => return rouille::Response::from(Status::BadRequest),
+    };
+    let validate_pokemon::Response { errors } = validate_pokemon::execute(req);
+    rouille::Response::json(&Response {
+        valid: errors.is_empty(),
+        errors: errors
+            .into_iter()
+            .map(FieldError::from)
+            .collect::<Vec<FieldError>>(),
+    })
+}
+
+/// A bad request telling which fields are invalid and why.
+pub(super) fn bad_request(errors: Vec<validate_pokemon::FieldError>) -> rouille::Response {
+    rouille::Response::json(&Errors {
+        errors: errors
+            .into_iter()
+            .map(FieldError::from)
+            .collect::<Vec<FieldError>>(),
+    })
+    .with_status_code(400)
+}
@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Tells the work done for a request to stop, either because it was
+/// cancelled or because its deadline has passed. Clones share the same state.
+#[derive(Clone)]
+pub struct Cancellation {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            deadline: None,
+        }
+    }
+
+    /// Keeps the earliest of the current deadline and `timeout` from now.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        let deadline = Instant::now() + timeout;
+        Self {
+            deadline: Some(match self.deadline {
+                Some(current) if current < deadline => current,
+                _ => deadline,
+            }),
+            ..self
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_be_cancelled_by_any_clone() {
+        let cancellation = Cancellation::new();
+        let clone = cancellation.clone();
+
+        assert!(!cancellation.is_cancelled());
+        clone.cancel();
+        assert!(cancellation.is_cancelled());
+    }
+
+    #[test]
+    fn it_should_be_cancelled_once_the_earliest_deadline_has_passed() {
+        let cancellation = Cancellation::new()
+            .with_timeout(Duration::ZERO)
+            .with_timeout(Duration::from_secs(60));
+
+        assert!(cancellation.is_cancelled());
+        assert!(!Cancellation::new()
+            .with_timeout(Duration::from_secs(60))
+            .is_cancelled());
+    }
+}
@@ -0,0 +1,330 @@
+use crate::clock::now;
+use crate::domain::{
+    create_webhook, create_wish, fetch_all_pokemons, fetch_checksum, fetch_wishlist,
+    ingest_pokemons,
+};
+use crate::repositories::pokemon::Repository;
+use crate::repositories::webhook::Repository as WebhookRepository;
+use crate::repositories::wishlist::Repository as WishlistRepository;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::sync::Arc;
+
+const VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    created_at: u64,
+    /// See `fetch_checksum::checksum`, absent from the older archives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Pokemon {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Webhook {
+    url: String,
+    secret: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Wish {
+    number: u16,
+    name: String,
+    note: Option<String>,
+}
+
+/// Writes the Pokemons, webhooks and wishlist of the instance to a gzipped
+/// tarball holding one JSON file each, plus a manifest.
+pub fn create(
+    path: &str,
+    repo: Arc<dyn Repository>,
+    webhook_repo: Arc<dyn WebhookRepository>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+) {
+    let (pokemons, webhooks, wishes) = match (
+        fetch_all_pokemons::execute(
+            repo,
+            fetch_all_pokemons::Request {
+                sort_by: fetch_all_pokemons::SortBy::NumberAscending,
+                offset: 0,
+                limit: None,
+                class: None,
+            },
+        ),
+        webhook_repo.fetch_all(),
+        fetch_wishlist::execute(wishlist_repo),
+    ) {
+        (Ok(res), Ok(webhooks), Ok(wishes)) => (res.pokemons, webhooks, wishes),
+        _ => {
+            println!("An unknown error occurred");
+            return;
+        }
+    };
+
+    let counts = (pokemons.len(), webhooks.len(), wishes.len());
+    let checksum = fetch_checksum::checksum(
+        pokemons
+            .iter()
+            .map(|p| (p.number, p.name.as_str(), p.types.as_slice())),
+    );
+    let files = [
+        (
+            "manifest.json",
+            serde_json::to_vec_pretty(&Manifest {
+                version: VERSION,
+                created_at: now(),
+                checksum: Some(checksum),
+            }),
+        ),
+        (
+            "pokemons.json",
+            serde_json::to_vec_pretty(
+                &pokemons
+                    .into_iter()
+                    .map(|p| Pokemon {
+                        number: p.number,
+                        name: p.name,
+                        types: p.types,
+                    })
+                    .collect::<Vec<Pokemon>>(),
+            ),
+        ),
+        (
+            "webhooks.json",
+            serde_json::to_vec_pretty(
+                &webhooks
+                    .into_iter()
+                    .map(|w| Webhook {
+                        url: String::from(w.url),
+                        secret: String::from(w.secret),
+                    })
+                    .collect::<Vec<Webhook>>(),
+            ),
+        ),
+        (
+            "wishlist.json",
+            serde_json::to_vec_pretty(
+                &wishes
+                    .into_iter()
+                    .map(|w| Wish {
+                        number: w.number,
+                        name: w.name,
+                        note: w.note,
+                    })
+                    .collect::<Vec<Wish>>(),
+            ),
+        ),
+    ];
+
+    match write_archive(path, files) {
+        Ok(()) => println!(
+            "{} Pokemon(s), {} webhook(s) and {} wish(es) archived to {}",
+            counts.0, counts.1, counts.2, path
+        ),
+        _ => println!("An error occurred while writing {}", path),
+    }
+}
+
+/// Adds the content of an archive to the configured backends. Pokemons and
+/// wishes that already exist are kept, as are webhooks with the same url.
+pub fn restore(
+    path: &str,
+    repo: Arc<dyn Repository>,
+    webhook_repo: Arc<dyn WebhookRepository>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+) {
+    let files = match read_archive(path) {
+        Ok(files) => files,
+        _ => {
+            println!("An error occurred while reading {}", path);
+            return;
+        }
+    };
+
+    let parsed = (
+        parse::<Manifest>(&files, "manifest.json"),
+        parse::<Vec<Pokemon>>(&files, "pokemons.json"),
+        parse::<Vec<Webhook>>(&files, "webhooks.json"),
+        parse::<Vec<Wish>>(&files, "wishlist.json"),
+    );
+    let (pokemons, webhooks, wishes) = match parsed {
+        (Ok(manifest), Ok(pokemons), Ok(webhooks), Ok(wishes)) if manifest.version == VERSION => {
+            (pokemons, webhooks, wishes)
+        }
+        _ => {
+            println!("{} is not a Pokedex archive", path);
+            return;
+        }
+    };
+
+    let reqs = pokemons
+        .into_iter()
+        .map(|p| ingest_pokemons::Request {
+            number: p.number,
+            name: p.name,
+            types: p.types,
+            details: ingest_pokemons::Details::default(),
+        })
+        .collect::<Vec<ingest_pokemons::Request>>();
+    match ingest_pokemons::execute(repo, ingest_pokemons::Policy::Skip, reqs) {
+        Ok(res) => println!(
+            "{} Pokemon(s) restored, {} already existed, {} invalid",
+            res.inserted, res.conflicts, res.invalid
+        ),
+        Err(ingest_pokemons::Error::Conflict) | Err(ingest_pokemons::Error::Unknown) => {
+            println!("An unknown error occurred while restoring the Pokemons");
+            return;
+        }
+    }
+
+    let urls = match webhook_repo.fetch_all() {
+        Ok(existing) => existing
+            .into_iter()
+            .map(|w| String::from(w.url))
+            .collect::<Vec<String>>(),
+        _ => {
+            println!("An unknown error occurred while restoring the webhooks");
+            return;
+        }
+    };
+    let mut restored = 0;
+    for webhook in webhooks.into_iter().filter(|w| !urls.contains(&w.url)) {
+        let req = create_webhook::Request {
+            url: webhook.url,
+            secret: webhook.secret,
+        };
+        if create_webhook::execute(webhook_repo.clone(), req).is_ok() {
+            restored += 1;
+        }
+    }
+    println!("{} webhook(s) restored", restored);
+
+    let mut restored = 0;
+    for wish in wishes {
+        let req = create_wish::Request {
+            number: wish.number,
+            name: wish.name,
+            note: wish.note,
+        };
+        if create_wish::execute(wishlist_repo.clone(), req).is_ok() {
+            restored += 1;
+        }
+    }
+    println!("{} wish(es) restored", restored);
+}
+
+/// The number of Pokemons of an archive and their checksum, checked against
+/// the one of the manifest when there is one.
+pub fn checksum(path: &str) -> Result<(usize, String), &'static str> {
+    let files = match read_archive(path) {
+        Ok(files) => files,
+        _ => return Err("cannot be read"),
+    };
+
+    let (manifest, pokemons) = match (
+        parse::<Manifest>(&files, "manifest.json"),
+        parse::<Vec<Pokemon>>(&files, "pokemons.json"),
+    ) {
+        (Ok(manifest), Ok(pokemons)) if manifest.version == VERSION => (manifest, pokemons),
+        _ => return Err("is not a Pokedex archive"),
+    };
+
+    let checksum = fetch_checksum::checksum(
+        pokemons
+            .iter()
+            .map(|p| (p.number, p.name.as_str(), p.types.as_slice())),
+    );
+    match manifest.checksum {
+        Some(expected) if expected != checksum => Err("does not match its own checksum"),
+        _ => Ok((pokemons.len(), checksum)),
+    }
+}
+
+fn write_archive(path: &str, files: [(&str, serde_json::Result<Vec<u8>>); 4]) -> Result<(), ()> {
+    let file = match File::create(path) {
+        Ok(file) => file,
+        _ => return Err(()),
+    };
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    for (name, content) in files {
+        let content = match content {
+            Ok(content) => content,
+            _ => return Err(()),
+        };
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(now());
+        header.set_cksum();
+        if builder
+            .append_data(&mut header, name, content.as_slice())
+            .is_err()
+        {
+            return Err(());
+        }
+    }
+
+    match builder.into_inner().map(|encoder| encoder.finish()) {
+        Ok(Ok(_)) => Ok(()),
+        _ => Err(()),
+    }
+}
+
+fn read_archive(path: &str) -> Result<BTreeMap<String, String>, ()> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        _ => return Err(()),
+    };
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let entries = match archive.entries() {
+        Ok(entries) => entries,
+        _ => return Err(()),
+    };
+
+    let mut files = BTreeMap::new();
+
+    for entry in entries {
+        let mut entry = match entry {
+            Ok(entry) => entry,
+            _ => return Err(()),
+        };
+        let name = match entry.path() {
+            Ok(name) => name.to_string_lossy().to_string(),
+            _ => return Err(()),
+        };
+        let mut content = String::new();
+        if entry.read_to_string(&mut content).is_err() {
+            return Err(());
+        }
+        files.insert(name, content);
+    }
+
+    Ok(files)
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(
+    files: &BTreeMap<String, String>,
+    name: &str,
+) -> Result<T, ()> {
+    match files
+        .get(name)
+        .map(|content| serde_json::from_str::<T>(content))
+    {
+        Some(Ok(value)) => Ok(value),
+        _ => Err(()),
+    }
+}
@@ -0,0 +1,30 @@
+use crate::domain::clean_up;
+use crate::repositories::{change, webhook};
+use crate::retention::Policy;
+use std::sync::Arc;
+
+/// Removes the records the policy no longer keeps. With `dry_run`, only
+/// tells how many would be removed.
+pub fn run(
+    policy: Policy,
+    change_repo: Arc<dyn change::Repository>,
+    webhook_repo: Arc<dyn webhook::Repository>,
+    dry_run: bool,
+) {
+    if policy.is_empty() {
+        println!("Nothing to clean up, set --keep-changes or --keep-deliveries");
+        return;
+    }
+
+    match clean_up::execute(change_repo, webhook_repo, policy.request(dry_run)) {
+        Ok(res) if dry_run => println!(
+            "{} change(s) and {} webhook delivery(ies) would be removed",
+            res.changes, res.deliveries
+        ),
+        Ok(res) => println!(
+            "{} change(s) and {} webhook delivery(ies) removed",
+            res.changes, res.deliveries
+        ),
+        Err(clean_up::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
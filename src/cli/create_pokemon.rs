@@ -1,42 +1,105 @@
-use crate::cli::{prompt_name, prompt_number, prompt_types};
+use crate::cli::{fail, fail_fields, history, prompt_types, show, Output};
 use crate::domain::create_pokemon;
-use crate::repositories::pokemon::Repository;
+use crate::kernel::validation;
+use crate::service::PokedexService;
+use dialoguer::Input;
+use serde::Serialize;
 use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct Response {
     number: u16,
     name: String,
     types: Vec<String>,
 }
 
-pub fn run(repo: Arc<dyn Repository>) {
-    let number = prompt_number();
-    let name = prompt_name();
-    let types = prompt_types();
+pub fn run(service: Arc<PokedexService>, output: Output) {
+    let number = prompt_valid_number();
+    let name = prompt_valid_name("");
+    let types = prompt_valid_types(&[]);
 
-    let req = match (number, name, types) {
-        (Ok(number), Ok(name), Ok(types)) => create_pokemon::Request {
+    let (number, req) = match (number, name, types) {
+        (Ok(number), Ok(name), Ok(types)) => (
             number,
-            name,
-            types,
-        },
+            create_pokemon::Request {
+                number,
+                name,
+                types,
+            },
+        ),
         _ => {
-            println!("An error occurred during the prompt");
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
             return;
         }
     };
-    match create_pokemon::execute(repo, req) {
-        Ok(res) => println!(
-            "{:?}",
-            Response {
+    match service.create_pokemon(req) {
+        Ok(res) => show(
+            output,
+            &Response {
                 number: res.number,
                 name: res.name,
                 types: res.types,
-            }
+            },
+        ),
+        Err(create_pokemon::Error::BadRequest(errors)) => fail_fields(output, number, &errors),
+        Err(create_pokemon::Error::Conflict) => fail(
+            output,
+            "conflict",
+            Some(number),
+            "The Pokemon already exists",
         ),
-        Err(create_pokemon::Error::BadRequest) => println!("The request is invalid"),
-        Err(create_pokemon::Error::Conflict) => println!("The Pokemon already exists"),
-        Err(create_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+        Err(create_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
     };
 }
+
+fn prompt_valid_number() -> Result<u16, ()> {
+    history::with(history::Kind::Number, |history| {
+        match Input::new()
+            .with_prompt("Pokemon number")
+            .history_with(history)
+            .validate_with(|number: &u16| validation::validate_number(*number))
+            .interact_text()
+        {
+            Ok(number) => Ok(number),
+            _ => Err(()),
+        }
+    })
+}
+
+pub(super) fn prompt_valid_name(initial: &str) -> Result<String, ()> {
+    history::with(history::Kind::Name, |history| {
+        match Input::new()
+            .with_prompt("Pokemon name")
+            .with_initial_text(initial)
+            .history_with(history)
+            .allow_empty(true)
+            .validate_with(|name: &String| validation::validate_name(name))
+            .interact_text()
+        {
+            Ok(name) => Ok(name),
+            _ => Err(()),
+        }
+    })
+}
+
+pub(super) fn prompt_valid_types(selected: &[String]) -> Result<Vec<String>, ()> {
+    loop {
+        let types = match prompt_types(selected) {
+            Ok(types) => types,
+            _ => return Err(()),
+        };
+        match validation::validate_types(&types) {
+            Ok(()) => return Ok(types),
+            Err(messages) => messages
+                .iter()
+                .for_each(|message| println!("Pokemon types {}", message)),
+        }
+    }
+}
@@ -1,22 +1,42 @@
-use crate::cli::prompt_number;
+use crate::cli::{fail, prompt_number, Output};
 use crate::domain::delete_pokemon;
-use crate::repositories::pokemon::Repository;
+use crate::service::PokedexService;
 use std::sync::Arc;
 
-pub fn run(repo: Arc<dyn Repository>) {
+pub fn run(service: Arc<PokedexService>, output: Output) {
     let number = prompt_number();
 
-    let req = match number {
-        Ok(number) => delete_pokemon::Request { number },
+    let number = match number {
+        Ok(number) => number,
         _ => {
-            println!("An error occurred during the prompt");
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
             return;
         }
     };
-    match delete_pokemon::execute(repo, req) {
-        Ok(()) => println!("The Pokemon has been deleted"),
-        Err(delete_pokemon::Error::BadRequest) => println!("The request is invalid"),
-        Err(delete_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
-        Err(delete_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+    match service.delete_pokemon(delete_pokemon::Request { number }) {
+        Ok(()) => match output {
+            Output::Text => println!("The Pokemon has been deleted"),
+            Output::Json => println!("{{\"deleted\":{}}}", number),
+        },
+        Err(delete_pokemon::Error::BadRequest) => fail(
+            output,
+            "bad_request",
+            Some(number),
+            "The request is invalid",
+        ),
+        Err(delete_pokemon::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon does not exist",
+        ),
+        Err(delete_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
     }
 }
@@ -0,0 +1,53 @@
+use crate::domain::{delete_pokemons_by_type, fetch_pokemons_by_type};
+use crate::service::PokedexService;
+use std::sync::Arc;
+
+/// Deletes the Pokemons matching `filter`, given as `type=NAME`. Without
+/// `confirmed`, only tells how many Pokemons would be deleted.
+pub fn run(service: Arc<PokedexService>, filter: &str, confirmed: bool) {
+    let pokemon_type = match filter.split_once('=') {
+        Some(("type", pokemon_type)) => String::from(pokemon_type),
+        _ => {
+            println!("The filter must be type=NAME");
+            return;
+        }
+    };
+
+    if !confirmed {
+        let req = fetch_pokemons_by_type::Request {
+            pokemon_type: pokemon_type.clone(),
+        };
+        match service.fetch_pokemons_by_type(req) {
+            Ok(res) => println!(
+                "{} Pokemon(s) of type {} would be deleted, add --confirm to delete them",
+                res.len(),
+                pokemon_type
+            ),
+            Err(fetch_pokemons_by_type::Error::BadRequest) => {
+                println!("{} is not a known type", pokemon_type)
+            }
+            Err(fetch_pokemons_by_type::Error::Unknown) => println!("An unknown error occurred"),
+        }
+        return;
+    }
+
+    let req = delete_pokemons_by_type::Request {
+        pokemon_type: pokemon_type.clone(),
+        confirmed,
+    };
+    match service.delete_pokemons_by_type(req) {
+        Ok(res) => {
+            for pokemon in res.iter() {
+                println!("#{} {} deleted", pokemon.number, pokemon.name);
+            }
+            println!("{} Pokemon(s) deleted", res.len());
+        }
+        Err(delete_pokemons_by_type::Error::BadRequest) => {
+            println!("{} is not a known type", pokemon_type)
+        }
+        Err(delete_pokemons_by_type::Error::NotConfirmed) => {
+            println!("Add --confirm to delete the Pokemons")
+        }
+        Err(delete_pokemons_by_type::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
@@ -0,0 +1,71 @@
+use crate::diff::{self, Difference, Direction};
+use crate::repositories::pokemon::Repository;
+use console::style;
+use std::sync::Arc;
+
+/// Prints the records found on a single side and the fields differing, then
+/// reconciles them in `direction` if any. Tells whether both sides match in
+/// the end.
+pub fn run(
+    left: Arc<dyn Repository>,
+    right: Arc<dyn Repository>,
+    direction: Option<Direction>,
+) -> bool {
+    let differences = match diff::compare(left.clone(), right.clone()) {
+        Ok(differences) => differences,
+        _ => {
+            println!("{} the Pokemons can't be fetched", style("✘").red());
+            return false;
+        }
+    };
+
+    if differences.is_empty() {
+        println!("{} both sides hold the same Pokemons", style("✔").green());
+        return true;
+    }
+
+    for difference in differences.iter() {
+        match difference {
+            Difference::OnlyInLeft(pokemon) => println!(
+                "{} #{} {} is only in left",
+                style("<").red(),
+                pokemon.number.value(),
+                pokemon.name.as_str()
+            ),
+            Difference::OnlyInRight(pokemon) => println!(
+                "{} #{} {} is only in right",
+                style(">").green(),
+                pokemon.number.value(),
+                pokemon.name.as_str()
+            ),
+            Difference::Differing { left, fields, .. } => {
+                println!(
+                    "{} #{} {} differs",
+                    style("~").yellow(),
+                    left.number.value(),
+                    left.name.as_str()
+                );
+                for field in fields {
+                    println!("    {}: {:?} | {:?}", field.name, field.left, field.right);
+                }
+            }
+        }
+    }
+    println!("{} difference(s)", differences.len());
+
+    let direction = match direction {
+        Some(direction) => direction,
+        None => return false,
+    };
+
+    let applied = diff::apply(&differences, left, right, direction);
+    println!(
+        "{} {} difference(s) reconciled",
+        style("✔").green(),
+        applied.applied
+    );
+    for number in applied.failed.iter() {
+        println!("{} #{} can't be written", style("✘").red(), number);
+    }
+    applied.failed.is_empty()
+}
@@ -0,0 +1,29 @@
+use crate::repositories::diagnostics::{self, Check};
+use console::style;
+
+/// Prints a line per check and tells whether all of them passed.
+pub fn run(sqlite: Option<&str>, airtable: Option<(&str, &str)>) -> bool {
+    let mut checks = vec![];
+
+    if let Some((api_key, workspace_id)) = airtable {
+        checks.extend(diagnostics::airtable(api_key, workspace_id));
+    }
+
+    if let Some(path) = sqlite {
+        checks.extend(diagnostics::sqlite(path));
+    }
+
+    if checks.is_empty() {
+        println!("No backend configured, the in-memory store has nothing to check");
+        return true;
+    }
+
+    for Check { name, result } in checks.iter() {
+        match result {
+            Ok(()) => println!("{} {}", style("✔").green(), name),
+            Err(message) => println!("{} {}: {}", style("✘").red(), name, message),
+        }
+    }
+
+    checks.iter().all(|check| check.result.is_ok())
+}
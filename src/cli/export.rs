@@ -0,0 +1,15 @@
+use crate::export::{self, Error};
+use crate::repositories::pokemon::Repository;
+use std::fs;
+use std::sync::Arc;
+
+pub fn run(repo: Arc<dyn Repository>, format: &str, path: &str) {
+    match export::export(repo, format) {
+        Ok((_, bytes)) => match fs::write(path, bytes) {
+            Ok(()) => println!("The Pokedex has been exported to {}", path),
+            _ => println!("An error occurred while writing {}", path),
+        },
+        Err(Error::BadRequest) => println!("The export format is not supported"),
+        Err(Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
@@ -1,26 +1,77 @@
+use crate::cli::{copy_to_clipboard, fail, show, Output};
 use crate::domain::fetch_all_pokemons;
-use crate::repositories::pokemon::Repository;
+use crate::service::PokedexService;
+use dialoguer::{theme::ColorfulTheme, Select};
+use serde::Serialize;
 use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct Response {
     number: u16,
     name: String,
     types: Vec<String>,
 }
 
-pub fn run(repo: Arc<dyn Repository>) {
-    match fetch_all_pokemons::execute(repo) {
-        Ok(res) => res.into_iter().for_each(|p| {
-            println!(
-                "{:?}",
-                Response {
+pub fn run(service: Arc<PokedexService>, output: Output, copy: bool) {
+    let sort_by = match prompt_sort_by() {
+        Ok(sort_by) => sort_by,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+
+    let req = fetch_all_pokemons::Request {
+        sort_by,
+        offset: 0,
+        limit: None,
+        class: None,
+    };
+    match service.fetch_all_pokemons(req) {
+        Ok(res) => {
+            let res = res
+                .pokemons
+                .into_iter()
+                .map(|p| Response {
                     number: p.number,
                     name: p.name,
                     types: p.types,
-                }
-            );
-        }),
-        Err(fetch_all_pokemons::Error::Unknown) => println!("An unknown error occurred"),
+                })
+                .collect::<Vec<Response>>();
+            res.iter().for_each(|p| show(output, p));
+            if copy {
+                copy_to_clipboard(&res);
+            }
+        }
+        Err(fetch_all_pokemons::Error::BadRequest) => {
+            fail(output, "bad_request", None, "The request is invalid")
+        }
+        Err(fetch_all_pokemons::Error::Unknown) => {
+            fail(output, "unknown", None, "An unknown error occurred")
+        }
+    }
+}
+
+fn prompt_sort_by() -> Result<fetch_all_pokemons::SortBy, ()> {
+    let index = match Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Sort by")
+        .items(&["Number", "Number, descending", "Name", "Type"])
+        .default(0)
+        .interact()
+    {
+        Ok(index) => index,
+        _ => return Err(()),
+    };
+
+    match index {
+        0 => Ok(fetch_all_pokemons::SortBy::NumberAscending),
+        1 => Ok(fetch_all_pokemons::SortBy::NumberDescending),
+        2 => Ok(fetch_all_pokemons::SortBy::Name),
+        _ => Ok(fetch_all_pokemons::SortBy::Type),
     }
 }
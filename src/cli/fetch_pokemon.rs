@@ -1,36 +1,165 @@
-use crate::cli::prompt_number;
-use crate::domain::fetch_pokemon;
-use crate::repositories::pokemon::Repository;
+use crate::cli::{copy_to_clipboard, fail, prompt_number, show, Output};
+use crate::domain::{fetch_pokemon, fetch_types};
+use crate::service::PokedexService;
+use console::{style, Color};
+use serde::Serialize;
 use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct Response {
     number: u16,
     name: String,
     types: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    abilities: Vec<AbilityView>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sprite: Option<String>,
+    class: String,
 }
 
-pub fn run(repo: Arc<dyn Repository>) {
+#[derive(Debug, Serialize)]
+pub(super) struct AbilityView {
+    name: String,
+    hidden: bool,
+}
+
+impl From<fetch_pokemon::Ability> for AbilityView {
+    fn from(ability: fetch_pokemon::Ability) -> Self {
+        Self {
+            name: ability.name,
+            hidden: ability.hidden,
+        }
+    }
+}
+
+pub fn run(service: Arc<PokedexService>, output: Output, copy: bool) {
     let number = prompt_number();
 
-    let req = match number {
-        Ok(number) => fetch_pokemon::Request { number },
+    let number = match number {
+        Ok(number) => number,
         _ => {
-            println!("An error occurred during the prompt");
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
             return;
         }
     };
-    match fetch_pokemon::execute(repo, req) {
-        Ok(res) => println!(
-            "{:?}",
-            Response {
+    match service.fetch_pokemon(fetch_pokemon::Request { number }) {
+        Ok(res) => {
+            let res = Response {
                 number: res.number,
                 name: res.name,
                 types: res.types,
+                abilities: res.abilities.into_iter().map(AbilityView::from).collect(),
+                height: res.height,
+                weight: res.weight,
+                sprite: res.sprite,
+                class: res.class,
+            };
+            match output {
+                Output::Text => print_card(&res),
+                Output::Json => show(output, &res),
             }
+            if copy {
+                copy_to_clipboard(&res);
+            }
+        }
+        Err(fetch_pokemon::Error::BadRequest) => fail(
+            output,
+            "bad_request",
+            Some(number),
+            "The request is invalid",
         ),
-        Err(fetch_pokemon::Error::BadRequest) => println!("The request is invalid"),
-        Err(fetch_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
-        Err(fetch_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+        Err(fetch_pokemon::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon does not exist",
+        ),
+        Err(fetch_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    }
+}
+
+fn print_card(res: &Response) {
+    println!();
+    println!(
+        "  {} {}",
+        style(format!("#{:03}", res.number)).dim(),
+        style(&res.name).bold()
+    );
+    println!("  {:<10}{:03}", "Number", res.number);
+    println!("  {:<10}{}", "Name", res.name);
+    println!(
+        "  {:<10}{}",
+        "Types",
+        res.types
+            .iter()
+            .map(|t| type_badge(t))
+            .collect::<Vec<String>>()
+            .join(" ")
+    );
+    if !res.abilities.is_empty() {
+        println!(
+            "  {:<10}{}",
+            "Abilities",
+            res.abilities
+                .iter()
+                .map(|a| match a.hidden {
+                    true => format!("{} {}", a.name, style("(hidden)").dim()),
+                    false => a.name.clone(),
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+    if res.class != "Normal" {
+        println!("  {:<10}{}", "Class", style(&res.class).yellow());
+    }
+    if let Some(height) = res.height {
+        println!("  {:<10}{:.1} m", "Height", height);
+    }
+    if let Some(weight) = res.weight {
+        println!("  {:<10}{:.1} kg", "Weight", weight);
+    }
+    if let Some(sprite) = &res.sprite {
+        println!("  {:<10}{}", "Sprite", style(sprite).underlined());
+    }
+    println!();
+}
+
+/// Styled after the type metadata, its hex color being approximated in the
+/// 256 colors of the terminal.
+pub(super) fn type_badge(t: &str) -> String {
+    let (label, color) = match fetch_types::execute(fetch_types::Request { generation: None })
+        .unwrap_or_default()
+        .into_iter()
+        .find(|m| m.name == t)
+    {
+        Some(metadata) => (
+            format!(" {} {} ", metadata.emoji, t),
+            color256(&metadata.color),
+        ),
+        None => (format!(" {} ", t), Color::White),
+    };
+
+    style(label).fg(Color::Black).bg(color).bold().to_string()
+}
+
+fn color256(hex: &str) -> Color {
+    match u32::from_str_radix(hex.trim_start_matches('#'), 16) {
+        Ok(rgb) => {
+            let level = |shift: u32| (((rgb >> shift) & 0xFF) * 5 + 127) / 255;
+            Color::Color256((16 + 36 * level(16) + 6 * level(8) + level(0)) as u8)
+        }
+        _ => Color::White,
     }
 }
@@ -0,0 +1,84 @@
+use crate::cli::{copy_to_clipboard, fail, show, Output};
+use crate::domain::fetch_pokemon_page;
+use crate::service::PokedexService;
+use dialoguer::{theme::ColorfulTheme, Select};
+use serde::Serialize;
+use std::sync::Arc;
+
+const PER_PAGE: u32 = 20;
+
+#[derive(Debug, Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+/// Lists the Pokemons a page at a time, so that large backends are never
+/// listed whole.
+pub fn run(service: Arc<PokedexService>, output: Output, copy: bool) {
+    let mut page = 1;
+
+    loop {
+        let req = fetch_pokemon_page::Request {
+            page,
+            per_page: PER_PAGE,
+        };
+        let (from, to, total) = match service.fetch_pokemon_page(req) {
+            Ok(res) => {
+                let pokemons = res
+                    .pokemons
+                    .into_iter()
+                    .map(|p| Response {
+                        number: p.number,
+                        name: p.name,
+                        types: p.types,
+                    })
+                    .collect::<Vec<Response>>();
+                pokemons.iter().for_each(|p| show(output, p));
+                if copy {
+                    copy_to_clipboard(&pokemons);
+                }
+                (res.from, res.to, res.total)
+            }
+            Err(fetch_pokemon_page::Error::BadRequest) => {
+                fail(output, "bad_request", None, "The request is invalid");
+                return;
+            }
+            Err(fetch_pokemon_page::Error::Unknown) => {
+                fail(output, "unknown", None, "An unknown error occurred");
+                return;
+            }
+        };
+
+        if let Output::Text = output {
+            println!("showing {}–{} of {}", from, to, total);
+        }
+
+        let mut choices = vec![];
+        if to < total {
+            choices.push(("Next page", page + 1));
+        }
+        if page > 1 {
+            choices.push(("Previous page", page - 1));
+        }
+        if choices.is_empty() {
+            return;
+        }
+        choices.push(("Back", 0));
+
+        page = match Select::with_theme(&ColorfulTheme::default())
+            .items(
+                &choices
+                    .iter()
+                    .map(|(label, _)| *label)
+                    .collect::<Vec<&str>>(),
+            )
+            .default(0)
+            .interact()
+        {
+            Ok(index) if choices[index].1 > 0 => choices[index].1,
+            _ => return,
+        };
+    }
+}
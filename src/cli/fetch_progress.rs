@@ -0,0 +1,76 @@
+use crate::cli::{fail, show, Output};
+use crate::domain::fetch_progress;
+use crate::service::PokedexService;
+use console::style;
+use dialoguer::Input;
+use serde::Serialize;
+use std::sync::Arc;
+
+const BAR_WIDTH: usize = 30;
+
+#[derive(Debug, Serialize)]
+struct Response {
+    target: u16,
+    registered: u32,
+    percentage: f64,
+    missing: Vec<u16>,
+}
+
+pub fn run(service: Arc<PokedexService>, output: Output) {
+    let target = match Input::<u16>::new()
+        .with_prompt("Target number")
+        .default(151)
+        .interact_text()
+    {
+        Ok(target) => target,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+
+    let req = fetch_progress::Request {
+        target: Some(target),
+    };
+    match service.fetch_progress(req) {
+        Ok(res) => {
+            let res = Response {
+                target: res.target,
+                registered: res.registered,
+                percentage: res.percentage,
+                missing: res.missing,
+            };
+            match output {
+                Output::Text => print_bar(&res),
+                Output::Json => show(output, &res),
+            }
+        }
+        Err(fetch_progress::Error::BadRequest) => fail(
+            output,
+            "bad_request",
+            Some(target),
+            "The request is invalid",
+        ),
+        Err(fetch_progress::Error::Unknown) => {
+            fail(output, "unknown", None, "An unknown error occurred")
+        }
+    }
+}
+
+fn print_bar(res: &Response) {
+    let filled = (res.percentage / 100.0 * BAR_WIDTH as f64).round() as usize;
+    println!(
+        "  [{}{}] {:.1}% ({}/{})",
+        style("█".repeat(filled)).green(),
+        style("░".repeat(BAR_WIDTH - filled)).dim(),
+        res.percentage,
+        res.registered,
+        res.target
+    );
+    println!("  {} remaining", res.missing.len());
+}
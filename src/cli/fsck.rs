@@ -0,0 +1,41 @@
+use crate::repositories::pokemon::SqliteRepository;
+use console::style;
+
+/// Opening the database migrates its schema, then the orphaned rows are
+/// removed. Tells whether the database could be repaired.
+pub fn run(sqlite: Option<&str>) -> bool {
+    let path = match sqlite {
+        Some(path) => path,
+        None => {
+            println!("No sqlite database configured, there is nothing to repair");
+            return true;
+        }
+    };
+
+    let repo = match SqliteRepository::try_new(path) {
+        Ok(repo) => repo,
+        _ => {
+            println!("{} {} can't be opened nor migrated", style("✘").red(), path);
+            return false;
+        }
+    };
+    println!(
+        "{} types are deleted along with their Pokemon",
+        style("✔").green()
+    );
+
+    match repo.remove_orphans() {
+        Ok(removed) => {
+            println!(
+                "{} {} orphaned types row(s) removed",
+                style("✔").green(),
+                removed
+            );
+            true
+        }
+        _ => {
+            println!("{} orphaned types rows can't be removed", style("✘").red());
+            false
+        }
+    }
+}
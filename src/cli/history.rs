@@ -0,0 +1,81 @@
+use dialoguer::History;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::sync::Mutex;
+
+const MAX_ENTRIES: usize = 50;
+
+static HISTORIES: Mutex<Histories> = Mutex::new(Histories {
+    numbers: PromptHistory(VecDeque::new()),
+    names: PromptHistory(VecDeque::new()),
+    path: None,
+});
+
+pub enum Kind {
+    Number,
+    Name,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Histories {
+    numbers: PromptHistory,
+    names: PromptHistory,
+    #[serde(skip)]
+    path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PromptHistory(VecDeque<String>);
+
+impl<T: ToString> History<T> for PromptHistory {
+    fn read(&self, pos: usize) -> Option<String> {
+        self.0.get(pos).cloned()
+    }
+
+    fn write(&mut self, val: &T) {
+        let val = val.to_string();
+        self.0.retain(|entry| *entry != val);
+        self.0.push_front(val);
+        self.0.truncate(MAX_ENTRIES);
+    }
+}
+
+/// Keeps the entered values across sessions by loading and saving them in the file at `path`.
+pub fn persist_to(path: &str) {
+    let mut lock = match HISTORIES.lock() {
+        Ok(lock) => lock,
+        _ => return,
+    };
+
+    if let Ok(Ok(histories)) =
+        fs::read_to_string(path).map(|content| serde_json::from_str::<Histories>(&content))
+    {
+        lock.numbers = histories.numbers;
+        lock.names = histories.names;
+    }
+    lock.path = Some(String::from(path));
+}
+
+pub fn with<F, R>(kind: Kind, prompt: F) -> Result<R, ()>
+where
+    F: FnOnce(&mut PromptHistory) -> Result<R, ()>,
+{
+    let mut lock = match HISTORIES.lock() {
+        Ok(lock) => lock,
+        _ => return Err(()),
+    };
+
+    let res = match kind {
+        Kind::Number => prompt(&mut lock.numbers),
+        Kind::Name => prompt(&mut lock.names),
+    };
+
+    if let Some(path) = &lock.path {
+        if let Ok(content) = serde_json::to_string(&*lock) {
+            fs::write(path, content).ok();
+        }
+    }
+
+    res
+}
@@ -0,0 +1,75 @@
+use crate::domain::ingest_pokemons::{self, Policy, Status};
+use crate::pokeapi;
+use crate::repositories::pokemon::Repository;
+use serde::Deserialize;
+use std::fs;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct Pokemon {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+/// Imports a JSON array of Pokemons, as served by GET /pokemons, or of
+/// PokeAPI records with the `pokeapi` format.
+pub fn run(repo: Arc<dyn Repository>, path: &str, format: &str, policy: Policy) {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        _ => {
+            println!("An error occurred while reading {}", path);
+            return;
+        }
+    };
+
+    let reqs = match format {
+        "pokeapi" => match pokeapi::read(&bytes) {
+            Ok(reqs) => reqs,
+            _ => {
+                println!("{} is not a JSON array of PokeAPI Pokemons", path);
+                return;
+            }
+        },
+        _ => match serde_json::from_slice::<Vec<Pokemon>>(&bytes) {
+            Ok(pokemons) => pokemons
+                .into_iter()
+                .map(|p| ingest_pokemons::Request {
+                    number: p.number,
+                    name: p.name,
+                    types: p.types,
+                    details: ingest_pokemons::Details::default(),
+                })
+                .collect::<Vec<ingest_pokemons::Request>>(),
+            _ => {
+                println!("{} is not a JSON array of Pokemons", path);
+                return;
+            }
+        },
+    };
+
+    match ingest_pokemons::execute(repo, policy, reqs) {
+        Ok(res) => {
+            for outcome in res.outcomes.iter() {
+                let status = match outcome.status {
+                    Status::Inserted => "inserted",
+                    Status::Overwritten => "overwritten",
+                    Status::Skipped => "skipped, the number is taken",
+                    Status::Invalid => "invalid",
+                };
+                println!("#{} {}", outcome.number, status);
+                for error in outcome.errors.iter() {
+                    println!("  {} {}", error.field, error.message);
+                }
+            }
+            println!(
+                "{} Pokemon(s) inserted, {} overwritten, {} skipped, {} invalid",
+                res.inserted, res.overwritten, res.conflicts, res.invalid
+            );
+        }
+        Err(ingest_pokemons::Error::Conflict) => {
+            println!("Nothing was imported: some numbers are already taken")
+        }
+        Err(ingest_pokemons::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
@@ -1,19 +1,86 @@
+pub mod archive;
+pub mod cleanup;
 mod create_pokemon;
 mod delete_pokemon;
+pub mod delete_pokemons;
+pub mod diff;
+pub mod doctor;
+pub mod export;
 mod fetch_all_pokemons;
 mod fetch_pokemon;
+mod fetch_pokemon_page;
+mod fetch_progress;
+pub mod fsck;
+mod history;
+pub mod import;
+mod patch_pokemon;
+pub mod report;
+pub mod reset;
+pub mod sync;
+mod type_chart;
+mod update_pokemon;
+mod update_pokemon_abilities;
+mod update_pokemon_types;
+pub mod verify;
+mod wishlist;
 
-use crate::repositories::pokemon::Repository;
+use crate::domain::fetch_types;
+use crate::domain::validate_pokemon::FieldError;
+use crate::repositories::wishlist::Repository as WishlistRepository;
+use crate::service::PokedexService;
+use arboard::Clipboard;
 use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
+use serde::Serialize;
+use std::fmt::Debug;
 use std::sync::Arc;
 
-pub fn run(repo: Arc<dyn Repository>) {
+#[derive(Clone, Copy)]
+pub enum Output {
+    Text,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Failure<'a> {
+    error: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    number: Option<u16>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<InvalidField<'a>>,
+}
+
+#[derive(Serialize)]
+struct InvalidField<'a> {
+    field: &'a str,
+    message: &'a str,
+}
+
+pub fn run(
+    service: Arc<PokedexService>,
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    output: Output,
+    copy: bool,
+    history_path: Option<&str>,
+) {
+    if let Some(path) = history_path {
+        history::persist_to(path);
+    }
+
     loop {
         let choices = [
             "Fetch all Pokemons",
             "Fetch a Pokemon",
+            "Copy all Pokemons",
+            "Copy a Pokemon",
             "Create a Pokemon",
+            "Update a Pokemon",
+            "Edit the name or the types of a Pokemon",
+            "Edit the types of a Pokemon",
+            "Edit the abilities of a Pokemon",
             "Delete a Pokemon",
+            "Show the Pokedex progress",
+            "Manage the wishlist",
+            "Explore the type chart",
             "Exit",
         ];
         let index = match Select::with_theme(&ColorfulTheme::default())
@@ -27,41 +94,131 @@ pub fn run(repo: Arc<dyn Repository>) {
         };
 
         match index {
-            0 => fetch_all_pokemons::run(repo.clone()),
-            1 => fetch_pokemon::run(repo.clone()),
-            2 => create_pokemon::run(repo.clone()),
-            3 => delete_pokemon::run(repo.clone()),
-            4 => break,
+            0 => fetch_pokemon_page::run(service.clone(), output, copy),
+            1 => fetch_pokemon::run(service.clone(), output, copy),
+            2 => fetch_all_pokemons::run(service.clone(), output, true),
+            3 => fetch_pokemon::run(service.clone(), output, true),
+            4 => create_pokemon::run(service.clone(), output),
+            5 => update_pokemon::run(service.clone(), output),
+            6 => patch_pokemon::run(service.clone(), output),
+            7 => update_pokemon_types::run(service.clone(), output),
+            8 => update_pokemon_abilities::run(service.clone(), output),
+            9 => delete_pokemon::run(service.clone(), output),
+            10 => fetch_progress::run(service.clone(), output),
+            11 => wishlist::run(wishlist_repo.clone(), service.clone(), output),
+            12 => type_chart::run(service.clone(), output),
+            13 => break,
             _ => continue,
         };
     }
 }
 
 pub fn prompt_number() -> Result<u16, ()> {
-    match Input::new().with_prompt("Pokemon number").interact_text() {
-        Ok(number) => Ok(number),
-        _ => Err(()),
-    }
-}
-
-pub fn prompt_name() -> Result<String, ()> {
-    match Input::new().with_prompt("Pokemon name").interact_text() {
-        Ok(name) => Ok(name),
-        _ => Err(()),
-    }
+    history::with(history::Kind::Number, |history| {
+        match Input::new()
+            .with_prompt("Pokemon number")
+            .history_with(history)
+            .interact_text()
+        {
+            Ok(number) => Ok(number),
+            _ => Err(()),
+        }
+    })
 }
 
-pub fn prompt_types() -> Result<Vec<String>, ()> {
-    let types = ["Electric", "Fire"];
+/// The `selected` types start checked.
+pub fn prompt_types(selected: &[String]) -> Result<Vec<String>, ()> {
+    let types = fetch_types::execute(fetch_types::Request { generation: None }).unwrap_or_default();
     match MultiSelect::new()
         .with_prompt("Pokemon types")
-        .items(&types)
+        .defaults(
+            &types
+                .iter()
+                .map(|t| selected.contains(&t.name))
+                .collect::<Vec<bool>>(),
+        )
+        .items(
+            &types
+                .iter()
+                .map(|t| format!("{} {}", t.emoji, t.name))
+                .collect::<Vec<String>>(),
+        )
         .interact()
     {
         Ok(indexes) => Ok(indexes
             .into_iter()
-            .map(|index| String::from(types[index]))
+            .map(|index| types[index].name.clone())
             .collect::<Vec<String>>()),
         _ => Err(()),
     }
 }
+
+/// Prints a result, debug formatted for humans or as a JSON line.
+pub fn show<T: Serialize + Debug>(output: Output, value: &T) {
+    match (output, serde_json::to_string(value)) {
+        (Output::Json, Ok(json)) => println!("{}", json),
+        _ => println!("{:?}", value),
+    }
+}
+
+/// Reports a failure: the message for humans, or a JSON object such as
+/// `{"error":"conflict","number":25}` on stderr for scripts.
+pub fn fail(output: Output, error: &'static str, number: Option<u16>, message: &str) {
+    let failure = Failure {
+        error,
+        number,
+        fields: vec![],
+    };
+    match (output, serde_json::to_string(&failure)) {
+        (Output::Json, Ok(json)) => eprintln!("{}", json),
+        _ => println!("{}", message),
+    }
+}
+
+/// Like `fail` with a `bad_request`, telling which fields are invalid and
+/// why.
+pub fn fail_fields(output: Output, number: u16, errors: &[FieldError]) {
+    let failure = Failure {
+        error: "bad_request",
+        number: Some(number),
+        fields: errors
+            .iter()
+            .map(|e| InvalidField {
+                field: &e.field,
+                message: &e.message,
+            })
+            .collect(),
+    };
+    match (output, serde_json::to_string(&failure)) {
+        (Output::Json, Ok(json)) => eprintln!("{}", json),
+        _ => {
+            println!("The request is invalid:");
+            for error in errors {
+                println!("  {} {}", error.field, error.message);
+            }
+        }
+    }
+}
+
+pub fn copy_to_clipboard<T: Serialize>(value: &T) {
+    let json = match serde_json::to_string_pretty(value) {
+        Ok(json) => json,
+        _ => {
+            println!("An error occurred while serializing the clipboard content");
+            return;
+        }
+    };
+
+    match Clipboard::new().map(|mut clipboard| clipboard.set_text(json)) {
+        Ok(Ok(())) => println!("Copied to the clipboard"),
+        _ => println!("An error occurred while accessing the clipboard"),
+    }
+}
+
+pub fn supported_types() -> Vec<String> {
+    fetch_types::execute(fetch_types::Request { generation: None })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| t.name)
+        .collect::<Vec<String>>()
+}
@@ -0,0 +1,77 @@
+use crate::cli::create_pokemon::{prompt_valid_name, prompt_valid_types};
+use crate::cli::{fail, fail_fields, prompt_number, show, Output};
+use crate::domain::patch_pokemon;
+use crate::service::PokedexService;
+use dialoguer::{theme::ColorfulTheme, Select};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+/// Prompts for a single field, the other one is left as it is.
+pub fn run(service: Arc<PokedexService>, output: Output) {
+    let req = match prompt_number().and_then(prompt_field) {
+        Ok(req) => req,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+
+    let number = req.number;
+    match service.patch_pokemon(req) {
+        Ok(res) => show(
+            output,
+            &Response {
+                number: res.number,
+                name: res.name,
+                types: res.types,
+            },
+        ),
+        Err(patch_pokemon::Error::BadRequest(errors)) => fail_fields(output, number, &errors),
+        Err(patch_pokemon::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon does not exist",
+        ),
+        Err(patch_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    };
+}
+
+fn prompt_field(number: u16) -> Result<patch_pokemon::Request, ()> {
+    let index = match Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Field to edit")
+        .items(&["Name", "Types"])
+        .default(0)
+        .interact()
+    {
+        Ok(index) => index,
+        _ => return Err(()),
+    };
+
+    match index {
+        0 => prompt_valid_name("").map(|name| patch_pokemon::Request {
+            number,
+            name: Some(name),
+            types: None,
+        }),
+        _ => prompt_valid_types(&[]).map(|types| patch_pokemon::Request {
+            number,
+            name: None,
+            types: Some(types),
+        }),
+    }
+}
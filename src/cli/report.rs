@@ -0,0 +1,81 @@
+use crate::clock::{now, parse_period};
+use crate::domain::entities::WebhookEvent;
+use crate::domain::fetch_activity_report;
+use crate::notifiers::webhook::WebhookNotifier;
+use crate::repositories::change::Repository as ChangeRepository;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct Event {
+    since: u64,
+    markdown: String,
+}
+
+/// Prints the activity of the last `period` as Markdown, such as `7d` or
+/// `12h`, and optionally sends it to the webhooks.
+pub fn run(
+    change_repo: Arc<dyn ChangeRepository>,
+    period: &str,
+    notifier: Option<WebhookNotifier>,
+) {
+    let since = match parse_period(period) {
+        Ok(seconds) => now().saturating_sub(seconds),
+        _ => {
+            println!("The period must be a number of days or hours, such as 7d or 12h");
+            return;
+        }
+    };
+
+    let res =
+        match fetch_activity_report::execute(change_repo, fetch_activity_report::Request { since })
+        {
+            Ok(res) => res,
+            Err(fetch_activity_report::Error::Unknown) => {
+                println!("An unknown error occurred");
+                return;
+            }
+        };
+
+    let markdown = render(period, &res);
+    print!("{}", markdown);
+
+    if let Some(notifier) = notifier {
+        notifier.notify_and_wait(WebhookEvent::ReportGenerated, &Event { since, markdown });
+    }
+}
+
+fn render(period: &str, res: &fetch_activity_report::Response) -> String {
+    let mut lines = vec![
+        format!("# Pokedex activity over the last {}", period),
+        String::new(),
+        format!("- {} new Pokemon(s)", res.created.len()),
+        format!("- {} update(s)", res.updated),
+        format!("- {} deletion(s)", res.deleted.len()),
+    ];
+
+    if !res.created.is_empty() {
+        lines.push(String::from("\n## New entries\n"));
+        for entry in res.created.iter() {
+            lines.push(format!("- #{} {}", entry.number, entry.name));
+        }
+    }
+
+    if !res.deleted.is_empty() {
+        lines.push(String::from("\n## Deletions\n"));
+        for number in res.deleted.iter() {
+            lines.push(format!("- #{}", number));
+        }
+    }
+
+    if !res.types.is_empty() {
+        lines.push(String::from("\n## Most active types\n"));
+        lines.push(String::from("| Type | Changes |"));
+        lines.push(String::from("| --- | --- |"));
+        for t in res.types.iter() {
+            lines.push(format!("| {} | {} |", t.name, t.changes));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
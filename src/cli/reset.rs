@@ -0,0 +1,14 @@
+use crate::domain::clear_pokemons;
+use crate::service::PokedexService;
+use std::sync::Arc;
+
+/// Deletes every Pokemon. Without `confirmed`, deletes nothing.
+pub fn run(service: Arc<PokedexService>, confirmed: bool) {
+    match service.clear_pokemons(clear_pokemons::Request { confirmed }) {
+        Ok(res) => println!("{} Pokemon(s) deleted", res.numbers.len()),
+        Err(clear_pokemons::Error::NotConfirmed) => {
+            println!("This deletes every Pokemon, add --yes to do it")
+        }
+        Err(clear_pokemons::Error::Unknown) => println!("An unknown error occurred"),
+    }
+}
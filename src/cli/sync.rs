@@ -0,0 +1,216 @@
+use crate::domain::entities::ChangeCursor;
+use crate::replication::{self, Change};
+use crate::repositories::change::Repository as ChangeRepository;
+use crate::repositories::pokemon::Repository;
+use dialoguer::{theme::ColorfulTheme, Select};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Arc;
+
+const BATCH_SIZE: u32 = 100;
+
+/// Where the local cache stands relative to the remote instance: the last
+/// remote change pulled and the last local change pushed.
+#[derive(Serialize, Deserialize, Default)]
+struct State {
+    remote_cursor: Option<String>,
+    local_cursor: Option<String>,
+}
+
+pub fn pull(
+    url: &str,
+    state_path: &str,
+    repo: Arc<dyn Repository>,
+    change_repo: Arc<dyn ChangeRepository>,
+) {
+    let mut state = load_state(state_path);
+
+    let remote = match replication::fetch_all_changes(url, state.remote_cursor.as_deref()) {
+        Ok(remote) => remote,
+        _ => {
+            println!("The remote Pokedex is unreachable");
+            return;
+        }
+    };
+    let local = match fetch_local_changes(change_repo, &state) {
+        Ok((local, _)) => local,
+        _ => {
+            println!("An unknown error occurred");
+            return;
+        }
+    };
+
+    let mut pulled = 0;
+    for (number, change) in last_change_by_number(remote.changes) {
+        if local.contains_key(&number) && !take_remote(number) {
+            continue;
+        }
+        if replication::apply(repo.clone(), change).is_err() {
+            println!("An error occurred while pulling Pokemon #{}", number);
+            return;
+        }
+        pulled += 1;
+    }
+
+    state.remote_cursor = remote.cursor;
+    match save_state(state_path, &state) {
+        Ok(()) => println!("{} change(s) pulled", pulled),
+        _ => println!("An error occurred while saving the sync state"),
+    }
+}
+
+pub fn push(
+    url: &str,
+    state_path: &str,
+    repo: Arc<dyn Repository>,
+    change_repo: Arc<dyn ChangeRepository>,
+) {
+    let mut state = load_state(state_path);
+
+    let remote = match replication::fetch_all_changes(url, state.remote_cursor.as_deref()) {
+        Ok(remote) => remote,
+        _ => {
+            println!("The remote Pokedex is unreachable");
+            return;
+        }
+    };
+    let (local, local_cursor) = match fetch_local_changes(change_repo, &state) {
+        Ok(local) => local,
+        _ => {
+            println!("An unknown error occurred");
+            return;
+        }
+    };
+    let remote = last_change_by_number(remote.changes);
+
+    let mut pushed = 0;
+    for (number, change) in local {
+        if let Some(remote_change) = remote.get(&number) {
+            if take_remote(number) {
+                if replication::apply(repo.clone(), remote_change.clone()).is_err() {
+                    println!("An error occurred while pulling Pokemon #{}", number);
+                    return;
+                }
+                continue;
+            }
+        }
+        match send(url, change) {
+            Ok(()) => pushed += 1,
+            _ => {
+                println!("An error occurred while pushing Pokemon #{}", number);
+                return;
+            }
+        }
+    }
+
+    state.local_cursor = local_cursor;
+    match save_state(state_path, &state) {
+        Ok(()) => println!("{} change(s) pushed", pushed),
+        _ => println!("An error occurred while saving the sync state"),
+    }
+}
+
+fn take_remote(number: u16) -> bool {
+    let choices = ["Keep the local version", "Take the remote version"];
+    matches!(
+        Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Pokemon #{} was changed both locally and remotely",
+                number
+            ))
+            .items(&choices)
+            .default(0)
+            .interact(),
+        Ok(1)
+    )
+}
+
+fn fetch_local_changes(
+    change_repo: Arc<dyn ChangeRepository>,
+    state: &State,
+) -> Result<(BTreeMap<u16, Change>, Option<String>), ()> {
+    let mut cursor = match state.local_cursor.clone().map(ChangeCursor::try_from) {
+        Some(Ok(cursor)) => Some(cursor),
+        Some(Err(())) => return Err(()),
+        None => None,
+    };
+    let mut changes = vec![];
+
+    loop {
+        let page = match change_repo.fetch_since(cursor, BATCH_SIZE) {
+            Ok(page) => page,
+            _ => return Err(()),
+        };
+        let caught_up = page.len() < BATCH_SIZE as usize;
+        for change in page {
+            cursor = Some(change.cursor);
+            changes.push(Change::from(change));
+        }
+        if caught_up {
+            return Ok((last_change_by_number(changes), cursor.map(String::from)));
+        }
+    }
+}
+
+fn last_change_by_number(changes: Vec<Change>) -> BTreeMap<u16, Change> {
+    changes
+        .into_iter()
+        .map(|change| (change.number, change))
+        .collect::<BTreeMap<u16, Change>>()
+}
+
+/// Replaces the remote Pokemon with the local one, creating it when the
+/// remote Pokemon is missing, so that the fields the change carries all
+/// make it there.
+fn send(url: &str, change: Change) -> Result<(), ()> {
+    let url = format!("{}/pokemons/{}", url.trim_end_matches('/'), change.number);
+
+    match (change.kind.as_str(), &change.name, &change.types) {
+        ("created" | "updated", Some(name), Some(types)) => {
+            let mut body = ureq::json!({
+                "name": name,
+                "types": types,
+            });
+            // A change giving the class holds the whole Pokemon: the
+            // measurements and the sprite it leaves out are not set.
+            if let Some(class) = &change.class {
+                body["evolutions"] = ureq::json!(change.evolutions);
+                body["abilities"] = ureq::json!(change.abilities);
+                body["height"] = ureq::json!(change.height);
+                body["weight"] = ureq::json!(change.weight);
+                body["sprite"] = ureq::json!(change.sprite);
+                body["class"] = ureq::json!(class);
+            }
+            match ureq::put(&url).send_json(body.clone()) {
+                Ok(_) => Ok(()),
+                Err(ureq::Error::Status(404, _)) => {
+                    match ureq::put(&url).set("If-None-Match", "*").send_json(body) {
+                        Ok(_) => Ok(()),
+                        _ => Err(()),
+                    }
+                }
+                _ => Err(()),
+            }
+        }
+        ("deleted", _, _) => match ureq::delete(&url).call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            _ => Err(()),
+        },
+        _ => Err(()),
+    }
+}
+
+fn load_state(path: &str) -> State {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<State>(&content).unwrap_or_default(),
+        _ => State::default(),
+    }
+}
+
+fn save_state(path: &str, state: &State) -> Result<(), ()> {
+    match serde_json::to_string(state) {
+        Ok(content) => fs::write(path, content).map_err(|_| ()),
+        _ => Err(()),
+    }
+}
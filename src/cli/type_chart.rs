@@ -0,0 +1,219 @@
+use crate::cli::fetch_pokemon::type_badge;
+use crate::cli::{fail, prompt_number, show, supported_types, Output};
+use crate::domain::{fetch_pokemon, fetch_type_matchups};
+use crate::service::PokedexService;
+use console::{style, Color};
+use dialoguer::{theme::ColorfulTheme, Select};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+struct Matchup {
+    attacking: String,
+    defending: String,
+    multiplier: f32,
+}
+
+enum Defender {
+    Every,
+    Type(String),
+    Pokemon,
+}
+
+/// Shows how effective a type, or every type, is against another type, a
+/// Pokemon or every type, as a grid colored by multiplier.
+pub fn run(service: Arc<PokedexService>, output: Output) {
+    let types = supported_types();
+    let prompted = prompt_attacking(&types)
+        .and_then(|attacking| prompt_defender(&types).map(|defender| (attacking, defender)));
+    let (attacking, defender) = match prompted {
+        Ok(prompted) => prompted,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+
+    let (defending, label) = match defender {
+        Defender::Every => (None, None),
+        Defender::Type(t) => (Some(vec![t]), None),
+        Defender::Pokemon => match fetch_defender(service, output) {
+            Some(pokemon) => (Some(pokemon.types), Some(pokemon.name)),
+            None => return,
+        },
+    };
+
+    let req = fetch_type_matchups::Request {
+        attacking,
+        defending,
+        generation: None,
+    };
+    match fetch_type_matchups::execute(req) {
+        Ok(res) => match output {
+            Output::Text => print_grid(&res, label),
+            Output::Json => {
+                for row in res.rows.iter() {
+                    for (defending, multiplier) in res.defending.iter().zip(row.multipliers.iter())
+                    {
+                        show(
+                            output,
+                            &Matchup {
+                                attacking: row.attacking.clone(),
+                                defending: defending.clone(),
+                                multiplier: *multiplier,
+                            },
+                        );
+                    }
+                }
+            }
+        },
+        Err(fetch_type_matchups::Error::BadRequest) => {
+            fail(output, "bad_request", None, "The request is invalid")
+        }
+    }
+}
+
+fn prompt_attacking(types: &[String]) -> Result<Option<String>, ()> {
+    let mut items = vec![String::from("Every type")];
+    items.extend(types.iter().cloned());
+
+    match select("Attacking type", &items) {
+        Ok(0) => Ok(None),
+        Ok(index) => Ok(Some(types[index - 1].clone())),
+        _ => Err(()),
+    }
+}
+
+fn prompt_defender(types: &[String]) -> Result<Defender, ()> {
+    let mut items = vec![String::from("Every type")];
+    items.extend(types.iter().cloned());
+    items.push(String::from("A Pokemon"));
+
+    match select("Defending type", &items) {
+        Ok(0) => Ok(Defender::Every),
+        Ok(index) if index <= types.len() => Ok(Defender::Type(types[index - 1].clone())),
+        Ok(_) => Ok(Defender::Pokemon),
+        _ => Err(()),
+    }
+}
+
+fn fetch_defender(service: Arc<PokedexService>, output: Output) -> Option<fetch_pokemon::Response> {
+    let number = match prompt_number() {
+        Ok(number) => number,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return None;
+        }
+    };
+
+    match service.fetch_pokemon(fetch_pokemon::Request { number }) {
+        Ok(pokemon) => Some(pokemon),
+        Err(fetch_pokemon::Error::BadRequest) => {
+            fail(
+                output,
+                "bad_request",
+                Some(number),
+                "The request is invalid",
+            );
+            None
+        }
+        Err(fetch_pokemon::Error::NotFound) => {
+            fail(
+                output,
+                "not_found",
+                Some(number),
+                "The Pokemon does not exist",
+            );
+            None
+        }
+        Err(fetch_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred");
+            None
+        }
+    }
+}
+
+fn select(prompt: &str, items: &[String]) -> Result<usize, ()> {
+    match Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(items)
+        .default(0)
+        .interact()
+    {
+        Ok(index) => Ok(index),
+        _ => Err(()),
+    }
+}
+
+/// Every type is shortened to three letters in the header, so that the
+/// whole chart fits in a terminal. The attacking types end the rows, as the
+/// width of their emoji varies.
+fn print_grid(res: &fetch_type_matchups::Response, label: Option<String>) {
+    let headers = match (label, &res.defending[..]) {
+        (Some(label), _) => vec![label],
+        (None, [defending]) => vec![defending.clone()],
+        (None, defending) => defending
+            .iter()
+            .map(|t| t.chars().take(3).collect::<String>())
+            .collect(),
+    };
+    let width = headers.iter().map(|h| h.chars().count()).max().unwrap_or(0) + 1;
+
+    println!();
+    println!(
+        "  {}",
+        headers
+            .iter()
+            .map(|h| format!("{:^width$}", h, width = width))
+            .collect::<String>()
+    );
+    for row in res.rows.iter() {
+        println!(
+            "  {} {}",
+            row.multipliers
+                .iter()
+                .map(|m| cell(*m, width))
+                .collect::<String>(),
+            type_badge(&row.attacking)
+        );
+    }
+    println!();
+    println!(
+        "  {} super effective  {} not very effective  {} no effect",
+        cell(2.0, 3),
+        cell(0.5, 3),
+        cell(0.0, 3)
+    );
+    println!();
+}
+
+fn cell(multiplier: f32, width: usize) -> String {
+    // Every multiplier is a power of two from ¼ to 4, or 0.
+    let label = match (multiplier * 4.0) as u8 {
+        0 => "0",
+        1 => "¼",
+        2 => "½",
+        8 => "2",
+        16 => "4",
+        _ => "1",
+    };
+    let cell = format!("{:^width$}", label, width = width);
+
+    match multiplier {
+        m if m <= 0.0 => style(cell).fg(Color::White).bg(Color::Black),
+        m if m < 1.0 => style(cell).fg(Color::Black).bg(Color::Red),
+        m if m > 1.0 => style(cell).fg(Color::Black).bg(Color::Green),
+        _ => style(cell).dim(),
+    }
+    .to_string()
+}
@@ -0,0 +1,95 @@
+use crate::cli::create_pokemon::{prompt_valid_name, prompt_valid_types};
+use crate::cli::{fail, fail_fields, prompt_number, show, Output};
+use crate::domain::{fetch_pokemon, update_pokemon};
+use crate::service::PokedexService;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+/// Prompts for the new name and types, starting from the current ones.
+pub fn run(service: Arc<PokedexService>, output: Output) {
+    let number = match prompt_number() {
+        Ok(number) => number,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+
+    let current = match service.fetch_pokemon(fetch_pokemon::Request { number }) {
+        Ok(res) => res,
+        Err(fetch_pokemon::Error::BadRequest) => {
+            fail(
+                output,
+                "bad_request",
+                Some(number),
+                "The request is invalid",
+            );
+            return;
+        }
+        Err(fetch_pokemon::Error::NotFound) => {
+            fail(
+                output,
+                "not_found",
+                Some(number),
+                "The Pokemon does not exist",
+            );
+            return;
+        }
+        Err(fetch_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred");
+            return;
+        }
+    };
+
+    let req = match (
+        prompt_valid_name(&current.name),
+        prompt_valid_types(&current.types),
+    ) {
+        (Ok(name), Ok(types)) => update_pokemon::Request {
+            number,
+            name,
+            types,
+        },
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+    match service.update_pokemon(req) {
+        Ok(res) => show(
+            output,
+            &Response {
+                number: res.number,
+                name: res.name,
+                types: res.types,
+            },
+        ),
+        Err(update_pokemon::Error::BadRequest(errors)) => fail_fields(output, number, &errors),
+        Err(update_pokemon::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon does not exist",
+        ),
+        Err(update_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    };
+}
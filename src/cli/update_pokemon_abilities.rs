@@ -0,0 +1,130 @@
+use crate::cli::fetch_pokemon::AbilityView;
+use crate::cli::{fail, fail_fields, prompt_number, show, Output};
+use crate::domain::fetch_pokemon::{self, Ability};
+use crate::domain::update_pokemon_abilities;
+use crate::service::PokedexService;
+use dialoguer::Input;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    abilities: Vec<AbilityView>,
+}
+
+pub fn run(service: Arc<PokedexService>, output: Output) {
+    let number = match prompt_number() {
+        Ok(number) => number,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+
+    let current = match service.fetch_pokemon(fetch_pokemon::Request { number }) {
+        Ok(res) => res.abilities,
+        Err(fetch_pokemon::Error::BadRequest) => {
+            fail(
+                output,
+                "bad_request",
+                Some(number),
+                "The request is invalid",
+            );
+            return;
+        }
+        Err(fetch_pokemon::Error::NotFound) => {
+            fail(
+                output,
+                "not_found",
+                Some(number),
+                "The Pokemon does not exist",
+            );
+            return;
+        }
+        Err(fetch_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred");
+            return;
+        }
+    };
+
+    let req = match prompt_abilities(&current) {
+        Ok(abilities) => update_pokemon_abilities::Request { number, abilities },
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+    match service.update_pokemon_abilities(req) {
+        Ok(res) => show(
+            output,
+            &Response {
+                number: res.number,
+                name: res.name,
+                types: res.types,
+                abilities: res.abilities.into_iter().map(AbilityView::from).collect(),
+            },
+        ),
+        Err(update_pokemon_abilities::Error::BadRequest(errors)) => {
+            fail_fields(output, number, &errors)
+        }
+        Err(update_pokemon_abilities::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon does not exist",
+        ),
+        Err(update_pokemon_abilities::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    }
+}
+
+/// The abilities are comma separated, the hidden one ending with a `*`, and
+/// none of them removes them all.
+fn prompt_abilities(current: &[Ability]) -> Result<Vec<Ability>, ()> {
+    let initial = current
+        .iter()
+        .map(|a| match a.hidden {
+            true => format!("{}*", a.name),
+            false => a.name.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    match Input::<String>::new()
+        .with_prompt("Pokemon abilities (the hidden one ending with *)")
+        .with_initial_text(initial)
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(abilities) => Ok(abilities
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(|a| match a.strip_suffix('*') {
+                Some(name) => Ability {
+                    name: String::from(name),
+                    hidden: true,
+                },
+                None => Ability {
+                    name: String::from(a),
+                    hidden: false,
+                },
+            })
+            .collect()),
+        _ => Err(()),
+    }
+}
@@ -0,0 +1,138 @@
+use crate::cli::{fail, fail_fields, prompt_number, show, supported_types, Output};
+use crate::domain::{fetch_pokemon, update_pokemon_types};
+use crate::service::PokedexService;
+use dialoguer::Select;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+pub fn run(service: Arc<PokedexService>, output: Output) {
+    let number = match prompt_number() {
+        Ok(number) => number,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+
+    let current = match service.fetch_pokemon(fetch_pokemon::Request { number }) {
+        Ok(res) => res.types,
+        Err(fetch_pokemon::Error::BadRequest) => {
+            fail(
+                output,
+                "bad_request",
+                Some(number),
+                "The request is invalid",
+            );
+            return;
+        }
+        Err(fetch_pokemon::Error::NotFound) => {
+            fail(
+                output,
+                "not_found",
+                Some(number),
+                "The Pokemon does not exist",
+            );
+            return;
+        }
+        Err(fetch_pokemon::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred");
+            return;
+        }
+    };
+
+    let req = match prompt_operation(&current) {
+        Ok((operation, pokemon_type)) => update_pokemon_types::Request {
+            number,
+            operation,
+            pokemon_type,
+        },
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+    match service.update_pokemon_types(req) {
+        Ok(res) => show(
+            output,
+            &Response {
+                number: res.number,
+                name: res.name,
+                types: res.types,
+            },
+        ),
+        Err(update_pokemon_types::Error::BadRequest(errors)) => {
+            fail_fields(output, number, &errors)
+        }
+        Err(update_pokemon_types::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon does not exist",
+        ),
+        Err(update_pokemon_types::Error::LastType) => fail(
+            output,
+            "last_type",
+            Some(number),
+            "A Pokemon must keep at least one type",
+        ),
+        Err(update_pokemon_types::Error::TooManyTypes) => fail(
+            output,
+            "too_many_types",
+            Some(number),
+            "The Pokemon already has as many types as allowed",
+        ),
+        Err(update_pokemon_types::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    }
+}
+
+fn prompt_operation(current: &[String]) -> Result<(update_pokemon_types::Operation, String), ()> {
+    let addable = supported_types()
+        .into_iter()
+        .filter(|t| !current.contains(t))
+        .collect::<Vec<String>>();
+
+    let mut choices = vec![];
+    for t in addable.iter() {
+        choices.push((update_pokemon_types::Operation::Add, t.clone()));
+    }
+    for t in current.iter() {
+        choices.push((update_pokemon_types::Operation::Remove, t.clone()));
+    }
+
+    let items = choices
+        .iter()
+        .map(|(operation, t)| match operation {
+            update_pokemon_types::Operation::Add => format!("Add {}", t),
+            update_pokemon_types::Operation::Remove => format!("Remove {}", t),
+        })
+        .collect::<Vec<String>>();
+
+    match Select::new()
+        .with_prompt("Pokemon types")
+        .items(&items)
+        .default(0)
+        .interact()
+    {
+        Ok(index) => Ok(choices.remove(index)),
+        _ => Err(()),
+    }
+}
@@ -0,0 +1,54 @@
+use crate::cli::archive;
+use crate::domain::fetch_checksum;
+use crate::repositories::diagnostics::{self, Check};
+use crate::repositories::pokemon::Repository;
+use console::style;
+use std::sync::Arc;
+
+/// Prints the checksum of the Pokemons, compares it with the one of an
+/// archive and looks for corrupt sqlite rows. Tells whether all is well.
+pub fn run(repo: Arc<dyn Repository>, sqlite: Option<&str>, archive_path: Option<&str>) -> bool {
+    let mut checks = vec![];
+
+    let current = match fetch_checksum::execute(repo) {
+        Ok(res) => {
+            println!("{} Pokemon(s), checksum {}", res.count, res.checksum);
+            Some(res)
+        }
+        Err(fetch_checksum::Error::Unknown) => {
+            checks.push(Check {
+                name: String::from("records"),
+                result: Err(String::from("some Pokemons can't be read")),
+            });
+            None
+        }
+    };
+
+    if let (Some(current), Some(path)) = (&current, archive_path) {
+        checks.push(Check {
+            name: format!("same Pokemons as {}", path),
+            result: match archive::checksum(path) {
+                Ok((_, checksum)) if checksum == current.checksum => Ok(()),
+                Ok((count, _)) => Err(format!(
+                    "the archive holds {} Pokemon(s) and differs, restore it with: \
+                    pokedex reset --yes && pokedex archive restore {}",
+                    count, path
+                )),
+                Err(message) => Err(format!("the archive {}", message)),
+            },
+        });
+    }
+
+    if let Some(path) = sqlite {
+        checks.extend(diagnostics::sqlite_integrity(path));
+    }
+
+    for Check { name, result } in checks.iter() {
+        match result {
+            Ok(()) => println!("{} {}", style("✔").green(), name),
+            Err(message) => println!("{} {}: {}", style("✘").red(), name, message),
+        }
+    }
+
+    checks.iter().all(|check| check.result.is_ok())
+}
@@ -0,0 +1,217 @@
+use crate::cli::{fail, fail_fields, prompt_number, prompt_types, show, Output};
+use crate::domain::{create_wish, delete_wish, fetch_wishlist, promote_wish};
+use crate::repositories::wishlist::Repository as WishlistRepository;
+use crate::service::PokedexService;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize)]
+struct Wish {
+    number: u16,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Pokemon {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+}
+
+pub fn run(
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    service: Arc<PokedexService>,
+    output: Output,
+) {
+    let choices = [
+        "Show the wishlist",
+        "Add a wish",
+        "Remove a wish",
+        "Promote a wish to the Pokedex",
+        "Back",
+    ];
+    let index = match Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Wishlist")
+        .items(&choices)
+        .default(0)
+        .interact()
+    {
+        Ok(index) => index,
+        _ => return,
+    };
+
+    match index {
+        0 => show_wishlist(wishlist_repo, output),
+        1 => add(wishlist_repo, output),
+        2 => remove(wishlist_repo, output),
+        3 => promote(wishlist_repo, service, output),
+        _ => {}
+    };
+}
+
+fn show_wishlist(wishlist_repo: Arc<dyn WishlistRepository>, output: Output) {
+    match fetch_wishlist::execute(wishlist_repo) {
+        Ok(res) => res.into_iter().for_each(|w| {
+            show(
+                output,
+                &Wish {
+                    number: w.number,
+                    name: w.name,
+                    note: w.note,
+                },
+            )
+        }),
+        Err(fetch_wishlist::Error::Unknown) => {
+            fail(output, "unknown", None, "An unknown error occurred")
+        }
+    };
+}
+
+fn add(wishlist_repo: Arc<dyn WishlistRepository>, output: Output) {
+    let number = prompt_number();
+    let name = prompt_text("Pokemon name");
+    let note = prompt_text("Note (optional)");
+
+    let (number, req) = match (number, name, note) {
+        (Ok(number), Ok(name), Ok(note)) => (
+            number,
+            create_wish::Request {
+                number,
+                name,
+                note: (!note.trim().is_empty()).then_some(note),
+            },
+        ),
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+    match create_wish::execute(wishlist_repo, req) {
+        Ok(res) => show(
+            output,
+            &Wish {
+                number: res.number,
+                name: res.name,
+                note: res.note,
+            },
+        ),
+        Err(create_wish::Error::BadRequest) => fail(
+            output,
+            "bad_request",
+            Some(number),
+            "The request is invalid",
+        ),
+        Err(create_wish::Error::Conflict) => fail(
+            output,
+            "conflict",
+            Some(number),
+            "The Pokemon is already on the wishlist",
+        ),
+        Err(create_wish::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    };
+}
+
+fn remove(wishlist_repo: Arc<dyn WishlistRepository>, output: Output) {
+    let number = match prompt_number() {
+        Ok(number) => number,
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+    match delete_wish::execute(wishlist_repo, delete_wish::Request { number }) {
+        Ok(()) => match output {
+            Output::Text => println!("The wish has been removed"),
+            Output::Json => println!("{{\"deleted\":{}}}", number),
+        },
+        Err(delete_wish::Error::BadRequest) => fail(
+            output,
+            "bad_request",
+            Some(number),
+            "The request is invalid",
+        ),
+        Err(delete_wish::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon is not on the wishlist",
+        ),
+        Err(delete_wish::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    };
+}
+
+fn promote(
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    service: Arc<PokedexService>,
+    output: Output,
+) {
+    let number = prompt_number();
+    let types = prompt_types(&[]);
+
+    let (number, req) = match (number, types) {
+        (Ok(number), Ok(types)) => (number, promote_wish::Request { number, types }),
+        _ => {
+            fail(
+                output,
+                "prompt",
+                None,
+                "An error occurred during the prompt",
+            );
+            return;
+        }
+    };
+    match service.promote_wish(wishlist_repo, req) {
+        Ok(res) => show(
+            output,
+            &Pokemon {
+                number: res.number,
+                name: res.name,
+                types: res.types,
+            },
+        ),
+        Err(promote_wish::Error::BadRequest(errors)) => fail_fields(output, number, &errors),
+        Err(promote_wish::Error::NotFound) => fail(
+            output,
+            "not_found",
+            Some(number),
+            "The Pokemon is not on the wishlist",
+        ),
+        Err(promote_wish::Error::Conflict) => fail(
+            output,
+            "conflict",
+            Some(number),
+            "The Pokemon already exists",
+        ),
+        Err(promote_wish::Error::Unknown) => {
+            fail(output, "unknown", Some(number), "An unknown error occurred")
+        }
+    };
+}
+
+fn prompt_text(prompt: &str) -> Result<String, ()> {
+    match Input::new()
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .interact_text()
+    {
+        Ok(text) => Ok(text),
+        _ => Err(()),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::api::{create_pokemon, fetch_all_pokemons, fetch_pokemon};
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Conflict,
+    Unknown,
+}
+
+pub struct PokedexClient {
+    url: String,
+}
+
+impl PokedexClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn create(&self, req: &create_pokemon::Request) -> Result<create_pokemon::Response, Error> {
+        let body = match serde_json::to_value(req) {
+            Ok(body) => body,
+            _ => return Err(Error::BadRequest),
+        };
+        match ureq::post(&format!("{}/pokemons", self.url)).send_json(body) {
+            Ok(res) => match res.into_json::<create_pokemon::Response>() {
+                Ok(res) => Ok(res),
+                _ => Err(Error::Unknown),
+            },
+            Err(err) => Err(Error::from(err)),
+        }
+    }
+
+    pub fn fetch(&self, number: u16) -> Result<fetch_pokemon::Response, Error> {
+        match ureq::get(&format!("{}/pokemons/{}", self.url, number)).call() {
+            Ok(res) => match res.into_json::<fetch_pokemon::Response>() {
+                Ok(res) => Ok(res),
+                _ => Err(Error::Unknown),
+            },
+            Err(err) => Err(Error::from(err)),
+        }
+    }
+
+    pub fn delete(&self, number: u16) -> Result<(), Error> {
+        match ureq::delete(&format!("{}/pokemons/{}", self.url, number)).call() {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::from(err)),
+        }
+    }
+
+    pub fn list(&self) -> Result<Vec<fetch_all_pokemons::Response>, Error> {
+        match ureq::get(&format!("{}/pokemons", self.url)).call() {
+            Ok(res) => match res.into_json::<Vec<fetch_all_pokemons::Response>>() {
+                Ok(res) => Ok(res),
+                _ => Err(Error::Unknown),
+            },
+            Err(err) => Err(Error::from(err)),
+        }
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(400, _) => Self::BadRequest,
+            ureq::Error::Status(404, _) => Self::NotFound,
+            ureq::Error::Status(409, _) => Self::Conflict,
+            _ => Self::Unknown,
+        }
+    }
+}
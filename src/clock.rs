@@ -0,0 +1,19 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the number of seconds elapsed since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Parses a number of days or hours, such as `7d` or `12h`, into seconds.
+pub fn parse_period(period: &str) -> Result<u64, ()> {
+    let (value, unit) = period.split_at(period.len().saturating_sub(1));
+    match (value.parse::<u64>(), unit) {
+        (Ok(days), "d") => Ok(days * 24 * 3600),
+        (Ok(hours), "h") => Ok(hours * 3600),
+        _ => Err(()),
+    }
+}
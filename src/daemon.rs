@@ -0,0 +1,147 @@
+use std::fs::{self, OpenOptions};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub enum Status {
+    Running(u32),
+    /// The PID file names a process which is gone.
+    Stale(u32),
+    Stopped,
+}
+
+/// Forks into the background, detached from the terminal, with the output
+/// appended to `log_file`. The parent writes the PID file and exits, only
+/// the child returns. Must run before any thread is spawned.
+pub fn start(pid_file: &str, log_file: &str) -> Result<(), String> {
+    if let Status::Running(pid) = status(pid_file) {
+        return Err(format!("Already running with PID {}", pid));
+    }
+
+    let log = match OpenOptions::new().create(true).append(true).open(log_file) {
+        Ok(log) => log,
+        Err(err) => return Err(format!("Cannot open {}: {}", log_file, err)),
+    };
+
+    match sys::fork_detached(log) {
+        Ok(Some(pid)) => {
+            if let Err(err) = fs::write(pid_file, format!("{}\n", pid)) {
+                sys::terminate(pid);
+                return Err(format!("Cannot write {}: {}", pid_file, err));
+            }
+            println!("Started in the background with PID {}", pid);
+            std::process::exit(0)
+        }
+        Ok(None) => Ok(()),
+        Err(()) => Err(String::from("Cannot fork into the background")),
+    }
+}
+
+pub fn status(pid_file: &str) -> Status {
+    let pid = match fs::read_to_string(pid_file).map(|pid| pid.trim().parse::<u32>()) {
+        Ok(Ok(pid)) => pid,
+        _ => return Status::Stopped,
+    };
+
+    match sys::is_alive(pid) {
+        true => Status::Running(pid),
+        false => Status::Stale(pid),
+    }
+}
+
+/// Asks the process to terminate, waits for it to exit and removes the PID
+/// file. Returns the PID of the stopped process.
+pub fn stop(pid_file: &str, timeout: Duration) -> Result<u32, String> {
+    let pid = match status(pid_file) {
+        Status::Running(pid) => pid,
+        Status::Stale(_) => {
+            fs::remove_file(pid_file).ok();
+            return Err(String::from("Not running, the stale PID file was removed"));
+        }
+        Status::Stopped => return Err(String::from("Not running")),
+    };
+
+    sys::terminate(pid);
+
+    let start = Instant::now();
+    while sys::is_alive(pid) {
+        if start.elapsed() > timeout {
+            return Err(format!("PID {} did not stop within {:?}", pid, timeout));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    fs::remove_file(pid_file).ok();
+    Ok(pid)
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::fs::{File, OpenOptions};
+    use std::os::unix::io::AsRawFd;
+
+    /// `Some(child)` in the parent, `None` in the child.
+    pub fn fork_detached(log: File) -> Result<Option<u32>, ()> {
+        let null = match OpenOptions::new().read(true).open("/dev/null") {
+            Ok(null) => null,
+            _ => return Err(()),
+        };
+
+        match unsafe { libc::fork() } {
+            -1 => Err(()),
+            0 => unsafe {
+                libc::setsid();
+                libc::dup2(null.as_raw_fd(), libc::STDIN_FILENO);
+                libc::dup2(log.as_raw_fd(), libc::STDOUT_FILENO);
+                libc::dup2(log.as_raw_fd(), libc::STDERR_FILENO);
+                Ok(None)
+            },
+            pid => Ok(Some(pid as u32)),
+        }
+    }
+
+    pub fn is_alive(pid: u32) -> bool {
+        unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+    }
+
+    pub fn terminate(pid: u32) {
+        unsafe {
+            libc::kill(pid as libc::pid_t, libc::SIGTERM);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use std::fs::File;
+
+    pub fn fork_detached(_log: File) -> Result<Option<u32>, ()> {
+        Err(())
+    }
+
+    pub fn is_alive(_pid: u32) -> bool {
+        false
+    }
+
+    pub fn terminate(_pid: u32) {}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_tell_a_running_process_from_a_stale_pid_file() {
+        let path = std::env::temp_dir().join(format!("pokedex-{}.pid", std::process::id()));
+        let path = path.to_string_lossy();
+
+        assert!(matches!(status(&path), Status::Stopped));
+
+        fs::write(path.as_ref(), format!("{}\n", std::process::id())).ok();
+        assert!(matches!(status(&path), Status::Running(pid) if pid == std::process::id()));
+
+        fs::write(path.as_ref(), format!("{}\n", i32::MAX)).ok();
+        assert!(matches!(status(&path), Status::Stale(_)));
+        assert!(stop(&path, Duration::ZERO).is_err());
+        assert!(matches!(status(&path), Status::Stopped));
+    }
+}
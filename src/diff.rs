@@ -0,0 +1,400 @@
+use crate::domain::entities::{
+    Evolution, Pokemon, PokemonAbilities, PokemonSprite, PokemonType, PokemonTypes,
+};
+use crate::repositories::pokemon::{DeleteError, InsertError, Query, Repository, UpdateError};
+use std::sync::Arc;
+
+/// A field of a Pokemon holding different values on both sides.
+pub struct Field {
+    pub name: &'static str,
+    pub left: String,
+    pub right: String,
+}
+
+pub enum Difference {
+    OnlyInLeft(Pokemon),
+    OnlyInRight(Pokemon),
+    Differing {
+        left: Pokemon,
+        right: Pokemon,
+        fields: Vec<Field>,
+    },
+}
+
+impl Difference {
+    pub fn number(&self) -> u16 {
+        match self {
+            Difference::OnlyInLeft(pokemon) | Difference::OnlyInRight(pokemon) => {
+                pokemon.number.value()
+            }
+            Difference::Differing { left, .. } => left.number.value(),
+        }
+    }
+}
+
+/// Which side is the source of truth when reconciling.
+#[derive(Clone, Copy)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TryFrom<&str> for Direction {
+    type Error = ();
+
+    fn try_from(d: &str) -> Result<Self, Self::Error> {
+        match d {
+            "left-to-right" => Ok(Self::LeftToRight),
+            "right-to-left" => Ok(Self::RightToLeft),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Applied {
+    pub applied: u32,
+    /// The numbers of the Pokemons the target backend failed to write.
+    pub failed: Vec<u16>,
+}
+
+/// The Pokemons found on a single side and those differing, by number.
+pub fn compare(
+    left: Arc<dyn Repository>,
+    right: Arc<dyn Repository>,
+) -> Result<Vec<Difference>, ()> {
+    let (left_pokemons, right_pokemons) =
+        match (left.fetch_all(Query::new()), right.fetch_all(Query::new())) {
+            (Ok(left_pokemons), Ok(right_pokemons)) => (left_pokemons, right_pokemons),
+            _ => return Err(()),
+        };
+
+    let mut differences = vec![];
+
+    for pokemon in left_pokemons.iter() {
+        match right_pokemons.iter().find(|p| p.number == pokemon.number) {
+            Some(other) => {
+                let fields = fields(pokemon, other);
+                if !fields.is_empty() {
+                    differences.push(Difference::Differing {
+                        left: pokemon.clone(),
+                        right: other.clone(),
+                        fields,
+                    });
+                }
+            }
+            None => differences.push(Difference::OnlyInLeft(pokemon.clone())),
+        }
+    }
+
+    for pokemon in right_pokemons.iter() {
+        if !left_pokemons.iter().any(|p| p.number == pokemon.number) {
+            differences.push(Difference::OnlyInRight(pokemon.clone()));
+        }
+    }
+
+    differences.sort_by_key(Difference::number);
+    Ok(differences)
+}
+
+/// Writes the Pokemons of the source side over the other one, which loses
+/// those the source doesn't have.
+pub fn apply(
+    differences: &[Difference],
+    left: Arc<dyn Repository>,
+    right: Arc<dyn Repository>,
+    direction: Direction,
+) -> Applied {
+    let target = match direction {
+        Direction::LeftToRight => right,
+        Direction::RightToLeft => left,
+    };
+
+    let mut applied = Applied {
+        applied: 0,
+        failed: vec![],
+    };
+
+    for difference in differences {
+        let res = match (difference, direction) {
+            (Difference::OnlyInLeft(pokemon), Direction::LeftToRight)
+            | (Difference::OnlyInRight(pokemon), Direction::RightToLeft) => {
+                insert(target.as_ref(), pokemon)
+            }
+            (Difference::OnlyInLeft(pokemon), Direction::RightToLeft)
+            | (Difference::OnlyInRight(pokemon), Direction::LeftToRight) => {
+                match target.delete(pokemon.number.clone()) {
+                    Ok(()) | Err(DeleteError::NotFound) => Ok(()),
+                    Err(DeleteError::Unknown) => Err(()),
+                }
+            }
+            (Difference::Differing { left, .. }, Direction::LeftToRight) => {
+                update(target.as_ref(), left)
+            }
+            (Difference::Differing { right, .. }, Direction::RightToLeft) => {
+                update(target.as_ref(), right)
+            }
+        };
+
+        match res {
+            Ok(()) => applied.applied += 1,
+            Err(()) => applied.failed.push(difference.number()),
+        }
+    }
+
+    applied
+}
+
+fn insert(target: &dyn Repository, pokemon: &Pokemon) -> Result<(), ()> {
+    match target.insert(
+        pokemon.number.clone(),
+        pokemon.name.clone(),
+        pokemon.types.clone(),
+    ) {
+        Ok(_) => details(target, pokemon),
+        Err(InsertError::Conflict) | Err(InsertError::Unknown) => Err(()),
+    }
+}
+
+fn update(target: &dyn Repository, pokemon: &Pokemon) -> Result<(), ()> {
+    match target.update(
+        pokemon.number.clone(),
+        pokemon.name.clone(),
+        pokemon.types.clone(),
+    ) {
+        Ok(_) => details(target, pokemon),
+        Err(UpdateError::NotFound) | Err(UpdateError::Unknown) => Err(()),
+    }
+}
+
+// The evolutions, the abilities, the measurements, the sprite and the class
+// aren't written along with the Pokemon.
+fn details(target: &dyn Repository, pokemon: &Pokemon) -> Result<(), ()> {
+    match (
+        target.update_evolutions(pokemon.number.clone(), pokemon.evolutions.clone()),
+        target.update_abilities(pokemon.number.clone(), pokemon.abilities.clone()),
+        target.update_measurements(pokemon.number.clone(), pokemon.height, pokemon.weight),
+        target.update_sprite(pokemon.number.clone(), pokemon.sprite.clone()),
+        target.update_class(pokemon.number.clone(), pokemon.class),
+    ) {
+        (Ok(_), Ok(_), Ok(_), Ok(_), Ok(_)) => Ok(()),
+        _ => Err(()),
+    }
+}
+
+fn fields(left: &Pokemon, right: &Pokemon) -> Vec<Field> {
+    [
+        Field {
+            name: "name",
+            left: String::from(left.name.as_str()),
+            right: String::from(right.name.as_str()),
+        },
+        Field {
+            name: "types",
+            left: types(&left.types),
+            right: types(&right.types),
+        },
+        Field {
+            name: "evolutions",
+            left: evolutions(&left.evolutions),
+            right: evolutions(&right.evolutions),
+        },
+        Field {
+            name: "abilities",
+            left: abilities(&left.abilities),
+            right: abilities(&right.abilities),
+        },
+        Field {
+            name: "height",
+            left: measurement(left.height.map(f64::from), "m"),
+            right: measurement(right.height.map(f64::from), "m"),
+        },
+        Field {
+            name: "weight",
+            left: measurement(left.weight.map(f64::from), "kg"),
+            right: measurement(right.weight.map(f64::from), "kg"),
+        },
+        Field {
+            name: "sprite",
+            left: sprite(&left.sprite),
+            right: sprite(&right.sprite),
+        },
+        Field {
+            name: "class",
+            left: String::from(left.class),
+            right: String::from(right.class),
+        },
+    ]
+    .into_iter()
+    .filter(|field| field.left != field.right)
+    .collect()
+}
+
+fn measurement(value: Option<f64>, unit: &str) -> String {
+    match value {
+        Some(value) => format!("{:.1} {}", value, unit),
+        None => String::new(),
+    }
+}
+
+fn sprite(sprite: &Option<PokemonSprite>) -> String {
+    match sprite {
+        Some(sprite) => String::from(sprite.as_str()),
+        None => String::new(),
+    }
+}
+
+fn types(types: &PokemonTypes) -> String {
+    types
+        .as_slice()
+        .iter()
+        .map(PokemonType::as_str)
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+fn evolutions(evolutions: &[Evolution]) -> String {
+    evolutions
+        .iter()
+        .map(|e| match e.level {
+            Some(level) => format!(
+                "#{} by {} at level {}",
+                e.target.value(),
+                String::from(e.method),
+                level
+            ),
+            None => format!("#{} by {}", e.target.value(), String::from(e.method)),
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn abilities(abilities: &Option<PokemonAbilities>) -> String {
+    match abilities {
+        Some(abilities) => abilities
+            .as_slice()
+            .iter()
+            .map(|a| match a.hidden {
+                true => format!("{} (hidden)", a.name),
+                false => a.name.clone(),
+            })
+            .collect::<Vec<String>>()
+            .join(", "),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    // Pikachu on both sides with different types, Charmander only on the
+    // right.
+    fn sides() -> (Arc<InMemoryRepository>, Arc<InMemoryRepository>) {
+        let left = Arc::new(InMemoryRepository::new());
+        let right = Arc::new(InMemoryRepository::new());
+        left.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        right
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::charmander(),
+            )
+            .ok();
+        right
+            .insert(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            )
+            .ok();
+        right
+            .update_abilities(
+                PokemonNumber::charmander(),
+                Some(PokemonAbilities::charmander()),
+            )
+            .ok();
+        (left, right)
+    }
+
+    #[test]
+    fn it_should_report_the_records_of_a_single_side_and_the_differing_fields() {
+        let (left, right) = sides();
+
+        let res = compare(left, right);
+
+        match res.as_deref() {
+            Ok([Difference::OnlyInRight(charmander), Difference::Differing { fields, .. }]) => {
+                assert_eq!(charmander.number.value(), 4);
+                match &fields[..] {
+                    [types] => {
+                        assert_eq!(types.name, "types");
+                        assert_eq!(types.left, "Electric");
+                        assert_eq!(types.right, "Fire");
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_report_nothing_when_both_sides_match() {
+        let (left, _) = sides();
+
+        let res = compare(left.clone(), left);
+
+        assert!(matches!(res.as_deref(), Ok([])));
+    }
+
+    #[test]
+    fn it_should_make_the_target_a_copy_of_the_source() {
+        let (left, right) = sides();
+        let differences = match compare(left.clone(), right.clone()) {
+            Ok(differences) => differences,
+            _ => unreachable!(),
+        };
+
+        let applied = apply(
+            &differences,
+            left.clone(),
+            right.clone(),
+            Direction::RightToLeft,
+        );
+
+        assert_eq!(applied.applied, 2);
+        assert!(applied.failed.is_empty());
+        assert!(matches!(compare(left.clone(), right).as_deref(), Ok([])));
+        assert!(matches!(
+            left.fetch_one(PokemonNumber::charmander()),
+            Ok(pokemon) if pokemon.abilities.is_some()
+        ));
+    }
+
+    #[test]
+    fn it_should_delete_the_records_the_source_does_not_have() {
+        let (left, right) = sides();
+        let differences = match compare(left.clone(), right.clone()) {
+            Ok(differences) => differences,
+            _ => unreachable!(),
+        };
+
+        let applied = apply(
+            &differences,
+            left.clone(),
+            right.clone(),
+            Direction::LeftToRight,
+        );
+
+        assert_eq!(applied.applied, 2);
+        assert!(right.fetch_one(PokemonNumber::charmander()).is_err());
+        assert!(matches!(compare(left, right).as_deref(), Ok([])));
+    }
+}
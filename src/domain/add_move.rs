@@ -0,0 +1,178 @@
+use crate::domain::entities::{LearnedMove, Move, PokemonNumber};
+use crate::domain::fetch_moves;
+use crate::repositories::moves::{InsertError, Repository as MovesRepository};
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use std::sync::Arc;
+
+/// Teaches a move to a Pokemon at a level. The details of the move replace
+/// those it had for the other Pokemons learning it.
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+    pub move_type: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub level: u8,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    /// The Pokemon already learns a move of this name.
+    Conflict,
+    Unknown,
+}
+
+pub fn execute(
+    moves_repo: Arc<dyn MovesRepository>,
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<fetch_moves::LearnedMove, Error> {
+    let (number, learned) = match (
+        PokemonNumber::try_from(req.number),
+        Move::try_new(req.name, req.move_type, req.power, req.accuracy, req.pp)
+            .and_then(|details| LearnedMove::try_new(details, req.level)),
+    ) {
+        (Ok(number), Ok(learned)) => (number, learned),
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch_one(number.clone()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    match moves_repo.insert(number, learned) {
+        Ok(learned) => Ok(fetch_moves::LearnedMove::from(learned)),
+        Err(InsertError::Conflict) => Err(Error::Conflict),
+        Err(InsertError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::moves::InMemoryRepository as InMemoryMovesRepository;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu(), Move::thunderbolt(), 26);
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        let mut req = Request::new(PokemonNumber::pikachu(), Move::thunderbolt(), 26);
+        req.move_type = String::from("Plasma");
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_level_is_invalid() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), Move::thunderbolt(), 0);
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        let req = Request::new(PokemonNumber::charmander(), Move::thunderbolt(), 26);
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_pokemon_already_learns_the_move() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        let repo = pikachu();
+        execute(
+            moves_repo.clone(),
+            repo.clone(),
+            Request::new(PokemonNumber::pikachu(), Move::thunderbolt(), 26),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::pikachu(), Move::thunderbolt(), 30);
+
+        let res = execute(moves_repo, repo, req);
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_learned_move_otherwise() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), Move::thunderbolt(), 26);
+
+        let res = execute(moves_repo.clone(), pikachu(), req);
+
+        match res {
+            Ok(learned) => {
+                assert_eq!(learned.name, "Thunderbolt");
+                assert_eq!(learned.level, 26);
+            }
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            moves_repo.fetch_all(PokemonNumber::pikachu()),
+            Ok(learnset) if learnset.len() == 1
+        ));
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, details: Move, level: u8) -> Self {
+            Self {
+                number: u16::from(number),
+                name: details.name,
+                move_type: String::from(details.move_type),
+                power: details.power,
+                accuracy: details.accuracy,
+                pp: details.pp,
+                level,
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::domain::entities::PokemonName;
+use crate::repositories::pokemon::{FetchAllError, NameExistsError, Query, Repository};
+use std::sync::Arc;
+
+const MAX_DISTANCE: usize = 2;
+const MAX_SUGGESTIONS: usize = 5;
+
+pub struct Request {
+    pub name: String,
+}
+
+pub struct Response {
+    pub valid: bool,
+    pub available: bool,
+    pub suggestions: Vec<String>,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let wanted = req.name.to_lowercase();
+    let name = match PokemonName::try_from(req.name) {
+        Ok(name) => name,
+        _ => {
+            return Ok(Response {
+                valid: false,
+                available: false,
+                suggestions: vec![],
+            })
+        }
+    };
+
+    let available = match repo.name_exists(name) {
+        Ok(exists) => !exists,
+        Err(NameExistsError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let pokemons = match repo.fetch_all(Query::new()) {
+        Ok(pokemons) => pokemons,
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let mut matches = pokemons
+        .into_iter()
+        .map(|p| {
+            (
+                distance(&wanted, &p.name.as_str().to_lowercase()),
+                String::from(p.name),
+            )
+        })
+        .filter(|(distance, _)| *distance <= MAX_DISTANCE)
+        .collect::<Vec<(usize, String)>>();
+    matches.sort();
+
+    Ok(Response {
+        valid: true,
+        available,
+        suggestions: matches
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, name)| name)
+            .collect::<Vec<String>>(),
+    })
+}
+
+// Levenshtein distance between two names.
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonName::pikachu());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_report_an_invalid_name_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonName::bad());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => assert!(!res.valid),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_report_a_taken_name_with_close_matches() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request {
+            name: String::from("pikachu"),
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert!(res.valid);
+                assert!(!res.available);
+                assert_eq!(res.suggestions, vec![String::from(PokemonName::pikachu())]);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_report_an_available_name_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request {
+            name: String::from("Mew"),
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert!(res.valid);
+                assert!(res.available);
+                assert!(res.suggestions.is_empty());
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(name: PokemonName) -> Self {
+            Self {
+                name: String::from(name),
+            }
+        }
+    }
+}
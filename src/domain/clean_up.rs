@@ -0,0 +1,205 @@
+use crate::repositories::change::{self, TrimError};
+use crate::repositories::webhook::{self, TrimDeliveriesError};
+use std::sync::Arc;
+
+/// The timestamps, in seconds since the unix epoch, before which the
+/// records are removed. `None` keeps them all.
+pub struct Request {
+    pub changes_before: Option<u64>,
+    pub deliveries_before: Option<u64>,
+    pub dry_run: bool,
+}
+
+/// How many records were removed, or would be with `dry_run`.
+pub struct Response {
+    pub changes: u32,
+    pub deliveries: u32,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+/// Trims the change log down to what replaying it still needs, and removes
+/// the old webhook deliveries.
+pub fn execute(
+    change_repo: Arc<dyn change::Repository>,
+    webhook_repo: Arc<dyn webhook::Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let changes = match req.changes_before {
+        Some(timestamp) => match change_repo.trim(timestamp, req.dry_run) {
+            Ok(changes) => changes,
+            Err(TrimError::Unknown) => return Err(Error::Unknown),
+        },
+        None => 0,
+    };
+
+    let deliveries = match req.deliveries_before {
+        Some(timestamp) => match webhook_repo.trim_deliveries(timestamp, req.dry_run) {
+            Ok(deliveries) => deliveries,
+            Err(TrimDeliveriesError::Unknown) => return Err(Error::Unknown),
+        },
+        None => 0,
+    };
+
+    Ok(Response {
+        changes,
+        deliveries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        ChangeKind, Delivery, Pokemon, PokemonName, PokemonNumber, PokemonTypes, WebhookEvent,
+        WebhookId,
+    };
+    use crate::repositories::change::Repository as ChangeRepository;
+    use crate::repositories::webhook::Repository as WebhookRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let change_repo = Arc::new(change::InMemoryRepository::new().with_error());
+        let webhook_repo = Arc::new(webhook::InMemoryRepository::new());
+        let req = Request::new(Some(100), None, false);
+
+        let res = execute(change_repo, webhook_repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_count_without_removing_anything_on_a_dry_run() {
+        let change_repo = Arc::new(change::InMemoryRepository::new());
+        let webhook_repo = Arc::new(webhook::InMemoryRepository::new());
+        fill(change_repo.clone(), webhook_repo.clone());
+        let req = Request::new(Some(250), Some(250), true);
+
+        let res = execute(change_repo.clone(), webhook_repo.clone(), req);
+
+        match res {
+            Ok(Response {
+                changes: 1,
+                deliveries: 2,
+            }) => {}
+            _ => unreachable!(),
+        };
+        assert_eq!(change_repo.fetch_until(300).map(|c| c.len()).ok(), Some(4));
+        assert_eq!(
+            webhook_repo
+                .fetch_deliveries(WebhookId::first())
+                .map(|d| d.len())
+                .ok(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn it_should_keep_the_latest_change_of_every_pokemon() {
+        let change_repo = Arc::new(change::InMemoryRepository::new());
+        let webhook_repo = Arc::new(webhook::InMemoryRepository::new());
+        fill(change_repo.clone(), webhook_repo.clone());
+        let req = Request::new(Some(250), Some(250), false);
+
+        let res = execute(change_repo.clone(), webhook_repo.clone(), req);
+
+        match res {
+            Ok(Response {
+                changes: 1,
+                deliveries: 2,
+            }) => {}
+            _ => unreachable!(),
+        };
+        match change_repo.fetch_until(300).as_deref() {
+            Ok([charmander, first_update, second_update]) => {
+                assert!(matches!(charmander.kind, ChangeKind::Created));
+                assert!(charmander.number == PokemonNumber::charmander());
+                assert_eq!(first_update.timestamp, 200);
+                assert_eq!(second_update.timestamp, 300);
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            webhook_repo
+                .fetch_deliveries(WebhookId::first())
+                .map(|d| d.len())
+                .ok(),
+            Some(1)
+        );
+    }
+
+    /// Pikachu is created then updated twice, Charmander only created.
+    fn fill(
+        change_repo: Arc<change::InMemoryRepository>,
+        webhook_repo: Arc<webhook::InMemoryRepository>,
+    ) {
+        let pikachu = Pokemon::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+        change_repo
+            .insert(
+                ChangeKind::Created,
+                PokemonNumber::pikachu(),
+                Some(pikachu.clone()),
+                100,
+            )
+            .ok();
+        change_repo
+            .insert(
+                ChangeKind::Created,
+                PokemonNumber::charmander(),
+                Some(Pokemon::new(
+                    PokemonNumber::charmander(),
+                    PokemonName::charmander(),
+                    PokemonTypes::charmander(),
+                )),
+                100,
+            )
+            .ok();
+        change_repo
+            .insert(
+                ChangeKind::Updated,
+                PokemonNumber::pikachu(),
+                Some(pikachu.clone()),
+                200,
+            )
+            .ok();
+        change_repo
+            .insert(
+                ChangeKind::Updated,
+                PokemonNumber::pikachu(),
+                Some(pikachu),
+                300,
+            )
+            .ok();
+
+        for timestamp in [100, 200, 300] {
+            webhook_repo
+                .insert_delivery(Delivery {
+                    webhook_id: WebhookId::first(),
+                    event: WebhookEvent::PokemonUpdated,
+                    attempt: 1,
+                    status_code: Some(200),
+                    timestamp,
+                })
+                .ok();
+        }
+    }
+
+    impl Request {
+        fn new(changes_before: Option<u64>, deliveries_before: Option<u64>, dry_run: bool) -> Self {
+            Self {
+                changes_before,
+                deliveries_before,
+                dry_run,
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::repositories::pokemon::{ClearError, Repository};
+use std::sync::Arc;
+
+/// Nothing is deleted unless `confirmed`: there is no undoing it.
+pub struct Request {
+    pub confirmed: bool,
+}
+
+pub struct Response {
+    pub numbers: Vec<u16>,
+}
+
+pub enum Error {
+    NotConfirmed,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    if !req.confirmed {
+        return Err(Error::NotConfirmed);
+    }
+
+    match repo.clear() {
+        Ok(pokemons) => Ok(Response {
+            numbers: pokemons.into_iter().map(|p| u16::from(p.number)).collect(),
+        }),
+        Err(ClearError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request { confirmed: true };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_confirmed_error_and_keep_the_pokemons_when_not_confirmed() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request { confirmed: false };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Err(Error::NotConfirmed) => {}
+            _ => unreachable!(),
+        };
+        assert!(matches!(repo.count(), Ok(1)));
+    }
+
+    #[test]
+    fn it_should_delete_every_pokemon_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request { confirmed: true };
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(res.numbers, vec![4, 25]),
+            _ => unreachable!(),
+        };
+        assert!(matches!(repo.count(), Ok(0)));
+    }
+}
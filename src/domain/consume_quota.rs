@@ -0,0 +1,106 @@
+use crate::domain::entities::ApiKey;
+use crate::repositories::usage::{IncrementError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub key: String,
+    pub limit: u32,
+    pub day: u64,
+}
+
+pub struct Response {
+    pub limit: u32,
+    pub remaining: u32,
+}
+
+pub enum Error {
+    BadRequest,
+    QuotaExceeded,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match ApiKey::try_from(req.key) {
+        Ok(key) => match repo.increment(key, req.day) {
+            Ok(count) if count > req.limit => Err(Error::QuotaExceeded),
+            Ok(count) => Ok(Response {
+                limit: req.limit,
+                remaining: req.limit - count,
+            }),
+            Err(IncrementError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::usage::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(ApiKey::bad(), 1);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(ApiKey::ash(), 1);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_quota_exceeded_error_when_the_limit_is_reached() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.increment(ApiKey::ash(), 0).ok();
+        let req = Request::new(ApiKey::ash(), 1);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::QuotaExceeded) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_remaining_quota_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(ApiKey::ash(), 2);
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.limit, 2);
+                assert_eq!(res.remaining, 1);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(key: ApiKey, limit: u32) -> Self {
+            Self {
+                key: String::from(key),
+                limit,
+                day: 0,
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use crate::domain::validate_pokemon::{self, FieldError};
 use crate::repositories::pokemon::{InsertError, Repository};
 use std::sync::Arc;
 
@@ -15,7 +16,8 @@ pub struct Response {
 }
 
 pub enum Error {
-    BadRequest,
+    /// The invalid fields, each with the reason.
+    BadRequest(Vec<FieldError>),
     Conflict,
     Unknown,
 }
@@ -23,14 +25,15 @@ pub enum Error {
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
     match (
         PokemonNumber::try_from(req.number),
-        PokemonName::try_from(req.name),
-        PokemonTypes::try_from(req.types),
+        PokemonName::try_from(req.name.clone()),
+        PokemonTypes::try_from(req.types.clone()),
     ) {
         (Ok(number), Ok(name), Ok(types)) => match repo.insert(number, name, types) {
             Ok(Pokemon {
                 number,
                 name,
                 types,
+                ..
             }) => Ok(Response {
                 number: u16::from(number),
                 name: String::from(name),
@@ -39,7 +42,14 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Erro
             Err(InsertError::Conflict) => Err(Error::Conflict),
             Err(InsertError::Unknown) => Err(Error::Unknown),
         },
-        _ => Err(Error::BadRequest),
+        _ => {
+            let req = validate_pokemon::Request {
+                number: req.number,
+                name: req.name,
+                types: req.types,
+            };
+            Err(Error::BadRequest(validate_pokemon::execute(req).errors))
+        }
     }
 }
 
@@ -60,7 +70,13 @@ mod tests {
         let res = execute(repo, req);
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => {
+                    assert_eq!(error.field, "name");
+                    assert_eq!(error.message, "must not be empty");
+                }
+                _ => unreachable!(),
+            },
             _ => unreachable!(),
         };
     }
@@ -0,0 +1,92 @@
+use crate::domain::entities::{Webhook, WebhookSecret, WebhookUrl};
+use crate::repositories::webhook::{InsertError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub url: String,
+    pub secret: String,
+}
+
+pub struct Response {
+    pub id: u32,
+    pub url: String,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match (
+        WebhookUrl::try_from(req.url),
+        WebhookSecret::try_from(req.secret),
+    ) {
+        (Ok(url), Ok(secret)) => match repo.insert(url, secret) {
+            Ok(Webhook { id, url, .. }) => Ok(Response {
+                id: u32::from(id),
+                url: String::from(url),
+            }),
+            Err(InsertError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::WebhookId;
+    use crate::repositories::webhook::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookUrl::bad(), WebhookSecret::example());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(WebhookUrl::example(), WebhookSecret::example());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_webhook_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookUrl::example(), WebhookSecret::example());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.id, u32::from(WebhookId::first()));
+                assert_eq!(res.url, String::from(WebhookUrl::example()));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(url: WebhookUrl, secret: WebhookSecret) -> Self {
+            Self {
+                url: String::from(url),
+                secret: String::from(secret),
+            }
+        }
+    }
+}
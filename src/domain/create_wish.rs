@@ -0,0 +1,137 @@
+use crate::domain::entities::{PokemonName, PokemonNumber, Wish, WishNote};
+use crate::repositories::wishlist::{InsertError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+    pub note: Option<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub note: Option<String>,
+}
+
+pub enum Error {
+    BadRequest,
+    Conflict,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let note = match req.note.map(WishNote::try_from) {
+        Some(Ok(note)) => Some(note),
+        Some(Err(())) => return Err(Error::BadRequest),
+        None => None,
+    };
+
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonName::try_from(req.name),
+    ) {
+        (Ok(number), Ok(name)) => match repo.insert(number, name, note) {
+            Ok(Wish { number, name, note }) => Ok(Response {
+                number: u16::from(number),
+                name: String::from(name),
+                note: note.map(String::from),
+            }),
+            Err(InsertError::Conflict) => Err(Error::Conflict),
+            Err(InsertError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::wishlist::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), PokemonName::bad(), None);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_note_is_empty() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut req = Request::new(PokemonNumber::pikachu(), PokemonName::pikachu(), None);
+        req.note = Some(String::from("  "));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_pokemon_is_already_wished() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(PokemonNumber::pikachu(), PokemonName::pikachu(), None)
+            .ok();
+        let req = Request::new(PokemonNumber::pikachu(), PokemonName::pikachu(), None);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu(), PokemonName::pikachu(), None);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_wish_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            Some(WishNote::example()),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.name, String::from(PokemonName::pikachu()));
+                assert_eq!(res.note, Some(String::from(WishNote::example())));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, name: PokemonName, note: Option<WishNote>) -> Self {
+            Self {
+                number: u16::from(number),
+                name: String::from(name),
+                note: note.map(String::from),
+            }
+        }
+    }
+}
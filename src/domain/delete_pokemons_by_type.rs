@@ -0,0 +1,142 @@
+use crate::domain::entities::PokemonType;
+use crate::repositories::pokemon::{DeleteByTypeError, Repository};
+use std::sync::Arc;
+
+/// Nothing is deleted unless `confirmed`, so that no filter can be applied
+/// by mistake.
+pub struct Request {
+    pub pokemon_type: String,
+    pub confirmed: bool,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    BadRequest,
+    NotConfirmed,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let pokemon_type = match PokemonType::try_from(req.pokemon_type) {
+        Ok(pokemon_type) => pokemon_type,
+        _ => return Err(Error::BadRequest),
+    };
+
+    if !req.confirmed {
+        return Err(Error::NotConfirmed);
+    }
+
+    match repo.delete_by_type(pokemon_type) {
+        Ok(pokemons) => Ok(pokemons
+            .into_iter()
+            .map(|p| Response {
+                number: u16::from(p.number),
+                name: String::from(p.name),
+                types: Vec::<String>::from(p.types),
+            })
+            .collect::<Vec<Response>>()),
+        Err(DeleteByTypeError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::{InMemoryRepository, Query};
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new("Fire", true);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new("Plasma", true);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_confirmed_error_and_keep_the_pokemons_when_not_confirmed() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request::new("Fire", false);
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Err(Error::NotConfirmed) => {}
+            _ => unreachable!(),
+        };
+        assert!(matches!(repo.count(), Ok(1)));
+    }
+
+    #[test]
+    fn it_should_delete_the_pokemons_having_the_type_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request::new("Fire", true);
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_all(Query::new()) {
+            Ok(pokemons) => {
+                assert_eq!(pokemons.len(), 1);
+                assert!(pokemons[0].number == PokemonNumber::pikachu());
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(pokemon_type: &str, confirmed: bool) -> Self {
+            Self {
+                pokemon_type: String::from(pokemon_type),
+                confirmed,
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::domain::entities::WebhookId;
+use crate::repositories::webhook::{DeleteError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub id: u32,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    match WebhookId::try_from(req.id) {
+        Ok(id) => match repo.delete(id) {
+            Ok(()) => Ok(()),
+            Err(DeleteError::NotFound) => Err(Error::NotFound),
+            Err(DeleteError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookId::bad());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_webhook() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_ok_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(WebhookUrl::example(), WebhookSecret::example())
+            .ok();
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(()) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(id: WebhookId) -> Self {
+            Self { id: u32::from(id) }
+        }
+    }
+}
@@ -1,160 +1,545 @@
-use std::cmp::{PartialEq, PartialOrd};
+pub use crate::kernel::pokemon::{
+    Ability, Evolution, EvolutionMethod, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight,
+    PokemonName, PokemonNumber, PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
 
-#[derive(PartialEq, Clone, PartialOrd, Ord, Eq)]
-pub struct PokemonNumber(u16);
+#[derive(Clone)]
+pub struct WishNote(String);
+
+impl WishNote {
+    pub const MAX_LENGTH: usize = 280;
+}
 
-impl TryFrom<u16> for PokemonNumber {
+impl TryFrom<String> for WishNote {
     type Error = ();
 
-    fn try_from(n: u16) -> Result<Self, Self::Error> {
-        if n > 0 && n < 899 {
-            Ok(Self(n))
-        } else {
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        let n = n.trim();
+        if n.is_empty() || n.chars().count() > Self::MAX_LENGTH {
             Err(())
+        } else {
+            Ok(Self(String::from(n)))
         }
     }
 }
 
-impl From<PokemonNumber> for u16 {
-    fn from(n: PokemonNumber) -> Self {
+impl From<WishNote> for String {
+    fn from(n: WishNote) -> Self {
         n.0
     }
 }
 
 #[cfg(test)]
-impl PokemonNumber {
-    pub fn pikachu() -> Self {
-        Self(25)
+impl WishNote {
+    pub fn example() -> Self {
+        Self(String::from("Seen in Viridian Forest"))
     }
+}
+
+/// A Pokemon the user wants to add to the Pokedex.
+#[derive(Clone)]
+pub struct Wish {
+    pub number: PokemonNumber,
+    pub name: PokemonName,
+    pub note: Option<WishNote>,
+}
 
-    pub fn charmander() -> Self {
-        Self(4)
+impl Wish {
+    pub fn new(number: PokemonNumber, name: PokemonName, note: Option<WishNote>) -> Self {
+        Self { number, name, note }
     }
+}
 
-    pub fn bad() -> Self {
-        Self(0)
+/// A move, such as Thunderbolt. Status moves have no power and the moves
+/// which never miss no accuracy.
+#[derive(Clone)]
+pub struct Move {
+    pub name: String,
+    pub move_type: PokemonType,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+}
+
+impl Move {
+    pub const MAX_LENGTH: usize = 32;
+    pub const MAX_ACCURACY: u8 = 100;
+    pub const MAX_PP: u8 = 40;
+
+    /// Names are trimmed, and made of letters, digits, spaces, hyphens,
+    /// commas and apostrophes.
+    pub fn try_new(
+        name: String,
+        move_type: String,
+        power: Option<u8>,
+        accuracy: Option<u8>,
+        pp: u8,
+    ) -> Result<Self, ()> {
+        let name = name.trim().replace('’', "'");
+        let length = name.chars().count();
+
+        if length == 0
+            || length > Self::MAX_LENGTH
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == ',' || c == '\'')
+        {
+            return Err(());
+        }
+
+        match (PokemonType::try_from(move_type), power, accuracy, pp) {
+            (_, Some(0), _, _) | (_, _, Some(0), _) | (_, _, _, 0) => Err(()),
+            (_, _, Some(accuracy), _) if accuracy > Self::MAX_ACCURACY => Err(()),
+            (_, _, _, pp) if pp > Self::MAX_PP => Err(()),
+            (Ok(move_type), power, accuracy, pp) => Ok(Self {
+                name,
+                move_type,
+                power,
+                accuracy,
+                pp,
+            }),
+            _ => Err(()),
+        }
+    }
+
+    /// Whether both are the same move, names ignoring case.
+    pub fn is(&self, name: &str) -> bool {
+        self.name.to_lowercase() == name.trim().to_lowercase()
+    }
+}
+
+#[cfg(test)]
+impl Move {
+    pub fn thunderbolt() -> Self {
+        Self {
+            name: String::from("Thunderbolt"),
+            move_type: PokemonType::Electric,
+            power: Some(90),
+            accuracy: Some(100),
+            pp: 15,
+        }
+    }
+
+    pub fn growl() -> Self {
+        Self {
+            name: String::from("Growl"),
+            move_type: PokemonType::Normal,
+            power: None,
+            accuracy: Some(100),
+            pp: 40,
+        }
     }
 }
 
+/// A move of the learnset of a Pokemon, learned when reaching the level.
 #[derive(Clone)]
-pub struct PokemonName(String);
+pub struct LearnedMove {
+    pub details: Move,
+    pub level: u8,
+}
 
-impl TryFrom<String> for PokemonName {
+impl LearnedMove {
+    pub fn try_new(details: Move, level: u8) -> Result<Self, ()> {
+        if level == 0 || level > Evolution::MAX_LEVEL {
+            Err(())
+        } else {
+            Ok(Self { details, level })
+        }
+    }
+}
+
+/// The container of a cry, told by the media type it is uploaded with.
+#[derive(Clone, Copy, PartialEq)]
+pub enum CryFormat {
+    Ogg,
+    Mpeg,
+    Wav,
+    Webm,
+}
+
+impl TryFrom<String> for CryFormat {
     type Error = ();
 
-    fn try_from(n: String) -> Result<Self, Self::Error> {
-        if n.is_empty() {
+    /// The parameters of the media type, such as the codecs, are ignored.
+    fn try_from(content_type: String) -> Result<Self, Self::Error> {
+        match content_type
+            .split(';')
+            .next()
+            .map(|t| t.trim().to_lowercase())
+            .as_deref()
+        {
+            Some("audio/ogg") => Ok(Self::Ogg),
+            Some("audio/mpeg") => Ok(Self::Mpeg),
+            Some("audio/wav") | Some("audio/x-wav") => Ok(Self::Wav),
+            Some("audio/webm") => Ok(Self::Webm),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<CryFormat> for String {
+    fn from(format: CryFormat) -> Self {
+        String::from(match format {
+            CryFormat::Ogg => "audio/ogg",
+            CryFormat::Mpeg => "audio/mpeg",
+            CryFormat::Wav => "audio/wav",
+            CryFormat::Webm => "audio/webm",
+        })
+    }
+}
+
+/// A short audio clip of the sound the Pokemon makes.
+#[derive(Clone)]
+pub struct Cry {
+    pub format: CryFormat,
+    pub bytes: Vec<u8>,
+}
+
+impl Cry {
+    pub const MAX_SIZE: usize = 1024 * 1024;
+
+    pub fn try_new(content_type: String, bytes: Vec<u8>) -> Result<Self, ()> {
+        match CryFormat::try_from(content_type) {
+            Ok(format) if !bytes.is_empty() && bytes.len() <= Self::MAX_SIZE => {
+                Ok(Self { format, bytes })
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Cry {
+    pub fn pikachu() -> Self {
+        Self {
+            format: CryFormat::Ogg,
+            bytes: b"OggS pika pika".to_vec(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiKey(String);
+
+impl TryFrom<String> for ApiKey {
+    type Error = ();
+
+    fn try_from(k: String) -> Result<Self, Self::Error> {
+        if k.is_empty() {
             Err(())
         } else {
+            Ok(Self(k))
+        }
+    }
+}
+
+impl From<ApiKey> for String {
+    fn from(k: ApiKey) -> Self {
+        k.0
+    }
+}
+
+#[cfg(test)]
+impl ApiKey {
+    pub fn ash() -> Self {
+        Self(String::from("ash"))
+    }
+
+    pub fn bad() -> Self {
+        Self(String::from(""))
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub struct WebhookId(u32);
+
+impl TryFrom<u32> for WebhookId {
+    type Error = ();
+
+    fn try_from(n: u32) -> Result<Self, Self::Error> {
+        if n > 0 {
             Ok(Self(n))
+        } else {
+            Err(())
         }
     }
 }
 
-impl From<PokemonName> for String {
-    fn from(n: PokemonName) -> Self {
-        n.0
+impl From<WebhookId> for u32 {
+    fn from(id: WebhookId) -> Self {
+        id.0
     }
 }
 
 #[cfg(test)]
-impl PokemonName {
-    pub fn pikachu() -> Self {
-        Self(String::from("Pikachu"))
+impl WebhookId {
+    pub fn first() -> Self {
+        Self(1)
+    }
+
+    pub fn bad() -> Self {
+        Self(0)
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookUrl(String);
+
+impl TryFrom<String> for WebhookUrl {
+    type Error = ();
+
+    /// An http or https URL whose host is not on the server's own network,
+    /// since the server posts the events to it.
+    fn try_from(url: String) -> Result<Self, Self::Error> {
+        if PokemonSprite::has_public_host(&url)
+            && !url.chars().any(|c| c.is_whitespace() || c.is_control())
+        {
+            Ok(Self(url))
+        } else {
+            Err(())
+        }
     }
+}
 
-    pub fn charmander() -> Self {
-        Self(String::from("Charmander"))
+impl From<WebhookUrl> for String {
+    fn from(url: WebhookUrl) -> Self {
+        url.0
+    }
+}
+
+#[cfg(test)]
+impl WebhookUrl {
+    pub fn example() -> Self {
+        Self(String::from("https://example.com/hooks/pokedex"))
     }
 
     pub fn bad() -> Self {
-        Self(String::from(""))
+        Self(String::from("example.com"))
     }
 }
 
 #[derive(Clone)]
-pub struct PokemonTypes(Vec<PokemonType>);
+pub struct WebhookSecret(String);
 
-impl TryFrom<Vec<String>> for PokemonTypes {
+impl TryFrom<String> for WebhookSecret {
     type Error = ();
 
-    fn try_from(ts: Vec<String>) -> Result<Self, Self::Error> {
-        if ts.is_empty() {
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        if s.is_empty() {
             Err(())
         } else {
-            let mut pts = vec![];
-            for t in ts.iter() {
-                match PokemonType::try_from(String::from(t)) {
-                    Ok(pt) => pts.push(pt),
-                    _ => return Err(()),
-                }
-            }
-            Ok(Self(pts))
+            Ok(Self(s))
         }
     }
 }
 
-impl From<PokemonTypes> for Vec<String> {
-    fn from(pts: PokemonTypes) -> Self {
-        let mut ts = vec![];
-        for pt in pts.0.into_iter() {
-            ts.push(String::from(pt));
-        }
-        ts
+impl From<WebhookSecret> for String {
+    fn from(s: WebhookSecret) -> Self {
+        s.0
     }
 }
 
 #[cfg(test)]
-impl PokemonTypes {
-    pub fn pikachu() -> Self {
-        Self(vec![PokemonType::Electric])
+impl WebhookSecret {
+    pub fn example() -> Self {
+        Self(String::from("s3cret"))
     }
+}
 
-    pub fn charmander() -> Self {
-        Self(vec![PokemonType::Fire])
+#[derive(Clone)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub url: WebhookUrl,
+    pub secret: WebhookSecret,
+}
+
+impl Webhook {
+    pub fn new(id: WebhookId, url: WebhookUrl, secret: WebhookSecret) -> Self {
+        Self { id, url, secret }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum WebhookEvent {
+    PokemonCreated,
+    PokemonUpdated,
+    PokemonDeleted,
+    ReportGenerated,
+}
+
+impl TryFrom<String> for WebhookEvent {
+    type Error = ();
+
+    fn try_from(e: String) -> Result<Self, Self::Error> {
+        match e.as_str() {
+            "pokemon.created" => Ok(Self::PokemonCreated),
+            "pokemon.updated" => Ok(Self::PokemonUpdated),
+            "pokemon.deleted" => Ok(Self::PokemonDeleted),
+            "report.generated" => Ok(Self::ReportGenerated),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<WebhookEvent> for String {
+    fn from(e: WebhookEvent) -> Self {
+        String::from(match e {
+            WebhookEvent::PokemonCreated => "pokemon.created",
+            WebhookEvent::PokemonUpdated => "pokemon.updated",
+            WebhookEvent::PokemonDeleted => "pokemon.deleted",
+            WebhookEvent::ReportGenerated => "report.generated",
+        })
     }
 }
 
 #[derive(Clone)]
-enum PokemonType {
-    Electric,
-    Fire,
+pub struct Delivery {
+    pub webhook_id: WebhookId,
+    pub event: WebhookEvent,
+    pub attempt: u32,
+    pub status_code: Option<u16>,
+    pub timestamp: u64,
 }
 
-impl TryFrom<String> for PokemonType {
+#[derive(PartialEq, Clone, Copy, PartialOrd)]
+pub struct ChangeCursor(u64);
+
+impl TryFrom<String> for ChangeCursor {
     type Error = ();
 
-    fn try_from(t: String) -> Result<Self, Self::Error> {
-        match t.as_str() {
-            "Electric" => Ok(Self::Electric),
-            "Fire" => Ok(Self::Fire),
+    fn try_from(c: String) -> Result<Self, Self::Error> {
+        match c.parse::<u64>() {
+            Ok(c) => Ok(Self(c)),
             _ => Err(()),
         }
     }
 }
 
-impl From<PokemonType> for String {
-    fn from(t: PokemonType) -> Self {
-        String::from(match t {
-            PokemonType::Electric => "Electric",
-            PokemonType::Fire => "Fire",
+impl From<u64> for ChangeCursor {
+    fn from(c: u64) -> Self {
+        Self(c)
+    }
+}
+
+impl From<ChangeCursor> for u64 {
+    fn from(c: ChangeCursor) -> Self {
+        c.0
+    }
+}
+
+impl From<ChangeCursor> for String {
+    fn from(c: ChangeCursor) -> Self {
+        c.0.to_string()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl TryFrom<String> for ChangeKind {
+    type Error = ();
+
+    fn try_from(k: String) -> Result<Self, Self::Error> {
+        match k.as_str() {
+            "created" => Ok(Self::Created),
+            "updated" => Ok(Self::Updated),
+            "deleted" => Ok(Self::Deleted),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<ChangeKind> for String {
+    fn from(k: ChangeKind) -> Self {
+        String::from(match k {
+            ChangeKind::Created => "created",
+            ChangeKind::Updated => "updated",
+            ChangeKind::Deleted => "deleted",
         })
     }
 }
 
 #[derive(Clone)]
-pub struct Pokemon {
+pub struct Change {
+    pub cursor: ChangeCursor,
+    pub kind: ChangeKind,
     pub number: PokemonNumber,
-    pub name: PokemonName,
-    pub types: PokemonTypes,
+    pub pokemon: Option<Pokemon>,
+    pub timestamp: u64,
 }
 
-impl Pokemon {
-    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
-        Self {
-            number,
-            name,
-            types,
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_move(
+        name: &str,
+        move_type: &str,
+        power: Option<u8>,
+        accuracy: Option<u8>,
+        pp: u8,
+    ) -> Result<Move, ()> {
+        Move::try_new(
+            String::from(name),
+            String::from(move_type),
+            power,
+            accuracy,
+            pp,
+        )
+    }
+
+    #[test]
+    fn it_should_accept_moves_with_no_power_or_accuracy() {
+        assert!(new_move("Thunderbolt", "Electric", Some(90), Some(100), 15).is_ok());
+        assert!(new_move("Growl", "normal", None, Some(100), 40).is_ok());
+        assert!(new_move("Swift", "Normal", Some(60), None, 20).is_ok());
+        assert!(new_move("U-turn", "Bug", Some(70), Some(100), 20).is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_invalid_moves() {
+        assert!(new_move("", "Electric", Some(90), Some(100), 15).is_err());
+        assert!(new_move("Thunderbolt!", "Electric", Some(90), Some(100), 15).is_err());
+        assert!(new_move("Thunderbolt", "Plasma", Some(90), Some(100), 15).is_err());
+        assert!(new_move("Thunderbolt", "Electric", Some(0), Some(100), 15).is_err());
+        assert!(new_move("Thunderbolt", "Electric", Some(90), Some(101), 15).is_err());
+        assert!(new_move("Thunderbolt", "Electric", Some(90), Some(100), 0).is_err());
+        assert!(new_move("Thunderbolt", "Electric", Some(90), Some(100), 41).is_err());
+    }
+
+    #[test]
+    fn it_should_learn_moves_up_to_level_100() {
+        assert!(LearnedMove::try_new(Move::thunderbolt(), 1).is_ok());
+        assert!(LearnedMove::try_new(Move::thunderbolt(), 100).is_ok());
+        assert!(LearnedMove::try_new(Move::thunderbolt(), 0).is_err());
+        assert!(LearnedMove::try_new(Move::thunderbolt(), 101).is_err());
+    }
+
+    #[test]
+    fn it_should_only_accept_short_audio_clips_as_cries() {
+        let clip = || b"OggS".to_vec();
+        assert!(Cry::try_new(String::from("audio/ogg"), clip()).is_ok());
+        assert!(Cry::try_new(String::from("Audio/Ogg; codecs=opus"), clip()).is_ok());
+        assert!(Cry::try_new(String::from("audio/x-wav"), clip())
+            .is_ok_and(|cry| cry.format == CryFormat::Wav));
+        assert!(Cry::try_new(String::from("image/png"), clip()).is_err());
+        assert!(Cry::try_new(String::from("audio/ogg"), vec![]).is_err());
+        assert!(Cry::try_new(String::from("audio/ogg"), vec![0; Cry::MAX_SIZE + 1]).is_err());
+    }
+
+    #[test]
+    fn it_should_only_accept_public_hosts_as_webhooks() {
+        let url = |url: &str| WebhookUrl::try_from(String::from(url));
+        assert!(url("https://example.com/hooks/pokedex").is_ok());
+        assert!(url("http://93.184.216.34:8080/hooks").is_ok());
+        assert!(url("example.com").is_err());
+        assert!(url("http://localhost:8000/hooks").is_err());
+        assert!(url("http://169.254.169.254/latest/meta-data").is_err());
+        assert!(url("http://10.0.0.1/hooks").is_err());
+        assert!(url("http://[::1]/hooks").is_err());
     }
 }
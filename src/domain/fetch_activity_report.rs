@@ -0,0 +1,166 @@
+use crate::domain::entities::{ChangeCursor, ChangeKind, PokemonType};
+use crate::repositories::change::{FetchSinceError, Repository};
+use std::sync::Arc;
+
+const BATCH_SIZE: u32 = 1000;
+
+pub struct Request {
+    pub since: u64,
+}
+
+pub struct Response {
+    pub since: u64,
+    pub created: Vec<Entry>,
+    pub updated: u32,
+    pub deleted: Vec<u16>,
+    pub types: Vec<TypeActivity>,
+}
+
+pub struct Entry {
+    pub number: u16,
+    pub name: String,
+}
+
+pub struct TypeActivity {
+    pub name: String,
+    pub changes: u32,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+/// Summarizes the changes logged from `since`, a unix timestamp. A type is
+/// as active as the number of creations and updates of Pokemons having it;
+/// the most active types come first and types without changes are left out.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let mut res = Response {
+        since: req.since,
+        created: vec![],
+        updated: 0,
+        deleted: vec![],
+        types: vec![],
+    };
+    let mut counts = PokemonType::all()
+        .into_iter()
+        .map(|t| (t, 0))
+        .collect::<Vec<(PokemonType, u32)>>();
+    let mut cursor: Option<ChangeCursor> = None;
+
+    loop {
+        let page = match repo.fetch_since(cursor, BATCH_SIZE) {
+            Ok(page) => page,
+            Err(FetchSinceError::Unknown) => return Err(Error::Unknown),
+        };
+        let caught_up = page.len() < BATCH_SIZE as usize;
+
+        for change in page {
+            cursor = Some(change.cursor);
+            if change.timestamp < req.since {
+                continue;
+            }
+
+            match change.kind {
+                ChangeKind::Created => res.created.push(Entry {
+                    number: change.number.value(),
+                    name: change
+                        .pokemon
+                        .as_ref()
+                        .map(|p| String::from(p.name.as_str()))
+                        .unwrap_or_default(),
+                }),
+                ChangeKind::Updated => res.updated += 1,
+                ChangeKind::Deleted => res.deleted.push(change.number.value()),
+            }
+
+            if let Some(pokemon) = change.pokemon {
+                for pokemon_type in pokemon.types.as_slice() {
+                    if let Some((_, count)) = counts.iter_mut().find(|(t, _)| t == pokemon_type) {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        if caught_up {
+            break;
+        }
+    }
+
+    counts.retain(|(_, count)| *count > 0);
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    res.types = counts
+        .into_iter()
+        .map(|(t, changes)| TypeActivity {
+            name: String::from(t),
+            changes,
+        })
+        .collect::<Vec<TypeActivity>>();
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::change::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request { since: 0 };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_summarize_the_changes_of_the_period_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let pikachu = Pokemon::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+        let charmander = Pokemon::new(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        );
+        for (kind, pokemon, timestamp) in [
+            (ChangeKind::Created, Some(charmander.clone()), 10),
+            (ChangeKind::Created, Some(pikachu.clone()), 100),
+            (ChangeKind::Updated, Some(pikachu.clone()), 110),
+            (ChangeKind::Updated, Some(charmander.clone()), 120),
+            (ChangeKind::Deleted, None, 130),
+        ] {
+            let number = match &pokemon {
+                Some(pokemon) => pokemon.number.clone(),
+                None => PokemonNumber::charmander(),
+            };
+            repo.insert(kind, number, pokemon, timestamp).ok();
+        }
+        let req = Request { since: 100 };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.created.len(), 1);
+                assert_eq!(res.created[0].name, String::from(PokemonName::pikachu()));
+                assert_eq!(res.updated, 2);
+                assert_eq!(res.deleted, vec![u16::from(PokemonNumber::charmander())]);
+                assert_eq!(res.types.len(), 2);
+                assert_eq!(res.types[0].name, String::from("Electric"));
+                assert_eq!(res.types[0].changes, 2);
+                assert_eq!(res.types[1].changes, 1);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -1,26 +1,110 @@
-use crate::repositories::pokemon::{FetchAllError, Repository};
+use crate::domain::entities::PokemonClass;
+use crate::domain::fetch_pokemon::{Ability, Evolution};
+use crate::repositories::pokemon::{self, FetchAllError, Query, Repository};
 use std::sync::Arc;
 
+pub const MAX_LIMIT: u32 = 1000;
+
+/// The order of the listing, ties being broken by ascending number.
+#[derive(Clone, Copy)]
+pub enum SortBy {
+    NumberAscending,
+    NumberDescending,
+    Name,
+    /// By the first type of the Pokemons.
+    Type,
+}
+
+/// Sorts the Pokemons, skips `offset` of them, then returns `limit` of them
+/// at most, all of them without a limit.
+pub struct Request {
+    pub sort_by: SortBy,
+    pub offset: u32,
+    pub limit: Option<u32>,
+    /// Keeps the Pokemons of this class only.
+    pub class: Option<String>,
+}
+
 pub struct Response {
+    pub pokemons: Vec<Pokemon>,
+    pub warnings: Vec<Warning>,
+}
+
+pub struct Pokemon {
     pub number: u16,
     pub name: String,
     pub types: Vec<String>,
+    pub evolutions: Vec<Evolution>,
+    pub abilities: Vec<Ability>,
+    pub height: Option<f64>,
+    pub weight: Option<f64>,
+    pub sprite: Option<String>,
+    pub class: String,
+}
+
+/// A stored record left out of the listing because it isn't a valid Pokemon.
+pub struct Warning {
+    pub number: u16,
+    pub reason: String,
 }
 
 pub enum Error {
+    BadRequest,
     Unknown,
 }
 
-pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
-    match repo.fetch_all() {
-        Ok(pokemons) => Ok(pokemons
-            .into_iter()
-            .map(|p| Response {
-                number: u16::from(p.number),
-                name: String::from(p.name),
-                types: Vec::<String>::from(p.types),
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    if matches!(req.limit, Some(limit) if limit == 0 || limit > MAX_LIMIT) {
+        return Err(Error::BadRequest);
+    }
+
+    let class = match req.class.map(PokemonClass::try_from).transpose() {
+        Ok(class) => class,
+        _ => return Err(Error::BadRequest),
+    };
+
+    let (sort_by, descending) = match req.sort_by {
+        SortBy::NumberAscending => (pokemon::SortBy::Number, false),
+        SortBy::NumberDescending => (pokemon::SortBy::Number, true),
+        SortBy::Name => (pokemon::SortBy::Name, false),
+        SortBy::Type => (pokemon::SortBy::Type, false),
+    };
+    let query = Query {
+        sort_by,
+        descending,
+        offset: req.offset,
+        limit: req.limit,
+        class,
+        ..Query::new()
+    };
+    match repo.fetch_all_skipping_invalid(query) {
+        Ok((pokemons, invalid)) => {
+            let warnings = invalid
+                .into_iter()
+                .map(|record| Warning {
+                    number: record.number,
+                    reason: String::from(record.reason),
+                })
+                .collect::<Vec<Warning>>();
+
+            Ok(Response {
+                pokemons: pokemons
+                    .into_iter()
+                    .map(|p| Pokemon {
+                        number: u16::from(p.number),
+                        name: String::from(p.name),
+                        types: Vec::<String>::from(p.types),
+                        evolutions: p.evolutions.into_iter().map(Evolution::from).collect(),
+                        abilities: Ability::list(p.abilities),
+                        height: p.height.map(f64::from),
+                        weight: p.weight.map(f64::from),
+                        sprite: p.sprite.map(String::from),
+                        class: String::from(p.class),
+                    })
+                    .collect::<Vec<Pokemon>>(),
+                warnings,
             })
-            .collect::<Vec<Response>>()),
+        }
         Err(FetchAllError::Unknown) => Err(Error::Unknown),
     }
 }
@@ -29,13 +113,13 @@ pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
 mod tests {
     use super::*;
     use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
-    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::repositories::pokemon::{InMemoryRepository, InvalidRecord};
 
     #[test]
     fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
 
-        let res = execute(repo);
+        let res = execute(repo, Request::all());
 
         match res {
             Err(Error::Unknown) => {}
@@ -59,10 +143,10 @@ mod tests {
         )
         .ok();
 
-        let res = execute(repo);
+        let res = execute(repo, Request::all());
 
         match res {
-            Ok(res) => {
+            Ok(Response { pokemons: res, .. }) => {
                 assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
                 assert_eq!(res[0].name, String::from(PokemonName::charmander()));
                 assert_eq!(
@@ -76,4 +160,177 @@ mod tests {
             _ => unreachable!(),
         };
     }
+
+    #[test]
+    fn it_should_skip_and_report_the_invalid_records() {
+        let repo = Arc::new(InMemoryRepository::new().with_invalid(vec![InvalidRecord {
+            number: 7,
+            reason: "invalid name",
+        }]));
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+
+        let res = execute(repo, Request::all());
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.pokemons.len(), 1);
+                assert_eq!(res.pokemons[0].number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.warnings.len(), 1);
+                assert_eq!(res.warnings[0].number, 7);
+                assert_eq!(res.warnings[0].reason, "invalid name");
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_limit_is_zero() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            limit: Some(0),
+            ..Request::all()
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_class_is_unknown() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            class: Some(String::from("Shiny")),
+            ..Request::all()
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_of_the_requested_class() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        repo.update_class(PokemonNumber::pikachu(), PokemonClass::Mythical)
+            .ok();
+        let req = Request {
+            class: Some(String::from("mythical")),
+            ..Request::all()
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(Response { pokemons: res, .. }) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res[0].class, "Mythical");
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_page_of_the_pokemons() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request {
+            offset: 1,
+            limit: Some(1),
+            ..Request::all()
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(Response { pokemons: res, .. }) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, u16::from(PokemonNumber::pikachu()));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_in_the_requested_order() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+
+        for (sort_by, first) in [
+            (SortBy::NumberAscending, PokemonNumber::charmander()),
+            (SortBy::NumberDescending, PokemonNumber::pikachu()),
+            (SortBy::Name, PokemonNumber::charmander()),
+            (SortBy::Type, PokemonNumber::pikachu()),
+        ] {
+            let req = Request {
+                sort_by,
+                ..Request::all()
+            };
+
+            match execute(repo.clone(), req) {
+                Ok(Response { pokemons: res, .. }) => {
+                    assert_eq!(res.len(), 2);
+                    assert_eq!(res[0].number, u16::from(first));
+                }
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    impl Request {
+        fn all() -> Self {
+            Self {
+                sort_by: SortBy::NumberAscending,
+                offset: 0,
+                limit: None,
+                class: None,
+            }
+        }
+    }
 }
@@ -0,0 +1,61 @@
+use crate::repositories::webhook::{FetchAllError, Repository};
+use std::sync::Arc;
+
+pub struct Response {
+    pub id: u32,
+    pub url: String,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(webhooks) => Ok(webhooks
+            .into_iter()
+            .map(|w| Response {
+                id: u32::from(w.id),
+                url: String::from(w.url),
+            })
+            .collect::<Vec<Response>>()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{WebhookId, WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_all_the_webhooks_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(WebhookUrl::example(), WebhookSecret::example())
+            .ok();
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].id, u32::from(WebhookId::first()));
+                assert_eq!(res[0].url, String::from(WebhookUrl::example()));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
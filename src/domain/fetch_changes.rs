@@ -0,0 +1,215 @@
+use crate::domain::entities::ChangeCursor;
+use crate::domain::fetch_pokemon;
+use crate::repositories::change::{FetchSinceError, Repository};
+use std::sync::Arc;
+
+const MAX_LIMIT: u32 = 1000;
+
+pub struct Request {
+    pub since: Option<String>,
+    pub limit: u32,
+}
+
+pub struct Response {
+    pub changes: Vec<Change>,
+    pub cursor: Option<String>,
+}
+
+pub struct Change {
+    pub cursor: String,
+    pub kind: String,
+    pub number: u16,
+    /// The Pokemon as the change left it, none once deleted.
+    pub pokemon: Option<fetch_pokemon::Response>,
+    pub timestamp: u64,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    if req.limit == 0 || req.limit > MAX_LIMIT {
+        return Err(Error::BadRequest);
+    }
+
+    let since = match req.since.map(ChangeCursor::try_from) {
+        Some(Ok(since)) => Some(since),
+        Some(Err(())) => return Err(Error::BadRequest),
+        None => None,
+    };
+
+    match repo.fetch_since(since, req.limit) {
+        Ok(changes) => {
+            let cursor = match changes.last() {
+                Some(change) => Some(change.cursor),
+                None => since,
+            };
+            Ok(Response {
+                changes: changes
+                    .into_iter()
+                    .map(|c| Change {
+                        cursor: String::from(c.cursor),
+                        kind: String::from(c.kind),
+                        number: u16::from(c.number),
+                        pokemon: c.pokemon.map(fetch_pokemon::Response::from),
+                        timestamp: c.timestamp,
+                    })
+                    .collect::<Vec<Change>>(),
+                cursor: cursor.map(String::from),
+            })
+        }
+        Err(FetchSinceError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        ChangeKind, Pokemon, PokemonHeight, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::change::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_cursor_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            since: Some(String::from("not a cursor")),
+            limit: 10,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_limit_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            since: None,
+            limit: 0,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            since: None,
+            limit: 10,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_changes_after_the_cursor_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let pikachu = Pokemon::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+        repo.insert(
+            ChangeKind::Created,
+            PokemonNumber::pikachu(),
+            Some(pikachu),
+            0,
+        )
+        .ok();
+        repo.insert(ChangeKind::Deleted, PokemonNumber::pikachu(), None, 0)
+            .ok();
+        let req = Request {
+            since: Some(String::from("1")),
+            limit: 10,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.changes.len(), 1);
+                assert_eq!(res.changes[0].kind, String::from(ChangeKind::Deleted));
+                assert_eq!(res.changes[0].number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.cursor, Some(String::from("2")));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_give_the_whole_pokemon_of_each_change() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut charmander = Pokemon::new(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        );
+        charmander.height = Some(PokemonHeight::charmander());
+        repo.insert(
+            ChangeKind::Updated,
+            PokemonNumber::charmander(),
+            Some(charmander),
+            0,
+        )
+        .ok();
+        let req = Request {
+            since: None,
+            limit: 10,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => match &res.changes[..] {
+                [Change {
+                    pokemon: Some(pokemon),
+                    ..
+                }] => {
+                    assert_eq!(pokemon.name, "Charmander");
+                    assert_eq!(pokemon.height, Some(0.6));
+                    assert_eq!(pokemon.class, "Normal");
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_keep_the_cursor_when_there_is_no_new_change() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            since: Some(String::from("5")),
+            limit: 10,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert!(res.changes.is_empty());
+                assert_eq!(res.cursor, Some(String::from("5")));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,108 @@
+use crate::repositories::pokemon::{Repository, SnapshotError};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+pub struct Response {
+    pub count: usize,
+    pub checksum: String,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Response, Error> {
+    match repo.snapshot() {
+        Ok(pokemons) => {
+            let types = pokemons
+                .iter()
+                .map(|p| Vec::<String>::from(p.types.clone()))
+                .collect::<Vec<Vec<String>>>();
+            Ok(Response {
+                count: pokemons.len(),
+                checksum: checksum(
+                    pokemons
+                        .iter()
+                        .zip(types.iter())
+                        .map(|(p, types)| (p.number.value(), p.name.as_str(), types.as_slice())),
+                ),
+            })
+        }
+        Err(SnapshotError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+/// SHA-256 of the Pokemons sorted by number, one `number name types` line
+/// each: the same Pokemons always give the same checksum, wherever they are
+/// stored.
+pub fn checksum<'a>(pokemons: impl Iterator<Item = (u16, &'a str, &'a [String])>) -> String {
+    let mut lines = pokemons
+        .map(|(number, name, types)| {
+            (
+                number,
+                format!("{}\t{}\t{}\n", number, name, types.join(",")),
+            )
+        })
+        .collect::<Vec<(u16, String)>>();
+    lines.sort_by_key(|(number, _)| *number);
+
+    let mut hasher = Sha256::new();
+    for (_, line) in lines.iter() {
+        hasher.update(line.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_not_depend_on_the_order_of_the_pokemons() {
+        let (fire, electric) = (vec![String::from("Fire")], vec![String::from("Electric")]);
+        let pokemons = [
+            (4, "Charmander", fire.as_slice()),
+            (25, "Pikachu", electric.as_slice()),
+        ];
+
+        assert_eq!(
+            checksum(pokemons.iter().cloned()),
+            checksum(pokemons.iter().rev().cloned())
+        );
+    }
+
+    #[test]
+    fn it_should_change_when_a_pokemon_changes() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let before = execute(repo.clone());
+
+        repo.update_types(PokemonNumber::pikachu(), PokemonTypes::charmander())
+            .ok();
+        let after = execute(repo);
+
+        match (before, after) {
+            (Ok(before), Ok(after)) => assert_ne!(before.checksum, after.checksum),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,68 @@
+use crate::repositories::change::{LatestCursorError, Repository};
+use std::sync::Arc;
+
+pub struct Response {
+    pub version: u64,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+/// The collection version is the cursor of the latest logged change, so it
+/// moves forward on every mutation.
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Response, Error> {
+    match repo.latest_cursor() {
+        Ok(cursor) => Ok(Response {
+            version: cursor.map(u64::from).unwrap_or(0),
+        }),
+        Err(LatestCursorError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{ChangeKind, PokemonNumber};
+    use crate::repositories::change::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_zero_when_nothing_changed() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => assert_eq!(res.version, 0),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_latest_change_cursor_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(ChangeKind::Deleted, PokemonNumber::pikachu(), None, 0)
+            .ok();
+        repo.insert(ChangeKind::Deleted, PokemonNumber::charmander(), None, 0)
+            .ok();
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => assert_eq!(res.version, 2),
+            _ => unreachable!(),
+        };
+    }
+}
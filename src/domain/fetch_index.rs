@@ -0,0 +1,106 @@
+use crate::repositories::pokemon::{FetchAllError, Query, Repository, SortBy};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub struct Response {
+    pub letter: String,
+    pub pokemons: Vec<Pokemon>,
+}
+
+pub struct Pokemon {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    let query = Query {
+        sort_by: SortBy::Name,
+        ..Query::new()
+    };
+    let pokemons = match repo.fetch_all(query) {
+        Ok(pokemons) => pokemons,
+        Err(FetchAllError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let mut groups: BTreeMap<String, Vec<Pokemon>> = BTreeMap::new();
+    for p in pokemons {
+        let name = String::from(p.name);
+        groups.entry(letter(&name)).or_default().push(Pokemon {
+            number: u16::from(p.number),
+            name,
+            types: Vec::<String>::from(p.types),
+        });
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(letter, pokemons)| Response { letter, pokemons })
+        .collect::<Vec<Response>>())
+}
+
+fn letter(name: &str) -> String {
+    match name.chars().next() {
+        Some(c) if c.is_alphabetic() => c.to_uppercase().collect::<String>(),
+        _ => String::from("#"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_grouped_by_first_letter_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.len(), 2);
+                assert_eq!(res[0].letter, "C");
+                assert_eq!(
+                    res[0].pokemons[0].name,
+                    String::from(PokemonName::charmander())
+                );
+                assert_eq!(res[1].letter, "P");
+                assert_eq!(
+                    res[1].pokemons[0].name,
+                    String::from(PokemonName::pikachu())
+                );
+            }
+            _ => unreachable!(),
+        };
+    }
+}
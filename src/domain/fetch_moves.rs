@@ -0,0 +1,152 @@
+use crate::domain::entities::{self, PokemonNumber};
+use crate::repositories::moves::{FetchAllError, Repository as MovesRepository};
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+}
+
+/// A move of the learnset, with the level it is learned at.
+pub struct LearnedMove {
+    pub name: String,
+    pub move_type: String,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: u8,
+    pub level: u8,
+}
+
+impl From<entities::LearnedMove> for LearnedMove {
+    fn from(learned: entities::LearnedMove) -> Self {
+        Self {
+            name: learned.details.name,
+            move_type: String::from(learned.details.move_type),
+            power: learned.details.power,
+            accuracy: learned.details.accuracy,
+            pp: learned.details.pp,
+            level: learned.level,
+        }
+    }
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+/// The learnset of the Pokemon, by level and then by name.
+pub fn execute(
+    moves_repo: Arc<dyn MovesRepository>,
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Vec<LearnedMove>, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch_one(number.clone()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    match moves_repo.fetch_all(number) {
+        Ok(learnset) => Ok(learnset.into_iter().map(LearnedMove::from).collect()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Move, PokemonName, PokemonTypes};
+    use crate::repositories::moves::InMemoryRepository as InMemoryMovesRepository;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        let req = Request::new(PokemonNumber::bad());
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        let req = Request::new(PokemonNumber::charmander());
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_learnset_otherwise() {
+        let moves_repo = Arc::new(InMemoryMovesRepository::new());
+        for (details, level) in [(Move::thunderbolt(), 26), (Move::growl(), 1)] {
+            if let Ok(learned) = entities::LearnedMove::try_new(details, level) {
+                moves_repo.insert(PokemonNumber::pikachu(), learned).ok();
+            }
+        }
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(moves_repo, pikachu(), req);
+
+        match res {
+            Ok(learnset) => match &learnset[..] {
+                [growl, thunderbolt] => {
+                    assert_eq!(growl.name, "Growl");
+                    assert_eq!(growl.power, None);
+                    assert_eq!(thunderbolt.move_type, "Electric");
+                    assert_eq!(thunderbolt.level, 26);
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber) -> Self {
+            Self {
+                number: u16::from(number),
+            }
+        }
+    }
+}
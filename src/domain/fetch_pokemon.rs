@@ -1,4 +1,4 @@
-use crate::domain::entities::{Pokemon, PokemonNumber};
+use crate::domain::entities::{self, Pokemon, PokemonNumber};
 use crate::repositories::pokemon::{FetchOneError, Repository};
 use std::sync::Arc;
 
@@ -10,6 +10,57 @@ pub struct Response {
     pub number: u16,
     pub name: String,
     pub types: Vec<String>,
+    pub evolutions: Vec<Evolution>,
+    /// Empty until the abilities are set.
+    pub abilities: Vec<Ability>,
+    /// In meters, none until it is set.
+    pub height: Option<f64>,
+    /// In kilograms, none until it is set.
+    pub weight: Option<f64>,
+    /// The URL of the image of the Pokemon, none until it is set.
+    pub sprite: Option<String>,
+    /// Normal, Legendary or Mythical.
+    pub class: String,
+}
+
+/// What the Pokemon evolves into: the number of the evolution, a method
+/// such as `level` or `trade`, and the lowest level it happens at.
+pub struct Evolution {
+    pub target: u16,
+    pub method: String,
+    pub level: Option<u8>,
+}
+
+impl From<entities::Evolution> for Evolution {
+    fn from(evolution: entities::Evolution) -> Self {
+        Self {
+            target: u16::from(evolution.target),
+            method: String::from(evolution.method),
+            level: evolution.level,
+        }
+    }
+}
+
+pub struct Ability {
+    pub name: String,
+    pub hidden: bool,
+}
+
+impl Ability {
+    pub fn list(abilities: Option<entities::PokemonAbilities>) -> Vec<Self> {
+        abilities
+            .map(|abilities| {
+                abilities
+                    .as_slice()
+                    .iter()
+                    .map(|a| Self {
+                        name: a.name.clone(),
+                        hidden: a.hidden,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 pub enum Error {
@@ -18,18 +69,30 @@ pub enum Error {
     Unknown,
 }
 
+impl From<Pokemon> for Response {
+    fn from(pokemon: Pokemon) -> Self {
+        Self {
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            evolutions: pokemon
+                .evolutions
+                .into_iter()
+                .map(Evolution::from)
+                .collect(),
+            abilities: Ability::list(pokemon.abilities),
+            height: pokemon.height.map(f64::from),
+            weight: pokemon.weight.map(f64::from),
+            sprite: pokemon.sprite.map(String::from),
+            class: String::from(pokemon.class),
+        }
+    }
+}
+
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
     match PokemonNumber::try_from(req.number) {
         Ok(number) => match repo.fetch_one(number) {
-            Ok(Pokemon {
-                number,
-                name,
-                types,
-            }) => Ok(Response {
-                number: u16::from(number),
-                name: String::from(name),
-                types: Vec::<String>::from(types),
-            }),
+            Ok(pokemon) => Ok(Response::from(pokemon)),
             Err(FetchOneError::NotFound) => Err(Error::NotFound),
             Err(FetchOneError::Unknown) => Err(Error::Unknown),
         },
@@ -40,7 +103,7 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Erro
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::domain::entities::{PokemonHeight, PokemonName, PokemonSprite, PokemonTypes};
     use crate::repositories::pokemon::InMemoryRepository;
 
     #[test]
@@ -82,6 +145,87 @@ mod tests {
         };
     }
 
+    #[test]
+    fn it_should_return_the_evolutions_of_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let charmeleon = match entities::Evolution::try_new(5, String::from("level"), Some(16)) {
+            Ok(charmeleon) => charmeleon,
+            _ => unreachable!(),
+        };
+        repo.update_evolutions(PokemonNumber::charmander(), vec![charmeleon])
+            .ok();
+        let req = Request::new(PokemonNumber::charmander());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => match &res.evolutions[..] {
+                [evolution] => {
+                    assert_eq!(evolution.target, 5);
+                    assert_eq!(evolution.method, "level");
+                    assert_eq!(evolution.level, Some(16));
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_measurements_of_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        repo.update_measurements(
+            PokemonNumber::charmander(),
+            Some(PokemonHeight::charmander()),
+            None,
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::charmander());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.height, Some(0.6));
+                assert_eq!(res.weight, None);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_sprite_of_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.update_sprite(PokemonNumber::pikachu(), Some(PokemonSprite::pikachu()))
+            .ok();
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => assert_eq!(res.sprite, Some(String::from(PokemonSprite::pikachu()))),
+            _ => unreachable!(),
+        };
+    }
+
     #[test]
     fn it_should_return_the_pokemon_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
@@ -100,6 +244,7 @@ mod tests {
                 assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
                 assert_eq!(res.name, String::from(PokemonName::pikachu()));
                 assert_eq!(res.types, Vec::<String>::from(PokemonTypes::pikachu()));
+                assert_eq!(res.class, "Normal");
             }
             _ => unreachable!(),
         };
@@ -0,0 +1,133 @@
+use crate::domain::entities::{Pokemon, PokemonName};
+use crate::domain::fetch_pokemon::{Ability, Evolution};
+use crate::repositories::pokemon::{FetchByNameError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub name: String,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub evolutions: Vec<Evolution>,
+    pub abilities: Vec<Ability>,
+    pub height: Option<f64>,
+    pub weight: Option<f64>,
+    pub sprite: Option<String>,
+    pub class: String,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match PokemonName::try_from(req.name) {
+        Ok(name) => match repo.fetch_by_name(name) {
+            Ok(Pokemon {
+                number,
+                name,
+                types,
+                evolutions,
+                abilities,
+                height,
+                weight,
+                sprite,
+                class,
+            }) => Ok(Response {
+                number: u16::from(number),
+                name: String::from(name),
+                types: Vec::<String>::from(types),
+                evolutions: evolutions.into_iter().map(Evolution::from).collect(),
+                abilities: Ability::list(abilities),
+                height: height.map(f64::from),
+                weight: weight.map(f64::from),
+                sprite: sprite.map(String::from),
+                class: String::from(class),
+            }),
+            Err(FetchByNameError::NotFound) => Err(Error::NotFound),
+            Err(FetchByNameError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new("Pikachu");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(&String::from(PokemonName::bad()));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new("Pikachu");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_ignore_case_and_accents() {
+        let repo = Arc::new(InMemoryRepository::new());
+        match PokemonName::try_from(String::from("Píkachu")) {
+            Ok(name) => repo
+                .insert(PokemonNumber::pikachu(), name, PokemonTypes::pikachu())
+                .ok(),
+            _ => unreachable!(),
+        };
+        let req = Request::new("PIKACHU");
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.name, String::from("Píkachu"));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(name: &str) -> Self {
+            Self {
+                name: String::from(name),
+            }
+        }
+    }
+}
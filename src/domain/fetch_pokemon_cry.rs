@@ -0,0 +1,132 @@
+use crate::domain::entities::PokemonNumber;
+use crate::repositories::cry::{FetchError, Repository as CryRepository};
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+}
+
+pub struct Response {
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+pub enum Error {
+    BadRequest,
+    /// The Pokemon is missing or has no cry.
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(
+    cry_repo: Arc<dyn CryRepository>,
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch_one(number.clone()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    match cry_repo.fetch(number) {
+        Ok(cry) => Ok(Response {
+            content_type: String::from(cry.format),
+            bytes: cry.bytes,
+        }),
+        Err(FetchError::NotFound) => Err(Error::NotFound),
+        Err(FetchError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Cry, PokemonName, PokemonTypes};
+    use crate::repositories::cry::InMemoryRepository as InMemoryCryRepository;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(cry_repo, pikachu(), req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_has_no_cry() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(cry_repo, pikachu(), req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new());
+        cry_repo
+            .put(PokemonNumber::charmander(), Cry::pikachu())
+            .ok();
+        let req = Request::new(PokemonNumber::charmander());
+
+        let res = execute(cry_repo, pikachu(), req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_cry_otherwise() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new());
+        cry_repo.put(PokemonNumber::pikachu(), Cry::pikachu()).ok();
+        let req = Request::new(PokemonNumber::pikachu());
+
+        let res = execute(cry_repo, pikachu(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.content_type, "audio/ogg");
+                assert_eq!(res.bytes, Cry::pikachu().bytes);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber) -> Self {
+            Self {
+                number: u16::from(number),
+            }
+        }
+    }
+}
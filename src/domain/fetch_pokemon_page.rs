@@ -0,0 +1,138 @@
+use crate::repositories::pokemon::{CountError, FetchPageError, Repository};
+use std::sync::Arc;
+
+const MAX_PER_PAGE: u32 = 100;
+
+/// Pages are counted from 1.
+pub struct Request {
+    pub page: u32,
+    pub per_page: u32,
+}
+
+/// `from` and `to` are the positions of the first and last Pokemons of the
+/// page, counted from 1, out of `total`.
+pub struct Response {
+    pub pokemons: Vec<Pokemon>,
+    pub from: u32,
+    pub to: u32,
+    pub total: u32,
+}
+
+pub struct Pokemon {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    if req.page == 0 || req.per_page == 0 || req.per_page > MAX_PER_PAGE {
+        return Err(Error::BadRequest);
+    }
+
+    let offset = match (req.page - 1).checked_mul(req.per_page) {
+        Some(offset) => offset,
+        None => return Err(Error::BadRequest),
+    };
+
+    let total = match repo.count() {
+        Ok(total) => total,
+        Err(CountError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let pokemons = match repo.fetch_page(offset, req.per_page) {
+        Ok(pokemons) => pokemons,
+        Err(FetchPageError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let count = pokemons.len() as u32;
+
+    Ok(Response {
+        pokemons: pokemons
+            .into_iter()
+            .map(|p| Pokemon {
+                number: u16::from(p.number),
+                name: String::from(p.name),
+                types: Vec::<String>::from(p.types),
+            })
+            .collect::<Vec<Pokemon>>(),
+        from: if count == 0 { offset } else { offset + 1 },
+        to: offset + count,
+        total,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(1, 20);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        for req in [
+            Request::new(0, 20),
+            Request::new(1, 0),
+            Request::new(1, 101),
+        ] {
+            match execute(repo.clone(), req) {
+                Err(Error::BadRequest) => {}
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_page_and_the_total_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request::new(2, 1);
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.pokemons.len(), 1);
+                assert_eq!(res.pokemons[0].number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!((res.from, res.to, res.total), (2, 2, 2));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(page: u32, per_page: u32) -> Self {
+            Self { page, per_page }
+        }
+    }
+}
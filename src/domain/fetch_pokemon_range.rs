@@ -0,0 +1,122 @@
+use crate::domain::entities::PokemonNumber;
+use crate::repositories::pokemon::{FetchRangeError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub from: u16,
+    pub to: u16,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    match (
+        PokemonNumber::try_from(req.from),
+        PokemonNumber::try_from(req.to),
+    ) {
+        (Ok(from), Ok(to)) if from <= to => match repo.fetch_range(from, to) {
+            Ok(pokemons) => Ok(pokemons
+                .into_iter()
+                .map(|p| Response {
+                    number: u16::from(p.number),
+                    name: String::from(p.name),
+                    types: Vec::<String>::from(p.types),
+                })
+                .collect::<Vec<Response>>()),
+            Err(FetchRangeError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::charmander(), PokemonNumber::pikachu());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::bad(), PokemonNumber::pikachu());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_range_is_reversed() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), PokemonNumber::charmander());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_within_the_range_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::charmander(), PokemonNumber::charmander());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(from: PokemonNumber, to: PokemonNumber) -> Self {
+            Self {
+                from: u16::from(from),
+                to: u16::from(to),
+            }
+        }
+    }
+}
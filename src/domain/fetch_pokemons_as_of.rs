@@ -0,0 +1,187 @@
+use crate::domain::entities::{ChangeKind, PokemonNumber};
+use crate::domain::fetch_pokemon;
+use crate::repositories::change::{FetchUntilError, Repository};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Only the Pokemon of `number` when given, all of them otherwise.
+pub struct Request {
+    pub as_of: u64,
+    pub number: Option<u16>,
+}
+
+pub struct Response {
+    pub pokemons: Vec<fetch_pokemon::Response>,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+/// Replays the change log up to `as_of`, in seconds since the unix epoch
+/// like the timestamps of the changes. The Pokemons stored before the log
+/// was started can't be told apart, and are left out. The changes logged
+/// before the whole Pokemon was kept only give back its name and types.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let number = match req.number.map(PokemonNumber::try_from) {
+        Some(Ok(number)) => Some(number),
+        Some(Err(())) => return Err(Error::BadRequest),
+        None => None,
+    };
+
+    let changes = match repo.fetch_until(req.as_of) {
+        Ok(changes) => changes,
+        Err(FetchUntilError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let mut pokemons = BTreeMap::new();
+    for change in changes {
+        if number
+            .as_ref()
+            .is_some_and(|number| *number != change.number)
+        {
+            continue;
+        }
+        match (change.kind, change.pokemon) {
+            (ChangeKind::Created, Some(pokemon)) | (ChangeKind::Updated, Some(pokemon)) => {
+                pokemons.insert(u16::from(change.number), pokemon);
+            }
+            _ => {
+                pokemons.remove(&u16::from(change.number));
+            }
+        };
+    }
+
+    if number.is_some() && pokemons.is_empty() {
+        return Err(Error::NotFound);
+    }
+
+    Ok(Response {
+        pokemons: pokemons
+            .into_values()
+            .map(fetch_pokemon::Response::from)
+            .collect::<Vec<fetch_pokemon::Response>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Pokemon as StoredPokemon, PokemonName, PokemonSprite, PokemonTypes,
+    };
+    use crate::repositories::change::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(100, None);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_number_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(100, Some(0));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_did_not_exist_yet() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            ChangeKind::Created,
+            PokemonNumber::pikachu(),
+            Some(pikachu(PokemonTypes::pikachu())),
+            200,
+        )
+        .ok();
+        let req = Request::new(100, Some(u16::from(PokemonNumber::pikachu())));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_as_they_were_at_that_time() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            ChangeKind::Created,
+            PokemonNumber::pikachu(),
+            Some(pikachu(PokemonTypes::pikachu())),
+            100,
+        )
+        .ok();
+        repo.insert(
+            ChangeKind::Created,
+            PokemonNumber::charmander(),
+            Some(StoredPokemon::new(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            )),
+            100,
+        )
+        .ok();
+        let mut updated = pikachu(PokemonTypes::charmander());
+        updated.sprite = Some(PokemonSprite::pikachu());
+        repo.insert(
+            ChangeKind::Updated,
+            PokemonNumber::pikachu(),
+            Some(updated),
+            200,
+        )
+        .ok();
+        repo.insert(ChangeKind::Deleted, PokemonNumber::charmander(), None, 200)
+            .ok();
+        repo.insert(
+            ChangeKind::Updated,
+            PokemonNumber::pikachu(),
+            Some(pikachu(PokemonTypes::pikachu())),
+            300,
+        )
+        .ok();
+
+        let res = execute(repo, Request::new(250, None));
+
+        match res {
+            Ok(Response { pokemons }) => match &pokemons[..] {
+                [pokemon] => {
+                    assert_eq!(pokemon.number, u16::from(PokemonNumber::pikachu()));
+                    assert_eq!(pokemon.types, vec!["Fire"]);
+                    assert_eq!(pokemon.sprite, Some(String::from(PokemonSprite::pikachu())));
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    fn pikachu(types: PokemonTypes) -> StoredPokemon {
+        StoredPokemon::new(PokemonNumber::pikachu(), PokemonName::pikachu(), types)
+    }
+
+    impl Request {
+        fn new(as_of: u64, number: Option<u16>) -> Self {
+            Self { as_of, number }
+        }
+    }
+}
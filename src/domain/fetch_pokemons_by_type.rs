@@ -0,0 +1,104 @@
+use crate::domain::entities::PokemonType;
+use crate::repositories::pokemon::{FetchByTypeError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub pokemon_type: String,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    match PokemonType::try_from(req.pokemon_type) {
+        Ok(pokemon_type) => match repo.fetch_by_type(pokemon_type) {
+            Ok(pokemons) => Ok(pokemons
+                .into_iter()
+                .map(|p| Response {
+                    number: u16::from(p.number),
+                    name: String::from(p.name),
+                    types: Vec::<String>::from(p.types),
+                })
+                .collect::<Vec<Response>>()),
+            Err(FetchByTypeError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new("Fire");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new("Plasma");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_pokemons_having_the_type_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request::new("Fire");
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(pokemon_type: &str) -> Self {
+            Self {
+                pokemon_type: String::from(pokemon_type),
+            }
+        }
+    }
+}
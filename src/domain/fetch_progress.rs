@@ -0,0 +1,126 @@
+use crate::domain::entities::PokemonNumber;
+use crate::repositories::pokemon::{FetchRangeError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub target: Option<u16>,
+}
+
+pub struct Response {
+    pub target: u16,
+    pub registered: u32,
+    pub percentage: f64,
+    pub missing: Vec<u16>,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+/// Completion of the Pokedex from #1 up to the target, the whole Pokedex by default.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let target = req.target.unwrap_or(PokemonNumber::max());
+    let (first, last) = match (PokemonNumber::try_from(1), PokemonNumber::try_from(target)) {
+        (Ok(first), Ok(last)) => (first, last),
+        _ => return Err(Error::BadRequest),
+    };
+
+    let registered = match repo.fetch_range(first, last) {
+        Ok(pokemons) => pokemons
+            .into_iter()
+            .map(|p| u16::from(p.number))
+            .collect::<Vec<u16>>(),
+        Err(FetchRangeError::Unknown) => return Err(Error::Unknown),
+    };
+
+    Ok(Response {
+        target,
+        registered: registered.len() as u32,
+        percentage: registered.len() as f64 * 100.0 / f64::from(target),
+        missing: (1..=target)
+            .filter(|n| !registered.contains(n))
+            .collect::<Vec<u16>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(Some(151));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_target_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(Some(u16::from(PokemonNumber::bad())));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_target_the_whole_pokedex_by_default() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(None);
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => assert_eq!(res.target, PokemonNumber::max()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_progress_towards_the_target_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        let req = Request::new(Some(5));
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.registered, 1);
+                assert_eq!(res.percentage, 20.0);
+                assert_eq!(res.missing, vec![1, 2, 3, 5]);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(target: Option<u16>) -> Self {
+            Self { target }
+        }
+    }
+}
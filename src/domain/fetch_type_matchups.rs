@@ -0,0 +1,143 @@
+use crate::domain::entities::{PokemonType, PokemonTypes};
+use crate::kernel::type_chart::TypeChart;
+
+/// Every type of the generation attacks when `attacking` is none, and
+/// defends on its own when `defending` is none. `defending` otherwise holds
+/// the types of a single Pokemon, such as Grass and Poison.
+pub struct Request {
+    pub attacking: Option<String>,
+    pub defending: Option<Vec<String>>,
+    pub generation: Option<u8>,
+}
+
+/// A row per attacking type, with its multiplier against every defender.
+pub struct Response {
+    pub defending: Vec<String>,
+    pub rows: Vec<Row>,
+}
+
+pub struct Row {
+    pub attacking: String,
+    pub multipliers: Vec<f32>,
+}
+
+pub enum Error {
+    /// An unknown generation, or a type which isn't one of it.
+    BadRequest,
+}
+
+pub fn execute(req: Request) -> Result<Response, Error> {
+    let chart = match req.generation.map(TypeChart::try_new) {
+        Some(Ok(chart)) => chart,
+        Some(Err(())) => return Err(Error::BadRequest),
+        None => TypeChart::latest(),
+    };
+
+    let attacking = match req.attacking.map(PokemonType::try_from) {
+        Some(Ok(t)) => vec![t],
+        Some(Err(())) => return Err(Error::BadRequest),
+        None => chart.types(),
+    };
+    let defending = match req.defending.map(PokemonTypes::try_from) {
+        Some(Ok(types)) => vec![types.as_slice().to_vec()],
+        Some(Err(())) => return Err(Error::BadRequest),
+        None => chart.types().into_iter().map(|t| vec![t]).collect(),
+    };
+
+    let rows = attacking
+        .iter()
+        .map(|attacking| {
+            defending
+                .iter()
+                .map(|defending| chart.effectiveness_against(attacking, defending))
+                .collect::<Option<Vec<f32>>>()
+                .map(|multipliers| Row {
+                    attacking: String::from(attacking.as_str()),
+                    multipliers,
+                })
+        })
+        .collect::<Option<Vec<Row>>>();
+
+    match rows {
+        Some(rows) => Ok(Response {
+            defending: defending
+                .iter()
+                .map(|types| {
+                    types
+                        .iter()
+                        .map(PokemonType::as_str)
+                        .collect::<Vec<&str>>()
+                        .join("/")
+                })
+                .collect(),
+            rows,
+        }),
+        None => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_type_is_not_of_the_generation() {
+        let req = Request {
+            attacking: Some(String::from("Fairy")),
+            defending: None,
+            generation: Some(5),
+        };
+
+        let res = execute(req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_whole_chart_of_the_generation() {
+        let req = Request {
+            attacking: None,
+            defending: None,
+            generation: Some(1),
+        };
+
+        let res = execute(req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.defending.len(), 15);
+                assert_eq!(res.rows.len(), 15);
+                assert!(res.rows.iter().all(|row| row.multipliers.len() == 15));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_combine_the_types_of_a_pokemon() {
+        let req = Request {
+            attacking: Some(String::from("Fire")),
+            defending: Some(vec![String::from("Grass"), String::from("Steel")]),
+            generation: None,
+        };
+
+        let res = execute(req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.defending, vec!["Grass/Steel"]);
+                match &res.rows[..] {
+                    [row] => {
+                        assert_eq!(row.attacking, "Fire");
+                        assert_eq!(row.multipliers, vec![4.0]);
+                    }
+                    _ => unreachable!(),
+                };
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,116 @@
+use crate::domain::entities::PokemonType;
+use crate::repositories::pokemon::{CountByTypeError, CountError, Repository};
+use std::sync::Arc;
+
+pub struct Response {
+    pub total: u32,
+    pub types: Vec<TypeStat>,
+}
+
+pub struct TypeStat {
+    pub name: String,
+    pub count: u32,
+    pub percentage: f64,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Response, Error> {
+    let (total, counts) = match (repo.count(), repo.count_by_type()) {
+        (Ok(total), Ok(counts)) => (total, counts),
+        (Err(CountError::Unknown), _) | (_, Err(CountByTypeError::Unknown)) => {
+            return Err(Error::Unknown)
+        }
+    };
+
+    let types = PokemonType::all()
+        .into_iter()
+        .map(|t| {
+            let count = counts
+                .iter()
+                .find(|(pt, _)| *pt == t)
+                .map(|(_, count)| *count)
+                .unwrap_or(0);
+            TypeStat {
+                name: String::from(t),
+                count,
+                percentage: match total {
+                    0 => 0.0,
+                    _ => f64::from(count) * 100.0 / f64::from(total),
+                },
+            }
+        })
+        .collect::<Vec<TypeStat>>();
+
+    Ok(Response { total, types })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_zero_percentages_when_the_repo_is_empty() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.total, 0);
+                assert!(res.types.iter().all(|t| t.percentage == 0.0));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_distribution_of_every_type_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.total, 2);
+                assert_eq!(res.types.len(), 18);
+                assert_eq!(res.types[0].name, "Normal");
+                assert_eq!(res.types[0].count, 0);
+                assert_eq!(res.types[1].name, "Fire");
+                assert_eq!(res.types[1].count, 1);
+                assert_eq!(res.types[3].name, "Electric");
+                assert_eq!(res.types[3].count, 1);
+                assert_eq!(res.types[3].percentage, 50.0);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::entities::PokemonType;
+use crate::kernel::type_chart::TypeChart;
+
+/// The types of a generation, of the latest one when none is given.
+pub struct Request {
+    pub generation: Option<u8>,
+}
+
+pub struct Response {
+    pub name: String,
+    pub color: String,
+    pub emoji: String,
+    pub description: String,
+    pub strong_against: Vec<String>,
+    pub weak_against: Vec<String>,
+}
+
+pub enum Error {
+    BadRequest,
+}
+
+/// The matchups follow the type chart of the generation.
+pub fn execute(req: Request) -> Result<Vec<Response>, Error> {
+    let chart = match req.generation.map(TypeChart::try_new) {
+        Some(Ok(chart)) => chart,
+        Some(Err(())) => return Err(Error::BadRequest),
+        None => TypeChart::latest(),
+    };
+
+    let names = |types: Vec<PokemonType>| types.into_iter().map(String::from).collect();
+    Ok(chart
+        .types()
+        .into_iter()
+        .map(|t| Response {
+            color: String::from(t.color()),
+            emoji: String::from(t.emoji()),
+            description: String::from(t.description()),
+            strong_against: names(chart.strong_against(&t)),
+            weak_against: names(chart.weak_against(&t)),
+            name: String::from(t),
+        })
+        .collect::<Vec<Response>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_return_every_supported_type() {
+        let res = match execute(Request { generation: None }) {
+            Ok(res) => res,
+            _ => unreachable!(),
+        };
+
+        let names = res.iter().map(|t| t.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(names.len(), 18);
+        assert_eq!(names[..4], ["Normal", "Fire", "Water", "Electric"]);
+        assert_eq!(names[17], "Fairy");
+        assert_eq!(res[3].color, "#F7D02C");
+        assert_eq!(res[1].emoji, "🔥");
+        assert_eq!(res[1].weak_against, vec!["Water", "Ground", "Rock"]);
+    }
+
+    #[test]
+    fn it_should_return_the_types_and_matchups_of_a_generation() {
+        let res = match execute(Request {
+            generation: Some(1),
+        }) {
+            Ok(res) => res,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(res.len(), 15);
+        match res.iter().find(|t| t.name == "Psychic") {
+            Some(psychic) => assert_eq!(psychic.weak_against, vec!["Bug"]),
+            None => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_generation_does_not_exist() {
+        let res = execute(Request {
+            generation: Some(10),
+        });
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,94 @@
+use crate::domain::entities::ApiKey;
+use crate::repositories::usage::{FetchError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub key: String,
+    pub limit: u32,
+    pub day: u64,
+}
+
+pub struct Response {
+    pub used: u32,
+    pub limit: u32,
+    pub remaining: u32,
+}
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match ApiKey::try_from(req.key) {
+        Ok(key) => match repo.fetch(key, req.day) {
+            Ok(used) => Ok(Response {
+                used,
+                limit: req.limit,
+                remaining: req.limit.saturating_sub(used),
+            }),
+            Err(FetchError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::usage::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(ApiKey::bad());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(ApiKey::ash());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_usage_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.increment(ApiKey::ash(), 0).ok();
+        let req = Request::new(ApiKey::ash());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.used, 1);
+                assert_eq!(res.limit, 10);
+                assert_eq!(res.remaining, 9);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(key: ApiKey) -> Self {
+            Self {
+                key: String::from(key),
+                limit: 10,
+                day: 0,
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::domain::entities::{Webhook, WebhookId};
+use crate::repositories::webhook::{FetchOneError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub id: u32,
+}
+
+pub struct Response {
+    pub id: u32,
+    pub url: String,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match WebhookId::try_from(req.id) {
+        Ok(id) => match repo.fetch_one(id) {
+            Ok(Webhook { id, url, .. }) => Ok(Response {
+                id: u32::from(id),
+                url: String::from(url),
+            }),
+            Err(FetchOneError::NotFound) => Err(Error::NotFound),
+            Err(FetchOneError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookId::bad());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_webhook() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_webhook_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(WebhookUrl::example(), WebhookSecret::example())
+            .ok();
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.id, u32::from(WebhookId::first()));
+                assert_eq!(res.url, String::from(WebhookUrl::example()));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(id: WebhookId) -> Self {
+            Self { id: u32::from(id) }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::domain::entities::WebhookId;
+use crate::repositories::webhook::{FetchDeliveriesError, FetchOneError, Repository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub id: u32,
+}
+
+pub struct Response {
+    pub event: String,
+    pub attempt: u32,
+    pub status_code: Option<u16>,
+    pub timestamp: u64,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let id = match WebhookId::try_from(req.id) {
+        Ok(id) => id,
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch_one(id) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    match repo.fetch_deliveries(id) {
+        Ok(deliveries) => Ok(deliveries
+            .into_iter()
+            .map(|d| Response {
+                event: String::from(d.event),
+                attempt: d.attempt,
+                status_code: d.status_code,
+                timestamp: d.timestamp,
+            })
+            .collect::<Vec<Response>>()),
+        Err(FetchDeliveriesError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Delivery, WebhookEvent, WebhookSecret, WebhookUrl};
+    use crate::repositories::webhook::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookId::bad());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_webhook() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_deliveries_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(WebhookUrl::example(), WebhookSecret::example())
+            .ok();
+        repo.insert_delivery(Delivery {
+            webhook_id: WebhookId::first(),
+            event: WebhookEvent::PokemonCreated,
+            attempt: 1,
+            status_code: Some(200),
+            timestamp: 0,
+        })
+        .ok();
+        let req = Request::new(WebhookId::first());
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.len(), 1);
+                assert_eq!(res[0].event, String::from(WebhookEvent::PokemonCreated));
+                assert_eq!(res[0].attempt, 1);
+                assert_eq!(res[0].status_code, Some(200));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(id: WebhookId) -> Self {
+            Self { id: u32::from(id) }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::repositories::wishlist::{FetchAllError, Repository};
+use std::sync::Arc;
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub note: Option<String>,
+}
+
+pub enum Error {
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_all() {
+        Ok(wishes) => Ok(wishes
+            .into_iter()
+            .map(|w| Response {
+                number: u16::from(w.number),
+                name: String::from(w.name),
+                note: w.note.map(String::from),
+            })
+            .collect::<Vec<Response>>()),
+        Err(FetchAllError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber};
+    use crate::repositories::wishlist::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_wishes_ordered_by_number_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(PokemonNumber::pikachu(), PokemonName::pikachu(), None)
+            .ok();
+        repo.insert(PokemonNumber::charmander(), PokemonName::charmander(), None)
+            .ok();
+
+        let res = execute(repo);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res[0].number, u16::from(PokemonNumber::charmander()));
+                assert_eq!(res[1].number, u16::from(PokemonNumber::pikachu()));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,16 @@
+/// A write about to be run, as the validators see it. The fields a patch or
+/// a change of types leaves as they are are none.
+pub struct Write {
+    pub action: &'static str,
+    pub number: u16,
+    pub name: Option<String>,
+    pub types: Option<Vec<String>>,
+}
+
+/// Custom rules on top of the ones of the value objects, such as scripts
+/// given by the user. Run before the use cases creating or updating a
+/// Pokemon, which fail with a bad request when a rule refuses the write.
+pub trait Validator: Send + Sync {
+    /// The reason of the refusal, logged and told to the client.
+    fn validate(&self, write: &Write) -> Result<(), String>;
+}
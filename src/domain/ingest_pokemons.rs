@@ -0,0 +1,623 @@
+use crate::domain::entities::{
+    self, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonTypes, PokemonWeight,
+};
+use crate::domain::fetch_pokemon::{Ability, Evolution};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{FetchOneError, InsertError, Repository, UpdateError};
+use crate::repositories::unit_of_work;
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub details: Details,
+}
+
+/// Stored along with the Pokemon when given, kept as they are otherwise.
+#[derive(Default)]
+pub struct Details {
+    pub evolutions: Option<Vec<Evolution>>,
+    pub abilities: Option<Vec<Ability>>,
+    /// In meters.
+    pub height: Option<f64>,
+    /// In kilograms.
+    pub weight: Option<f64>,
+    pub sprite: Option<String>,
+    pub class: Option<String>,
+}
+
+struct StoredDetails {
+    evolutions: Option<Vec<entities::Evolution>>,
+    abilities: Option<PokemonAbilities>,
+    height: Option<PokemonHeight>,
+    weight: Option<PokemonWeight>,
+    sprite: Option<PokemonSprite>,
+    class: Option<PokemonClass>,
+}
+
+/// What to do with a Pokemon whose number is already taken.
+#[derive(Clone, Copy)]
+pub enum Policy {
+    Skip,
+    Overwrite,
+    /// Imports nothing at all when any number is taken.
+    Fail,
+}
+
+pub struct Response {
+    pub inserted: usize,
+    pub overwritten: usize,
+    pub conflicts: usize,
+    pub invalid: usize,
+    pub outcomes: Vec<Outcome>,
+}
+
+/// What happened to each requested Pokemon, in the order of the request.
+pub struct Outcome {
+    pub number: u16,
+    /// As stored when the Pokemon is valid, as requested otherwise.
+    pub name: String,
+    pub types: Vec<String>,
+    pub status: Status,
+    /// Why the Pokemon is invalid, empty otherwise.
+    pub errors: Vec<FieldError>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Inserted,
+    Overwritten,
+    Skipped,
+    Invalid,
+}
+
+pub enum Error {
+    Conflict,
+    Unknown,
+}
+
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    policy: Policy,
+    reqs: Vec<Request>,
+) -> Result<Response, Error> {
+    let mut statuses = vec![];
+    let mut numbers = vec![];
+    let mut names = vec![];
+    let mut errors = vec![];
+    let mut pokemons = vec![];
+    for req in reqs {
+        numbers.push(req.number);
+        match (
+            PokemonNumber::try_from(req.number),
+            PokemonName::try_from(req.name.clone()),
+            PokemonTypes::try_from(req.types.clone()),
+            details(req.number, &req.details),
+        ) {
+            (Ok(number), Ok(name), Ok(types), Ok(details)) => {
+                names.push((
+                    String::from(name.clone()),
+                    Vec::<String>::from(types.clone()),
+                ));
+                statuses.push(None);
+                errors.push(vec![]);
+                pokemons.push((Pokemon::new(number, name, types), details));
+            }
+            (_, _, _, details) => {
+                names.push((req.name.clone(), req.types.clone()));
+                let mut found = validate_pokemon::execute(validate_pokemon::Request {
+                    number: req.number,
+                    name: req.name,
+                    types: req.types,
+                })
+                .errors;
+                if let Err(details_errors) = details {
+                    found.extend(details_errors);
+                }
+                statuses.push(Some(Status::Invalid));
+                errors.push(found);
+            }
+        }
+    }
+
+    let mut imported =
+        match unit_of_work::run(repo, |repo| import(repo, policy, pokemons), Error::Unknown) {
+            Ok(imported) => imported.into_iter(),
+            Err(err) => return Err(err),
+        };
+    for status in statuses.iter_mut().filter(|status| status.is_none()) {
+        *status = imported.next();
+    }
+
+    let outcomes = numbers
+        .into_iter()
+        .zip(names)
+        .zip(statuses)
+        .zip(errors)
+        .map(|(((number, (name, types)), status), errors)| Outcome {
+            number,
+            name,
+            types,
+            status: status.unwrap_or(Status::Invalid),
+            errors,
+        })
+        .collect::<Vec<Outcome>>();
+    let count = |wanted: Status| {
+        outcomes
+            .iter()
+            .filter(|outcome| outcome.status == wanted)
+            .count()
+    };
+
+    Ok(Response {
+        inserted: count(Status::Inserted),
+        overwritten: count(Status::Overwritten),
+        conflicts: count(Status::Skipped),
+        invalid: count(Status::Invalid),
+        outcomes,
+    })
+}
+
+fn details(number: u16, details: &Details) -> Result<StoredDetails, Vec<FieldError>> {
+    let evolutions = match &details.evolutions {
+        Some(evolutions) if evolutions.is_empty() => Ok(None),
+        Some(evolutions) => match evolutions
+            .iter()
+            .map(|e| entities::Evolution::try_new(e.target, e.method.clone(), e.level))
+            .collect::<Result<Vec<entities::Evolution>, ()>>()
+        {
+            Ok(stored) if validate_pokemon::evolutions_errors(number, evolutions).is_empty() => {
+                Ok(Some(stored))
+            }
+            _ => Err(validate_pokemon::evolutions_errors(number, evolutions)),
+        },
+        None => Ok(None),
+    };
+    let abilities = match &details.abilities {
+        Some(abilities) if abilities.is_empty() => Ok(None),
+        Some(abilities) => match abilities
+            .iter()
+            .map(|a| entities::Ability::try_new(a.name.clone(), a.hidden))
+            .collect::<Result<Vec<entities::Ability>, ()>>()
+            .and_then(PokemonAbilities::try_from)
+        {
+            Ok(abilities) => Ok(Some(abilities)),
+            _ => Err(validate_pokemon::abilities_errors(abilities)),
+        },
+        None => Ok(None),
+    };
+    let height = match details
+        .height
+        .map(|height| (height, PokemonHeight::try_from(height)))
+    {
+        Some((height, Err(()))) => Err(validate_pokemon::height_errors(height)),
+        height => Ok(height.and_then(|(_, height)| height.ok())),
+    };
+    let weight = match details
+        .weight
+        .map(|weight| (weight, PokemonWeight::try_from(weight)))
+    {
+        Some((weight, Err(()))) => Err(validate_pokemon::weight_errors(weight)),
+        weight => Ok(weight.and_then(|(_, weight)| weight.ok())),
+    };
+    let sprite = match &details.sprite {
+        Some(url) => match PokemonSprite::try_from(url.clone()) {
+            Ok(sprite) => Ok(Some(sprite)),
+            _ => Err(validate_pokemon::sprite_errors(url)),
+        },
+        None => Ok(None),
+    };
+
+    let class = match &details.class {
+        Some(class) => match PokemonClass::try_from(class.clone()) {
+            Ok(class) => Ok(Some(class)),
+            _ => Err(validate_pokemon::class_errors(class)),
+        },
+        None => Ok(None),
+    };
+
+    match (evolutions, abilities, height, weight, sprite, class) {
+        (Ok(evolutions), Ok(abilities), Ok(height), Ok(weight), Ok(sprite), Ok(class)) => {
+            Ok(StoredDetails {
+                evolutions,
+                abilities,
+                height,
+                weight,
+                sprite,
+                class,
+            })
+        }
+        (evolutions, abilities, height, weight, sprite, class) => Err([
+            evolutions.err(),
+            abilities.err(),
+            height.err(),
+            weight.err(),
+            sprite.err(),
+            class.err(),
+        ]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect()),
+    }
+}
+
+/// Imports the valid Pokemons as a whole, giving the status of each one.
+fn import(
+    repo: Arc<dyn Repository>,
+    policy: Policy,
+    pokemons: Vec<(Pokemon, StoredDetails)>,
+) -> Result<Vec<Status>, Error> {
+    let (pokemons, details): (Vec<Pokemon>, Vec<StoredDetails>) = pokemons.into_iter().unzip();
+    if let Policy::Fail = policy {
+        match has_conflicts(repo.clone(), &pokemons) {
+            Ok(false) => {}
+            Ok(true) => return Err(Error::Conflict),
+            Err(()) => return Err(Error::Unknown),
+        }
+    }
+
+    let results = repo.insert_many(pokemons.clone());
+    let mut statuses = vec![];
+    for ((pokemon, details), result) in pokemons.into_iter().zip(details).zip(results) {
+        let number = pokemon.number.clone();
+        let status = match (result, policy) {
+            (Ok(_), _) => Status::Inserted,
+            (Err(InsertError::Conflict), Policy::Skip) => Status::Skipped,
+            (Err(InsertError::Conflict), Policy::Overwrite) => {
+                match overwrite(repo.clone(), pokemon) {
+                    Ok(()) => Status::Overwritten,
+                    Err(err) => return Err(err),
+                }
+            }
+            (Err(InsertError::Conflict), Policy::Fail) => return Err(Error::Conflict),
+            (Err(InsertError::Unknown), _) => return Err(Error::Unknown),
+        };
+        if status != Status::Skipped {
+            match store_details(repo.clone(), number, details) {
+                Ok(()) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        statuses.push(status);
+    }
+    Ok(statuses)
+}
+
+/// Sets the given details only, the height and the weight each on its own.
+fn store_details(
+    repo: Arc<dyn Repository>,
+    number: PokemonNumber,
+    details: StoredDetails,
+) -> Result<(), Error> {
+    if let Some(evolutions) = details.evolutions {
+        if repo.update_evolutions(number.clone(), evolutions).is_err() {
+            return Err(Error::Unknown);
+        }
+    }
+    if details.abilities.is_some()
+        && repo
+            .update_abilities(number.clone(), details.abilities)
+            .is_err()
+    {
+        return Err(Error::Unknown);
+    }
+    if details.height.is_some() || details.weight.is_some() {
+        let (height, weight) = match repo.fetch_one(number.clone()) {
+            Ok(stored) => (
+                details.height.or(stored.height),
+                details.weight.or(stored.weight),
+            ),
+            _ => return Err(Error::Unknown),
+        };
+        if repo
+            .update_measurements(number.clone(), height, weight)
+            .is_err()
+        {
+            return Err(Error::Unknown);
+        }
+    }
+    if details.sprite.is_some() && repo.update_sprite(number.clone(), details.sprite).is_err() {
+        return Err(Error::Unknown);
+    }
+    if let Some(class) = details.class {
+        if repo.update_class(number, class).is_err() {
+            return Err(Error::Unknown);
+        }
+    }
+    Ok(())
+}
+
+/// A number taken in the repository or twice in the request is a conflict.
+fn has_conflicts(repo: Arc<dyn Repository>, pokemons: &[Pokemon]) -> Result<bool, ()> {
+    for (index, pokemon) in pokemons.iter().enumerate() {
+        if pokemons[..index].iter().any(|p| p.number == pokemon.number) {
+            return Ok(true);
+        }
+        match repo.fetch_one(pokemon.number.clone()) {
+            Ok(_) => return Ok(true),
+            Err(FetchOneError::NotFound) => {}
+            Err(FetchOneError::Unknown) => return Err(()),
+        }
+    }
+    Ok(false)
+}
+
+/// Renames and retypes the stored Pokemon in place, so that its other fields
+/// are kept.
+fn overwrite(repo: Arc<dyn Repository>, pokemon: Pokemon) -> Result<(), Error> {
+    match repo.update(
+        pokemon.number.clone(),
+        pokemon.name.clone(),
+        pokemon.types.clone(),
+    ) {
+        Ok(_) => Ok(()),
+        Err(UpdateError::NotFound) => {
+            match repo.insert(pokemon.number, pokemon.name, pokemon.types) {
+                Ok(_) => Ok(()),
+                Err(_) => Err(Error::Unknown),
+            }
+        }
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::PokemonHeight;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let reqs = vec![Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )];
+
+        let res = execute(repo, Policy::Skip, reqs);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_count_inserted_conflicting_and_invalid_pokemons_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let reqs = vec![
+            Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            ),
+            Request::new(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            ),
+            Request::new(
+                PokemonNumber::bad(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            ),
+        ];
+
+        let res = execute(repo, Policy::Skip, reqs);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.inserted, 1);
+                assert_eq!(res.conflicts, 1);
+                assert_eq!(res.invalid, 1);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    fn requests() -> Vec<Request> {
+        vec![
+            Request::new(
+                PokemonNumber::pikachu(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            ),
+            Request::new(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            ),
+        ]
+    }
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_report_the_outcome_of_each_pokemon() {
+        let repo = repo_with_pikachu();
+        let mut reqs = requests();
+        reqs.push(Request::new(
+            PokemonNumber::bad(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        ));
+
+        let res = execute(repo, Policy::Skip, reqs);
+
+        match &res {
+            Ok(res) => assert_eq!(
+                res.outcomes
+                    .iter()
+                    .map(|outcome| (outcome.number, outcome.status))
+                    .collect::<Vec<(u16, Status)>>(),
+                vec![
+                    (25, Status::Skipped),
+                    (4, Status::Inserted),
+                    (0, Status::Invalid)
+                ]
+            ),
+            _ => unreachable!(),
+        };
+        match res {
+            Ok(res) => match res.outcomes[2].errors.as_slice() {
+                [error] => assert_eq!(error.field, "number"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_replace_the_conflicting_pokemons_when_overwriting() {
+        let repo = repo_with_pikachu();
+        repo.update_measurements(
+            PokemonNumber::pikachu(),
+            Some(PokemonHeight::charmander()),
+            None,
+        )
+        .ok();
+
+        let res = execute(repo.clone(), Policy::Overwrite, requests());
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.inserted, 1);
+                assert_eq!(res.overwritten, 1);
+                assert_eq!(res.conflicts, 0);
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => {
+                assert_eq!(String::from(pokemon.name), "Charmander");
+                assert_eq!(pokemon.height.map(f64::from), Some(0.6));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_store_the_given_details_with_the_pokemons() {
+        let repo = repo_with_pikachu();
+        repo.update_measurements(
+            PokemonNumber::pikachu(),
+            Some(PokemonHeight::charmander()),
+            None,
+        )
+        .ok();
+        let mut reqs = requests();
+        reqs[0].details.weight = Some(6.0);
+        reqs[1].details.abilities = Some(vec![Ability {
+            name: String::from("Blaze"),
+            hidden: false,
+        }]);
+        reqs.push(Request {
+            number: 7,
+            name: String::from("Squirtle"),
+            types: vec![String::from("Water")],
+            details: Details {
+                height: Some(0.0),
+                ..Details::default()
+            },
+        });
+
+        let res = execute(repo.clone(), Policy::Overwrite, reqs);
+
+        match res {
+            Ok(res) => match res.outcomes[2].errors.as_slice() {
+                [error] => assert_eq!(error.field, "height"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => {
+                assert_eq!(pokemon.height.map(f64::from), Some(0.6));
+                assert_eq!(pokemon.weight.map(f64::from), Some(6.0));
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::charmander()) {
+            Ok(pokemon) => assert!(pokemon.abilities.is_some()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_store_the_evolutions_and_the_class_of_the_pokemons() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut reqs = requests();
+        reqs[0].details.class = Some(String::from("Legendary"));
+        reqs[0].details.evolutions = Some(vec![Evolution {
+            target: 26,
+            method: String::from("level"),
+            level: Some(16),
+        }]);
+        reqs[1].details.class = Some(String::from("Plasma"));
+
+        let res = execute(repo.clone(), Policy::Skip, reqs);
+
+        match res {
+            Ok(res) => match res.outcomes[1].errors.as_slice() {
+                [error] => assert_eq!(error.field, "class"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => {
+                assert_eq!(pokemon.evolutions.len(), 1);
+                assert!(pokemon.class == PokemonClass::Legendary);
+            }
+            _ => unreachable!(),
+        };
+        assert!(repo.fetch_one(PokemonNumber::charmander()).is_err());
+    }
+
+    #[test]
+    fn it_should_import_nothing_on_conflict_when_failing() {
+        let repo = repo_with_pikachu();
+
+        let res = execute(repo.clone(), Policy::Fail, requests());
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::charmander()) {
+            Err(FetchOneError::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+            Self {
+                number: u16::from(number),
+                name: String::from(name),
+                types: Vec::<String>::from(types),
+                details: Details::default(),
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::trace;
+use std::sync::Arc;
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Succeeded,
+    Failed,
+    Rejected,
+}
+
+/// Wraps the execution of every use case, for logging, validation or any
+/// other concern shared by all of them. `next` runs the rest of the chain
+/// and then the use case; not calling it rejects the call.
+pub trait Middleware: Send + Sync {
+    fn handle(&self, use_case: &'static str, next: &mut dyn FnMut() -> Outcome) -> Outcome;
+}
+
+#[derive(Clone)]
+pub struct Pipeline {
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl Pipeline {
+    pub fn new(middlewares: Vec<Arc<dyn Middleware>>) -> Self {
+        Self { middlewares }
+    }
+
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.middlewares.push(middleware);
+    }
+
+    /// Runs the use case through the middlewares, in the order they were
+    /// given. A rejected call fails with `rejected`.
+    pub fn run<T, E>(
+        &self,
+        use_case: &'static str,
+        execute: impl FnOnce() -> Result<T, E>,
+        rejected: E,
+    ) -> Result<T, E> {
+        let mut execute = Some(execute);
+        let mut res = None;
+
+        self.call(0, use_case, &mut || match execute.take() {
+            Some(execute) => {
+                let executed = execute();
+                let outcome = match executed {
+                    Ok(_) => Outcome::Succeeded,
+                    Err(_) => Outcome::Failed,
+                };
+                res = Some(executed);
+                outcome
+            }
+            None => Outcome::Rejected,
+        });
+
+        match res {
+            Some(res) => res,
+            None => Err(rejected),
+        }
+    }
+
+    fn call(
+        &self,
+        index: usize,
+        use_case: &'static str,
+        execute: &mut dyn FnMut() -> Outcome,
+    ) -> Outcome {
+        match self.middlewares.get(index) {
+            Some(middleware) => middleware.handle(use_case, &mut || {
+                self.call(index + 1, use_case, &mut *execute)
+            }),
+            None => execute(),
+        }
+    }
+}
+
+/// Logs every use case run on stderr, with its outcome, its duration and
+/// the trace of the request it serves.
+pub struct Logger;
+
+impl Middleware for Logger {
+    fn handle(&self, use_case: &'static str, next: &mut dyn FnMut() -> Outcome) -> Outcome {
+        let start = Instant::now();
+        let outcome = next();
+        match trace::current() {
+            Some(context) => eprintln!(
+                "{} {:?} in {:?} trace {}",
+                use_case,
+                outcome,
+                start.elapsed(),
+                context.trace_id()
+            ),
+            None => eprintln!("{} {:?} in {:?}", use_case, outcome, start.elapsed()),
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Trace(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl Middleware for Trace {
+        fn handle(&self, use_case: &'static str, next: &mut dyn FnMut() -> Outcome) -> Outcome {
+            self.record(format!("{} before {}", self.0, use_case));
+            let outcome = next();
+            self.record(format!("{} after {:?}", self.0, outcome));
+            outcome
+        }
+    }
+
+    impl Trace {
+        fn record(&self, line: String) {
+            if let Ok(mut lines) = self.1.lock() {
+                lines.push(line);
+            }
+        }
+    }
+
+    struct Deny;
+
+    impl Middleware for Deny {
+        fn handle(&self, _: &'static str, _: &mut dyn FnMut() -> Outcome) -> Outcome {
+            Outcome::Rejected
+        }
+    }
+
+    #[test]
+    fn it_should_run_the_middlewares_around_the_use_case_in_order() {
+        let lines = Arc::new(Mutex::new(vec![]));
+        let pipeline = Pipeline::new(vec![
+            Arc::new(Trace("outer", lines.clone())),
+            Arc::new(Trace("inner", lines.clone())),
+        ]);
+
+        let res = pipeline.run("fetch_pokemon", || Err::<(), _>("not found"), "rejected");
+
+        assert_eq!(res, Err("not found"));
+        match lines.lock() {
+            Ok(lines) => assert_eq!(
+                *lines,
+                vec![
+                    "outer before fetch_pokemon",
+                    "inner before fetch_pokemon",
+                    "inner after Failed",
+                    "outer after Failed",
+                ]
+            ),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_not_run_the_use_case_when_a_middleware_rejects_it() {
+        let mut pipeline = Pipeline::new(vec![]);
+        pipeline.push(Arc::new(Deny));
+        let mut ran = false;
+
+        let res = pipeline.run(
+            "delete_pokemon",
+            || {
+                ran = true;
+                Ok(())
+            },
+            "rejected",
+        );
+
+        assert_eq!(res, Err("rejected"));
+        assert!(!ran);
+    }
+}
@@ -1,5 +1,54 @@
+pub mod add_move;
+pub mod check_name;
+pub mod clean_up;
+pub mod clear_pokemons;
+pub mod consume_quota;
 pub mod create_pokemon;
+pub mod create_webhook;
+pub mod create_wish;
 pub mod delete_pokemon;
+pub mod delete_pokemons_by_type;
+pub mod delete_webhook;
+pub mod delete_wish;
 pub mod entities;
+pub mod fetch_activity_report;
 pub mod fetch_all_pokemons;
+pub mod fetch_all_webhooks;
+pub mod fetch_changes;
+pub mod fetch_checksum;
+pub mod fetch_collection_version;
+pub mod fetch_index;
+pub mod fetch_moves;
 pub mod fetch_pokemon;
+pub mod fetch_pokemon_by_name;
+pub mod fetch_pokemon_cry;
+pub mod fetch_pokemon_page;
+pub mod fetch_pokemon_range;
+pub mod fetch_pokemons_as_of;
+pub mod fetch_pokemons_by_type;
+pub mod fetch_progress;
+pub mod fetch_type_matchups;
+pub mod fetch_type_stats;
+pub mod fetch_types;
+pub mod fetch_usage;
+pub mod fetch_webhook;
+pub mod fetch_webhook_deliveries;
+pub mod fetch_wishlist;
+pub mod hooks;
+pub mod ingest_pokemons;
+#[cfg(feature = "server")]
+pub mod middleware;
+pub mod patch_pokemon;
+pub mod promote_wish;
+pub mod remove_move;
+pub mod replace_pokemon;
+pub mod update_pokemon;
+pub mod update_pokemon_abilities;
+pub mod update_pokemon_class;
+pub mod update_pokemon_cry;
+pub mod update_pokemon_evolutions;
+pub mod update_pokemon_measurements;
+pub mod update_pokemon_sprite;
+pub mod update_pokemon_types;
+pub mod update_pokemons;
+pub mod validate_pokemon;
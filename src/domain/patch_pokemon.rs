@@ -0,0 +1,182 @@
+use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{FetchOneError, Repository, UpdateError};
+use crate::repositories::unit_of_work;
+use std::sync::Arc;
+
+/// The fields left out keep their current value.
+pub struct Request {
+    pub number: u16,
+    pub name: Option<String>,
+    pub types: Option<Vec<String>>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    /// The invalid fields, each with the reason.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown,
+}
+
+/// Merges the given fields into the Pokemon as a whole, so that a field
+/// changed meanwhile by someone else isn't overwritten where the backend
+/// supports transactions.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let (number, name, types) = match (
+        PokemonNumber::try_from(req.number),
+        req.name.clone().map(PokemonName::try_from).transpose(),
+        req.types.clone().map(PokemonTypes::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(name), Ok(types)) => (number, name, types),
+        _ => {
+            let mut errors = validate_pokemon::number_errors(req.number);
+            if let Some(name) = &req.name {
+                errors.extend(validate_pokemon::name_errors(name));
+            }
+            if let Some(types) = &req.types {
+                errors.extend(validate_pokemon::types_errors(types));
+            }
+            return Err(Error::BadRequest(errors));
+        }
+    };
+
+    match unit_of_work::run(
+        repo,
+        |repo| merge(repo, number, name, types),
+        Error::Unknown,
+    ) {
+        Ok(Pokemon {
+            number,
+            name,
+            types,
+            ..
+        }) => Ok(Response {
+            number: u16::from(number),
+            name: String::from(name),
+            types: Vec::<String>::from(types),
+        }),
+        Err(err) => Err(err),
+    }
+}
+
+fn merge(
+    repo: Arc<dyn Repository>,
+    number: PokemonNumber,
+    name: Option<PokemonName>,
+    types: Option<PokemonTypes>,
+) -> Result<Pokemon, Error> {
+    let current = match repo.fetch_one(number.clone()) {
+        Ok(current) => current,
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    if name.is_none() && types.is_none() {
+        return Ok(current);
+    }
+
+    match repo.update(
+        number,
+        name.unwrap_or(current.name),
+        types.unwrap_or(current.types),
+    ) {
+        Ok(pokemon) => Ok(pokemon),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_field_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            name: None,
+            types: Some(vec![]),
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => {
+                    assert_eq!(error.field, "types");
+                    assert_eq!(error.message, "must contain at least one type");
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            name: Some(String::from(PokemonName::charmander())),
+            types: None,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            name: Some(String::from(PokemonName::charmander())),
+            types: None,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_keep_the_fields_left_out_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            name: Some(String::from(PokemonName::charmander())),
+            types: None,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.name, String::from(PokemonName::charmander()));
+                assert_eq!(res.types, Vec::<String>::from(PokemonTypes::pikachu()));
+            }
+            _ => unreachable!(),
+        };
+    }
+}
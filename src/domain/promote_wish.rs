@@ -0,0 +1,173 @@
+use crate::domain::create_pokemon;
+use crate::domain::entities::{PokemonNumber, Wish};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::Repository;
+use crate::repositories::wishlist::{DeleteError, FetchOneError, Repository as WishlistRepository};
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+    pub types: Vec<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    /// Invalid types or an invalid number, with the reasons.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Conflict,
+    Unknown,
+}
+
+/// Creates the Pokemon of a wish with the given types, then removes the wish.
+pub fn execute(
+    wishlist_repo: Arc<dyn WishlistRepository>,
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        _ => {
+            return Err(Error::BadRequest(validate_pokemon::number_errors(
+                req.number,
+            )))
+        }
+    };
+
+    let Wish { number, name, .. } = match wishlist_repo.fetch_one(number) {
+        Ok(wish) => wish,
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let req = create_pokemon::Request {
+        number: number.value(),
+        name: String::from(name),
+        types: req.types,
+    };
+    let res = match create_pokemon::execute(repo, req) {
+        Ok(res) => res,
+        Err(create_pokemon::Error::BadRequest(errors)) => return Err(Error::BadRequest(errors)),
+        Err(create_pokemon::Error::Conflict) => return Err(Error::Conflict),
+        Err(create_pokemon::Error::Unknown) => return Err(Error::Unknown),
+    };
+
+    match wishlist_repo.delete(number) {
+        Ok(()) | Err(DeleteError::NotFound) => Ok(Response {
+            number: res.number,
+            name: res.name,
+            types: res.types,
+        }),
+        Err(DeleteError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+    use crate::repositories::wishlist::InMemoryRepository as InMemoryWishlistRepository;
+
+    fn wishlist() -> Arc<InMemoryWishlistRepository> {
+        let wishlist_repo = Arc::new(InMemoryWishlistRepository::new());
+        wishlist_repo
+            .insert(PokemonNumber::pikachu(), PokemonName::pikachu(), None)
+            .ok();
+        wishlist_repo
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let wishlist_repo = Arc::new(InMemoryWishlistRepository::new().with_error());
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), PokemonTypes::pikachu());
+
+        let res = execute(wishlist_repo, repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_wishlist_does_not_contain_the_pokemon() {
+        let wishlist_repo = Arc::new(InMemoryWishlistRepository::new());
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), PokemonTypes::pikachu());
+
+        let res = execute(wishlist_repo, repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_types_are_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut req = Request::new(PokemonNumber::pikachu(), PokemonTypes::pikachu());
+        req.types = vec![];
+
+        let res = execute(wishlist(), repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.field, "types"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_pokemon_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::pikachu(), PokemonTypes::pikachu());
+
+        let res = execute(wishlist(), repo, req);
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_create_the_pokemon_and_remove_the_wish_otherwise() {
+        let wishlist_repo = wishlist();
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), PokemonTypes::pikachu());
+
+        let res = execute(wishlist_repo.clone(), repo.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(res.name, String::from(PokemonName::pikachu())),
+            _ => unreachable!(),
+        };
+        assert!(repo.fetch_one(PokemonNumber::pikachu()).is_ok());
+        assert!(wishlist_repo.fetch_one(PokemonNumber::pikachu()).is_err());
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, types: PokemonTypes) -> Self {
+            Self {
+                number: u16::from(number),
+                types: Vec::<String>::from(types),
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::domain::entities::PokemonNumber;
+use crate::repositories::moves::{DeleteError, Repository};
+use std::sync::Arc;
+
+/// Removes a move from the learnset of a Pokemon, the other Pokemons still
+/// learning it.
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+}
+
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    match PokemonNumber::try_from(req.number) {
+        Ok(number) if !req.name.trim().is_empty() => match repo.delete(number, &req.name) {
+            Ok(()) => Ok(()),
+            Err(DeleteError::NotFound) => Err(Error::NotFound),
+            Err(DeleteError::Unknown) => Err(Error::Unknown),
+        },
+        _ => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{LearnedMove, Move};
+    use crate::repositories::moves::InMemoryRepository;
+
+    fn thunderbolt() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        if let Ok(learned) = LearnedMove::try_new(Move::thunderbolt(), 26) {
+            repo.insert(PokemonNumber::pikachu(), learned).ok();
+        }
+        repo
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu(), "Thunderbolt");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let req = Request::new(PokemonNumber::bad(), "Thunderbolt");
+
+        let res = execute(thunderbolt(), req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_learn_the_move() {
+        let req = Request::new(PokemonNumber::charmander(), "Thunderbolt");
+
+        let res = execute(thunderbolt(), req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_remove_the_move_otherwise() {
+        let repo = thunderbolt();
+        let req = Request::new(PokemonNumber::pikachu(), "thunderbolt");
+
+        let res = execute(repo.clone(), req);
+
+        assert!(res.is_ok());
+        assert!(matches!(
+            repo.fetch_all(PokemonNumber::pikachu()),
+            Ok(learnset) if learnset.is_empty()
+        ));
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, name: &str) -> Self {
+            Self {
+                number: u16::from(number),
+                name: String::from(name),
+            }
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use crate::domain::entities::{
+    self, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonTypes, PokemonWeight,
+};
+use crate::domain::fetch_pokemon::{Ability, Evolution};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{FetchOneError, InsertError, Repository, UpdateError};
+use crate::repositories::unit_of_work;
+use std::sync::Arc;
+
+/// The fields after the types are optional: the ones left out keep their
+/// value, and `Some(None)` removes a measurement or the sprite.
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    /// Creates the Pokemon instead, only when its number is free.
+    pub create: bool,
+    pub evolutions: Option<Vec<Evolution>>,
+    pub abilities: Option<Vec<Ability>>,
+    /// In meters.
+    pub height: Option<Option<f64>>,
+    /// In kilograms.
+    pub weight: Option<Option<f64>>,
+    pub sprite: Option<Option<String>>,
+    pub class: Option<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    /// The invalid fields, each with the reason.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    /// The number is taken, when creating.
+    Conflict,
+    Unknown,
+}
+
+/// The request once checked, ready to be written.
+struct Replacement {
+    number: PokemonNumber,
+    name: PokemonName,
+    types: PokemonTypes,
+    evolutions: Option<Vec<entities::Evolution>>,
+    abilities: Option<Option<PokemonAbilities>>,
+    height: Option<Option<PokemonHeight>>,
+    weight: Option<Option<PokemonWeight>>,
+    sprite: Option<Option<PokemonSprite>>,
+    class: Option<PokemonClass>,
+}
+
+/// Replaces the Pokemon and the given fields as a whole with
+/// `unit_of_work::run`, so that a failing write leaves it as it was. Every
+/// field is checked before anything is written.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let create = req.create;
+    let replacement = match parse(&req) {
+        Some(replacement) => replacement,
+        None => return Err(Error::BadRequest(errors(&req))),
+    };
+
+    match unit_of_work::run(
+        repo,
+        |repo| write(repo, create, replacement),
+        Error::Unknown,
+    ) {
+        Ok(Pokemon {
+            number,
+            name,
+            types,
+            ..
+        }) => Ok(Response {
+            number: u16::from(number),
+            name: String::from(name),
+            types: Vec::<String>::from(types),
+        }),
+        Err(err) => Err(err),
+    }
+}
+
+fn parse(req: &Request) -> Option<Replacement> {
+    let evolutions = match &req.evolutions {
+        Some(evolutions)
+            if !validate_pokemon::evolutions_errors(req.number, evolutions).is_empty() =>
+        {
+            return None
+        }
+        Some(evolutions) => match evolutions
+            .iter()
+            .map(|e| entities::Evolution::try_new(e.target, e.method.clone(), e.level))
+            .collect::<Result<Vec<entities::Evolution>, ()>>()
+        {
+            Ok(evolutions) => Some(evolutions),
+            _ => return None,
+        },
+        None => None,
+    };
+    let abilities = match &req.abilities {
+        Some(abilities) if abilities.is_empty() => Some(None),
+        Some(abilities) => match abilities
+            .iter()
+            .map(|a| entities::Ability::try_new(a.name.clone(), a.hidden))
+            .collect::<Result<Vec<entities::Ability>, ()>>()
+            .and_then(PokemonAbilities::try_from)
+        {
+            Ok(abilities) => Some(Some(abilities)),
+            _ => return None,
+        },
+        None => None,
+    };
+
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonName::try_from(req.name.clone()),
+        PokemonTypes::try_from(req.types.clone()),
+        req.height
+            .map(|height| height.map(PokemonHeight::try_from).transpose())
+            .transpose(),
+        req.weight
+            .map(|weight| weight.map(PokemonWeight::try_from).transpose())
+            .transpose(),
+        req.sprite
+            .clone()
+            .map(|sprite| sprite.map(PokemonSprite::try_from).transpose())
+            .transpose(),
+        req.class.clone().map(PokemonClass::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(name), Ok(types), Ok(height), Ok(weight), Ok(sprite), Ok(class)) => {
+            Some(Replacement {
+                number,
+                name,
+                types,
+                evolutions,
+                abilities,
+                height,
+                weight,
+                sprite,
+                class,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn errors(req: &Request) -> Vec<FieldError> {
+    let mut errors = validate_pokemon::execute(validate_pokemon::Request {
+        number: req.number,
+        name: req.name.clone(),
+        types: req.types.clone(),
+    })
+    .errors;
+    if let Some(evolutions) = &req.evolutions {
+        errors.extend(validate_pokemon::evolutions_errors(req.number, evolutions));
+    }
+    if let Some(abilities) = &req.abilities {
+        errors.extend(validate_pokemon::abilities_errors(abilities));
+    }
+    if let Some(Some(height)) = req.height {
+        errors.extend(validate_pokemon::height_errors(height));
+    }
+    if let Some(Some(weight)) = req.weight {
+        errors.extend(validate_pokemon::weight_errors(weight));
+    }
+    if let Some(Some(sprite)) = &req.sprite {
+        errors.extend(validate_pokemon::sprite_errors(sprite));
+    }
+    if let Some(class) = &req.class {
+        errors.extend(validate_pokemon::class_errors(class));
+    }
+    errors
+}
+
+fn write(
+    repo: Arc<dyn Repository>,
+    create: bool,
+    replacement: Replacement,
+) -> Result<Pokemon, Error> {
+    let number = replacement.number;
+    let mut pokemon = match create {
+        true => match repo.insert(number.clone(), replacement.name, replacement.types) {
+            Ok(pokemon) => pokemon,
+            Err(InsertError::Conflict) => return Err(Error::Conflict),
+            Err(InsertError::Unknown) => return Err(Error::Unknown),
+        },
+        false => match repo.update(number.clone(), replacement.name, replacement.types) {
+            Ok(pokemon) => pokemon,
+            Err(UpdateError::NotFound) => return Err(Error::NotFound),
+            Err(UpdateError::Unknown) => return Err(Error::Unknown),
+        },
+    };
+
+    if let Some(evolutions) = replacement.evolutions {
+        pokemon = match repo.update_evolutions(number.clone(), evolutions) {
+            Ok(pokemon) => pokemon,
+            _ => return Err(Error::Unknown),
+        };
+    }
+    if let Some(abilities) = replacement.abilities {
+        pokemon = match repo.update_abilities(number.clone(), abilities) {
+            Ok(pokemon) => pokemon,
+            _ => return Err(Error::Unknown),
+        };
+    }
+    // The measurements are set together, the one left out is kept.
+    if replacement.height.is_some() || replacement.weight.is_some() {
+        let (height, weight) = match repo.fetch_one(number.clone()) {
+            Ok(stored) => (
+                replacement.height.unwrap_or(stored.height),
+                replacement.weight.unwrap_or(stored.weight),
+            ),
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+            Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+        };
+        pokemon = match repo.update_measurements(number.clone(), height, weight) {
+            Ok(pokemon) => pokemon,
+            _ => return Err(Error::Unknown),
+        };
+    }
+    if let Some(sprite) = replacement.sprite {
+        pokemon = match repo.update_sprite(number.clone(), sprite) {
+            Ok(pokemon) => pokemon,
+            _ => return Err(Error::Unknown),
+        };
+    }
+    if let Some(class) = replacement.class {
+        pokemon = match repo.update_class(number, class) {
+            Ok(pokemon) => pokemon,
+            _ => return Err(Error::Unknown),
+        };
+    }
+    Ok(pokemon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn request(create: bool) -> Request {
+        Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            name: String::from(PokemonName::pikachu()),
+            types: Vec::<String>::from(PokemonTypes::pikachu()),
+            create,
+            evolutions: None,
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: None,
+        }
+    }
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.update_measurements(
+            PokemonNumber::pikachu(),
+            Some(PokemonHeight::charmander()),
+            None,
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_and_write_nothing_when_a_field_is_invalid() {
+        let repo = repo_with_pikachu();
+        let mut req = request(false);
+        req.name = String::from(PokemonName::charmander());
+        req.class = Some(String::from("Plasma"));
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.field, "class"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => assert_eq!(String::from(pokemon.name), "Pikachu"),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo, request(false));
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_creating_a_taken_number() {
+        let repo = repo_with_pikachu();
+
+        let res = execute(repo, request(true));
+
+        match res {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo, request(false));
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_replace_the_given_fields_and_keep_the_others_otherwise() {
+        let repo = repo_with_pikachu();
+        let mut req = request(false);
+        req.abilities = Some(vec![Ability {
+            name: String::from("Static"),
+            hidden: false,
+        }]);
+        req.weight = Some(Some(6.0));
+        req.class = Some(String::from("Legendary"));
+
+        let res = execute(repo.clone(), req);
+
+        assert!(res.is_ok());
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => {
+                assert!(pokemon.abilities.is_some());
+                assert_eq!(pokemon.height.map(f64::from), Some(0.6));
+                assert_eq!(pokemon.weight.map(f64::from), Some(6.0));
+                assert!(pokemon.class == PokemonClass::Legendary);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,152 @@
+use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{Repository, UpdateError};
+use std::sync::Arc;
+
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    /// The invalid fields, each with the reason.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown,
+}
+
+/// Replaces the name and the types of an existing Pokemon.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match (
+        PokemonNumber::try_from(req.number),
+        PokemonName::try_from(req.name.clone()),
+        PokemonTypes::try_from(req.types.clone()),
+    ) {
+        (Ok(number), Ok(name), Ok(types)) => match repo.update(number, name, types) {
+            Ok(Pokemon {
+                number,
+                name,
+                types,
+                ..
+            }) => Ok(Response {
+                number: u16::from(number),
+                name: String::from(name),
+                types: Vec::<String>::from(types),
+            }),
+            Err(UpdateError::NotFound) => Err(Error::NotFound),
+            Err(UpdateError::Unknown) => Err(Error::Unknown),
+        },
+        _ => {
+            let req = validate_pokemon::Request {
+                number: req.number,
+                name: req.name,
+                types: req.types,
+            };
+            Err(Error::BadRequest(validate_pokemon::execute(req).errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::bad(),
+            PokemonTypes::pikachu(),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.field, "name"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_pokemon_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request::new(
+            PokemonNumber::pikachu(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.number, u16::from(PokemonNumber::pikachu()));
+                assert_eq!(res.name, String::from(PokemonName::charmander()));
+                assert_eq!(res.types, Vec::<String>::from(PokemonTypes::charmander()));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+            Self {
+                number: u16::from(number),
+                name: String::from(name),
+                types: Vec::<String>::from(types),
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use crate::domain::entities::{self, PokemonAbilities, PokemonNumber};
+use crate::domain::fetch_pokemon::Ability;
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{Repository, UpdateError};
+use std::sync::Arc;
+
+/// Replaces every ability of the Pokemon, none removing them all.
+pub struct Request {
+    pub number: u16,
+    pub abilities: Vec<Ability>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub abilities: Vec<Ability>,
+}
+
+pub enum Error {
+    /// An invalid ability, too many of them, several hidden ones, or the
+    /// same one twice, with the reasons.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let errors = || {
+        let mut errors = validate_pokemon::number_errors(req.number);
+        errors.extend(validate_pokemon::abilities_errors(&req.abilities));
+        errors
+    };
+
+    let (number, abilities) = match (
+        PokemonNumber::try_from(req.number),
+        req.abilities
+            .iter()
+            .map(|a| entities::Ability::try_new(a.name.clone(), a.hidden))
+            .collect::<Result<Vec<entities::Ability>, ()>>(),
+    ) {
+        (Ok(number), Ok(abilities)) if abilities.is_empty() => (number, None),
+        (Ok(number), Ok(abilities)) => match PokemonAbilities::try_from(abilities) {
+            Ok(abilities) => (number, Some(abilities)),
+            _ => return Err(Error::BadRequest(errors())),
+        },
+        _ => return Err(Error::BadRequest(errors())),
+    };
+
+    match repo.update_abilities(number, abilities) {
+        Ok(pokemon) => Ok(Response {
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            abilities: Ability::list(pokemon.abilities),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::charmander(), vec![ability("Blaze", false)]);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_an_ability_is_invalid() {
+        let repo = charmander();
+        let req = Request::new(PokemonNumber::charmander(), vec![ability("Bl@ze", false)]);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.field, "abilities"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_two_abilities_are_hidden() {
+        let repo = charmander();
+        let req = Request::new(
+            PokemonNumber::charmander(),
+            vec![ability("Blaze", true), ability("Solar Power", true)],
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.message, "must contain at most one hidden ability"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::charmander(), vec![ability("Blaze", false)]);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_pokemon_otherwise() {
+        let repo = charmander();
+        let req = Request::new(
+            PokemonNumber::charmander(),
+            vec![ability("Blaze", false), ability("Solar Power", true)],
+        );
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => match &res.abilities[..] {
+                [blaze, solar_power] => {
+                    assert_eq!(blaze.name, "Blaze");
+                    assert!(!blaze.hidden);
+                    assert_eq!(solar_power.name, "Solar Power");
+                    assert!(solar_power.hidden);
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::charmander()),
+            Ok(pokemon) if pokemon.abilities.is_some()
+        ));
+    }
+
+    #[test]
+    fn it_should_remove_the_abilities_when_none_are_given() {
+        let repo = charmander();
+        repo.update_abilities(
+            PokemonNumber::charmander(),
+            Some(PokemonAbilities::charmander()),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::charmander(), vec![]);
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert!(res.abilities.is_empty()),
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::charmander()),
+            Ok(pokemon) if pokemon.abilities.is_none()
+        ));
+    }
+
+    fn charmander() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    fn ability(name: &str, hidden: bool) -> Ability {
+        Ability {
+            name: String::from(name),
+            hidden,
+        }
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, abilities: Vec<Ability>) -> Self {
+            Self {
+                number: u16::from(number),
+                abilities,
+            }
+        }
+    }
+}
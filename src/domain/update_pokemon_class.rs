@@ -0,0 +1,137 @@
+use crate::domain::entities::{PokemonClass, PokemonNumber};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{Repository, UpdateError};
+use std::sync::Arc;
+
+/// Makes the Pokemon a normal, a legendary or a mythical one.
+pub struct Request {
+    pub number: u16,
+    pub class: String,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub class: String,
+}
+
+pub enum Error {
+    /// A class other than Normal, Legendary and Mythical, with the reason.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let (number, class) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonClass::try_from(req.class.clone()),
+    ) {
+        (Ok(number), Ok(class)) => (number, class),
+        _ => {
+            let mut errors = validate_pokemon::number_errors(req.number);
+            errors.extend(validate_pokemon::class_errors(&req.class));
+            return Err(Error::BadRequest(errors));
+        }
+    };
+
+    match repo.update_class(number, class) {
+        Ok(pokemon) => Ok(Response {
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            class: String::from(pokemon.class),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu(), "Legendary");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_class_is_unknown() {
+        let req = Request::new(PokemonNumber::pikachu(), "Shiny");
+
+        let res = execute(pikachu(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => {
+                    assert_eq!(error.field, "class");
+                    assert_eq!(error.message, "must be Normal, Legendary or Mythical");
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), "Legendary");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_pokemon_otherwise() {
+        let repo = pikachu();
+        let req = Request::new(PokemonNumber::pikachu(), "legendary");
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(res.class, "Legendary"),
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::pikachu()),
+            Ok(pokemon) if pokemon.class == PokemonClass::Legendary
+        ));
+    }
+
+    fn pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, class: &str) -> Self {
+            Self {
+                number: u16::from(number),
+                class: String::from(class),
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::domain::entities::{Cry, PokemonNumber};
+use crate::repositories::cry::{PutError, Repository as CryRepository};
+use crate::repositories::pokemon::{FetchOneError, Repository};
+use std::sync::Arc;
+
+/// Replaces the cry of the Pokemon by the clip, `content_type` being its
+/// media type such as `audio/ogg`.
+pub struct Request {
+    pub number: u16,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub content_type: String,
+    /// In bytes.
+    pub size: usize,
+}
+
+pub enum Error {
+    /// An empty or too large clip, or one which isn't in a known audio
+    /// format.
+    BadRequest,
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(
+    cry_repo: Arc<dyn CryRepository>,
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Response, Error> {
+    let (number, cry) = match (
+        PokemonNumber::try_from(req.number),
+        Cry::try_new(req.content_type, req.bytes),
+    ) {
+        (Ok(number), Ok(cry)) => (number, cry),
+        _ => return Err(Error::BadRequest),
+    };
+
+    match repo.fetch_one(number.clone()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let res = Response {
+        number: u16::from(number.clone()),
+        content_type: String::from(cry.format),
+        size: cry.bytes.len(),
+    };
+    match cry_repo.put(number, cry) {
+        Ok(()) => Ok(res),
+        Err(PutError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::cry::InMemoryRepository as InMemoryCryRepository;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu(), Cry::pikachu());
+
+        let res = execute(cry_repo, pikachu(), req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_clip_is_not_audio() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new());
+        let mut req = Request::new(PokemonNumber::pikachu(), Cry::pikachu());
+        req.content_type = String::from("text/plain");
+
+        let res = execute(cry_repo, pikachu(), req);
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new());
+        let req = Request::new(PokemonNumber::charmander(), Cry::pikachu());
+
+        let res = execute(cry_repo, pikachu(), req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_store_the_cry_otherwise() {
+        let cry_repo = Arc::new(InMemoryCryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), Cry::pikachu());
+
+        let res = execute(cry_repo.clone(), pikachu(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.content_type, "audio/ogg");
+                assert_eq!(res.size, Cry::pikachu().bytes.len());
+            }
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            cry_repo.fetch(PokemonNumber::pikachu()),
+            Ok(cry) if cry.bytes == Cry::pikachu().bytes
+        ));
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, cry: Cry) -> Self {
+            Self {
+                number: u16::from(number),
+                content_type: String::from(cry.format),
+                bytes: cry.bytes,
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::domain::entities::{self, PokemonNumber};
+use crate::domain::fetch_pokemon::Evolution;
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{Repository, UpdateError};
+use std::sync::Arc;
+
+/// Replaces every evolution of the Pokemon, none removing them all. The
+/// evolutions don't need to be stored yet.
+pub struct Request {
+    pub number: u16,
+    pub evolutions: Vec<Evolution>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub evolutions: Vec<Evolution>,
+}
+
+pub enum Error {
+    /// An invalid evolution, the Pokemon itself, or the same one twice,
+    /// with the reasons.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let errors = || {
+        let mut errors = validate_pokemon::number_errors(req.number);
+        errors.extend(validate_pokemon::evolutions_errors(
+            req.number,
+            &req.evolutions,
+        ));
+        errors
+    };
+
+    let (number, evolutions) = match (
+        PokemonNumber::try_from(req.number),
+        req.evolutions
+            .iter()
+            .map(|e| entities::Evolution::try_new(e.target, e.method.clone(), e.level))
+            .collect::<Result<Vec<entities::Evolution>, ()>>(),
+    ) {
+        (Ok(number), Ok(evolutions)) => (number, evolutions),
+        _ => return Err(Error::BadRequest(errors())),
+    };
+
+    for (index, evolution) in evolutions.iter().enumerate() {
+        if evolution.target == number
+            || evolutions[..index]
+                .iter()
+                .any(|e| e.target == evolution.target)
+        {
+            return Err(Error::BadRequest(errors()));
+        }
+    }
+
+    match repo.update_evolutions(number, evolutions) {
+        Ok(pokemon) => Ok(Response {
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            evolutions: pokemon
+                .evolutions
+                .into_iter()
+                .map(Evolution::from)
+                .collect(),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::charmander(), vec![charmeleon()]);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_an_evolution_is_invalid() {
+        let repo = charmander();
+        let req = Request::new(
+            PokemonNumber::charmander(),
+            vec![Evolution {
+                target: 5,
+                method: String::from("level"),
+                level: None,
+            }],
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => {
+                    assert_eq!(error.field, "evolutions");
+                    assert_eq!(error.message, "#5 must evolve at a given level");
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_pokemon_evolves_into_itself() {
+        let repo = charmander();
+        let req = Request::new(
+            PokemonNumber::charmander(),
+            vec![Evolution {
+                target: u16::from(PokemonNumber::charmander()),
+                method: String::from("trade"),
+                level: None,
+            }],
+        );
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.message, "must not contain the Pokemon itself"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::charmander(), vec![charmeleon()]);
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_pokemon_otherwise() {
+        let repo = charmander();
+        let req = Request::new(PokemonNumber::charmander(), vec![charmeleon()]);
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => match &res.evolutions[..] {
+                [evolution] => {
+                    assert_eq!(evolution.target, 5);
+                    assert_eq!(evolution.level, Some(16));
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::charmander()),
+            Ok(pokemon) if pokemon.evolutions.len() == 1
+        ));
+    }
+
+    fn charmander() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    fn charmeleon() -> Evolution {
+        Evolution {
+            target: 5,
+            method: String::from("level"),
+            level: Some(16),
+        }
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, evolutions: Vec<Evolution>) -> Self {
+            Self {
+                number: u16::from(number),
+                evolutions,
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::domain::entities::{PokemonHeight, PokemonNumber, PokemonWeight};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{Repository, UpdateError};
+use std::sync::Arc;
+
+/// Replaces the height, in meters, and the weight, in kilograms, of the
+/// Pokemon. None removes a measurement.
+pub struct Request {
+    pub number: u16,
+    pub height: Option<f64>,
+    pub weight: Option<f64>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub height: Option<f64>,
+    pub weight: Option<f64>,
+}
+
+pub enum Error {
+    /// A measurement which isn't positive or is out of bounds, with the
+    /// reason.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let (number, height, weight) = match (
+        PokemonNumber::try_from(req.number),
+        req.height.map(PokemonHeight::try_from).transpose(),
+        req.weight.map(PokemonWeight::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(height), Ok(weight)) => (number, height, weight),
+        _ => {
+            let mut errors = validate_pokemon::number_errors(req.number);
+            if let Some(height) = req.height {
+                errors.extend(validate_pokemon::height_errors(height));
+            }
+            if let Some(weight) = req.weight {
+                errors.extend(validate_pokemon::weight_errors(weight));
+            }
+            return Err(Error::BadRequest(errors));
+        }
+    };
+
+    match repo.update_measurements(number, height, weight) {
+        Ok(pokemon) => Ok(Response {
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            height: pokemon.height.map(f64::from),
+            weight: pokemon.weight.map(f64::from),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::charmander(), Some(0.6), Some(8.5));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_measurement_is_not_positive() {
+        let req = Request::new(PokemonNumber::charmander(), Some(0.6), Some(-8.5));
+
+        let res = execute(charmander(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.field, "weight"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_a_measurement_is_out_of_bounds() {
+        let req = Request::new(PokemonNumber::charmander(), Some(600.0), Some(8.5));
+
+        let res = execute(charmander(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => {
+                    assert_eq!(error.field, "height");
+                    assert_eq!(error.message, "must be between 0.1 and 100 meters");
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::charmander(), Some(0.6), Some(8.5));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_pokemon_otherwise() {
+        let repo = charmander();
+        let req = Request::new(PokemonNumber::charmander(), Some(0.6), Some(8.5));
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => {
+                assert_eq!(res.height, Some(0.6));
+                assert_eq!(res.weight, Some(8.5));
+            }
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::charmander()),
+            Ok(pokemon) if pokemon.height == Some(PokemonHeight::charmander())
+                && pokemon.weight == Some(PokemonWeight::charmander())
+        ));
+    }
+
+    #[test]
+    fn it_should_remove_the_measurements_when_none_are_given() {
+        let repo = charmander();
+        repo.update_measurements(
+            PokemonNumber::charmander(),
+            Some(PokemonHeight::charmander()),
+            Some(PokemonWeight::charmander()),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::charmander(), None, None);
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert!(res.height.is_none() && res.weight.is_none()),
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::charmander()),
+            Ok(pokemon) if pokemon.height.is_none() && pokemon.weight.is_none()
+        ));
+    }
+
+    fn charmander() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        repo
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, height: Option<f64>, weight: Option<f64>) -> Self {
+            Self {
+                number: u16::from(number),
+                height,
+                weight,
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::domain::entities::{PokemonNumber, PokemonSprite};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{Repository, UpdateError};
+use std::sync::Arc;
+
+/// Replaces the URL of the image of the Pokemon. None removes it.
+pub struct Request {
+    pub number: u16,
+    pub url: Option<String>,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+    pub sprite: Option<String>,
+}
+
+pub enum Error {
+    /// A URL which isn't a web address, with the reason.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let (number, sprite) = match (
+        PokemonNumber::try_from(req.number),
+        req.url.clone().map(PokemonSprite::try_from).transpose(),
+    ) {
+        (Ok(number), Ok(sprite)) => (number, sprite),
+        _ => {
+            let mut errors = validate_pokemon::number_errors(req.number);
+            if let Some(url) = &req.url {
+                errors.extend(validate_pokemon::sprite_errors(url));
+            }
+            return Err(Error::BadRequest(errors));
+        }
+    };
+
+    match repo.update_sprite(number, sprite) {
+        Ok(pokemon) => Ok(Response {
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            sprite: pokemon.sprite.map(String::from),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu(), Some(PokemonSprite::pikachu()));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_url_is_not_a_web_address() {
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            url: Some(String::from("file:///etc/passwd")),
+        };
+
+        let res = execute(pikachu(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.field, "sprite"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), Some(PokemonSprite::pikachu()));
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_pokemon_otherwise() {
+        let repo = pikachu();
+        let req = Request::new(PokemonNumber::pikachu(), Some(PokemonSprite::pikachu()));
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(res.sprite, Some(String::from(PokemonSprite::pikachu()))),
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::pikachu()),
+            Ok(pokemon) if pokemon.sprite == Some(PokemonSprite::pikachu())
+        ));
+    }
+
+    #[test]
+    fn it_should_remove_the_sprite_when_none_is_given() {
+        let repo = pikachu();
+        repo.update_sprite(PokemonNumber::pikachu(), Some(PokemonSprite::pikachu()))
+            .ok();
+        let req = Request::new(PokemonNumber::pikachu(), None);
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert!(res.sprite.is_none()),
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::pikachu()),
+            Ok(pokemon) if pokemon.sprite.is_none()
+        ));
+    }
+
+    fn pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, sprite: Option<PokemonSprite>) -> Self {
+            Self {
+                number: u16::from(number),
+                url: sprite.map(String::from),
+            }
+        }
+    }
+}
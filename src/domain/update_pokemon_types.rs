@@ -0,0 +1,212 @@
+use crate::domain::entities::{PokemonNumber, PokemonType, PokemonTypes};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{FetchOneError, Repository, UpdateError};
+use std::sync::Arc;
+
+pub enum Operation {
+    Add,
+    Remove,
+}
+
+pub struct Request {
+    pub number: u16,
+    pub operation: Operation,
+    pub pokemon_type: String,
+}
+
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub enum Error {
+    /// The invalid fields, each with the reason.
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    LastType,
+    TooManyTypes,
+    Unknown,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let (number, pokemon_type) = match (
+        PokemonNumber::try_from(req.number),
+        PokemonType::try_from(req.pokemon_type.clone()),
+    ) {
+        (Ok(number), Ok(pokemon_type)) => (number, String::from(pokemon_type)),
+        _ => {
+            let mut errors = validate_pokemon::number_errors(req.number);
+            errors.extend(validate_pokemon::types_errors(&[req.pokemon_type]));
+            return Err(Error::BadRequest(errors));
+        }
+    };
+
+    let pokemon = match repo.fetch_one(number.clone()) {
+        Ok(pokemon) => pokemon,
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Unknown) => return Err(Error::Unknown),
+    };
+
+    let mut types = Vec::<String>::from(pokemon.types);
+    match req.operation {
+        Operation::Add if !types.contains(&pokemon_type) => types.push(pokemon_type),
+        Operation::Add => {}
+        Operation::Remove => types.retain(|t| *t != pokemon_type),
+    }
+
+    let types = match (PokemonTypes::try_from(types), req.operation) {
+        (Ok(types), _) => types,
+        (_, Operation::Add) => return Err(Error::TooManyTypes),
+        (_, Operation::Remove) => return Err(Error::LastType),
+    };
+
+    match repo.update_types(number, types) {
+        Ok(pokemon) => Ok(Response {
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Unknown) => Err(Error::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::PokemonName;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Add, "Fire");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Add, "Plasma");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.message, "Plasma is not a known type"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_repo_does_not_contain_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Add, "Fire");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_last_type_error_when_removing_the_only_type() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Remove, "Electric");
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::LastType) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_updated_pokemon_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Add, "Fire");
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(
+                res.types,
+                vec![String::from("Electric"), String::from("Fire")]
+            ),
+            _ => unreachable!(),
+        };
+
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Remove, "Electric");
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => assert_eq!(res.types, vec![String::from("Fire")]),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_match_the_type_whatever_its_case() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Add, "electric");
+
+        let res = execute(repo.clone(), req);
+
+        match res {
+            Ok(res) => assert_eq!(res.types, vec![String::from("Electric")]),
+            _ => unreachable!(),
+        };
+
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Add, "FIRE");
+        execute(repo.clone(), req).ok();
+        let req = Request::new(PokemonNumber::pikachu(), Operation::Remove, "elec");
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => assert_eq!(res.types, vec![String::from("Fire")]),
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn new(number: PokemonNumber, operation: Operation, pokemon_type: &str) -> Self {
+            Self {
+                number: u16::from(number),
+                operation,
+                pokemon_type: String::from(pokemon_type),
+            }
+        }
+    }
+}
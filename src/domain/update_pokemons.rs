@@ -0,0 +1,337 @@
+use crate::domain::entities::{Pokemon, PokemonNumber, PokemonType, PokemonTypes};
+use crate::domain::validate_pokemon::{self, FieldError};
+use crate::repositories::pokemon::{FetchOneError, Repository, UpdateError};
+use crate::repositories::unit_of_work;
+use std::sync::Arc;
+
+pub const MAX_CHANGES: usize = 100;
+
+pub enum Change {
+    SetTypes(Vec<String>),
+    AddType(String),
+    RemoveType(String),
+}
+
+/// Changes to the same Pokemon apply one after the other, in the order of
+/// the request.
+pub struct Request {
+    pub number: u16,
+    pub change: Change,
+}
+
+/// What happened to each requested change, in the order of the request.
+pub struct Outcome {
+    pub number: u16,
+    pub status: Status,
+}
+
+pub enum Status {
+    /// The Pokemon as this change left it.
+    Updated {
+        name: String,
+        types: Vec<String>,
+    },
+    /// Valid, but left out since another change failed.
+    NotApplied,
+    /// An invalid number or type, with the reasons.
+    Invalid(Vec<FieldError>),
+    NotFound,
+    LastType,
+    TooManyTypes,
+}
+
+pub enum Error {
+    /// No change at all, or more than `MAX_CHANGES`.
+    BadRequest(Vec<FieldError>),
+    /// Nothing was applied, the outcomes tell which changes failed.
+    Rejected(Vec<Outcome>),
+    Unknown,
+}
+
+/// Applies every change or none: the changes are all checked first, then
+/// written as a whole with `unit_of_work::run`.
+pub fn execute(repo: Arc<dyn Repository>, reqs: Vec<Request>) -> Result<Vec<Outcome>, Error> {
+    if reqs.is_empty() || reqs.len() > MAX_CHANGES {
+        return Err(Error::BadRequest(vec![FieldError::new(
+            "changes",
+            &format!("must contain between 1 and {} changes", MAX_CHANGES),
+        )]));
+    }
+
+    let mut pokemons = vec![];
+    let mut planned = vec![];
+    for req in reqs {
+        let number = req.number;
+        match plan(repo.clone(), &mut pokemons, req) {
+            Ok(res) => planned.push((number, res)),
+            Err(()) => return Err(Error::Unknown),
+        }
+    }
+
+    if planned.iter().any(|(_, res)| res.is_err()) {
+        return Err(Error::Rejected(
+            planned
+                .into_iter()
+                .map(|(number, res)| Outcome {
+                    number,
+                    status: match res {
+                        Ok(_) => Status::NotApplied,
+                        Err(status) => status,
+                    },
+                })
+                .collect::<Vec<Outcome>>(),
+        ));
+    }
+
+    match unit_of_work::run(repo, |repo| write(repo, pokemons), Error::Unknown) {
+        Ok(()) => Ok(planned
+            .into_iter()
+            .filter_map(|(number, res)| res.ok().map(|pokemon| (number, pokemon)))
+            .map(|(number, pokemon)| Outcome {
+                number,
+                status: Status::Updated {
+                    name: String::from(pokemon.name),
+                    types: Vec::<String>::from(pokemon.types),
+                },
+            })
+            .collect::<Vec<Outcome>>()),
+        Err(err) => Err(err),
+    }
+}
+
+/// The Pokemon as the change leaves it. `pokemons` holds the Pokemons
+/// changed so far, as the next changes must see them.
+fn plan(
+    repo: Arc<dyn Repository>,
+    pokemons: &mut Vec<Pokemon>,
+    req: Request,
+) -> Result<Result<Pokemon, Status>, ()> {
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => number,
+        _ => {
+            return Ok(Err(Status::Invalid(validate_pokemon::number_errors(
+                req.number,
+            ))))
+        }
+    };
+
+    let pokemon = match pokemons.iter().position(|p| p.number == number) {
+        Some(index) => pokemons.remove(index),
+        None => match repo.fetch_one(number) {
+            Ok(pokemon) => pokemon,
+            Err(FetchOneError::NotFound) => return Ok(Err(Status::NotFound)),
+            Err(FetchOneError::Unknown) => return Err(()),
+        },
+    };
+
+    match types(&pokemon, req.change) {
+        Ok(types) => {
+            let pokemon = Pokemon::new(pokemon.number, pokemon.name, types);
+            pokemons.push(pokemon.clone());
+            Ok(Ok(pokemon))
+        }
+        Err(status) => {
+            pokemons.push(pokemon);
+            Ok(Err(status))
+        }
+    }
+}
+
+fn types(pokemon: &Pokemon, change: Change) -> Result<PokemonTypes, Status> {
+    let mut types = Vec::<String>::from(pokemon.types.clone());
+
+    match change {
+        Change::SetTypes(types) => match PokemonTypes::try_from(types.clone()) {
+            Ok(types) => Ok(types),
+            _ => Err(Status::Invalid(validate_pokemon::types_errors(&types))),
+        },
+        Change::AddType(pokemon_type) => match PokemonType::try_from(pokemon_type.clone()) {
+            Ok(pokemon_type) => {
+                let pokemon_type = String::from(pokemon_type);
+                if !types.contains(&pokemon_type) {
+                    types.push(pokemon_type);
+                }
+                match PokemonTypes::try_from(types) {
+                    Ok(types) => Ok(types),
+                    _ => Err(Status::TooManyTypes),
+                }
+            }
+            _ => Err(Status::Invalid(validate_pokemon::types_errors(&[
+                pokemon_type,
+            ]))),
+        },
+        Change::RemoveType(pokemon_type) => match PokemonType::try_from(pokemon_type.clone()) {
+            Ok(pokemon_type) => {
+                types.retain(|t| t != pokemon_type.as_str());
+                match PokemonTypes::try_from(types) {
+                    Ok(types) => Ok(types),
+                    _ => Err(Status::LastType),
+                }
+            }
+            _ => Err(Status::Invalid(validate_pokemon::types_errors(&[
+                pokemon_type,
+            ]))),
+        },
+    }
+}
+
+fn write(repo: Arc<dyn Repository>, pokemons: Vec<Pokemon>) -> Result<(), Error> {
+    for pokemon in pokemons {
+        match repo.update_types(pokemon.number, pokemon.types) {
+            Ok(_) => {}
+            Err(UpdateError::NotFound) | Err(UpdateError::Unknown) => return Err(Error::Unknown),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::PokemonName;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_an_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let reqs = vec![Request::add(PokemonNumber::pikachu(), "Fire")];
+
+        let res = execute(repo, reqs);
+
+        match res {
+            Err(Error::Unknown) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_there_is_no_change() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo, vec![]);
+
+        match res {
+            Err(Error::BadRequest(errors)) => match errors.as_slice() {
+                [error] => assert_eq!(error.message, "must contain between 1 and 100 changes"),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_tell_why_a_change_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let reqs = vec![Request::add(PokemonNumber::pikachu(), "Plasma")];
+
+        let res = execute(repo, reqs);
+
+        match res {
+            Err(Error::Rejected(outcomes)) => match &outcomes[..] {
+                [Outcome {
+                    status: Status::Invalid(errors),
+                    ..
+                }] => match errors.as_slice() {
+                    [error] => {
+                        assert_eq!(error.field, "types");
+                        assert_eq!(error.message, "Plasma is not a known type");
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_apply_nothing_and_tell_which_changes_failed() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let reqs = vec![
+            Request::add(PokemonNumber::pikachu(), "Fire"),
+            Request::add(PokemonNumber::charmander(), "Electric"),
+        ];
+
+        let res = execute(repo.clone(), reqs);
+
+        match res {
+            Err(Error::Rejected(outcomes)) => match outcomes[..] {
+                [Outcome {
+                    status: Status::NotApplied,
+                    ..
+                }, Outcome {
+                    number: 4,
+                    status: Status::NotFound,
+                }] => {}
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => assert_eq!(Vec::<String>::from(pokemon.types), vec!["Electric"]),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_apply_the_changes_of_a_pokemon_one_after_the_other_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let reqs = vec![
+            Request::add(PokemonNumber::pikachu(), "Fire"),
+            Request {
+                number: u16::from(PokemonNumber::pikachu()),
+                change: Change::RemoveType(String::from("Electric")),
+            },
+        ];
+
+        let res = execute(repo.clone(), reqs);
+
+        match res {
+            Ok(outcomes) => match &outcomes[..] {
+                [Outcome {
+                    status: Status::Updated { types: first, .. },
+                    ..
+                }, Outcome {
+                    status: Status::Updated { types: last, .. },
+                    ..
+                }] => {
+                    assert_eq!(first, &vec!["Electric", "Fire"]);
+                    assert_eq!(last, &vec!["Fire"]);
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => assert_eq!(Vec::<String>::from(pokemon.types), vec!["Fire"]),
+            _ => unreachable!(),
+        };
+    }
+
+    impl Request {
+        fn add(number: PokemonNumber, pokemon_type: &str) -> Self {
+            Self {
+                number: u16::from(number),
+                change: Change::AddType(String::from(pokemon_type)),
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::domain::fetch_pokemon::{Ability, Evolution};
+use crate::kernel::validation::{
+    validate_abilities, validate_class, validate_evolutions, validate_height, validate_name,
+    validate_number, validate_sprite, validate_types, validate_weight,
+};
+
+pub struct Request {
+    pub number: u16,
+    pub name: String,
+    pub types: Vec<String>,
+}
+
+pub struct Response {
+    pub errors: Vec<FieldError>,
+}
+
+/// Why a field of a request is invalid. The use cases writing a Pokemon
+/// fail with them, so that clients can tell what to fix.
+pub struct FieldError {
+    pub field: String,
+    /// Meant to follow the name of the field, as in "number must be between
+    /// 1 and 1025".
+    pub message: String,
+}
+
+pub fn execute(req: Request) -> Response {
+    let mut errors = number_errors(req.number);
+    errors.extend(name_errors(&req.name));
+    errors.extend(types_errors(&req.types));
+
+    Response { errors }
+}
+
+// The errors of single fields, for the use cases given a part of a Pokemon.
+
+pub fn number_errors(number: u16) -> Vec<FieldError> {
+    match validate_number(number) {
+        Ok(()) => vec![],
+        Err(message) => vec![FieldError::new("number", &message)],
+    }
+}
+
+pub fn name_errors(name: &str) -> Vec<FieldError> {
+    match validate_name(name) {
+        Ok(()) => vec![],
+        Err(message) => vec![FieldError::new("name", &message)],
+    }
+}
+
+pub fn types_errors(types: &[String]) -> Vec<FieldError> {
+    match validate_types(types) {
+        Ok(()) => vec![],
+        Err(messages) => messages
+            .iter()
+            .map(|message| FieldError::new("types", message))
+            .collect(),
+    }
+}
+
+pub fn height_errors(meters: f64) -> Vec<FieldError> {
+    match validate_height(meters) {
+        Ok(()) => vec![],
+        Err(message) => vec![FieldError::new("height", &message)],
+    }
+}
+
+pub fn weight_errors(kilograms: f64) -> Vec<FieldError> {
+    match validate_weight(kilograms) {
+        Ok(()) => vec![],
+        Err(message) => vec![FieldError::new("weight", &message)],
+    }
+}
+
+pub fn evolutions_errors(number: u16, evolutions: &[Evolution]) -> Vec<FieldError> {
+    let evolutions = evolutions
+        .iter()
+        .map(|e| (e.target, e.method.as_str(), e.level))
+        .collect::<Vec<(u16, &str, Option<u8>)>>();
+    match validate_evolutions(number, &evolutions) {
+        Ok(()) => vec![],
+        Err(messages) => messages
+            .iter()
+            .map(|message| FieldError::new("evolutions", message))
+            .collect(),
+    }
+}
+
+pub fn abilities_errors(abilities: &[Ability]) -> Vec<FieldError> {
+    let abilities = abilities
+        .iter()
+        .map(|a| (a.name.as_str(), a.hidden))
+        .collect::<Vec<(&str, bool)>>();
+    match validate_abilities(&abilities) {
+        Ok(()) => vec![],
+        Err(messages) => messages
+            .iter()
+            .map(|message| FieldError::new("abilities", message))
+            .collect(),
+    }
+}
+
+pub fn sprite_errors(url: &str) -> Vec<FieldError> {
+    match validate_sprite(url) {
+        Ok(()) => vec![],
+        Err(message) => vec![FieldError::new("sprite", &message)],
+    }
+}
+
+pub fn class_errors(class: &str) -> Vec<FieldError> {
+    match validate_class(class) {
+        Ok(()) => vec![],
+        Err(message) => vec![FieldError::new("class", &message)],
+    }
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: String::from(field),
+            message: String::from(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+
+    #[test]
+    fn it_should_return_every_invalid_field_when_request_is_invalid() {
+        let req = Request {
+            number: u16::from(PokemonNumber::bad()),
+            name: String::from(PokemonName::bad()),
+            types: vec![String::from("Electric"), String::from("Plasma")],
+        };
+
+        let res = execute(req);
+
+        let fields = res
+            .errors
+            .iter()
+            .map(|e| e.field.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(fields, vec!["number", "name", "types"]);
+        assert_eq!(res.errors[2].message, "Plasma is not a known type");
+    }
+
+    #[test]
+    fn it_should_return_no_error_otherwise() {
+        let req = Request {
+            number: u16::from(PokemonNumber::pikachu()),
+            name: String::from(PokemonName::pikachu()),
+            types: Vec::<String>::from(PokemonTypes::pikachu()),
+        };
+
+        let res = execute(req);
+
+        assert!(res.errors.is_empty());
+    }
+}
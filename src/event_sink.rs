@@ -0,0 +1,173 @@
+use crate::domain::fetch_changes;
+use crate::repositories::change::Repository as ChangeRepository;
+use serde::Serialize;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const BATCH_SIZE: u32 = 100;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Serialize)]
+struct Event {
+    cursor: String,
+    kind: String,
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    types: Option<Vec<String>>,
+    timestamp: u64,
+}
+
+/// Publishes the change log to a NATS subject. The cursor of the last change
+/// the server acknowledged is saved to `cursor_path` when given, so changes
+/// are delivered at least once across restarts and connection losses.
+pub fn spawn(
+    change_repo: Arc<dyn ChangeRepository>,
+    url: String,
+    subject: String,
+    cursor_path: Option<String>,
+) {
+    thread::spawn(move || {
+        let mut cursor = cursor_path
+            .as_deref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|cursor| String::from(cursor.trim()));
+        let mut connection: Option<Connection> = None;
+
+        loop {
+            let req = fetch_changes::Request {
+                since: cursor.clone(),
+                limit: BATCH_SIZE,
+            };
+            let res = match fetch_changes::execute(change_repo.clone(), req) {
+                Ok(res) => res,
+                _ => {
+                    thread::sleep(RETRY_INTERVAL);
+                    continue;
+                }
+            };
+
+            if res.changes.is_empty() {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+
+            let mut conn = match connection.take().map_or_else(|| Connection::open(&url), Ok) {
+                Ok(conn) => conn,
+                _ => {
+                    eprintln!("The NATS server at {} is unreachable, retrying", url);
+                    thread::sleep(RETRY_INTERVAL);
+                    continue;
+                }
+            };
+
+            let events = res
+                .changes
+                .into_iter()
+                .map(|c| Event {
+                    cursor: c.cursor,
+                    kind: c.kind,
+                    number: c.number,
+                    name: c.pokemon.as_ref().map(|p| p.name.clone()),
+                    types: c.pokemon.map(|p| p.types),
+                    timestamp: c.timestamp,
+                })
+                .collect::<Vec<Event>>();
+
+            if conn.publish(&subject, &events).is_err() {
+                thread::sleep(RETRY_INTERVAL);
+                continue;
+            }
+            connection = Some(conn);
+
+            cursor = res.cursor;
+            if let (Some(path), Some(cursor)) = (cursor_path.as_deref(), cursor.as_deref()) {
+                fs::write(path, cursor).ok();
+            }
+        }
+    });
+}
+
+/// A connection speaking the NATS text protocol.
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    fn open(url: &str) -> Result<Self, ()> {
+        let address = url.trim_start_matches("nats://");
+        let writer = match TcpStream::connect(address) {
+            Ok(stream) => stream,
+            _ => return Err(()),
+        };
+        let reader = match writer.try_clone() {
+            Ok(stream) => BufReader::new(stream),
+            _ => return Err(()),
+        };
+        let mut conn = Self { reader, writer };
+
+        match conn.read_line() {
+            Ok(line) if line.starts_with("INFO") => {}
+            _ => return Err(()),
+        }
+        match conn
+            .writer
+            .write_all(b"CONNECT {\"verbose\":false,\"pedantic\":false}\r\n")
+        {
+            Ok(()) => Ok(conn),
+            _ => Err(()),
+        }
+    }
+
+    /// Sends the events then waits for the answer to a PING, which the
+    /// server only sends once it has processed everything before it.
+    fn publish(&mut self, subject: &str, events: &[Event]) -> Result<(), ()> {
+        let mut buffer = vec![];
+        for event in events {
+            let payload = match serde_json::to_vec(event) {
+                Ok(payload) => payload,
+                _ => return Err(()),
+            };
+            buffer.extend(format!("PUB {} {}\r\n", subject, payload.len()).into_bytes());
+            buffer.extend(payload);
+            buffer.extend(b"\r\n");
+        }
+        buffer.extend(b"PING\r\n");
+
+        if self.writer.write_all(&buffer).is_err() {
+            return Err(());
+        }
+
+        loop {
+            match self.read_line() {
+                Ok(line) if line.starts_with("PONG") => return Ok(()),
+                Ok(line) if line.starts_with("PING") => {
+                    if self.writer.write_all(b"PONG\r\n").is_err() {
+                        return Err(());
+                    }
+                }
+                Ok(line) if line.starts_with("-ERR") => {
+                    eprintln!("The NATS server answered {}", line.trim_end());
+                    return Err(());
+                }
+                Ok(_) => {}
+                Err(()) => return Err(()),
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String, ()> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) | Err(_) => Err(()),
+            Ok(_) => Ok(line),
+        }
+    }
+}
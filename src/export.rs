@@ -0,0 +1,298 @@
+use crate::domain::{fetch_all_pokemons, fetch_type_stats};
+use crate::pokeapi;
+use crate::repositories::pokemon::{InMemoryRepository, Repository};
+use printpdf::image_crate::{self, DynamicImage};
+use printpdf::path::PaintMode;
+use printpdf::{
+    BuiltinFont, Color, Greyscale, Image, ImageTransform, IndirectFontRef, Mm, PdfDocument,
+    PdfLayerReference, Rect,
+};
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use std::sync::Arc;
+
+// The cards have the size of the trading cards, so that the printed pages
+// can be cut into the pockets of a binder, three by three on A4 pages.
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const CARD_WIDTH: f32 = 63.0;
+const CARD_HEIGHT: f32 = 88.0;
+const CARDS_PER_ROW: usize = 3;
+const CARDS_PER_PAGE: usize = 9;
+const MARGIN_X: f32 = (PAGE_WIDTH - CARDS_PER_ROW as f32 * CARD_WIDTH) / 2.0;
+const MARGIN_Y: f32 = (PAGE_HEIGHT - 3.0 * CARD_HEIGHT) / 2.0;
+/// The largest side of the sprites on the cards, in millimeters.
+const SPRITE_SIZE: f32 = 48.0;
+
+pub enum Error {
+    BadRequest,
+    Unknown,
+}
+
+pub enum ExportFormat {
+    Xlsx,
+    Pdf,
+    /// A JSON array of records shaped like those of pokeapi.co.
+    PokeApi,
+}
+
+impl TryFrom<&str> for ExportFormat {
+    type Error = ();
+
+    fn try_from(format: &str) -> Result<Self, Self::Error> {
+        match format {
+            "xlsx" => Ok(Self::Xlsx),
+            "pdf" => Ok(Self::Pdf),
+            "pokeapi" => Ok(Self::PokeApi),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Pdf => "application/pdf",
+            Self::PokeApi => "application/json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Xlsx => "xlsx",
+            Self::Pdf => "pdf",
+            Self::PokeApi => "json",
+        }
+    }
+}
+
+/// Renders the whole Pokedex: a sheet listing the Pokemons and a sheet with
+/// the number of Pokemons of each type, a printable card per Pokemon, or the
+/// records of PokeAPI.
+/// Both sheets are computed from an in-memory copy of a snapshot, so that
+/// writes running meanwhile show up in none or in both.
+pub fn export(repo: Arc<dyn Repository>, format: &str) -> Result<(ExportFormat, Vec<u8>), Error> {
+    let format = match ExportFormat::try_from(format) {
+        Ok(format) => format,
+        _ => return Err(Error::BadRequest),
+    };
+
+    let copy = InMemoryRepository::new();
+    match repo.snapshot() {
+        Ok(pokemons) => {
+            copy.insert_many(pokemons);
+        }
+        _ => return Err(Error::Unknown),
+    };
+    let copy = Arc::new(copy);
+
+    let (pokemons, stats) = match (
+        fetch_all_pokemons::execute(
+            copy.clone(),
+            fetch_all_pokemons::Request {
+                sort_by: fetch_all_pokemons::SortBy::NumberAscending,
+                offset: 0,
+                limit: None,
+                class: None,
+            },
+        ),
+        fetch_type_stats::execute(copy),
+    ) {
+        (Ok(res), Ok(stats)) => (res.pokemons, stats),
+        _ => return Err(Error::Unknown),
+    };
+
+    let bytes = match format {
+        ExportFormat::Xlsx => xlsx(&pokemons, &stats).map_err(|_| ()),
+        ExportFormat::Pdf => pdf(&pokemons).map_err(|_| ()),
+        ExportFormat::PokeApi => pokeapi::write(&pokemons),
+    };
+
+    match bytes {
+        Ok(bytes) => Ok((format, bytes)),
+        _ => Err(Error::Unknown),
+    }
+}
+
+fn xlsx(
+    pokemons: &[fetch_all_pokemons::Pokemon],
+    stats: &fetch_type_stats::Response,
+) -> Result<Vec<u8>, XlsxError> {
+    let header = Format::new().set_bold().set_background_color("#DDDDDD");
+    let percentage = Format::new().set_num_format("0.0\"%\"");
+    let mut workbook = Workbook::new();
+
+    let sheet = workbook.add_worksheet().set_name("Pokemons")?;
+    write_header(
+        sheet,
+        &header,
+        &[("Number", 10.0), ("Name", 16.0), ("Types", 24.0)],
+    )?;
+    for (row, pokemon) in pokemons.iter().enumerate() {
+        let row = row as u32 + 1;
+        sheet.write_number(row, 0, pokemon.number)?;
+        sheet.write_string(row, 1, &pokemon.name)?;
+        sheet.write_string(row, 2, pokemon.types.join(", "))?;
+    }
+    sheet.autofilter(0, 0, pokemons.len() as u32, 2)?;
+
+    let sheet = workbook.add_worksheet().set_name("Types")?;
+    write_header(
+        sheet,
+        &header,
+        &[("Type", 16.0), ("Pokemons", 12.0), ("Share", 10.0)],
+    )?;
+    for (row, stat) in stats.types.iter().enumerate() {
+        let row = row as u32 + 1;
+        sheet.write_string(row, 0, &stat.name)?;
+        sheet.write_number(row, 1, stat.count)?;
+        sheet.write_number_with_format(row, 2, stat.percentage, &percentage)?;
+    }
+
+    workbook.save_to_buffer()
+}
+
+fn write_header(
+    sheet: &mut Worksheet,
+    format: &Format,
+    columns: &[(&str, f64)],
+) -> Result<(), XlsxError> {
+    for (col, (title, width)) in columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, format)?;
+        sheet.set_column_width(col as u16, *width)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    Ok(())
+}
+
+struct Fonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+fn pdf(pokemons: &[fetch_all_pokemons::Pokemon]) -> Result<Vec<u8>, printpdf::Error> {
+    let (doc, page, layer) = PdfDocument::new("Pokedex", Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Cards");
+    let fonts = Fonts {
+        regular: doc.add_builtin_font(BuiltinFont::Helvetica)?,
+        bold: doc.add_builtin_font(BuiltinFont::HelveticaBold)?,
+    };
+    let mut layer = doc.get_page(page).get_layer(layer);
+
+    for (i, pokemon) in pokemons.iter().enumerate() {
+        if i > 0 && i % CARDS_PER_PAGE == 0 {
+            let (page, next) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Cards");
+            layer = doc.get_page(page).get_layer(next);
+        }
+        let slot = i % CARDS_PER_PAGE;
+        let x = MARGIN_X + (slot % CARDS_PER_ROW) as f32 * CARD_WIDTH;
+        let y = PAGE_HEIGHT - MARGIN_Y - (slot / CARDS_PER_ROW + 1) as f32 * CARD_HEIGHT;
+        card(&layer, &fonts, pokemon, x, y);
+    }
+
+    doc.save_to_bytes()
+}
+
+// (x, y) is the lower left corner of the card.
+fn card(
+    layer: &PdfLayerReference,
+    fonts: &Fonts,
+    pokemon: &fetch_all_pokemons::Pokemon,
+    x: f32,
+    y: f32,
+) {
+    layer.set_outline_color(Color::Greyscale(Greyscale::new(0.6, None)));
+    layer.set_outline_thickness(0.5);
+    layer.add_rect(
+        Rect::new(Mm(x), Mm(y), Mm(x + CARD_WIDTH), Mm(y + CARD_HEIGHT))
+            .with_mode(PaintMode::Stroke),
+    );
+
+    layer.set_fill_color(Color::Greyscale(Greyscale::new(0.4, None)));
+    layer.use_text(
+        format!("#{:03}", pokemon.number),
+        9.0,
+        Mm(x + 4.0),
+        Mm(y + CARD_HEIGHT - 8.0),
+        &fonts.regular,
+    );
+    layer.set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+    layer.use_text(
+        &pokemon.name,
+        14.0,
+        Mm(x + 4.0),
+        Mm(y + CARD_HEIGHT - 15.0),
+        &fonts.bold,
+    );
+    layer.use_text(
+        pokemon.types.join(" / "),
+        10.0,
+        Mm(x + 4.0),
+        Mm(y + 6.0),
+        &fonts.regular,
+    );
+
+    if let Some(Ok(sprite)) = pokemon.sprite.as_deref().map(sprite) {
+        // The dpi scales the largest side of the image to SPRITE_SIZE.
+        let pixels = sprite.width().max(sprite.height()) as f32;
+        Image::from_dynamic_image(&sprite).add_to_layer(
+            layer.clone(),
+            ImageTransform {
+                translate_x: Some(Mm(x + (CARD_WIDTH - SPRITE_SIZE) / 2.0)),
+                translate_y: Some(Mm(y + 14.0)),
+                dpi: Some(pixels * 25.4 / SPRITE_SIZE),
+                ..ImageTransform::default()
+            },
+        );
+    }
+}
+
+/// Downloads the image at the sprite URL. A card is printed without its
+/// sprite when it can't be read.
+fn sprite(url: &str) -> Result<DynamicImage, ()> {
+    match crate::sprite::download(url).map(|image| image_crate::load_from_memory(&image.bytes)) {
+        Ok(Ok(image)) => Ok(image),
+        _ => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use printpdf::lopdf;
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_format_is_unknown() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = export(repo, "odt");
+
+        assert!(matches!(res, Err(Error::BadRequest)));
+    }
+
+    #[test]
+    fn it_should_print_nine_cards_per_page() {
+        let repo = Arc::new(InMemoryRepository::new());
+        for number in 1..=10 {
+            match (
+                PokemonNumber::try_from(number),
+                PokemonName::try_from(format!("Pokemon {}", number)),
+            ) {
+                (Ok(number), Ok(name)) => {
+                    repo.insert(number, name, PokemonTypes::pikachu()).ok();
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let res = export(repo, "pdf");
+
+        match res {
+            Ok((ExportFormat::Pdf, bytes)) => match lopdf::Document::load_mem(&bytes) {
+                Ok(doc) => assert_eq!(doc.get_pages().len(), 2),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,3 @@
+pub mod pokemon;
+pub mod type_chart;
+pub mod validation;
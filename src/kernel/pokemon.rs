@@ -0,0 +1,1076 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{PartialEq, PartialOrd};
+use core::net::IpAddr;
+use core::ops::RangeInclusive;
+use core::sync::atomic::{AtomicU16, AtomicUsize, Ordering};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(PartialEq, Clone, PartialOrd, Ord, Eq)]
+pub struct PokemonNumber(u16);
+
+impl TryFrom<u16> for PokemonNumber {
+    type Error = ();
+
+    fn try_from(n: u16) -> Result<Self, Self::Error> {
+        Self::bounded(n, Self::max())
+    }
+}
+
+impl From<PokemonNumber> for u16 {
+    fn from(n: PokemonNumber) -> Self {
+        n.0
+    }
+}
+
+/// Last number of each generation, from Generation I to Generation IX.
+const GENERATION_ENDS: [u16; 9] = [151, 251, 386, 493, 649, 721, 809, 905, 1025];
+
+static MAX: AtomicU16 = AtomicU16::new(PokemonNumber::DEFAULT_MAX);
+
+impl PokemonNumber {
+    pub const DEFAULT_MAX: u16 = 1025;
+    pub const GENERATIONS: u8 = GENERATION_ENDS.len() as u8;
+
+    /// The highest accepted number, `DEFAULT_MAX` unless changed by `set_max`.
+    pub fn max() -> u16 {
+        MAX.load(Ordering::Relaxed)
+    }
+
+    /// Accepts numbers past the official Pokedex, for fakemon projects. Meant
+    /// to be called once at startup, before any number is parsed.
+    pub fn set_max(max: u16) {
+        MAX.store(max, Ordering::Relaxed);
+    }
+
+    /// The numbers of a generation, counted from 1.
+    pub fn generation_range(generation: u8) -> Option<RangeInclusive<u16>> {
+        let index = usize::from(generation).checked_sub(1)?;
+        let end = *GENERATION_ENDS.get(index)?;
+        let start = match index {
+            0 => 1,
+            _ => GENERATION_ENDS[index - 1] + 1,
+        };
+        Some(start..=end)
+    }
+
+    /// The generation the Pokemon was introduced in, none for fakemons.
+    pub fn generation(&self) -> Option<u8> {
+        GENERATION_ENDS
+            .iter()
+            .position(|end| self.0 <= *end)
+            .map(|index| index as u8 + 1)
+    }
+
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+
+    fn bounded(n: u16, max: u16) -> Result<Self, ()> {
+        if n > 0 && n <= max {
+            Ok(Self(n))
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[cfg(test)]
+impl PokemonNumber {
+    pub fn pikachu() -> Self {
+        Self(25)
+    }
+
+    pub fn charmander() -> Self {
+        Self(4)
+    }
+
+    pub fn bad() -> Self {
+        Self(0)
+    }
+}
+
+#[derive(Clone)]
+pub struct PokemonName(String);
+
+/// Names are trimmed and normalized to NFC, so that "Flabébé" is stored the
+/// same way whichever form of "é" it was typed with. Typographic apostrophes
+/// become straight ones. Length is counted in graphemes.
+impl TryFrom<String> for PokemonName {
+    type Error = ();
+
+    fn try_from(n: String) -> Result<Self, Self::Error> {
+        let n = n.trim().replace('’', "'").nfc().collect::<String>();
+        let length = n.graphemes(true).count();
+
+        if length == 0 || length > Self::MAX_LENGTH {
+            return Err(());
+        }
+
+        if n.chars()
+            .all(|c| c.is_alphanumeric() || is_combining_mark(c) || Self::PUNCTUATION.contains(&c))
+        {
+            Ok(Self(n))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl From<PokemonName> for String {
+    fn from(n: PokemonName) -> Self {
+        n.0
+    }
+}
+
+impl PokemonName {
+    /// The games never display more than 12 characters for a name.
+    pub const MAX_LENGTH: usize = 12;
+
+    // Punctuation found in official names: Farfetch'd, Mr. Mime, Porygon-Z,
+    // Type: Null, Nidoran♀ and Nidoran♂.
+    const PUNCTUATION: [char; 7] = [' ', '\'', '.', '-', ':', '♀', '♂'];
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Lowercase form of `name` stripped of its accents, under which
+    /// "flabebe" and "Flabébé" are the same name.
+    pub fn fold(name: &str) -> String {
+        name.nfd()
+            .filter(|c| !is_combining_mark(*c))
+            .collect::<String>()
+            .to_lowercase()
+    }
+}
+
+#[cfg(test)]
+impl PokemonName {
+    pub fn pikachu() -> Self {
+        Self(String::from("Pikachu"))
+    }
+
+    pub fn charmander() -> Self {
+        Self(String::from("Charmander"))
+    }
+
+    pub fn bad() -> Self {
+        Self(String::from(""))
+    }
+}
+
+#[derive(Clone)]
+pub struct PokemonTypes(Vec<PokemonType>);
+
+impl TryFrom<Vec<String>> for PokemonTypes {
+    type Error = ();
+
+    fn try_from(ts: Vec<String>) -> Result<Self, Self::Error> {
+        Self::bounded(ts, Self::max_count())
+    }
+}
+
+impl From<PokemonTypes> for Vec<String> {
+    fn from(pts: PokemonTypes) -> Self {
+        let mut ts = vec![];
+        for pt in pts.0.into_iter() {
+            ts.push(String::from(pt));
+        }
+        ts
+    }
+}
+
+static MAX_TYPE_COUNT: AtomicUsize = AtomicUsize::new(PokemonTypes::DEFAULT_MAX_COUNT);
+
+impl PokemonTypes {
+    pub const DEFAULT_MAX_COUNT: usize = 2;
+
+    /// The most types a Pokemon can have, `DEFAULT_MAX_COUNT` unless changed
+    /// by `set_max_count`.
+    pub fn max_count() -> usize {
+        MAX_TYPE_COUNT.load(Ordering::Relaxed)
+    }
+
+    /// Meant to be called once at startup, before any type is parsed.
+    pub fn set_max_count(max_count: usize) {
+        MAX_TYPE_COUNT.store(max_count, Ordering::Relaxed);
+    }
+
+    pub fn as_slice(&self) -> &[PokemonType] {
+        &self.0
+    }
+
+    /// Between one and `max_count` known types, none of them repeated.
+    fn bounded(ts: Vec<String>, max_count: usize) -> Result<Self, ()> {
+        if ts.is_empty() || ts.len() > max_count {
+            return Err(());
+        }
+
+        let mut pts = vec![];
+        for t in ts.into_iter() {
+            match PokemonType::try_from(t) {
+                Ok(pt) if !pts.contains(&pt) => pts.push(pt),
+                _ => return Err(()),
+            }
+        }
+        Ok(Self(pts))
+    }
+}
+
+#[cfg(test)]
+impl PokemonTypes {
+    pub fn pikachu() -> Self {
+        Self(vec![PokemonType::Electric])
+    }
+
+    pub fn charmander() -> Self {
+        Self(vec![PokemonType::Fire])
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum PokemonType {
+    Normal,
+    Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl PokemonType {
+    /// The official types, in the order of the games.
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Normal,
+            Self::Fire,
+            Self::Water,
+            Self::Electric,
+            Self::Grass,
+            Self::Ice,
+            Self::Fighting,
+            Self::Poison,
+            Self::Ground,
+            Self::Flying,
+            Self::Psychic,
+            Self::Bug,
+            Self::Rock,
+            Self::Ghost,
+            Self::Dragon,
+            Self::Dark,
+            Self::Steel,
+            Self::Fairy,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Fire => "Fire",
+            Self::Water => "Water",
+            Self::Electric => "Electric",
+            Self::Grass => "Grass",
+            Self::Ice => "Ice",
+            Self::Fighting => "Fighting",
+            Self::Poison => "Poison",
+            Self::Ground => "Ground",
+            Self::Flying => "Flying",
+            Self::Psychic => "Psychic",
+            Self::Bug => "Bug",
+            Self::Rock => "Rock",
+            Self::Ghost => "Ghost",
+            Self::Dragon => "Dragon",
+            Self::Dark => "Dark",
+            Self::Steel => "Steel",
+            Self::Fairy => "Fairy",
+        }
+    }
+
+    pub fn color(&self) -> &'static str {
+        match self {
+            Self::Normal => "#A8A77A",
+            Self::Fire => "#EE8130",
+            Self::Water => "#6390F0",
+            Self::Electric => "#F7D02C",
+            Self::Grass => "#7AC74C",
+            Self::Ice => "#96D9D6",
+            Self::Fighting => "#C22E28",
+            Self::Poison => "#A33EA1",
+            Self::Ground => "#E2BF65",
+            Self::Flying => "#A98FF3",
+            Self::Psychic => "#F95587",
+            Self::Bug => "#A6B91A",
+            Self::Rock => "#B6A136",
+            Self::Ghost => "#735797",
+            Self::Dragon => "#6F35FC",
+            Self::Dark => "#705746",
+            Self::Steel => "#B7B7CE",
+            Self::Fairy => "#D685AD",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Self::Normal => "⚪",
+            Self::Fire => "🔥",
+            Self::Water => "💧",
+            Self::Electric => "⚡",
+            Self::Grass => "🌿",
+            Self::Ice => "❄",
+            Self::Fighting => "🥊",
+            Self::Poison => "🧪",
+            Self::Ground => "⛰",
+            Self::Flying => "🕊",
+            Self::Psychic => "🔮",
+            Self::Bug => "🐛",
+            Self::Rock => "🪨",
+            Self::Ghost => "👻",
+            Self::Dragon => "🐉",
+            Self::Dark => "🌑",
+            Self::Steel => "⚙",
+            Self::Fairy => "✨",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Normal => "Plain fighters with no weakness but Fighting moves.",
+            Self::Fire => "Burning attackers which may leave their foes burned.",
+            Self::Water => "Swimmers which douse Fire and wear down Ground and Rock.",
+            Self::Electric => "Fast attackers which may paralyze their foes.",
+            Self::Grass => "Plants which drain their foes and heal themselves.",
+            Self::Ice => "Frosty attackers which may freeze their foes.",
+            Self::Fighting => "Strong hitters which break through Normal and Rock.",
+            Self::Poison => "Toxic attackers which may poison their foes.",
+            Self::Ground => "Diggers which Electric moves can't touch.",
+            Self::Flying => "Birds and fliers which Ground moves can't touch.",
+            Self::Psychic => "Mind benders which may confuse their foes.",
+            Self::Bug => "Insects which grow fast and swarm their foes.",
+            Self::Rock => "Sturdy defenders which shrug off Fire and Flying moves.",
+            Self::Ghost => "Spirits which Normal and Fighting moves can't touch.",
+            Self::Dragon => "Rare and mighty creatures which resist the elements.",
+            Self::Dark => "Tricksters which Psychic moves can't touch.",
+            Self::Steel => "Armored defenders which resist most types.",
+            Self::Fairy => "Enchanting creatures which Dragon moves can't touch.",
+        }
+    }
+}
+
+impl TryFrom<String> for PokemonType {
+    type Error = ();
+
+    /// Types are matched whatever their case. The only alias is "elec", for
+    /// Electric.
+    fn try_from(t: String) -> Result<Self, Self::Error> {
+        match t.trim().to_lowercase().as_str() {
+            "normal" => Ok(Self::Normal),
+            "fire" => Ok(Self::Fire),
+            "water" => Ok(Self::Water),
+            "electric" | "elec" => Ok(Self::Electric),
+            "grass" => Ok(Self::Grass),
+            "ice" => Ok(Self::Ice),
+            "fighting" => Ok(Self::Fighting),
+            "poison" => Ok(Self::Poison),
+            "ground" => Ok(Self::Ground),
+            "flying" => Ok(Self::Flying),
+            "psychic" => Ok(Self::Psychic),
+            "bug" => Ok(Self::Bug),
+            "rock" => Ok(Self::Rock),
+            "ghost" => Ok(Self::Ghost),
+            "dragon" => Ok(Self::Dragon),
+            "dark" => Ok(Self::Dark),
+            "steel" => Ok(Self::Steel),
+            "fairy" => Ok(Self::Fairy),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<PokemonType> for String {
+    fn from(t: PokemonType) -> Self {
+        String::from(t.as_str())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EvolutionMethod {
+    Level,
+    Item,
+    Trade,
+    Friendship,
+    Other,
+}
+
+impl TryFrom<String> for EvolutionMethod {
+    type Error = ();
+
+    fn try_from(m: String) -> Result<Self, Self::Error> {
+        match m.trim().to_lowercase().as_str() {
+            "level" => Ok(Self::Level),
+            "item" => Ok(Self::Item),
+            "trade" => Ok(Self::Trade),
+            "friendship" => Ok(Self::Friendship),
+            "other" => Ok(Self::Other),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<EvolutionMethod> for String {
+    fn from(m: EvolutionMethod) -> Self {
+        String::from(match m {
+            EvolutionMethod::Level => "level",
+            EvolutionMethod::Item => "item",
+            EvolutionMethod::Trade => "trade",
+            EvolutionMethod::Friendship => "friendship",
+            EvolutionMethod::Other => "other",
+        })
+    }
+}
+
+/// What a Pokemon evolves into, such as Charmander into Charmeleon at level
+/// 16. The level is the lowest one the evolution happens at.
+#[derive(Clone)]
+pub struct Evolution {
+    pub target: PokemonNumber,
+    pub method: EvolutionMethod,
+    pub level: Option<u8>,
+}
+
+impl Evolution {
+    pub const MAX_LEVEL: u8 = 100;
+
+    /// Leveling up needs a level, the other methods may have one.
+    pub fn try_new(target: u16, method: String, level: Option<u8>) -> Result<Self, ()> {
+        match (
+            PokemonNumber::try_from(target),
+            EvolutionMethod::try_from(method),
+            level,
+        ) {
+            (_, _, Some(level)) if level == 0 || level > Self::MAX_LEVEL => Err(()),
+            (_, Ok(EvolutionMethod::Level), None) => Err(()),
+            (Ok(target), Ok(method), level) => Ok(Self {
+                target,
+                method,
+                level,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// An ability, such as Blaze, the hidden ones being those a Pokemon only
+/// gets in special ways.
+#[derive(Clone)]
+pub struct Ability {
+    pub name: String,
+    pub hidden: bool,
+}
+
+impl Ability {
+    pub const MAX_LENGTH: usize = 32;
+
+    /// Names are trimmed, and made of letters, digits, spaces, hyphens and
+    /// apostrophes.
+    pub fn try_new(name: String, hidden: bool) -> Result<Self, ()> {
+        let name = name.trim().replace('’', "'");
+        let length = name.chars().count();
+
+        if length == 0 || length > Self::MAX_LENGTH {
+            return Err(());
+        }
+
+        if name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '\'')
+        {
+            Ok(Self { name, hidden })
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PokemonAbilities(Vec<Ability>);
+
+impl TryFrom<Vec<Ability>> for PokemonAbilities {
+    type Error = ();
+
+    /// Between one and `MAX_COUNT` abilities of different names, one of them
+    /// at most being hidden.
+    fn try_from(abilities: Vec<Ability>) -> Result<Self, Self::Error> {
+        if abilities.is_empty()
+            || abilities.len() > Self::MAX_COUNT
+            || abilities.iter().filter(|a| a.hidden).count() > 1
+        {
+            return Err(());
+        }
+
+        for (index, ability) in abilities.iter().enumerate() {
+            if abilities[..index]
+                .iter()
+                .any(|a| a.name.to_lowercase() == ability.name.to_lowercase())
+            {
+                return Err(());
+            }
+        }
+        Ok(Self(abilities))
+    }
+}
+
+impl PokemonAbilities {
+    pub const MAX_COUNT: usize = 3;
+
+    pub fn as_slice(&self) -> &[Ability] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl PokemonAbilities {
+    pub fn charmander() -> Self {
+        Self(vec![
+            Ability {
+                name: String::from("Blaze"),
+                hidden: false,
+            },
+            Ability {
+                name: String::from("Solar Power"),
+                hidden: true,
+            },
+        ])
+    }
+}
+
+/// A height in meters, kept to the decimeter as the games do.
+#[derive(Clone, Copy, PartialEq)]
+pub struct PokemonHeight(u16);
+
+impl TryFrom<f64> for PokemonHeight {
+    type Error = ();
+
+    /// Rounded to the decimeter, between 0.1 and `MAX` meters.
+    fn try_from(meters: f64) -> Result<Self, Self::Error> {
+        match tenths(meters, Self::MAX) {
+            Some(decimeters) => Ok(Self(decimeters)),
+            None => Err(()),
+        }
+    }
+}
+
+impl From<PokemonHeight> for f64 {
+    fn from(height: PokemonHeight) -> Self {
+        height.meters()
+    }
+}
+
+impl PokemonHeight {
+    /// Eternamax Eternatus, the tallest of all, is 100 meters tall.
+    pub const MAX: f64 = 100.0;
+
+    pub fn meters(&self) -> f64 {
+        f64::from(self.0) / 10.0
+    }
+}
+
+#[cfg(test)]
+impl PokemonHeight {
+    pub fn charmander() -> Self {
+        Self(6)
+    }
+}
+
+/// A weight in kilograms, kept to the hectogram as the games do.
+#[derive(Clone, Copy, PartialEq)]
+pub struct PokemonWeight(u16);
+
+impl TryFrom<f64> for PokemonWeight {
+    type Error = ();
+
+    /// Rounded to the hectogram, between 0.1 and `MAX` kilograms.
+    fn try_from(kilograms: f64) -> Result<Self, Self::Error> {
+        match tenths(kilograms, Self::MAX) {
+            Some(hectograms) => Ok(Self(hectograms)),
+            None => Err(()),
+        }
+    }
+}
+
+impl From<PokemonWeight> for f64 {
+    fn from(weight: PokemonWeight) -> Self {
+        weight.kilograms()
+    }
+}
+
+impl PokemonWeight {
+    /// Celesteela and Cosmoem, the heaviest of all, weigh 999.9 kilograms.
+    pub const MAX: f64 = 1000.0;
+
+    pub fn kilograms(&self) -> f64 {
+        f64::from(self.0) / 10.0
+    }
+}
+
+#[cfg(test)]
+impl PokemonWeight {
+    pub fn charmander() -> Self {
+        Self(85)
+    }
+}
+
+/// The address of an image of the Pokemon, served by another site.
+#[derive(Clone, PartialEq)]
+pub struct PokemonSprite(String);
+
+impl TryFrom<String> for PokemonSprite {
+    type Error = ();
+
+    /// An http or https URL with a host, without spaces, of at most `MAX_LEN`
+    /// bytes. The host must not be the machine itself nor on its private
+    /// network, which the server would otherwise fetch the sprite from.
+    fn try_from(url: String) -> Result<Self, Self::Error> {
+        if !Self::has_public_host(&url)
+            || url.len() > Self::MAX_LEN
+            || url.chars().any(|c| c.is_whitespace() || c.is_control())
+        {
+            return Err(());
+        }
+
+        Ok(Self(url))
+    }
+}
+
+impl From<PokemonSprite> for String {
+    fn from(sprite: PokemonSprite) -> Self {
+        sprite.0
+    }
+}
+
+impl PokemonSprite {
+    pub const MAX_LEN: usize = 2048;
+
+    /// Whether the URL is an http or https one whose host is neither the
+    /// machine itself nor a literal address of its private network.
+    pub fn has_public_host(url: &str) -> bool {
+        let authority = match url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+        {
+            Some(rest) => rest.split(['/', '?', '#']).next().unwrap_or_default(),
+            None => return false,
+        };
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        let host = match host.strip_prefix('[') {
+            Some(rest) => rest.split(']').next().unwrap_or_default(),
+            None => host.split(':').next().unwrap_or_default(),
+        };
+
+        !(host.is_empty()
+            || host.eq_ignore_ascii_case("localhost")
+            || host.to_ascii_lowercase().ends_with(".localhost")
+            || matches!(host.parse::<IpAddr>(), Ok(ip) if !Self::is_public(ip)))
+    }
+
+    /// Whether the address can be reached from anywhere, rather than being
+    /// a loopback, private, link-local or unspecified one.
+    pub fn is_public(ip: IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(ip) => {
+                !(ip.is_loopback()
+                    || ip.is_private()
+                    || ip.is_link_local()
+                    || ip.is_unspecified()
+                    || ip.is_broadcast()
+                    // Shared address space, 100.64.0.0/10.
+                    || (ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64))
+            }
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ip) => Self::is_public(IpAddr::V4(ip)),
+                None => {
+                    !(ip.is_loopback()
+                        || ip.is_unspecified()
+                        || ip.is_unique_local()
+                        || ip.is_unicast_link_local())
+                }
+            },
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl PokemonSprite {
+    pub fn pikachu() -> Self {
+        Self(String::from(
+            "https://img.pokemondb.net/sprites/home/normal/pikachu.png",
+        ))
+    }
+}
+
+/// Whether the Pokemon is a common one, a legendary one or a mythical one,
+/// the latter being only given away at events.
+#[derive(Clone, Copy, PartialEq)]
+pub enum PokemonClass {
+    Normal,
+    Legendary,
+    Mythical,
+}
+
+impl PokemonClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Legendary => "Legendary",
+            Self::Mythical => "Mythical",
+        }
+    }
+}
+
+impl TryFrom<String> for PokemonClass {
+    type Error = ();
+
+    /// Classes are matched whatever their case.
+    fn try_from(c: String) -> Result<Self, Self::Error> {
+        match c.trim().to_lowercase().as_str() {
+            "normal" => Ok(Self::Normal),
+            "legendary" => Ok(Self::Legendary),
+            "mythical" => Ok(Self::Mythical),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<PokemonClass> for String {
+    fn from(c: PokemonClass) -> Self {
+        String::from(c.as_str())
+    }
+}
+
+// The value rounded to the tenth and counted in tenths, unless it rounds to
+// zero or is over `max`. There is no `f64::round` without std.
+fn tenths(value: f64, max: f64) -> Option<u16> {
+    if value.is_nan() || value < 0.05 || value > max {
+        return None;
+    }
+    Some((value * 10.0 + 0.5) as u16)
+}
+
+#[derive(Clone)]
+pub struct Pokemon {
+    pub number: PokemonNumber,
+    pub name: PokemonName,
+    pub types: PokemonTypes,
+    pub evolutions: Vec<Evolution>,
+    /// None until they are set.
+    pub abilities: Option<PokemonAbilities>,
+    /// None until it is set, like the weight.
+    pub height: Option<PokemonHeight>,
+    pub weight: Option<PokemonWeight>,
+    pub sprite: Option<PokemonSprite>,
+    /// Normal until it is set.
+    pub class: PokemonClass,
+}
+
+impl Pokemon {
+    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+        Self {
+            number,
+            name,
+            types,
+            evolutions: vec![],
+            abilities: None,
+            height: None,
+            weight: None,
+            sprite: None,
+            class: PokemonClass::Normal,
+        }
+    }
+
+    pub fn with_evolutions(self, evolutions: Vec<Evolution>) -> Self {
+        Self { evolutions, ..self }
+    }
+
+    pub fn with_abilities(self, abilities: Option<PokemonAbilities>) -> Self {
+        Self { abilities, ..self }
+    }
+
+    pub fn with_measurements(
+        self,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Self {
+        Self {
+            height,
+            weight,
+            ..self
+        }
+    }
+
+    pub fn with_sprite(self, sprite: Option<PokemonSprite>) -> Self {
+        Self { sprite, ..self }
+    }
+
+    pub fn with_class(self, class: PokemonClass) -> Self {
+        Self { class, ..self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_accept_numbers_up_to_generation_ix() {
+        assert!(PokemonNumber::try_from(0).is_err());
+        assert!(PokemonNumber::try_from(1).is_ok());
+        assert!(PokemonNumber::try_from(1025).is_ok());
+        assert!(PokemonNumber::try_from(1026).is_err());
+    }
+
+    #[test]
+    fn it_should_accept_numbers_up_to_a_custom_max() {
+        assert!(PokemonNumber::bounded(1200, 1200).is_ok());
+        assert!(PokemonNumber::bounded(1201, 1200).is_err());
+        assert!(PokemonNumber::bounded(152, 151).is_err());
+    }
+
+    #[test]
+    fn it_should_need_a_level_to_evolve_by_leveling_up() {
+        assert!(Evolution::try_new(5, String::from("level"), Some(16)).is_ok());
+        assert!(Evolution::try_new(5, String::from("Level"), None).is_err());
+        assert!(Evolution::try_new(5, String::from("level"), Some(101)).is_err());
+        assert!(Evolution::try_new(26, String::from("item"), None).is_ok());
+        assert!(Evolution::try_new(26, String::from("stone"), None).is_err());
+        assert!(Evolution::try_new(0, String::from("trade"), None).is_err());
+    }
+
+    #[test]
+    fn it_should_round_the_measurements_to_the_tenth_within_bounds() {
+        assert!(matches!(PokemonHeight::try_from(0.64), Ok(h) if h.meters() == 0.6));
+        assert!(matches!(PokemonHeight::try_from(100.0), Ok(h) if h.meters() == 100.0));
+        assert!(PokemonHeight::try_from(0.04).is_err());
+        assert!(PokemonHeight::try_from(-1.7).is_err());
+        assert!(PokemonHeight::try_from(100.1).is_err());
+        assert!(PokemonHeight::try_from(f64::NAN).is_err());
+        assert!(matches!(PokemonWeight::try_from(8.5), Ok(w) if w.kilograms() == 8.5));
+        assert!(matches!(PokemonWeight::try_from(999.96), Ok(w) if w.kilograms() == 1000.0));
+        assert!(PokemonWeight::try_from(0.0).is_err());
+        assert!(PokemonWeight::try_from(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn it_should_only_know_the_normal_legendary_and_mythical_classes() {
+        assert!(matches!(
+            PokemonClass::try_from(String::from(" legendary ")),
+            Ok(PokemonClass::Legendary)
+        ));
+        assert!(matches!(
+            PokemonClass::try_from(String::from("Mythical")),
+            Ok(PokemonClass::Mythical)
+        ));
+        assert!(PokemonClass::try_from(String::from("Ultra Beast")).is_err());
+        assert_eq!(String::from(PokemonClass::Normal), "Normal");
+    }
+
+    #[test]
+    fn it_should_only_accept_web_addresses_as_sprites() {
+        assert!(PokemonSprite::try_from(String::from("https://example.com/25.png")).is_ok());
+        assert!(PokemonSprite::try_from(String::from("http://example.com:8000/a.gif")).is_ok());
+        assert!(PokemonSprite::try_from(String::from("http://localhost:8000/a.gif")).is_err());
+        assert!(PokemonSprite::try_from(String::from("http://127.0.0.1/a.gif")).is_err());
+        assert!(PokemonSprite::try_from(String::from("http://user@10.0.0.7:80/a.gif")).is_err());
+        assert!(PokemonSprite::try_from(String::from("http://169.254.169.254/latest")).is_err());
+        assert!(PokemonSprite::try_from(String::from("http://[::1]:8000/a.gif")).is_err());
+        assert!(PokemonSprite::try_from(String::from("http://[::ffff:192.168.1.1]/")).is_err());
+        assert!(PokemonSprite::try_from(String::from("https://93.184.216.34/25.png")).is_ok());
+        assert!(PokemonSprite::try_from(String::from("ftp://example.com/25.png")).is_err());
+        assert!(PokemonSprite::try_from(String::from("https:///25.png")).is_err());
+        assert!(PokemonSprite::try_from(String::from("https://example.com/a b.png")).is_err());
+        assert!(PokemonSprite::try_from(alloc::format!(
+            "https://example.com/{}",
+            "a".repeat(PokemonSprite::MAX_LEN)
+        ))
+        .is_err());
+    }
+
+    fn abilities(abilities: &[(&str, bool)]) -> Result<PokemonAbilities, ()> {
+        abilities
+            .iter()
+            .map(|(name, hidden)| Ability::try_new(String::from(*name), *hidden))
+            .collect::<Result<Vec<Ability>, ()>>()
+            .and_then(PokemonAbilities::try_from)
+    }
+
+    #[test]
+    fn it_should_accept_one_to_three_abilities_with_one_hidden_at_most() {
+        assert!(abilities(&[("Blaze", false), ("Solar Power", true)]).is_ok());
+        assert!(abilities(&[]).is_err());
+        assert!(abilities(&[
+            ("Static", false),
+            ("Lightning Rod", true),
+            ("Levitate", true)
+        ])
+        .is_err());
+        assert!(abilities(&[("A", false), ("B", false), ("C", false), ("D", false)]).is_err());
+        assert!(abilities(&[("Blaze", false), ("blaze", true)]).is_err());
+        assert!(abilities(&[("Blaze!", false)]).is_err());
+    }
+
+    fn types(ts: &[&str], max_count: usize) -> Result<Vec<String>, ()> {
+        PokemonTypes::bounded(ts.iter().map(|t| String::from(*t)).collect(), max_count)
+            .map(Vec::<String>::from)
+    }
+
+    #[test]
+    fn it_should_reject_duplicate_types() {
+        assert!(types(&["Fire", "Fire"], 2).is_err());
+        assert!(types(&["Fire", "Electric", "Fire"], 3).is_err());
+    }
+
+    #[test]
+    fn it_should_reject_more_types_than_the_max_count() {
+        assert!(types(&[], 2).is_err());
+        assert_eq!(
+            types(&["Fire", "Electric"], 2),
+            Ok(vec![String::from("Fire"), String::from("Electric")])
+        );
+        assert!(types(&["Fire", "Electric"], 1).is_err());
+    }
+
+    fn pokemon_type(t: &str) -> Result<&'static str, ()> {
+        PokemonType::try_from(String::from(t)).map(|t| t.as_str())
+    }
+
+    #[test]
+    fn it_should_parse_types_whatever_their_case() {
+        assert_eq!(pokemon_type("Fire"), Ok("Fire"));
+        assert_eq!(pokemon_type("fire"), Ok("Fire"));
+        assert_eq!(pokemon_type("FIRE"), Ok("Fire"));
+        assert_eq!(pokemon_type(" eLeCtRiC "), Ok("Electric"));
+    }
+
+    #[test]
+    fn it_should_parse_type_aliases() {
+        assert_eq!(pokemon_type("elec"), Ok("Electric"));
+        assert_eq!(pokemon_type("ELEC"), Ok("Electric"));
+        assert_eq!(pokemon_type("Electrik"), Err(()));
+        assert_eq!(pokemon_type("fyre"), Err(()));
+        assert_eq!(pokemon_type("Plasma"), Err(()));
+    }
+
+    #[test]
+    fn it_should_parse_every_official_type() {
+        for t in PokemonType::all() {
+            assert_eq!(pokemon_type(t.as_str()), Ok(t.as_str()));
+        }
+        assert_eq!(PokemonType::all().len(), 18);
+        assert_eq!(
+            types(&["grass", "Poison"], 2),
+            Ok(vec![String::from("Grass"), String::from("Poison")])
+        );
+    }
+
+    #[test]
+    fn it_should_reject_a_type_repeated_under_an_alias() {
+        assert!(types(&["Electric", "elec"], 2).is_err());
+    }
+
+    #[test]
+    fn it_should_find_the_generation_of_a_number() {
+        let generation = |n| PokemonNumber::bounded(n, 2000).map(|n| n.generation());
+        assert_eq!(generation(1), Ok(Some(1)));
+        assert_eq!(generation(151), Ok(Some(1)));
+        assert_eq!(generation(152), Ok(Some(2)));
+        assert_eq!(generation(898), Ok(Some(8)));
+        assert_eq!(generation(906), Ok(Some(9)));
+        assert_eq!(generation(1025), Ok(Some(9)));
+        assert_eq!(generation(1026), Ok(None));
+    }
+
+    #[test]
+    fn it_should_give_the_numbers_of_a_generation() {
+        assert_eq!(PokemonNumber::generation_range(0), None);
+        assert_eq!(PokemonNumber::generation_range(1), Some(1..=151));
+        assert_eq!(PokemonNumber::generation_range(4), Some(387..=493));
+        assert_eq!(PokemonNumber::generation_range(9), Some(906..=1025));
+        assert_eq!(PokemonNumber::generation_range(10), None);
+    }
+
+    fn name(n: &str) -> Result<String, ()> {
+        PokemonName::try_from(String::from(n)).map(String::from)
+    }
+
+    #[test]
+    fn it_should_reject_an_empty_name() {
+        assert!(name("").is_err());
+        assert!(name("   ").is_err());
+    }
+
+    #[test]
+    fn it_should_trim_whitespace() {
+        assert_eq!(name("  Pikachu \n"), Ok(String::from("Pikachu")));
+    }
+
+    #[test]
+    fn it_should_normalize_to_nfc() {
+        let decomposed = "Flabe\u{301}be\u{301}";
+        assert_eq!(name(decomposed), Ok(String::from("Flab\u{e9}b\u{e9}")));
+        assert_eq!(name(decomposed), name("Flabébé"));
+    }
+
+    #[test]
+    fn it_should_accept_official_punctuation() {
+        for n in [
+            "Farfetch'd",
+            "Mr. Mime",
+            "Porygon-Z",
+            "Type: Null",
+            "Nidoran♀",
+        ] {
+            assert_eq!(name(n), Ok(String::from(n)));
+        }
+    }
+
+    #[test]
+    fn it_should_replace_typographic_apostrophes() {
+        assert_eq!(name("Farfetch’d"), Ok(String::from("Farfetch'd")));
+    }
+
+    #[test]
+    fn it_should_reject_other_symbols() {
+        assert!(name("Pikachu!").is_err());
+        assert!(name("<script>").is_err());
+    }
+
+    #[test]
+    fn it_should_fold_case_and_accents() {
+        assert_eq!(PokemonName::fold("Flabébé"), PokemonName::fold("FLABEBE"));
+    }
+
+    #[test]
+    fn it_should_count_graphemes_rather_than_bytes() {
+        assert!(name("Flabébéflabé").is_ok());
+        assert!(name("Flabe\u{301}be\u{301}flabe\u{301}").is_ok());
+        assert!(name("Flabébéflabéb").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::kernel::pokemon::PokemonNumber;
+use crate::kernel::pokemon::PokemonType::{self, *};
+use alloc::vec::Vec;
+
+/// How effective the moves of a type are against the other types, since
+/// Generation VI. The matchups left out are neutral.
+const MATCHUPS: &[(PokemonType, PokemonType, f32)] = &[
+    (Normal, Rock, 0.5),
+    (Normal, Ghost, 0.0),
+    (Normal, Steel, 0.5),
+    (Fire, Fire, 0.5),
+    (Fire, Water, 0.5),
+    (Fire, Grass, 2.0),
+    (Fire, Ice, 2.0),
+    (Fire, Bug, 2.0),
+    (Fire, Rock, 0.5),
+    (Fire, Dragon, 0.5),
+    (Fire, Steel, 2.0),
+    (Water, Fire, 2.0),
+    (Water, Water, 0.5),
+    (Water, Grass, 0.5),
+    (Water, Ground, 2.0),
+    (Water, Rock, 2.0),
+    (Water, Dragon, 0.5),
+    (Electric, Water, 2.0),
+    (Electric, Electric, 0.5),
+    (Electric, Grass, 0.5),
+    (Electric, Ground, 0.0),
+    (Electric, Flying, 2.0),
+    (Electric, Dragon, 0.5),
+    (Grass, Fire, 0.5),
+    (Grass, Water, 2.0),
+    (Grass, Grass, 0.5),
+    (Grass, Poison, 0.5),
+    (Grass, Ground, 2.0),
+    (Grass, Flying, 0.5),
+    (Grass, Bug, 0.5),
+    (Grass, Rock, 2.0),
+    (Grass, Dragon, 0.5),
+    (Grass, Steel, 0.5),
+    (Ice, Fire, 0.5),
+    (Ice, Water, 0.5),
+    (Ice, Grass, 2.0),
+    (Ice, Ice, 0.5),
+    (Ice, Ground, 2.0),
+    (Ice, Flying, 2.0),
+    (Ice, Dragon, 2.0),
+    (Ice, Steel, 0.5),
+    (Fighting, Normal, 2.0),
+    (Fighting, Ice, 2.0),
+    (Fighting, Poison, 0.5),
+    (Fighting, Flying, 0.5),
+    (Fighting, Psychic, 0.5),
+    (Fighting, Bug, 0.5),
+    (Fighting, Rock, 2.0),
+    (Fighting, Ghost, 0.0),
+    (Fighting, Dark, 2.0),
+    (Fighting, Steel, 2.0),
+    (Fighting, Fairy, 0.5),
+    (Poison, Grass, 2.0),
+    (Poison, Poison, 0.5),
+    (Poison, Ground, 0.5),
+    (Poison, Rock, 0.5),
+    (Poison, Ghost, 0.5),
+    (Poison, Steel, 0.0),
+    (Poison, Fairy, 2.0),
+    (Ground, Fire, 2.0),
+    (Ground, Electric, 2.0),
+    (Ground, Grass, 0.5),
+    (Ground, Poison, 2.0),
+    (Ground, Flying, 0.0),
+    (Ground, Bug, 0.5),
+    (Ground, Rock, 2.0),
+    (Ground, Steel, 2.0),
+    (Flying, Electric, 0.5),
+    (Flying, Grass, 2.0),
+    (Flying, Fighting, 2.0),
+    (Flying, Bug, 2.0),
+    (Flying, Rock, 0.5),
+    (Flying, Steel, 0.5),
+    (Psychic, Fighting, 2.0),
+    (Psychic, Poison, 2.0),
+    (Psychic, Psychic, 0.5),
+    (Psychic, Dark, 0.0),
+    (Psychic, Steel, 0.5),
+    (Bug, Fire, 0.5),
+    (Bug, Grass, 2.0),
+    (Bug, Fighting, 0.5),
+    (Bug, Poison, 0.5),
+    (Bug, Flying, 0.5),
+    (Bug, Psychic, 2.0),
+    (Bug, Ghost, 0.5),
+    (Bug, Dark, 2.0),
+    (Bug, Steel, 0.5),
+    (Bug, Fairy, 0.5),
+    (Rock, Fire, 2.0),
+    (Rock, Ice, 2.0),
+    (Rock, Fighting, 0.5),
+    (Rock, Ground, 0.5),
+    (Rock, Flying, 2.0),
+    (Rock, Bug, 2.0),
+    (Rock, Steel, 0.5),
+    (Ghost, Normal, 0.0),
+    (Ghost, Psychic, 2.0),
+    (Ghost, Ghost, 2.0),
+    (Ghost, Dark, 0.5),
+    (Dragon, Dragon, 2.0),
+    (Dragon, Steel, 0.5),
+    (Dragon, Fairy, 0.0),
+    (Dark, Fighting, 0.5),
+    (Dark, Psychic, 2.0),
+    (Dark, Ghost, 2.0),
+    (Dark, Dark, 0.5),
+    (Dark, Fairy, 0.5),
+    (Steel, Fire, 0.5),
+    (Steel, Water, 0.5),
+    (Steel, Electric, 0.5),
+    (Steel, Ice, 2.0),
+    (Steel, Rock, 2.0),
+    (Steel, Steel, 0.5),
+    (Steel, Fairy, 2.0),
+    (Fairy, Fire, 0.5),
+    (Fairy, Fighting, 2.0),
+    (Fairy, Poison, 0.5),
+    (Fairy, Dragon, 2.0),
+    (Fairy, Dark, 2.0),
+    (Fairy, Steel, 0.5),
+];
+
+/// The matchups which were different up to a generation, the earliest
+/// generation first.
+const CHANGES: &[(u8, PokemonType, PokemonType, f32)] = &[
+    (1, Bug, Poison, 2.0),
+    (1, Poison, Bug, 2.0),
+    (1, Ghost, Psychic, 0.0),
+    (1, Ice, Fire, 1.0),
+    (5, Ghost, Steel, 0.5),
+    (5, Dark, Steel, 0.5),
+];
+
+/// The types which came after Generation I, with the generation adding them.
+const INTRODUCTIONS: &[(PokemonType, u8)] = &[(Dark, 2), (Steel, 2), (Fairy, 6)];
+
+/// The type chart of a generation.
+pub struct TypeChart {
+    generation: u8,
+}
+
+impl TypeChart {
+    pub fn try_new(generation: u8) -> Result<Self, ()> {
+        match PokemonNumber::generation_range(generation) {
+            Some(_) => Ok(Self { generation }),
+            None => Err(()),
+        }
+    }
+
+    pub fn latest() -> Self {
+        Self {
+            generation: PokemonNumber::GENERATIONS,
+        }
+    }
+
+    pub fn generation(&self) -> u8 {
+        self.generation
+    }
+
+    /// The types of the generation, in the order of the games.
+    pub fn types(&self) -> Vec<PokemonType> {
+        PokemonType::all()
+            .into_iter()
+            .filter(|t| self.has(t))
+            .collect()
+    }
+
+    /// The multiplier of the damage a move of the `attacking` type deals to
+    /// a Pokemon of the `defending` type, none when either type doesn't
+    /// exist in the generation.
+    pub fn effectiveness(&self, attacking: &PokemonType, defending: &PokemonType) -> Option<f32> {
+        if !self.has(attacking) || !self.has(defending) {
+            return None;
+        }
+
+        let changed = CHANGES
+            .iter()
+            .find(|(until, a, d, _)| self.generation <= *until && a == attacking && d == defending)
+            .map(|(_, _, _, multiplier)| *multiplier);
+        let current = MATCHUPS
+            .iter()
+            .find(|(a, d, _)| a == attacking && d == defending)
+            .map(|(_, _, multiplier)| *multiplier);
+        Some(changed.or(current).unwrap_or(1.0))
+    }
+
+    /// The multipliers of every defending type multiplied together, for a
+    /// Pokemon having several types.
+    pub fn effectiveness_against(
+        &self,
+        attacking: &PokemonType,
+        defending: &[PokemonType],
+    ) -> Option<f32> {
+        defending.iter().try_fold(1.0, |multiplier, defending| {
+            self.effectiveness(attacking, defending)
+                .map(|effectiveness| multiplier * effectiveness)
+        })
+    }
+
+    /// The types the moves of `t` are super effective against.
+    pub fn strong_against(&self, t: &PokemonType) -> Vec<PokemonType> {
+        self.types()
+            .into_iter()
+            .filter(|defending| self.effectiveness(t, defending) == Some(2.0))
+            .collect()
+    }
+
+    /// The types whose moves are super effective against `t`.
+    pub fn weak_against(&self, t: &PokemonType) -> Vec<PokemonType> {
+        self.types()
+            .into_iter()
+            .filter(|attacking| self.effectiveness(attacking, t) == Some(2.0))
+            .collect()
+    }
+
+    fn has(&self, t: &PokemonType) -> bool {
+        INTRODUCTIONS
+            .iter()
+            .all(|(introduced, generation)| introduced != t || *generation <= self.generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn it_should_refuse_a_generation_which_does_not_exist() {
+        assert!(TypeChart::try_new(0).is_err());
+        assert!(TypeChart::try_new(PokemonNumber::GENERATIONS + 1).is_err());
+    }
+
+    #[test]
+    fn it_should_only_have_the_types_of_the_generation() {
+        assert_eq!(TypeChart::try_new(1).map(|c| c.types().len()), Ok(15));
+        assert_eq!(TypeChart::try_new(5).map(|c| c.types().len()), Ok(17));
+        assert_eq!(TypeChart::latest().types().len(), 18);
+        assert_eq!(
+            TypeChart::try_new(5).map(|c| c.effectiveness(&Dragon, &Fairy)),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn it_should_apply_the_matchups_of_the_generation() {
+        let chart = |generation| match TypeChart::try_new(generation) {
+            Ok(chart) => chart,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(chart(1).effectiveness(&Ghost, &Psychic), Some(0.0));
+        assert_eq!(chart(2).effectiveness(&Ghost, &Psychic), Some(2.0));
+        assert_eq!(chart(5).effectiveness(&Dark, &Steel), Some(0.5));
+        assert_eq!(chart(6).effectiveness(&Dark, &Steel), Some(1.0));
+        assert_eq!(chart(9).effectiveness(&Fire, &Grass), Some(2.0));
+        assert_eq!(chart(9).effectiveness(&Normal, &Ghost), Some(0.0));
+    }
+
+    #[test]
+    fn it_should_multiply_the_matchups_of_several_types() {
+        let chart = TypeChart::latest();
+
+        assert_eq!(
+            chart.effectiveness_against(&Ice, &[Grass, Ground]),
+            Some(4.0)
+        );
+        assert_eq!(
+            chart.effectiveness_against(&Electric, &[Water, Ground]),
+            Some(0.0)
+        );
+        assert!(chart.weak_against(&Fire) == vec![Water, Ground, Rock]);
+    }
+}
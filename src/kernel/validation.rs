@@ -0,0 +1,249 @@
+use crate::kernel::pokemon::{
+    Ability, Evolution, EvolutionMethod, PokemonAbilities, PokemonClass, PokemonHeight,
+    PokemonName, PokemonNumber, PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub fn validate_number(number: u16) -> Result<(), String> {
+    match PokemonNumber::try_from(number) {
+        Ok(_) => Ok(()),
+        _ => Err(format!("must be between 1 and {}", PokemonNumber::max())),
+    }
+}
+
+pub fn validate_name(name: &str) -> Result<(), String> {
+    match PokemonName::try_from(String::from(name)) {
+        Ok(_) => Ok(()),
+        _ if name.trim().is_empty() => Err(String::from("must not be empty")),
+        _ => Err(format!(
+            "must be at most {} letters, digits, spaces or ' . - : ♀ ♂",
+            PokemonName::MAX_LENGTH
+        )),
+    }
+}
+
+pub fn validate_types(types: &[String]) -> Result<(), Vec<String>> {
+    if types.is_empty() {
+        return Err(vec![String::from("must contain at least one type")]);
+    }
+
+    let mut messages = types
+        .iter()
+        .filter(|t| PokemonTypes::try_from(vec![String::from(*t)]).is_err())
+        .map(|t| format!("{} is not a known type", t))
+        .collect::<Vec<String>>();
+
+    let known = types
+        .iter()
+        .filter_map(|t| PokemonType::try_from(String::from(t)).ok())
+        .map(|t| t.as_str())
+        .collect::<Vec<&str>>();
+    for (index, t) in known.iter().enumerate() {
+        if known[..index].contains(t) && !known[index + 1..].contains(t) {
+            messages.push(format!("must not contain {} more than once", t));
+        }
+    }
+
+    match PokemonTypes::max_count() {
+        max_count if types.len() <= max_count => {}
+        1 => messages.push(String::from("must contain a single type")),
+        max_count => messages.push(format!("must contain at most {} types", max_count)),
+    }
+
+    match messages.is_empty() {
+        true => Ok(()),
+        false => Err(messages),
+    }
+}
+
+pub fn validate_height(meters: f64) -> Result<(), String> {
+    match PokemonHeight::try_from(meters) {
+        Ok(_) => Ok(()),
+        _ => Err(format!(
+            "must be between 0.1 and {} meters",
+            PokemonHeight::MAX
+        )),
+    }
+}
+
+pub fn validate_weight(kilograms: f64) -> Result<(), String> {
+    match PokemonWeight::try_from(kilograms) {
+        Ok(_) => Ok(()),
+        _ => Err(format!(
+            "must be between 0.1 and {} kilograms",
+            PokemonWeight::MAX
+        )),
+    }
+}
+
+/// The evolutions are given as their target, method and level.
+pub fn validate_evolutions(
+    number: u16,
+    evolutions: &[(u16, &str, Option<u8>)],
+) -> Result<(), Vec<String>> {
+    let mut messages = vec![];
+    for (index, (target, method, level)) in evolutions.iter().enumerate() {
+        let target_is_valid = PokemonNumber::try_from(*target).is_ok();
+        match EvolutionMethod::try_from(String::from(*method)) {
+            Err(()) => messages.push(format!("{} is not a known evolution method", method)),
+            Ok(_) if !target_is_valid => {
+                messages.push(format!("#{} is not a Pokemon number", target))
+            }
+            Ok(method) => match (method, level) {
+                (_, Some(level)) if *level == 0 || *level > Evolution::MAX_LEVEL => {
+                    messages.push(format!(
+                        "#{} must evolve at a level between 1 and {}",
+                        target,
+                        Evolution::MAX_LEVEL
+                    ))
+                }
+                (EvolutionMethod::Level, None) => {
+                    messages.push(format!("#{} must evolve at a given level", target))
+                }
+                _ => {}
+            },
+        }
+
+        if *target == number {
+            messages.push(String::from("must not contain the Pokemon itself"));
+        } else if evolutions[..index].iter().any(|(t, _, _)| t == target)
+            && !evolutions[index + 1..].iter().any(|(t, _, _)| t == target)
+        {
+            messages.push(format!("must not contain #{} more than once", target));
+        }
+    }
+
+    match messages.is_empty() {
+        true => Ok(()),
+        false => Err(messages),
+    }
+}
+
+/// The abilities are given as their name and whether they are hidden.
+pub fn validate_abilities(abilities: &[(&str, bool)]) -> Result<(), Vec<String>> {
+    let mut messages = abilities
+        .iter()
+        .filter(|(name, hidden)| Ability::try_new(String::from(*name), *hidden).is_err())
+        .map(|(name, _)| {
+            format!(
+                "{} must be at most {} letters, digits, spaces or ' -",
+                name,
+                Ability::MAX_LENGTH
+            )
+        })
+        .collect::<Vec<String>>();
+
+    let names = abilities
+        .iter()
+        .map(|(name, _)| name.trim().to_lowercase())
+        .collect::<Vec<String>>();
+    for (index, name) in names.iter().enumerate() {
+        if names[..index].contains(name) && !names[index + 1..].contains(name) {
+            messages.push(format!(
+                "must not contain {} more than once",
+                abilities[index].0.trim()
+            ));
+        }
+    }
+
+    if abilities.len() > PokemonAbilities::MAX_COUNT {
+        messages.push(format!(
+            "must contain at most {} abilities",
+            PokemonAbilities::MAX_COUNT
+        ));
+    }
+    if abilities.iter().filter(|(_, hidden)| *hidden).count() > 1 {
+        messages.push(String::from("must contain at most one hidden ability"));
+    }
+
+    match messages.is_empty() {
+        true => Ok(()),
+        false => Err(messages),
+    }
+}
+
+pub fn validate_sprite(url: &str) -> Result<(), String> {
+    match PokemonSprite::try_from(String::from(url)) {
+        Ok(_) => Ok(()),
+        _ => Err(format!(
+            "must be an http or https URL of a public host, of at most {} bytes",
+            PokemonSprite::MAX_LEN
+        )),
+    }
+}
+
+pub fn validate_class(class: &str) -> Result<(), String> {
+    match PokemonClass::try_from(String::from(class)) {
+        Ok(_) => Ok(()),
+        _ => Err(String::from("must be Normal, Legendary or Mythical")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_return_a_message_when_a_single_field_is_invalid() {
+        assert_eq!(
+            validate_number(u16::from(PokemonNumber::bad())),
+            Err(String::from("must be between 1 and 1025"))
+        );
+        assert_eq!(
+            validate_name(&String::from(PokemonName::bad())),
+            Err(String::from("must not be empty"))
+        );
+        assert_eq!(
+            validate_types(&[]),
+            Err(vec![String::from("must contain at least one type")])
+        );
+        assert_eq!(
+            validate_weight(-8.5),
+            Err(String::from("must be between 0.1 and 1000 kilograms"))
+        );
+    }
+
+    #[test]
+    fn it_should_report_every_invalid_evolution_and_ability() {
+        assert_eq!(
+            validate_evolutions(
+                4,
+                &[(5, "level", None), (4, "trade", None), (5, "swim", None)]
+            ),
+            Err(vec![
+                String::from("#5 must evolve at a given level"),
+                String::from("must not contain the Pokemon itself"),
+                String::from("swim is not a known evolution method"),
+                String::from("must not contain #5 more than once"),
+            ])
+        );
+        assert_eq!(
+            validate_abilities(&[("Blaze", true), ("Bl@ze", false), ("blaze", true)]),
+            Err(vec![
+                String::from("Bl@ze must be at most 32 letters, digits, spaces or ' -"),
+                String::from("must not contain blaze more than once"),
+                String::from("must contain at most one hidden ability"),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_report_repeated_types_and_too_many_types() {
+        let types = vec![
+            String::from("Fire"),
+            String::from("fire"),
+            String::from("FIRE"),
+        ];
+
+        assert_eq!(
+            validate_types(&types),
+            Err(vec![
+                String::from("must not contain Fire more than once"),
+                String::from("must contain at most 2 types"),
+            ])
+        );
+    }
+}
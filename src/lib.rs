@@ -0,0 +1,64 @@
+// The library surface keeps the repo conventions: value objects and
+// constructors return `Result<_, ()>` and repositories expose `new`.
+#![allow(clippy::new_without_default, clippy::result_unit_err)]
+// Without `std`, only the kernel is built, for the Pokedex hardware running
+// on microcontrollers.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "server")]
+#[macro_use]
+extern crate rouille;
+#[cfg(feature = "std")]
+extern crate serde;
+
+#[cfg(feature = "server")]
+pub mod api;
+#[cfg(feature = "std")]
+pub mod cancellation;
+#[cfg(feature = "server")]
+pub mod cli;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "server")]
+mod clock;
+#[cfg(feature = "server")]
+pub mod daemon;
+#[cfg(feature = "server")]
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod domain;
+#[cfg(feature = "nats")]
+pub mod event_sink;
+#[cfg(feature = "server")]
+pub mod export;
+pub mod kernel;
+#[cfg(feature = "server")]
+pub mod mirror;
+#[cfg(feature = "server")]
+pub mod notifiers;
+#[cfg(feature = "server")]
+pub mod outbox;
+#[cfg(feature = "server")]
+pub mod pokeapi;
+#[cfg(feature = "server")]
+pub mod replication;
+#[cfg(feature = "std")]
+pub mod repositories;
+#[cfg(feature = "server")]
+pub mod retention;
+#[cfg(feature = "server")]
+pub mod scripting;
+#[cfg(feature = "server")]
+pub mod service;
+#[cfg(feature = "server")]
+pub mod sprite;
+#[cfg(feature = "server")]
+pub mod systemd;
+#[cfg(feature = "server")]
+pub mod trace;
+#[cfg(feature = "std")]
+pub mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm;
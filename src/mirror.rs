@@ -0,0 +1,437 @@
+use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonType, PokemonTypes};
+use crate::repositories::pokemon::{
+    ClearError, DeleteByTypeError, DeleteError, InsertError, Query, Repository, UpdateError,
+};
+use crate::repositories::write_queue::{self, Write};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub struct Report {
+    pub copied: u32,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// A local record that did not match the remote backend. The remote backend
+/// is the source of truth, so the local record has been overwritten.
+pub enum Conflict {
+    Changed(u16),
+    Missing(u16),
+}
+
+pub struct Replay {
+    pub replayed: u32,
+    /// The writes the remote backend refused, which were dropped.
+    pub rejected: Vec<(Write, &'static str)>,
+}
+
+enum Failure {
+    Refused(&'static str),
+    Unknown,
+}
+
+/// Copies every Pokemon of `remote` into `local` once, then again every
+/// `interval` in the background, logging the records that had diverged.
+/// The writes waiting in `queue` are replayed first.
+pub fn spawn(
+    remote: Arc<dyn Repository>,
+    local: Arc<dyn Repository>,
+    queue: Option<Arc<dyn write_queue::Repository>>,
+    interval: Duration,
+) {
+    run(remote.clone(), local.clone(), queue.clone());
+
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        run(remote.clone(), local.clone(), queue.clone());
+    });
+}
+
+/// The local copy already has the queued writes, and would lose them if
+/// copied from the remote backend before they are replayed.
+fn run(
+    remote: Arc<dyn Repository>,
+    local: Arc<dyn Repository>,
+    queue: Option<Arc<dyn write_queue::Repository>>,
+) {
+    if let Some(queue) = queue {
+        report_replay(replay(remote.clone(), queue.clone()));
+        match queue.depth() {
+            Ok(0) => {}
+            Ok(depth) => {
+                eprintln!(
+                    "Mirror sync postponed, {} writes wait for the remote backend",
+                    depth
+                );
+                return;
+            }
+            _ => return,
+        }
+    }
+
+    report(sync(remote, local));
+}
+
+/// Sends the queued writes to `remote` in order, until it fails to answer.
+pub fn replay(
+    remote: Arc<dyn Repository>,
+    queue: Arc<dyn write_queue::Repository>,
+) -> Result<Replay, ()> {
+    let mut replay = Replay {
+        replayed: 0,
+        rejected: vec![],
+    };
+
+    loop {
+        let (id, write) = match queue.peek() {
+            Ok(Some(queued)) => queued,
+            Ok(None) => return Ok(replay),
+            _ => return Err(()),
+        };
+
+        match apply(remote.as_ref(), write.clone()) {
+            Ok(()) => replay.replayed += 1,
+            Err(Failure::Refused(reason)) => replay.rejected.push((write, reason)),
+            // A write failing while the backend answers would otherwise
+            // hold back the ones behind it forever.
+            Err(Failure::Unknown) if remote.count().is_ok() => {
+                replay.rejected.push((write, "the remote backend failed"))
+            }
+            Err(Failure::Unknown) => return Ok(replay),
+        }
+
+        if queue.remove(id).is_err() {
+            return Err(());
+        }
+    }
+}
+
+fn apply(remote: &dyn Repository, write: Write) -> Result<(), Failure> {
+    match write {
+        Write::Insert {
+            number,
+            name,
+            types,
+        } => match (
+            PokemonNumber::try_from(number),
+            PokemonName::try_from(name),
+            PokemonTypes::try_from(types),
+        ) {
+            (Ok(number), Ok(name), Ok(types)) => match remote.insert(number, name, types) {
+                Ok(_) => Ok(()),
+                Err(InsertError::Conflict) => Err(Failure::Refused("it already exists")),
+                Err(InsertError::Unknown) => Err(Failure::Unknown),
+            },
+            _ => Err(Failure::Refused("it is invalid")),
+        },
+        Write::UpdateTypes { number, types } => {
+            match (
+                PokemonNumber::try_from(number),
+                PokemonTypes::try_from(types),
+            ) {
+                (Ok(number), Ok(types)) => match remote.update_types(number, types) {
+                    Ok(_) => Ok(()),
+                    Err(UpdateError::NotFound) => Err(Failure::Refused("it does not exist")),
+                    Err(UpdateError::Unknown) => Err(Failure::Unknown),
+                },
+                _ => Err(Failure::Refused("it is invalid")),
+            }
+        }
+        Write::Update {
+            number,
+            name,
+            types,
+        } => match (
+            PokemonNumber::try_from(number),
+            PokemonName::try_from(name),
+            PokemonTypes::try_from(types),
+        ) {
+            (Ok(number), Ok(name), Ok(types)) => match remote.update(number, name, types) {
+                Ok(_) => Ok(()),
+                Err(UpdateError::NotFound) => Err(Failure::Refused("it does not exist")),
+                Err(UpdateError::Unknown) => Err(Failure::Unknown),
+            },
+            _ => Err(Failure::Refused("it is invalid")),
+        },
+        Write::Delete { number } => match PokemonNumber::try_from(number) {
+            Ok(number) => match remote.delete(number) {
+                Ok(()) | Err(DeleteError::NotFound) => Ok(()),
+                Err(DeleteError::Unknown) => Err(Failure::Unknown),
+            },
+            _ => Err(Failure::Refused("it is invalid")),
+        },
+        Write::DeleteByType { pokemon_type } => match PokemonType::try_from(pokemon_type) {
+            Ok(pokemon_type) => match remote.delete_by_type(pokemon_type) {
+                Ok(_) => Ok(()),
+                Err(DeleteByTypeError::Unknown) => Err(Failure::Unknown),
+            },
+            _ => Err(Failure::Refused("it is invalid")),
+        },
+        Write::Clear => match remote.clear() {
+            Ok(_) => Ok(()),
+            Err(ClearError::Unknown) => Err(Failure::Unknown),
+        },
+    }
+}
+
+/// Makes `local` an exact copy of `remote`.
+pub fn sync(remote: Arc<dyn Repository>, local: Arc<dyn Repository>) -> Result<Report, ()> {
+    let (remote_pokemons, local_pokemons) = match (
+        remote.fetch_all(Query::new()),
+        local.fetch_all(Query::new()),
+    ) {
+        (Ok(remote_pokemons), Ok(local_pokemons)) => (remote_pokemons, local_pokemons),
+        _ => return Err(()),
+    };
+
+    let mut report = Report {
+        copied: 0,
+        conflicts: vec![],
+    };
+
+    for pokemon in remote_pokemons.iter() {
+        match local_pokemons.iter().find(|p| p.number == pokemon.number) {
+            Some(local_pokemon) if same(local_pokemon, pokemon) => continue,
+            Some(_) => {
+                report
+                    .conflicts
+                    .push(Conflict::Changed(pokemon.number.value()));
+                local.delete(pokemon.number.clone()).ok();
+            }
+            None => {}
+        }
+
+        match local.insert(
+            pokemon.number.clone(),
+            pokemon.name.clone(),
+            pokemon.types.clone(),
+        ) {
+            Ok(_) => report.copied += 1,
+            _ => return Err(()),
+        }
+    }
+
+    for pokemon in local_pokemons.iter() {
+        if !remote_pokemons.iter().any(|p| p.number == pokemon.number) {
+            report
+                .conflicts
+                .push(Conflict::Missing(pokemon.number.value()));
+            local.delete(pokemon.number.clone()).ok();
+        }
+    }
+
+    Ok(report)
+}
+
+fn same(a: &Pokemon, b: &Pokemon) -> bool {
+    let types = |p: &Pokemon| {
+        p.types
+            .as_slice()
+            .iter()
+            .map(PokemonType::as_str)
+            .collect::<Vec<&str>>()
+    };
+    a.name.as_str() == b.name.as_str() && types(a) == types(b)
+}
+
+fn report(report: Result<Report, ()>) {
+    let report = match report {
+        Ok(report) => report,
+        _ => {
+            eprintln!("Mirror sync failed, reads are served from the last copy");
+            return;
+        }
+    };
+
+    for conflict in report.conflicts {
+        match conflict {
+            Conflict::Changed(number) => eprintln!(
+                "Mirror conflict: Pokemon {} differed from the remote backend, local copy replaced",
+                number
+            ),
+            Conflict::Missing(number) => eprintln!(
+                "Mirror conflict: Pokemon {} no longer exists on the remote backend, local copy removed",
+                number
+            ),
+        }
+    }
+}
+
+fn report_replay(replay: Result<Replay, ()>) {
+    let replay = match replay {
+        Ok(replay) => replay,
+        _ => {
+            eprintln!("Replaying the queued writes failed");
+            return;
+        }
+    };
+
+    if replay.replayed > 0 {
+        eprintln!("Replayed {} queued writes", replay.replayed);
+    }
+    for (write, reason) in replay.rejected {
+        eprintln!("Dropped the queued write '{}': {}", write, reason);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::{InMemoryRepository, MirroredRepository};
+    use crate::repositories::write_queue::Repository as WriteQueueRepository;
+
+    #[test]
+    fn it_should_copy_the_remote_pokemons_and_report_the_local_divergences() {
+        let remote = Arc::new(InMemoryRepository::new());
+        let local = Arc::new(InMemoryRepository::new());
+        remote
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+            .ok();
+        local
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::charmander(),
+            )
+            .ok();
+        local
+            .insert(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            )
+            .ok();
+
+        let res = sync(remote, local.clone());
+
+        match res {
+            Ok(report) => {
+                assert_eq!(report.copied, 1);
+                match report.conflicts[..] {
+                    [Conflict::Changed(25), Conflict::Missing(4)] => {}
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        };
+        match local.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => assert_eq!(
+                pokemon.types.as_slice()[0].as_str(),
+                PokemonTypes::pikachu().as_slice()[0].as_str()
+            ),
+            _ => unreachable!(),
+        };
+        assert!(local.fetch_one(PokemonNumber::charmander()).is_err());
+    }
+
+    #[test]
+    fn it_should_not_report_anything_when_the_copies_match() {
+        let remote = Arc::new(InMemoryRepository::new());
+        let local = Arc::new(InMemoryRepository::new());
+        for repo in [remote.clone(), local.clone()] {
+            repo.insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+            .ok();
+        }
+
+        let res = sync(remote, local);
+
+        match res {
+            Ok(report) => {
+                assert_eq!(report.copied, 0);
+                assert!(report.conflicts.is_empty());
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_queue_the_writes_while_the_remote_is_down_and_replay_them_in_order() {
+        let local = Arc::new(InMemoryRepository::new());
+        let queue = Arc::new(write_queue::InMemoryRepository::new());
+        let repo = MirroredRepository::new(
+            Arc::new(InMemoryRepository::new().with_error()),
+            local.clone(),
+        )
+        .with_queue(queue.clone());
+
+        let inserted = repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        );
+        let updated = repo.update_types(PokemonNumber::pikachu(), PokemonTypes::charmander());
+
+        assert!(inserted.is_ok() && updated.is_ok());
+        assert!(matches!(queue.depth(), Ok(2)));
+
+        let remote = Arc::new(InMemoryRepository::new());
+        match replay(remote.clone(), queue.clone()) {
+            Ok(replay) => {
+                assert_eq!(replay.replayed, 2);
+                assert!(replay.rejected.is_empty());
+            }
+            _ => unreachable!(),
+        };
+        assert!(matches!(queue.depth(), Ok(0)));
+        for repo in [remote, local] {
+            match repo.fetch_one(PokemonNumber::pikachu()) {
+                Ok(pokemon) => assert_eq!(pokemon.types.as_slice()[0].as_str(), "Fire"),
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_drop_the_queued_writes_the_remote_refuses() {
+        let remote = Arc::new(InMemoryRepository::new());
+        remote
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+            .ok();
+        let queue = Arc::new(write_queue::InMemoryRepository::new());
+        queue
+            .push(Write::Insert {
+                number: 25,
+                name: String::from("Pikachu"),
+                types: vec![String::from("Electric")],
+            })
+            .ok();
+
+        let res = replay(remote, queue.clone());
+
+        match res {
+            Ok(replay) => {
+                assert_eq!(replay.replayed, 0);
+                assert_eq!(replay.rejected.len(), 1);
+            }
+            _ => unreachable!(),
+        };
+        assert!(matches!(queue.depth(), Ok(0)));
+    }
+
+    #[test]
+    fn it_should_keep_the_queued_writes_while_the_remote_is_down() {
+        let queue = Arc::new(write_queue::InMemoryRepository::new());
+        queue.push(Write::Delete { number: 25 }).ok();
+
+        let res = replay(
+            Arc::new(InMemoryRepository::new().with_error()),
+            queue.clone(),
+        );
+
+        assert!(matches!(res, Ok(Replay { replayed: 0, .. })));
+        assert!(matches!(queue.depth(), Ok(1)));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::domain::entities::WebhookEvent;
+use crate::notifiers::Subscriber;
+use lettre::message::Mailbox;
+use lettre::{Message, SmtpTransport, Transport};
+use std::thread;
+
+const DEFAULT_TEMPLATE: &str = "[Pokedex] {event} #{number}
+The Pokedex received a {event} event for Pokemon #{number}.
+
+{data}
+";
+
+/// Emails the recipients when one of the chosen events happens. The first
+/// line of the template is the subject and the rest the body; `{event}`,
+/// `{data}` and the top-level fields of the event, such as `{number}`, are
+/// replaced.
+pub struct EmailNotifier {
+    transport: SmtpTransport,
+    from: Mailbox,
+    recipients: Vec<Mailbox>,
+    events: Vec<WebhookEvent>,
+    template: String,
+}
+
+impl EmailNotifier {
+    pub fn try_new(
+        smtp_url: &str,
+        from: &str,
+        recipients: Vec<&str>,
+        events: Vec<&str>,
+        template: Option<String>,
+    ) -> Result<Self, ()> {
+        let transport = match SmtpTransport::from_url(smtp_url) {
+            Ok(builder) => builder.build(),
+            _ => return Err(()),
+        };
+        let from = match from.parse::<Mailbox>() {
+            Ok(from) => from,
+            _ => return Err(()),
+        };
+        let recipients = match recipients
+            .into_iter()
+            .map(|r| r.parse::<Mailbox>())
+            .collect::<Result<Vec<Mailbox>, _>>()
+        {
+            Ok(recipients) if !recipients.is_empty() => recipients,
+            _ => return Err(()),
+        };
+        let events = match events
+            .into_iter()
+            .map(|e| WebhookEvent::try_from(String::from(e)))
+            .collect::<Result<Vec<WebhookEvent>, ()>>()
+        {
+            Ok(events) => events,
+            _ => return Err(()),
+        };
+
+        Ok(Self {
+            transport,
+            from,
+            recipients,
+            events,
+            template: template.unwrap_or_else(|| String::from(DEFAULT_TEMPLATE)),
+        })
+    }
+
+    fn render(&self, event: WebhookEvent, data: &serde_json::Value) -> (String, String) {
+        let mut text = self
+            .template
+            .replace("{event}", &String::from(event))
+            .replace(
+                "{data}",
+                &serde_json::to_string_pretty(data).unwrap_or_default(),
+            );
+        if let Some(fields) = data.as_object() {
+            for (key, value) in fields {
+                let value = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    value => value.to_string(),
+                };
+                text = text.replace(&format!("{{{}}}", key), &value);
+            }
+        }
+
+        match text.split_once('\n') {
+            Some((subject, body)) => (String::from(subject), String::from(body.trim_start())),
+            None => (text, String::new()),
+        }
+    }
+
+    /// The email of the event, if it is one of the chosen events.
+    fn message(&self, event: WebhookEvent, data: &serde_json::Value) -> Option<Message> {
+        if !self.events.contains(&event) {
+            return None;
+        }
+
+        let (subject, body) = self.render(event, data);
+        let mut builder = Message::builder().from(self.from.clone()).subject(subject);
+        for recipient in self.recipients.iter() {
+            builder = builder.to(recipient.clone());
+        }
+        builder.body(body).ok()
+    }
+}
+
+impl Subscriber for EmailNotifier {
+    fn receive(&self, event: WebhookEvent, data: &serde_json::Value) {
+        let message = match self.message(event, data) {
+            Some(message) => message,
+            None => return,
+        };
+
+        let transport = self.transport.clone();
+        thread::spawn(move || send(&transport, &message));
+    }
+
+    fn receive_and_wait(&self, event: WebhookEvent, data: &serde_json::Value) {
+        if let Some(message) = self.message(event, data) {
+            send(&self.transport, &message);
+        }
+    }
+}
+
+fn send(transport: &SmtpTransport, message: &Message) {
+    if transport.send(message).is_err() {
+        eprintln!("An error occurred while sending an email notification");
+    }
+}
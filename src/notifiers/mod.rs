@@ -0,0 +1,46 @@
+pub mod email;
+pub mod webhook;
+
+use crate::domain::entities::WebhookEvent;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Something told about every event of the api, such as a webhook or an
+/// email channel. Receiving must not block the request being served.
+pub trait Subscriber: Send + Sync {
+    fn receive(&self, event: WebhookEvent, data: &serde_json::Value);
+
+    /// Like `receive`, but returns once the event is delivered, for the
+    /// outbox which forgets the event right after.
+    fn receive_and_wait(&self, event: WebhookEvent, data: &serde_json::Value) {
+        self.receive(event, data);
+    }
+}
+
+/// Fans every event out to all the subscribers.
+pub struct EventBus {
+    subscribers: Vec<Arc<dyn Subscriber>>,
+}
+
+impl EventBus {
+    pub fn new(subscribers: Vec<Arc<dyn Subscriber>>) -> Self {
+        Self { subscribers }
+    }
+
+    pub fn notify<T: Serialize>(&self, event: WebhookEvent, data: &T) {
+        let data = match serde_json::to_value(data) {
+            Ok(data) => data,
+            _ => return,
+        };
+
+        for subscriber in self.subscribers.iter() {
+            subscriber.receive(event, &data);
+        }
+    }
+
+    pub fn notify_and_wait(&self, event: WebhookEvent, data: &serde_json::Value) {
+        for subscriber in self.subscribers.iter() {
+            subscriber.receive_and_wait(event, data);
+        }
+    }
+}
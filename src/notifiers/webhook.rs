@@ -0,0 +1,205 @@
+use crate::clock::now;
+use crate::domain::entities::{Delivery, Webhook, WebhookEvent};
+use crate::notifiers::Subscriber;
+use crate::repositories::webhook::Repository;
+use crate::sprite;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const MAX_ATTEMPTS: u32 = 5;
+/// How many deliveries run at once, whatever the number of events.
+const WORKERS: usize = 4;
+/// How many deliveries wait for a worker before the new ones are dropped.
+const QUEUE_LEN: usize = 1000;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize)]
+struct Payload<'a, T: Serialize> {
+    event: String,
+    data: &'a T,
+}
+
+/// An event to post to a webhook, and who to tell once it's done.
+struct Job {
+    webhook: Webhook,
+    event: WebhookEvent,
+    body: String,
+    done: Option<SyncSender<()>>,
+}
+
+/// Posts the events to the webhooks from a fixed pool of workers, so that a
+/// burst of events or a slow webhook can't start threads without bound.
+pub struct WebhookNotifier {
+    repo: Arc<dyn Repository>,
+    jobs: SyncSender<Job>,
+}
+
+impl WebhookNotifier {
+    pub fn new(repo: Arc<dyn Repository>) -> Self {
+        let (jobs, queue) = mpsc::sync_channel(QUEUE_LEN);
+        let queue = Arc::new(Mutex::new(queue));
+        for _ in 0..WORKERS {
+            let repo = repo.clone();
+            let queue = queue.clone();
+            thread::spawn(move || work(repo, queue));
+        }
+
+        Self { repo, jobs }
+    }
+
+    /// Queues the deliveries, dropping them when the queue is full.
+    pub fn notify<T: Serialize>(&self, event: WebhookEvent, data: &T) {
+        for (webhook, body) in self.prepare(event, data) {
+            let job = Job {
+                webhook,
+                event,
+                body,
+                done: None,
+            };
+            if let Err(TrySendError::Full(job)) = self.jobs.try_send(job) {
+                eprintln!(
+                    "The {} event could not be queued for webhook #{}",
+                    String::from(event),
+                    u32::from(job.webhook.id)
+                );
+            }
+        }
+    }
+
+    /// Like `notify`, but waits for room in the queue and returns once every
+    /// delivery succeeded or ran out of attempts, for callers that exit right
+    /// after.
+    pub fn notify_and_wait<T: Serialize>(&self, event: WebhookEvent, data: &T) {
+        let webhooks = self.prepare(event, data);
+        let (done, finished) = mpsc::sync_channel(webhooks.len());
+        let mut queued = 0;
+
+        for (webhook, body) in webhooks {
+            let job = Job {
+                webhook,
+                event,
+                body,
+                done: Some(done.clone()),
+            };
+            if self.jobs.send(job).is_ok() {
+                queued += 1;
+            }
+        }
+
+        for _ in 0..queued {
+            if finished.recv().is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Every webhook, with the body to post to it.
+    fn prepare<T: Serialize>(&self, event: WebhookEvent, data: &T) -> Vec<(Webhook, String)> {
+        let webhooks = match self.repo.fetch_all() {
+            Ok(webhooks) => webhooks,
+            _ => return vec![],
+        };
+
+        let body = match serde_json::to_string(&Payload {
+            event: String::from(event),
+            data,
+        }) {
+            Ok(body) => body,
+            _ => return vec![],
+        };
+
+        webhooks
+            .into_iter()
+            .map(|webhook| (webhook, body.clone()))
+            .collect()
+    }
+}
+
+/// Delivers the queued jobs one after the other, until the notifier is gone.
+fn work(repo: Arc<dyn Repository>, queue: Arc<Mutex<Receiver<Job>>>) {
+    loop {
+        let job = match queue.lock() {
+            Ok(queue) => match queue.recv() {
+                Ok(job) => job,
+                _ => return,
+            },
+            _ => return,
+        };
+
+        deliver(repo.clone(), job.webhook, job.event, job.body);
+        if let Some(done) = job.done {
+            done.send(()).ok();
+        }
+    }
+}
+
+impl Subscriber for WebhookNotifier {
+    fn receive(&self, event: WebhookEvent, data: &serde_json::Value) {
+        self.notify(event, data);
+    }
+
+    fn receive_and_wait(&self, event: WebhookEvent, data: &serde_json::Value) {
+        self.notify_and_wait(event, data);
+    }
+}
+
+/// Posts the body to the webhook, retrying with an exponential backoff until
+/// it answers with a 2xx status, and logs every attempt.
+fn deliver(repo: Arc<dyn Repository>, webhook: Webhook, event: WebhookEvent, body: String) {
+    let url = String::from(webhook.url);
+    let signature = match sign(&String::from(webhook.secret), &body) {
+        Ok(signature) => signature,
+        _ => return,
+    };
+
+    // Only public addresses are connected to, whatever the host resolves to.
+    let agent = ureq::AgentBuilder::new()
+        .timeout(TIMEOUT)
+        .resolver(sprite::resolve)
+        .build();
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let status_code = match agent
+            .post(&url)
+            .set("Content-Type", "application/json")
+            .set("X-Pokedex-Event", &String::from(event))
+            .set("X-Pokedex-Signature", &format!("sha256={}", signature))
+            .send_string(&body)
+        {
+            Ok(res) => Some(res.status()),
+            Err(ureq::Error::Status(status_code, _)) => Some(status_code),
+            _ => None,
+        };
+
+        repo.insert_delivery(Delivery {
+            webhook_id: webhook.id,
+            event,
+            attempt,
+            status_code,
+            timestamp: now(),
+        })
+        .ok();
+
+        if let Some(200..=299) = status_code {
+            return;
+        }
+
+        if attempt < MAX_ATTEMPTS {
+            thread::sleep(Duration::from_secs(1 << (attempt - 1)));
+        }
+    }
+}
+
+fn sign(secret: &str, body: &str) -> Result<String, ()> {
+    let mut mac = match Hmac::<Sha256>::new_from_slice(secret.as_bytes()) {
+        Ok(mac) => mac,
+        _ => return Err(()),
+    };
+    mac.update(body.as_bytes());
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
@@ -0,0 +1,95 @@
+use crate::notifiers::EventBus;
+use crate::repositories::outbox::Repository;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const BATCH: u32 = 100;
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delivers the events left in the outbox by a previous run, then the new
+/// ones as they are written, in the background.
+pub fn spawn(outbox: Arc<dyn Repository>, events: Arc<EventBus>) {
+    thread::spawn(move || loop {
+        if dispatch(outbox.as_ref(), &events).is_err() {
+            eprintln!("The outbox can't be read, it will be retried");
+        }
+        thread::sleep(INTERVAL);
+    });
+}
+
+/// Tells every subscriber about the pending events, oldest first, and
+/// removes each once they got it. An event is delivered again when the
+/// process stops before removing it, never lost. Tells how many were
+/// delivered.
+pub fn dispatch(outbox: &dyn Repository, events: &EventBus) -> Result<u32, ()> {
+    let mut delivered = 0;
+
+    loop {
+        let messages = match outbox.fetch_pending(BATCH) {
+            Ok(messages) => messages,
+            _ => return Err(()),
+        };
+
+        if messages.is_empty() {
+            return Ok(delivered);
+        }
+
+        for message in messages {
+            events.notify_and_wait(message.event, &message.data);
+            if outbox.remove(message.id).is_err() {
+                return Err(());
+            }
+            delivered += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonNumber, WebhookEvent};
+    use crate::notifiers::Subscriber;
+    use crate::repositories::outbox::{deleted_data, InMemoryRepository};
+    use std::sync::Mutex;
+
+    struct Recorder(Mutex<Vec<(WebhookEvent, serde_json::Value)>>);
+
+    impl Subscriber for Recorder {
+        fn receive(&self, event: WebhookEvent, data: &serde_json::Value) {
+            if let Ok(mut events) = self.0.lock() {
+                events.push((event, data.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_deliver_the_pending_events_once_in_order() {
+        let outbox = InMemoryRepository::new();
+        outbox
+            .push(
+                WebhookEvent::PokemonDeleted,
+                deleted_data(&PokemonNumber::pikachu()),
+            )
+            .ok();
+        outbox
+            .push(
+                WebhookEvent::PokemonDeleted,
+                deleted_data(&PokemonNumber::charmander()),
+            )
+            .ok();
+        let recorder = Arc::new(Recorder(Mutex::new(vec![])));
+        let events = EventBus::new(vec![recorder.clone()]);
+
+        assert!(matches!(dispatch(&outbox, &events), Ok(2)));
+        assert!(matches!(dispatch(&outbox, &events), Ok(0)));
+
+        match recorder.0.lock().as_deref().map(Vec::as_slice) {
+            Ok([(_, pikachu), (_, charmander)]) => {
+                assert_eq!(pikachu["number"], 25);
+                assert_eq!(charmander["number"], 4);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,365 @@
+use crate::domain::entities::PokemonName;
+use crate::domain::{fetch_all_pokemons, fetch_pokemon, ingest_pokemons};
+use serde::{Deserialize, Serialize};
+
+const BASE_URL: &str = "https://pokeapi.co/api/v2";
+
+/// The official names which don't come back from their slug by capitalizing
+/// it.
+const NAMES: [&str; 42] = [
+    "Nidoran♀",
+    "Nidoran♂",
+    "Farfetch'd",
+    "Mr. Mime",
+    "Ho-Oh",
+    "Mime Jr.",
+    "Porygon-Z",
+    "Flabébé",
+    "Type: Null",
+    "Jangmo-o",
+    "Hakamo-o",
+    "Kommo-o",
+    "Tapu Koko",
+    "Tapu Lele",
+    "Tapu Bulu",
+    "Tapu Fini",
+    "Sirfetch'd",
+    "Mr. Rime",
+    "Great Tusk",
+    "Scream Tail",
+    "Brute Bonnet",
+    "Flutter Mane",
+    "Slither Wing",
+    "Sandy Shocks",
+    "Iron Treads",
+    "Iron Bundle",
+    "Iron Hands",
+    "Iron Jugulis",
+    "Iron Moth",
+    "Iron Thorns",
+    "Roaring Moon",
+    "Iron Valiant",
+    "Wo-Chien",
+    "Chien-Pao",
+    "Ting-Lu",
+    "Chi-Yu",
+    "Walking Wake",
+    "Iron Leaves",
+    "Gouging Fire",
+    "Raging Bolt",
+    "Iron Boulder",
+    "Iron Crown",
+];
+
+/// A Pokemon as served by pokeapi.co at /api/v2/pokemon/{id}, keeping the
+/// fields the Pokedex knows about. The other ones are ignored when read.
+#[derive(Serialize, Deserialize)]
+struct Pokemon {
+    id: u16,
+    name: String,
+    species: Resource,
+    types: Vec<TypeSlot>,
+    /// In decimeters.
+    #[serde(default)]
+    height: Option<u32>,
+    /// In hectograms.
+    #[serde(default)]
+    weight: Option<u32>,
+    #[serde(default)]
+    abilities: Vec<AbilitySlot>,
+    #[serde(default)]
+    sprites: Sprites,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Resource {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TypeSlot {
+    slot: u8,
+    #[serde(rename = "type")]
+    pokemon_type: Resource,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AbilitySlot {
+    slot: u8,
+    is_hidden: bool,
+    ability: Resource,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Sprites {
+    #[serde(default)]
+    front_default: Option<String>,
+}
+
+/// Writes the Pokemons as a JSON array of PokeAPI records. The names become
+/// the lowercase slugs PokeAPI uses, "Mr. Mime" being "mr-mime".
+pub fn write(pokemons: &[fetch_all_pokemons::Pokemon]) -> Result<Vec<u8>, ()> {
+    let records = pokemons
+        .iter()
+        .map(|pokemon| Pokemon {
+            id: pokemon.number,
+            name: slug(&pokemon.name),
+            species: Resource {
+                name: slug(&pokemon.name),
+                url: Some(format!("{}/pokemon-species/{}/", BASE_URL, pokemon.number)),
+            },
+            types: pokemon
+                .types
+                .iter()
+                .enumerate()
+                .map(|(i, t)| TypeSlot {
+                    slot: i as u8 + 1,
+                    pokemon_type: Resource {
+                        name: t.to_lowercase(),
+                        url: type_id(t).map(|id| format!("{}/type/{}/", BASE_URL, id)),
+                    },
+                })
+                .collect::<Vec<TypeSlot>>(),
+            height: pokemon.height.map(|height| (height * 10.0).round() as u32),
+            weight: pokemon.weight.map(|weight| (weight * 10.0).round() as u32),
+            abilities: pokemon
+                .abilities
+                .iter()
+                .enumerate()
+                .map(|(i, a)| AbilitySlot {
+                    slot: i as u8 + 1,
+                    is_hidden: a.hidden,
+                    ability: Resource {
+                        name: slug(&a.name),
+                        url: None,
+                    },
+                })
+                .collect::<Vec<AbilitySlot>>(),
+            sprites: Sprites {
+                front_default: pokemon.sprite.clone(),
+            },
+        })
+        .collect::<Vec<Pokemon>>();
+
+    match serde_json::to_vec_pretty(&records) {
+        Ok(bytes) => Ok(bytes),
+        _ => Err(()),
+    }
+}
+
+/// Reads a JSON array of PokeAPI records, or a single one, as fetched from
+/// pokeapi.co. The name is the one of the species, the forms of a Pokemon
+/// sharing its number, and gets back its official spelling. The height and
+/// the weight are converted to meters and kilograms, and the sprite is the
+/// default front one.
+pub fn read(bytes: &[u8]) -> Result<Vec<ingest_pokemons::Request>, ()> {
+    let records = match serde_json::from_slice::<Vec<Pokemon>>(bytes) {
+        Ok(records) => records,
+        _ => match serde_json::from_slice::<Pokemon>(bytes) {
+            Ok(record) => vec![record],
+            _ => return Err(()),
+        },
+    };
+
+    Ok(records
+        .into_iter()
+        .map(|mut record| {
+            record.types.sort_by_key(|t| t.slot);
+            record.abilities.sort_by_key(|a| a.slot);
+            ingest_pokemons::Request {
+                number: record.id,
+                name: name(&record.species.name),
+                types: record
+                    .types
+                    .into_iter()
+                    .map(|t| t.pokemon_type.name)
+                    .collect::<Vec<String>>(),
+                details: ingest_pokemons::Details {
+                    abilities: Some(
+                        record
+                            .abilities
+                            .into_iter()
+                            .map(|a| fetch_pokemon::Ability {
+                                name: title(&a.ability.name, " "),
+                                hidden: a.is_hidden,
+                            })
+                            .collect::<Vec<fetch_pokemon::Ability>>(),
+                    ),
+                    height: record.height.map(|height| height as f64 / 10.0),
+                    weight: record.weight.map(|weight| weight as f64 / 10.0),
+                    sprite: record.sprites.front_default,
+                    ..ingest_pokemons::Details::default()
+                },
+            }
+        })
+        .collect::<Vec<ingest_pokemons::Request>>())
+}
+
+fn slug(name: &str) -> String {
+    PokemonName::fold(name)
+        .replace('♀', "-f")
+        .replace('♂', "-m")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-')
+        .collect::<String>()
+}
+
+fn name(slug: &str) -> String {
+    match NAMES.iter().find(|name| self::slug(name) == slug) {
+        Some(name) => String::from(*name),
+        None => title(slug, "-"),
+    }
+}
+
+fn title(slug: &str, separator: &str) -> String {
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+/// The ids of the types on pokeapi.co, which don't follow the order of the
+/// games.
+fn type_id(name: &str) -> Option<u8> {
+    match name.to_lowercase().as_str() {
+        "normal" => Some(1),
+        "fighting" => Some(2),
+        "flying" => Some(3),
+        "poison" => Some(4),
+        "ground" => Some(5),
+        "rock" => Some(6),
+        "bug" => Some(7),
+        "ghost" => Some(8),
+        "steel" => Some(9),
+        "fire" => Some(10),
+        "water" => Some(11),
+        "grass" => Some(12),
+        "electric" => Some(13),
+        "psychic" => Some(14),
+        "ice" => Some(15),
+        "dragon" => Some(16),
+        "dark" => Some(17),
+        "fairy" => Some(18),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::fetch_pokemon::Ability;
+
+    #[test]
+    fn it_should_read_a_record_fetched_from_pokeapi() {
+        let bytes = br#"{
+            "id": 29,
+            "name": "nidoran-f",
+            "base_experience": 55,
+            "species": {"name": "nidoran-f", "url": "https://pokeapi.co/api/v2/pokemon-species/29/"},
+            "types": [
+                {"slot": 2, "type": {"name": "ground", "url": "https://pokeapi.co/api/v2/type/5/"}},
+                {"slot": 1, "type": {"name": "poison", "url": "https://pokeapi.co/api/v2/type/4/"}}
+            ],
+            "height": 4,
+            "weight": 70,
+            "abilities": [
+                {"slot": 3, "is_hidden": true, "ability": {"name": "hustle"}},
+                {"slot": 1, "is_hidden": false, "ability": {"name": "poison-point"}}
+            ],
+            "sprites": {"front_default": "https://example.com/29.png", "back_default": null}
+        }"#;
+
+        match read(bytes).as_deref() {
+            Ok([req]) => {
+                assert_eq!(req.number, 29);
+                assert_eq!(req.name, "Nidoran♀");
+                assert_eq!(req.types, vec!["poison", "ground"]);
+                assert_eq!(req.details.height, Some(0.4));
+                assert_eq!(req.details.weight, Some(7.0));
+                match req.details.abilities.as_deref() {
+                    Some([first, hidden]) => {
+                        assert_eq!(first.name, "Poison Point");
+                        assert!(hidden.hidden);
+                    }
+                    _ => unreachable!(),
+                };
+                assert_eq!(
+                    req.details.sprite.as_deref(),
+                    Some("https://example.com/29.png")
+                );
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_read_back_what_it_wrote() {
+        let pokemon = fetch_all_pokemons::Pokemon {
+            number: 122,
+            name: String::from("Mr. Mime"),
+            types: vec![String::from("Psychic"), String::from("Fairy")],
+            evolutions: vec![],
+            abilities: vec![Ability {
+                name: String::from("Soundproof"),
+                hidden: false,
+            }],
+            height: Some(1.3),
+            weight: Some(54.5),
+            sprite: Some(String::from("https://example.com/122.png")),
+            class: String::from("Normal"),
+        };
+
+        let bytes = match write(&[pokemon]) {
+            Ok(bytes) => bytes,
+            _ => unreachable!(),
+        };
+
+        match serde_json::from_slice::<serde_json::Value>(&bytes) {
+            Ok(json) => {
+                assert_eq!(json[0]["species"]["name"], "mr-mime");
+                assert_eq!(json[0]["types"][1]["type"]["name"], "fairy");
+                assert_eq!(
+                    json[0]["types"][1]["type"]["url"],
+                    "https://pokeapi.co/api/v2/type/18/"
+                );
+                assert_eq!(json[0]["height"], 13);
+                assert_eq!(json[0]["weight"], 545);
+                assert_eq!(json[0]["abilities"][0]["ability"]["name"], "soundproof");
+            }
+            _ => unreachable!(),
+        };
+        match read(&bytes).as_deref() {
+            Ok([req]) => {
+                assert_eq!(req.number, 122);
+                assert_eq!(req.name, "Mr. Mime");
+                assert_eq!(req.types, vec!["psychic", "fairy"]);
+                assert_eq!(req.details.height, Some(1.3));
+                assert_eq!(req.details.weight, Some(54.5));
+                match req.details.abilities.as_deref() {
+                    Some([ability]) => assert_eq!(ability.name, "Soundproof"),
+                    _ => unreachable!(),
+                };
+                assert_eq!(
+                    req.details.sprite.as_deref(),
+                    Some("https://example.com/122.png")
+                );
+            }
+            _ => unreachable!(),
+        };
+        for official in NAMES {
+            assert!(PokemonName::try_from(String::from(official)).is_ok());
+            assert_eq!(name(&slug(official)), official);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use crate::api::fetch_pokemon::{Ability, Evolution};
+use crate::domain::entities;
+use crate::domain::{
+    create_pokemon, delete_pokemon, fetch_pokemon, update_pokemon, update_pokemon_abilities,
+    update_pokemon_class, update_pokemon_evolutions, update_pokemon_measurements,
+    update_pokemon_sprite,
+};
+use crate::repositories::pokemon::Repository;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const BATCH_SIZE: u32 = 100;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize)]
+pub struct Feed {
+    pub changes: Vec<Change>,
+    pub cursor: Option<String>,
+}
+
+/// A change of the feed, with the Pokemon as it left it. The instances
+/// which only log the name and the types leave the other fields out. The
+/// other ones always give the class, the measurements and the sprite being
+/// left out when not set.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Change {
+    /// Where the feed resumes once this change is applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub kind: String,
+    pub number: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evolutions: Option<Vec<Evolution>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abilities: Option<Vec<Ability>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprite: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+}
+
+impl From<entities::Change> for Change {
+    fn from(change: entities::Change) -> Self {
+        let pokemon = change.pokemon.map(fetch_pokemon::Response::from);
+        Self {
+            cursor: Some(String::from(change.cursor)),
+            kind: String::from(change.kind),
+            number: u16::from(change.number),
+            name: pokemon.as_ref().map(|p| p.name.clone()),
+            types: pokemon.as_ref().map(|p| p.types.clone()),
+            evolutions: pokemon
+                .as_ref()
+                .map(|p| p.evolutions.iter().map(Evolution::from).collect()),
+            abilities: pokemon
+                .as_ref()
+                .map(|p| p.abilities.iter().map(Ability::from).collect()),
+            height: pokemon.as_ref().and_then(|p| p.height),
+            weight: pokemon.as_ref().and_then(|p| p.weight),
+            sprite: pokemon.as_ref().and_then(|p| p.sprite.clone()),
+            class: pokemon.map(|p| p.class),
+        }
+    }
+}
+
+/// Fetches every change of the instance at `url` after `cursor`, page by page.
+pub fn fetch_all_changes(url: &str, cursor: Option<&str>) -> Result<Feed, ()> {
+    let mut all = Feed {
+        changes: vec![],
+        cursor: cursor.map(String::from),
+    };
+
+    loop {
+        let feed = match fetch_feed(url, all.cursor.as_deref()) {
+            Ok(feed) => feed,
+            _ => return Err(()),
+        };
+        let caught_up = feed.changes.len() < BATCH_SIZE as usize;
+        all.changes.extend(feed.changes);
+        if feed.cursor.is_some() {
+            all.cursor = feed.cursor;
+        }
+        if caught_up {
+            return Ok(all);
+        }
+    }
+}
+
+/// Tails the change feed of the primary instance at `url` from `cursor` and
+/// applies every change to `repo`, polling again once it has caught up. The
+/// cursor only moves past the changes that were applied: after a failure,
+/// the feed is read again from the failed change.
+pub fn spawn(url: String, repo: Arc<dyn Repository>, cursor: Option<String>) {
+    thread::spawn(move || {
+        let mut cursor = cursor;
+
+        loop {
+            if let Ok(feed) = fetch_feed(&url, cursor.as_deref()) {
+                let caught_up = feed.changes.len() < BATCH_SIZE as usize;
+                let mut failed = false;
+
+                for change in feed.changes {
+                    let (number, next) = (change.number, change.cursor.clone());
+                    match apply(repo.clone(), change) {
+                        Ok(()) => {
+                            if next.is_some() {
+                                cursor = next;
+                            }
+                        }
+                        Err(()) => {
+                            eprintln!("The change of #{} could not be replicated", number);
+                            failed = true;
+                            break;
+                        }
+                    }
+                }
+
+                if !failed && feed.cursor.is_some() {
+                    cursor = feed.cursor;
+                }
+
+                if !caught_up && !failed {
+                    continue;
+                }
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+pub fn fetch_feed(url: &str, cursor: Option<&str>) -> Result<Feed, ()> {
+    let mut req = ureq::get(&format!("{}/changes", url.trim_end_matches('/')))
+        .query("limit", &BATCH_SIZE.to_string());
+
+    if let Some(cursor) = cursor {
+        req = req.query("since", cursor);
+    }
+
+    match req.call() {
+        Ok(res) => match res.into_json::<Feed>() {
+            Ok(feed) => Ok(feed),
+            _ => Err(()),
+        },
+        _ => Err(()),
+    }
+}
+
+/// The primary is the source of truth: a Pokemon the replica already knows
+/// is updated in place, and created when it is missing. The fields the
+/// change leaves out are kept as they are. Fails at the first write that
+/// does, and skips the changes of unknown kinds.
+pub fn apply(repo: Arc<dyn Repository>, change: Change) -> Result<(), ()> {
+    let number = change.number;
+    match (change.kind.as_str(), change.name, change.types) {
+        ("created" | "updated", Some(name), Some(types)) => {
+            let req = update_pokemon::Request {
+                number,
+                name: name.clone(),
+                types: types.clone(),
+            };
+            match update_pokemon::execute(repo.clone(), req) {
+                Ok(_) => {}
+                Err(update_pokemon::Error::NotFound) => {
+                    let req = create_pokemon::Request {
+                        number,
+                        name,
+                        types,
+                    };
+                    if create_pokemon::execute(repo.clone(), req).is_err() {
+                        return Err(());
+                    }
+                }
+                _ => return Err(()),
+            }
+
+            if let Some(evolutions) = change.evolutions {
+                let req = update_pokemon_evolutions::Request {
+                    number,
+                    evolutions: evolutions.into_iter().map(From::from).collect(),
+                };
+                if update_pokemon_evolutions::execute(repo.clone(), req).is_err() {
+                    return Err(());
+                }
+            }
+            if let Some(abilities) = change.abilities {
+                let req = update_pokemon_abilities::Request {
+                    number,
+                    abilities: abilities.into_iter().map(From::from).collect(),
+                };
+                if update_pokemon_abilities::execute(repo.clone(), req).is_err() {
+                    return Err(());
+                }
+            }
+            if let Some(class) = change.class {
+                let req = update_pokemon_measurements::Request {
+                    number,
+                    height: change.height,
+                    weight: change.weight,
+                };
+                if update_pokemon_measurements::execute(repo.clone(), req).is_err() {
+                    return Err(());
+                }
+                let req = update_pokemon_sprite::Request {
+                    number,
+                    url: change.sprite,
+                };
+                if update_pokemon_sprite::execute(repo.clone(), req).is_err() {
+                    return Err(());
+                }
+                let req = update_pokemon_class::Request { number, class };
+                if update_pokemon_class::execute(repo, req).is_err() {
+                    return Err(());
+                }
+            }
+
+            Ok(())
+        }
+        // A Pokemon already missing is as good as deleted.
+        ("deleted", _, _) => {
+            let req = delete_pokemon::Request { number };
+            match delete_pokemon::execute(repo, req) {
+                Ok(()) | Err(delete_pokemon::Error::NotFound) => Ok(()),
+                _ => Err(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonAbilities, PokemonClass, PokemonName, PokemonNumber, PokemonTypes,
+    };
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    fn charmander() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .ok();
+        repo.update_abilities(
+            PokemonNumber::charmander(),
+            Some(PokemonAbilities::charmander()),
+        )
+        .ok();
+        repo
+    }
+
+    fn change(json: serde_json::Value) -> Change {
+        match serde_json::from_value::<Change>(json) {
+            Ok(change) => change,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_update_a_known_pokemon_in_place() {
+        let repo = charmander();
+
+        let res = apply(
+            repo.clone(),
+            change(serde_json::json!({
+                "kind": "updated",
+                "number": 4,
+                "name": "Charmander",
+                "types": ["Fire", "Dragon"],
+                "height": 0.6,
+                "class": "Legendary"
+            })),
+        );
+
+        assert!(res.is_ok());
+        match repo.fetch_one(PokemonNumber::charmander()) {
+            Ok(pokemon) => {
+                assert_eq!(Vec::<String>::from(pokemon.types), vec!["Fire", "Dragon"]);
+                assert!(pokemon.abilities.is_some());
+                assert!(pokemon.height.is_some() && pokemon.weight.is_none());
+                assert!(pokemon.class == PokemonClass::Legendary);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_create_a_missing_pokemon_with_all_its_fields() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = apply(
+            repo.clone(),
+            change(serde_json::json!({
+                "kind": "created",
+                "number": 4,
+                "name": "Charmander",
+                "types": ["Fire"],
+                "abilities": [{"name": "Blaze"}],
+                "class": "Normal"
+            })),
+        );
+
+        assert!(res.is_ok());
+        match repo.fetch_one(PokemonNumber::charmander()) {
+            Ok(pokemon) => assert!(pokemon.abilities.is_some()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_fail_on_a_change_that_cannot_be_applied() {
+        let repo = charmander();
+
+        let res = apply(
+            repo.clone(),
+            change(serde_json::json!({
+                "kind": "updated",
+                "number": 4,
+                "name": "Charmander",
+                "types": ["Plasma"]
+            })),
+        );
+
+        assert!(res.is_err());
+        match repo.fetch_one(PokemonNumber::charmander()) {
+            Ok(pokemon) => assert_eq!(Vec::<String>::from(pokemon.types), vec!["Fire"]),
+            _ => unreachable!(),
+        };
+    }
+}
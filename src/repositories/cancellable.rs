@@ -0,0 +1,294 @@
+use crate::cancellation::Cancellation;
+use crate::domain::entities::{
+    Evolution, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
+use crate::repositories::pokemon::{
+    Backend, ClearError, CountByTypeError, CountError, DeleteByTypeError, DeleteError,
+    FetchAllError, FetchByNameError, FetchByTypeError, FetchOneError, FetchPageError,
+    FetchRangeError, InsertError, InvalidRecord, NameExistsError, Query, Repository, SnapshotError,
+    TransactionError, UpdateError,
+};
+use std::sync::Arc;
+
+/// Refuses every call once cancelled, so that a request nobody waits for
+/// anymore stops reaching the backend. A call already running goes to its
+/// end, and ending a transaction is never refused.
+pub struct CancellableRepository {
+    inner: Arc<dyn Repository>,
+    cancellation: Cancellation,
+}
+
+impl CancellableRepository {
+    pub fn new(inner: Arc<dyn Repository>, cancellation: Cancellation) -> Self {
+        Self {
+            inner,
+            cancellation,
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+}
+
+impl Repository for CancellableRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        if self.is_cancelled() {
+            return Err(InsertError::Unknown);
+        }
+        self.inner.insert(number, name, types)
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        if self.is_cancelled() {
+            return pokemons
+                .into_iter()
+                .map(|_| Err(InsertError::Unknown))
+                .collect();
+        }
+        self.inner.insert_many(pokemons)
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        if self.is_cancelled() {
+            return Err(FetchAllError::Unknown);
+        }
+        self.inner.fetch_all(query)
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        if self.is_cancelled() {
+            return Err(FetchAllError::Unknown);
+        }
+        self.inner.fetch_all_skipping_invalid(query)
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        if self.is_cancelled() {
+            return Err(FetchRangeError::Unknown);
+        }
+        self.inner.fetch_range(from, to)
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        if self.is_cancelled() {
+            return Err(FetchByTypeError::Unknown);
+        }
+        self.inner.fetch_by_type(pokemon_type)
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        if self.is_cancelled() {
+            return Err(NameExistsError::Unknown);
+        }
+        self.inner.name_exists(name)
+    }
+
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        if self.is_cancelled() {
+            return Err(FetchPageError::Unknown);
+        }
+        self.inner.fetch_page(offset, limit)
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        if self.is_cancelled() {
+            return Err(CountError::Unknown);
+        }
+        self.inner.count()
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        if self.is_cancelled() {
+            return Err(CountByTypeError::Unknown);
+        }
+        self.inner.count_by_type()
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        if self.is_cancelled() {
+            return Err(FetchOneError::Unknown);
+        }
+        self.inner.fetch_one(number)
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        if self.is_cancelled() {
+            return Err(FetchByNameError::Unknown);
+        }
+        self.inner.fetch_by_name(name)
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.is_cancelled() {
+            return Err(UpdateError::Unknown);
+        }
+        self.inner.update_types(number, types)
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.is_cancelled() {
+            return Err(UpdateError::Unknown);
+        }
+        self.inner.update(number, name, types)
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.is_cancelled() {
+            return Err(UpdateError::Unknown);
+        }
+        self.inner.update_evolutions(number, evolutions)
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.is_cancelled() {
+            return Err(UpdateError::Unknown);
+        }
+        self.inner.update_abilities(number, abilities)
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.is_cancelled() {
+            return Err(UpdateError::Unknown);
+        }
+        self.inner.update_measurements(number, height, weight)
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.is_cancelled() {
+            return Err(UpdateError::Unknown);
+        }
+        self.inner.update_sprite(number, sprite)
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.is_cancelled() {
+            return Err(UpdateError::Unknown);
+        }
+        self.inner.update_class(number, class)
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        if self.is_cancelled() {
+            return Err(DeleteError::Unknown);
+        }
+        self.inner.delete(number)
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        if self.is_cancelled() {
+            return Err(DeleteByTypeError::Unknown);
+        }
+        self.inner.delete_by_type(pokemon_type)
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        if self.is_cancelled() {
+            return Err(ClearError::Unknown);
+        }
+        self.inner.clear()
+    }
+
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        if self.is_cancelled() {
+            return Err(SnapshotError::Unknown);
+        }
+        self.inner.snapshot()
+    }
+
+    fn begin(&self) -> Result<(), TransactionError> {
+        if self.is_cancelled() {
+            return Err(TransactionError::Unknown);
+        }
+        self.inner.begin()
+    }
+
+    fn commit(&self) -> Result<(), TransactionError> {
+        self.inner.commit()
+    }
+
+    fn rollback(&self) -> Result<(), TransactionError> {
+        self.inner.rollback()
+    }
+
+    fn uncancellable(&self) -> Option<Arc<dyn Repository>> {
+        Some(self.inner.clone())
+    }
+
+    fn backend(&self) -> Backend {
+        self.inner.backend()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_refuse_the_calls_once_cancelled() {
+        let inner = Arc::new(InMemoryRepository::new());
+        let cancellation = Cancellation::new();
+        let repo = CancellableRepository::new(inner.clone(), cancellation.clone());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+
+        cancellation.cancel();
+        let res = repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        );
+
+        assert!(matches!(res, Err(InsertError::Unknown)));
+        assert!(matches!(repo.count(), Err(CountError::Unknown)));
+        assert!(matches!(inner.count(), Ok(1)));
+    }
+}
@@ -0,0 +1,576 @@
+use crate::domain::entities::{Change, ChangeCursor, ChangeKind, Pokemon, PokemonNumber};
+#[cfg(feature = "server")]
+use crate::domain::entities::{PokemonName, PokemonType, PokemonTypes};
+#[cfg(feature = "server")]
+use crate::repositories::pokemon::PokemonRecord;
+#[cfg(feature = "server")]
+use rusqlite::{params, Connection, OpenFlags, Row};
+use std::sync::Mutex;
+
+pub enum InsertError {
+    Unknown,
+}
+
+pub enum FetchSinceError {
+    Unknown,
+}
+
+pub enum LatestCursorError {
+    Unknown,
+}
+
+pub enum FetchUntilError {
+    Unknown,
+}
+
+pub enum TrimError {
+    Unknown,
+}
+
+pub trait Repository: Send + Sync {
+    fn insert(
+        &self,
+        kind: ChangeKind,
+        number: PokemonNumber,
+        pokemon: Option<Pokemon>,
+        timestamp: u64,
+    ) -> Result<Change, InsertError>;
+
+    fn fetch_since(
+        &self,
+        since: Option<ChangeCursor>,
+        limit: u32,
+    ) -> Result<Vec<Change>, FetchSinceError>;
+
+    fn latest_cursor(&self) -> Result<Option<ChangeCursor>, LatestCursorError>;
+
+    /// The changes made at or before `timestamp`, oldest first.
+    fn fetch_until(&self, timestamp: u64) -> Result<Vec<Change>, FetchUntilError>;
+
+    /// Removes the changes made before `timestamp` which don't tell the
+    /// state of the Pokemons at that time: the ones followed by another
+    /// change of the same Pokemon before `timestamp`, and the deletions.
+    /// The latest change is always kept, as it is the collection version.
+    /// Tells how many changes are or, with `dry_run`, would be removed.
+    fn trim(&self, timestamp: u64, dry_run: bool) -> Result<u32, TrimError>;
+}
+
+pub struct InMemoryRepository {
+    error: bool,
+    changes: Mutex<Vec<Change>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let changes: Mutex<Vec<Change>> = Mutex::new(vec![]);
+        Self {
+            error: false,
+            changes,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(
+        &self,
+        kind: ChangeKind,
+        number: PokemonNumber,
+        pokemon: Option<Pokemon>,
+        timestamp: u64,
+    ) -> Result<Change, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let mut lock = match self.changes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let change = Change {
+            cursor: ChangeCursor::from(lock.len() as u64 + 1),
+            kind,
+            number,
+            pokemon,
+            timestamp,
+        };
+        lock.push(change.clone());
+        Ok(change)
+    }
+
+    fn fetch_since(
+        &self,
+        since: Option<ChangeCursor>,
+        limit: u32,
+    ) -> Result<Vec<Change>, FetchSinceError> {
+        if self.error {
+            return Err(FetchSinceError::Unknown);
+        }
+
+        let lock = match self.changes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchSinceError::Unknown),
+        };
+
+        Ok(lock
+            .iter()
+            .filter(|c| match since {
+                Some(since) => c.cursor > since,
+                None => true,
+            })
+            .take(limit as usize)
+            .cloned()
+            .collect::<Vec<Change>>())
+    }
+
+    fn latest_cursor(&self) -> Result<Option<ChangeCursor>, LatestCursorError> {
+        if self.error {
+            return Err(LatestCursorError::Unknown);
+        }
+
+        match self.changes.lock() {
+            Ok(lock) => Ok(lock.last().map(|c| c.cursor)),
+            _ => Err(LatestCursorError::Unknown),
+        }
+    }
+
+    fn fetch_until(&self, timestamp: u64) -> Result<Vec<Change>, FetchUntilError> {
+        if self.error {
+            return Err(FetchUntilError::Unknown);
+        }
+
+        match self.changes.lock() {
+            Ok(lock) => Ok(lock
+                .iter()
+                .filter(|c| c.timestamp <= timestamp)
+                .cloned()
+                .collect::<Vec<Change>>()),
+            _ => Err(FetchUntilError::Unknown),
+        }
+    }
+
+    fn trim(&self, timestamp: u64, dry_run: bool) -> Result<u32, TrimError> {
+        if self.error {
+            return Err(TrimError::Unknown);
+        }
+
+        let mut lock = match self.changes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(TrimError::Unknown),
+        };
+
+        let latest = lock.last().map(|c| c.cursor);
+        let trimmed = lock
+            .iter()
+            .filter(|c| c.timestamp < timestamp && Some(c.cursor) != latest)
+            .filter(|c| {
+                matches!(c.kind, ChangeKind::Deleted)
+                    || lock.iter().any(|later| {
+                        later.number == c.number
+                            && later.cursor > c.cursor
+                            && later.timestamp < timestamp
+                    })
+            })
+            .map(|c| c.cursor)
+            .collect::<Vec<ChangeCursor>>();
+
+        if !dry_run {
+            lock.retain(|c| !trimmed.contains(&c.cursor));
+        }
+        Ok(trimmed.len() as u32)
+    }
+}
+
+#[cfg(feature = "server")]
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+#[cfg(feature = "server")]
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match Self::migrate(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    /// The changes used to keep the name and the types only. They now keep
+    /// the whole Pokemon as a JSON document in a `pokemon` column, the older
+    /// ones going without it.
+    fn migrate(connection: &Connection) -> Result<(), ()> {
+        if connection
+            .execute_batch(
+                "create table if not exists changes (
+                    id integer primary key autoincrement,
+                    kind text not null,
+                    number integer not null,
+                    name text,
+                    types text not null,
+                    timestamp integer not null,
+                    pokemon text
+                );",
+            )
+            .is_err()
+        {
+            return Err(());
+        }
+
+        match connection.query_row(
+            "select
+                (select count(*) from sqlite_master where type = 'table' and name = 'changes'),
+                (select count(*) from pragma_table_info('changes') where name = 'pokemon')",
+            [],
+            |row| Ok((row.get::<usize, u32>(0)?, row.get::<usize, u32>(1)?)),
+        ) {
+            Ok((1, 0)) => {
+                match connection.execute_batch("alter table changes add column pokemon text") {
+                    Ok(()) => Ok(()),
+                    _ => Err(()),
+                }
+            }
+            Ok(_) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        let connection = match Connection::open_in_memory() {
+            Ok(connection) => connection,
+            _ => panic!("Error while opening the in-memory database"),
+        };
+
+        match connection
+            .execute_batch(
+                "create table changes (
+                    id integer primary key autoincrement,
+                    kind text not null,
+                    number integer not null,
+                    name text,
+                    types text not null,
+                    timestamp integer not null
+                );",
+            )
+            .map_err(|_| ())
+            .and_then(|()| Self::migrate(&connection))
+        {
+            Ok(()) => Self {
+                connection: Mutex::new(connection),
+            },
+            _ => panic!("Error while creating the in-memory database"),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for SqliteRepository {
+    fn insert(
+        &self,
+        kind: ChangeKind,
+        number: PokemonNumber,
+        pokemon: Option<Pokemon>,
+        timestamp: u64,
+    ) -> Result<Change, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let (name, types, record) = match &pokemon {
+            Some(pokemon) => (
+                Some(pokemon.name.as_str()),
+                pokemon
+                    .types
+                    .as_slice()
+                    .iter()
+                    .map(PokemonType::as_str)
+                    .collect::<Vec<&str>>()
+                    .join(","),
+                match serde_json::to_string(&PokemonRecord::from(pokemon)) {
+                    Ok(record) => Some(record),
+                    _ => return Err(InsertError::Unknown),
+                },
+            ),
+            None => (None, String::new(), None),
+        };
+
+        if lock
+            .execute(
+                "insert into changes (kind, number, name, types, timestamp, pokemon)
+                values (?, ?, ?, ?, ?, ?)",
+                params![
+                    String::from(kind),
+                    number.value(),
+                    name,
+                    types,
+                    timestamp,
+                    record
+                ],
+            )
+            .is_err()
+        {
+            return Err(InsertError::Unknown);
+        }
+
+        match u64::try_from(lock.last_insert_rowid()) {
+            Ok(cursor) => Ok(Change {
+                cursor: ChangeCursor::from(cursor),
+                kind,
+                number,
+                pokemon,
+                timestamp,
+            }),
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_since(
+        &self,
+        since: Option<ChangeCursor>,
+        limit: u32,
+    ) -> Result<Vec<Change>, FetchSinceError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchSinceError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select id, kind, number, name, types, timestamp, pokemon from changes
+            where id > ? order by id limit ?",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchSinceError::Unknown),
+        };
+
+        let since = since.map(u64::from).unwrap_or(0);
+        let mut rows = match stmt.query(params![since, limit]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchSinceError::Unknown),
+        };
+
+        let mut changes = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match decode(row) {
+                Ok(change) => changes.push(change),
+                _ => return Err(FetchSinceError::Unknown),
+            }
+        }
+
+        Ok(changes)
+    }
+
+    fn latest_cursor(&self) -> Result<Option<ChangeCursor>, LatestCursorError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(LatestCursorError::Unknown),
+        };
+
+        match lock.query_row("select max(id) from changes", [], |row| {
+            row.get::<usize, Option<u64>>(0)
+        }) {
+            Ok(cursor) => Ok(cursor.map(ChangeCursor::from)),
+            _ => Err(LatestCursorError::Unknown),
+        }
+    }
+
+    fn fetch_until(&self, timestamp: u64) -> Result<Vec<Change>, FetchUntilError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchUntilError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select id, kind, number, name, types, timestamp, pokemon from changes
+            where timestamp <= ? order by id",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchUntilError::Unknown),
+        };
+
+        let mut rows = match stmt.query(params![timestamp]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchUntilError::Unknown),
+        };
+
+        let mut changes = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match decode(row) {
+                Ok(change) => changes.push(change),
+                _ => return Err(FetchUntilError::Unknown),
+            }
+        }
+
+        Ok(changes)
+    }
+
+    fn trim(&self, timestamp: u64, dry_run: bool) -> Result<u32, TrimError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(TrimError::Unknown),
+        };
+
+        let filter = "where timestamp < ?1
+            and id < (select max(id) from changes)
+            and (
+                kind = 'deleted'
+                or id < (
+                    select max(later.id) from changes later
+                    where later.number = changes.number and later.timestamp < ?1
+                )
+            )";
+        let res = match dry_run {
+            true => lock.query_row(
+                &format!("select count(*) from changes {}", filter),
+                params![timestamp],
+                |row| row.get::<usize, u32>(0),
+            ),
+            false => lock
+                .execute(
+                    &format!("delete from changes {}", filter),
+                    params![timestamp],
+                )
+                .map(|trimmed| trimmed as u32),
+        };
+
+        match res {
+            Ok(trimmed) => Ok(trimmed),
+            _ => Err(TrimError::Unknown),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+/// A row of `select id, kind, number, name, types, timestamp, pokemon from
+/// changes`. The changes logged before the `pokemon` column only give back
+/// the name and the types.
+fn decode(row: &Row) -> Result<Change, ()> {
+    let (cursor, kind, number, name, types, timestamp, record) = match (
+        row.get::<usize, u64>(0),
+        row.get::<usize, String>(1).map(ChangeKind::try_from),
+        row.get::<usize, u16>(2).map(PokemonNumber::try_from),
+        row.get::<usize, Option<String>>(3),
+        row.get::<usize, String>(4),
+        row.get::<usize, u64>(5),
+        row.get::<usize, Option<String>>(6),
+    ) {
+        (
+            Ok(cursor),
+            Ok(Ok(kind)),
+            Ok(Ok(number)),
+            Ok(name),
+            Ok(types),
+            Ok(timestamp),
+            Ok(record),
+        ) => (cursor, kind, number, name, types, timestamp, record),
+        _ => return Err(()),
+    };
+
+    let pokemon = match (record, name) {
+        (Some(record), _) => {
+            match serde_json::from_str::<PokemonRecord>(&record).map(PokemonRecord::decode) {
+                Ok(Ok(pokemon)) => Some(pokemon),
+                _ => return Err(()),
+            }
+        }
+        (None, Some(name)) => match (
+            PokemonName::try_from(name),
+            PokemonTypes::try_from(types.split(',').map(String::from).collect::<Vec<String>>()),
+        ) {
+            (Ok(name), Ok(types)) => Some(Pokemon::new(number.clone(), name, types)),
+            _ => return Err(()),
+        },
+        (None, None) => None,
+    };
+
+    Ok(Change {
+        cursor: ChangeCursor::from(cursor),
+        kind,
+        number,
+        pokemon,
+        timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonAbilities, PokemonName, PokemonTypes};
+    use std::sync::Arc;
+
+    fn repositories() -> Vec<Arc<dyn Repository>> {
+        vec![
+            Arc::new(InMemoryRepository::new()),
+            #[cfg(feature = "server")]
+            Arc::new(SqliteRepository::in_memory()),
+        ]
+    }
+
+    #[test]
+    fn it_should_keep_the_whole_pokemon_with_its_change() {
+        for repo in repositories() {
+            let mut charmander = Pokemon::new(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            );
+            charmander.abilities = Some(PokemonAbilities::charmander());
+            repo.insert(
+                ChangeKind::Updated,
+                PokemonNumber::charmander(),
+                Some(charmander),
+                0,
+            )
+            .ok();
+            repo.insert(ChangeKind::Deleted, PokemonNumber::charmander(), None, 0)
+                .ok();
+
+            match repo.fetch_since(None, 10).as_deref() {
+                Ok([updated, deleted]) => {
+                    assert!(matches!(
+                        &updated.pokemon,
+                        Some(pokemon) if pokemon.abilities.is_some()
+                    ));
+                    assert!(deleted.pokemon.is_none());
+                }
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn it_should_create_the_changes_of_a_database_without_them() {
+        let connection = match Connection::open_in_memory() {
+            Ok(connection) => connection,
+            _ => unreachable!(),
+        };
+        assert!(SqliteRepository::migrate(&connection).is_ok());
+        let repo = SqliteRepository {
+            connection: Mutex::new(connection),
+        };
+
+        assert!(repo
+            .insert(ChangeKind::Deleted, PokemonNumber::pikachu(), None, 0)
+            .is_ok());
+        assert!(matches!(repo.fetch_since(None, 10).as_deref(), Ok([_])));
+    }
+}
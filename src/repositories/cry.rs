@@ -0,0 +1,302 @@
+#[cfg(feature = "server")]
+use crate::domain::entities::CryFormat;
+use crate::domain::entities::{Cry, PokemonNumber};
+#[cfg(feature = "server")]
+use rusqlite::{params, Connection, OpenFlags};
+use std::sync::Mutex;
+
+pub enum PutError {
+    Unknown,
+}
+
+pub enum FetchError {
+    NotFound,
+    Unknown,
+}
+
+pub enum DeleteError {
+    Unknown,
+}
+
+/// The cries of the Pokemons, a single one each, stored as they were
+/// uploaded.
+pub trait Repository: Send + Sync {
+    /// Stores the cry of the Pokemon, replacing the one it had, if any.
+    fn put(&self, number: PokemonNumber, cry: Cry) -> Result<(), PutError>;
+
+    fn fetch(&self, number: PokemonNumber) -> Result<Cry, FetchError>;
+
+    /// Forgets the cry of the Pokemon, having none being fine.
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError>;
+}
+
+pub struct InMemoryRepository {
+    error: bool,
+    cries: Mutex<Vec<(PokemonNumber, Cry)>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let cries: Mutex<Vec<(PokemonNumber, Cry)>> = Mutex::new(vec![]);
+        Self {
+            error: false,
+            cries,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn put(&self, number: PokemonNumber, cry: Cry) -> Result<(), PutError> {
+        if self.error {
+            return Err(PutError::Unknown);
+        }
+
+        let mut lock = match self.cries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PutError::Unknown),
+        };
+
+        lock.retain(|(n, _)| *n != number);
+        lock.push((number, cry));
+        Ok(())
+    }
+
+    fn fetch(&self, number: PokemonNumber) -> Result<Cry, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
+        let lock = match self.cries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        match lock.iter().find(|(n, _)| *n == number) {
+            Some((_, cry)) => Ok(cry.clone()),
+            None => Err(FetchError::NotFound),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let mut lock = match self.cries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        lock.retain(|(n, _)| *n != number);
+        Ok(())
+    }
+}
+
+/// Stores the cries as blobs in a `cries` table, created when missing. A
+/// cry goes along with its Pokemon.
+#[cfg(feature = "server")]
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+#[cfg(feature = "server")]
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match Self::migrate(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    fn migrate(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "pragma foreign_keys = 1;
+            create table if not exists cries (
+                pokemon_number integer primary key references pokemons(number) on delete cascade,
+                format text not null,
+                data blob not null
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        let connection = match Connection::open_in_memory() {
+            Ok(connection) => connection,
+            _ => panic!("Error while opening the in-memory database"),
+        };
+
+        match connection
+            .execute_batch(
+                "create table pokemons (number integer primary key, name text not null);",
+            )
+            .map_err(|_| ())
+            .and_then(|()| Self::migrate(&connection))
+        {
+            Ok(()) => Self {
+                connection: Mutex::new(connection),
+            },
+            _ => panic!("Error while creating the in-memory database"),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for SqliteRepository {
+    fn put(&self, number: PokemonNumber, cry: Cry) -> Result<(), PutError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PutError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into cries (pokemon_number, format, data) values (?, ?, ?)
+            on conflict (pokemon_number) do update set
+            format = excluded.format,
+            data = excluded.data",
+            params![number.value(), String::from(cry.format), cry.bytes],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(PutError::Unknown),
+        }
+    }
+
+    fn fetch(&self, number: PokemonNumber) -> Result<Cry, FetchError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select format, data from cries where pokemon_number = ?")
+        {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let mut rows = match stmt.query([number.value()]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        match rows.next() {
+            Ok(Some(row)) => match (
+                row.get::<usize, String>(0).map(CryFormat::try_from),
+                row.get::<usize, Vec<u8>>(1),
+            ) {
+                (Ok(Ok(format)), Ok(bytes)) => Ok(Cry { format, bytes }),
+                _ => Err(FetchError::Unknown),
+            },
+            Ok(None) => Err(FetchError::NotFound),
+            _ => Err(FetchError::Unknown),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.execute(
+            "delete from cries where pokemon_number = ?",
+            [number.value()],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::CryFormat;
+    use std::sync::Arc;
+
+    // Every backend must pass these, the sqlite one on a database holding
+    // Pikachu and Charmander.
+    fn repositories() -> Vec<Arc<dyn Repository>> {
+        vec![
+            Arc::new(InMemoryRepository::new()),
+            #[cfg(feature = "server")]
+            Arc::new(sqlite()),
+        ]
+    }
+
+    #[cfg(feature = "server")]
+    fn sqlite() -> SqliteRepository {
+        let repo = SqliteRepository::in_memory();
+        if let Ok(lock) = repo.connection.lock() {
+            lock.execute_batch(
+                "insert into pokemons (number, name) values (25, 'Pikachu'), (4, 'Charmander');",
+            )
+            .ok();
+        }
+        repo
+    }
+
+    #[test]
+    fn it_should_give_back_the_latest_cry_of_the_pokemon() {
+        for repo in repositories() {
+            assert!(matches!(
+                repo.fetch(PokemonNumber::pikachu()),
+                Err(FetchError::NotFound)
+            ));
+
+            repo.put(PokemonNumber::pikachu(), Cry::pikachu()).ok();
+            repo.put(
+                PokemonNumber::pikachu(),
+                Cry {
+                    format: CryFormat::Wav,
+                    bytes: b"RIFF".to_vec(),
+                },
+            )
+            .ok();
+
+            match repo.fetch(PokemonNumber::pikachu()) {
+                Ok(cry) => {
+                    assert!(cry.format == CryFormat::Wav);
+                    assert_eq!(cry.bytes, b"RIFF");
+                }
+                _ => unreachable!(),
+            };
+            assert!(matches!(
+                repo.fetch(PokemonNumber::charmander()),
+                Err(FetchError::NotFound)
+            ));
+        }
+    }
+
+    #[test]
+    fn it_should_forget_the_cry_once_deleted() {
+        for repo in repositories() {
+            repo.put(PokemonNumber::pikachu(), Cry::pikachu()).ok();
+
+            assert!(repo.delete(PokemonNumber::pikachu()).is_ok());
+            assert!(repo.delete(PokemonNumber::charmander()).is_ok());
+            assert!(matches!(
+                repo.fetch(PokemonNumber::pikachu()),
+                Err(FetchError::NotFound)
+            ));
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use crate::domain::entities::{PokemonName, PokemonNumber, PokemonType};
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+
+pub struct Check {
+    pub name: String,
+    pub result: Result<(), String>,
+}
+
+impl Check {
+    fn new(name: &str, result: Result<(), String>) -> Self {
+        Self {
+            name: String::from(name),
+            result,
+        }
+    }
+}
+
+// Tables and columns read and written by the sqlite repositories.
+const SQLITE_TABLES: [(&str, &[&str]); 7] = [
+    ("pokemons", &["number", "name"]),
+    ("types", &["pokemon_number", "name"]),
+    (
+        "changes",
+        &["id", "kind", "number", "name", "types", "timestamp"],
+    ),
+    ("usages", &["key", "day", "count"]),
+    ("webhooks", &["id", "url", "secret"]),
+    (
+        "deliveries",
+        &["webhook_id", "event", "attempt", "status_code", "timestamp"],
+    ),
+    ("wishes", &["number", "name", "note"]),
+];
+
+// Tables and fields read and written by the airtable repositories.
+const AIRTABLE_TABLES: [(&str, &[&str]); 2] = [
+    ("pokemons", &["number", "name", "types"]),
+    ("usages", &["key", "day", "count"]),
+];
+
+pub fn sqlite(path: &str) -> Vec<Check> {
+    if !Path::new(path).is_file() {
+        return vec![Check::new(
+            &format!("{} exists", path),
+            Err(String::from(
+                "no such file, create the database and its tables first",
+            )),
+        )];
+    }
+
+    let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE) {
+        Ok(connection) => connection,
+        Err(err) => return vec![Check::new(&format!("{} opens", path), Err(err.to_string()))],
+    };
+
+    let mut checks = vec![Check::new(&format!("{} opens", path), Ok(()))];
+
+    for (table, columns) in SQLITE_TABLES {
+        let result = match sqlite_columns(&connection, table) {
+            Ok(found) if found.is_empty() => Err(String::from("table is missing")),
+            Ok(found) => missing(columns, &found),
+            Err(err) => Err(err),
+        };
+        checks.push(Check::new(&format!("table {}", table), result));
+    }
+
+    checks.push(Check::new(
+        "database is writable",
+        match connection.execute_batch("begin immediate; rollback;") {
+            Ok(_) => Ok(()),
+            Err(err) => Err(err.to_string()),
+        },
+    ));
+
+    checks
+}
+
+/// Looks for the rows the sqlite repositories can't read back, each failed
+/// check telling how to repair them.
+pub fn sqlite_integrity(path: &str) -> Vec<Check> {
+    let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY) {
+        Ok(connection) => connection,
+        Err(err) => return vec![Check::new(&format!("{} opens", path), Err(err.to_string()))],
+    };
+
+    let mut checks = vec![];
+
+    checks.push(Check::new(
+        "database file",
+        match sqlite_rows(&connection, "pragma integrity_check") {
+            Ok(rows) if rows.iter().all(|row| row[0] == "ok") => Ok(()),
+            Ok(rows) => Err(format!(
+                "{}, restore the last archive",
+                rows.into_iter()
+                    .map(|row| row[0].clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+            Err(err) => Err(err),
+        },
+    ));
+
+    checks.push(Check::new(
+        "types of existing Pokemons",
+        match sqlite_rows(
+            &connection,
+            "select count(*) from types where pokemon_number not in (select number from pokemons)",
+        ) {
+            Ok(rows) if rows[0][0] == "0" => Ok(()),
+            Ok(rows) => Err(format!(
+                "{} row(s) point at missing Pokemons, remove them with: pokedex fsck",
+                rows[0][0]
+            )),
+            Err(err) => Err(err),
+        },
+    ));
+
+    checks.push(Check::new(
+        "Pokemons with a type",
+        match sqlite_rows(
+            &connection,
+            "select number from pokemons p
+            where not exists (select 1 from types t where t.pokemon_number = p.number)
+            order by number",
+        ) {
+            Ok(rows) if rows.is_empty() => Ok(()),
+            Ok(rows) => Err(format!(
+                "{} have no type, give them one or delete them",
+                numbers(&rows)
+            )),
+            Err(err) => Err(err),
+        },
+    ));
+
+    checks.push(Check::new(
+        "known types",
+        match sqlite_rows(&connection, "select distinct name from types order by name") {
+            Ok(rows) => {
+                let unknown = rows
+                    .into_iter()
+                    .map(|row| row[0].clone())
+                    .filter(|name| {
+                        PokemonType::try_from(name.clone()).map(|t| t.as_str()) != Ok(name.as_str())
+                    })
+                    .collect::<Vec<String>>();
+                match unknown.is_empty() {
+                    true => Ok(()),
+                    false => Err(format!(
+                        "{} unknown, rename them with: update types set name = '<type>' where name = '{}'",
+                        unknown.join(", "),
+                        unknown[0]
+                    )),
+                }
+            }
+            Err(err) => Err(err),
+        },
+    ));
+
+    checks.push(Check::new(
+        "types listed once",
+        match sqlite_rows(
+            &connection,
+            "select distinct pokemon_number from types
+            group by pokemon_number, name having count(*) > 1
+            order by pokemon_number",
+        ) {
+            Ok(rows) if rows.is_empty() => Ok(()),
+            Ok(rows) => Err(format!(
+                "{} have a type listed more than once, remove the repeats with: \
+                delete from types where rowid not in \
+                (select min(rowid) from types group by pokemon_number, name)",
+                numbers(&rows)
+            )),
+            Err(err) => Err(err),
+        },
+    ));
+
+    checks.push(Check::new(
+        "valid numbers and names",
+        match sqlite_rows(
+            &connection,
+            "select number, name from pokemons order by number",
+        ) {
+            Ok(rows) => {
+                let invalid = rows
+                    .into_iter()
+                    .filter(|row| {
+                        let number = row[0]
+                            .parse::<u16>()
+                            .map_err(|_| ())
+                            .and_then(PokemonNumber::try_from);
+                        number.is_err() || PokemonName::try_from(row[1].clone()).is_err()
+                    })
+                    .collect::<Vec<Vec<String>>>();
+                match invalid.is_empty() {
+                    true => Ok(()),
+                    false => Err(format!(
+                        "{} can't be read back, fix or delete them",
+                        numbers(&invalid)
+                    )),
+                }
+            }
+            Err(err) => Err(err),
+        },
+    ));
+
+    checks
+}
+
+/// Every row of the statement, each column read as text.
+fn sqlite_rows(connection: &Connection, sql: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut stmt = match connection.prepare(sql) {
+        Ok(stmt) => stmt,
+        Err(err) => return Err(err.to_string()),
+    };
+    let count = stmt.column_count();
+
+    let mut rows = match stmt.query([]) {
+        Ok(rows) => rows,
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let mut found = vec![];
+
+    while let Ok(Some(row)) = rows.next() {
+        let mut columns = vec![];
+        for index in 0..count {
+            match row.get::<usize, rusqlite::types::Value>(index) {
+                Ok(rusqlite::types::Value::Integer(n)) => columns.push(n.to_string()),
+                Ok(rusqlite::types::Value::Text(text)) => columns.push(text),
+                Ok(_) => columns.push(String::new()),
+                Err(err) => return Err(err.to_string()),
+            }
+        }
+        found.push(columns);
+    }
+
+    Ok(found)
+}
+
+fn numbers(rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .map(|row| format!("#{}", row[0]))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn sqlite_columns(connection: &Connection, table: &str) -> Result<Vec<String>, String> {
+    let mut stmt = match connection.prepare(&format!("pragma table_info({})", table)) {
+        Ok(stmt) => stmt,
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let mut rows = match stmt.query([]) {
+        Ok(rows) => rows,
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let mut columns = vec![];
+
+    while let Ok(Some(row)) = rows.next() {
+        match row.get::<&str, String>("name") {
+            Ok(name) => columns.push(name),
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    Ok(columns)
+}
+
+// Airtable offers no way to check write access without writing, so only
+// reads are checked.
+pub fn airtable(api_key: &str, workspace_id: &str) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for (table, fields) in AIRTABLE_TABLES {
+        let url = format!("https://api.airtable.com/v0/{}/{}", workspace_id, table);
+        let result = match ureq::get(&url)
+            .set("Authorization", &format!("Bearer {}", api_key))
+            .query("maxRecords", "1")
+            .call()
+        {
+            Ok(res) => match res.into_json::<serde_json::Value>() {
+                // Airtable leaves empty fields out of records, so a record
+                // can only tell which fields are missing when it is complete.
+                Ok(json) => match json["records"][0]["fields"].as_object() {
+                    Some(found) => missing(fields, &found.keys().cloned().collect::<Vec<String>>()),
+                    None => Ok(()),
+                },
+                Err(err) => Err(format!("unreadable response: {}", err)),
+            },
+            Err(ureq::Error::Status(401 | 403, _)) => Err(String::from(
+                "the API key was rejected or cannot read this base",
+            )),
+            Err(ureq::Error::Status(404, _)) => {
+                Err(format!("no {} table in the base {}", table, workspace_id))
+            }
+            Err(ureq::Error::Status(status, _)) => {
+                Err(format!("Airtable answered with status {}", status))
+            }
+            Err(err) => Err(format!("cannot reach Airtable: {}", err)),
+        };
+        checks.push(Check::new(&format!("airtable table {}", table), result));
+    }
+
+    checks
+}
+
+fn missing(expected: &[&str], found: &[String]) -> Result<(), String> {
+    let missing = expected
+        .iter()
+        .filter(|name| !found.iter().any(|f| f == *name))
+        .cloned()
+        .collect::<Vec<&str>>();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("missing {}", missing.join(", ")))
+    }
+}
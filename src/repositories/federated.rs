@@ -0,0 +1,393 @@
+use crate::domain::entities::{
+    Evolution, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
+use crate::repositories::pokemon::{
+    select, Backend, ClearError, CountByTypeError, CountError, DeleteByTypeError, DeleteError,
+    FetchAllError, FetchByNameError, FetchByTypeError, FetchOneError, FetchRangeError, InsertError,
+    InvalidRecord, NameExistsError, Query, Repository, SnapshotError, UpdateError,
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Reads several backends as a single one, such as a shared Airtable base
+/// along with a private sqlite file. When they store the same number, the
+/// Pokemon of the first backend is the one read. Writes only go to the
+/// primary backend, so the Pokemons stored elsewhere can't be changed.
+pub struct FederatedRepository {
+    backends: Vec<Arc<dyn Repository>>,
+    primary: usize,
+}
+
+impl FederatedRepository {
+    /// `backends` in order of precedence, `primary` being the index of the
+    /// one taking the writes.
+    pub fn try_new(backends: Vec<Arc<dyn Repository>>, primary: usize) -> Result<Self, ()> {
+        match primary < backends.len() {
+            true => Ok(Self { backends, primary }),
+            false => Err(()),
+        }
+    }
+
+    fn primary(&self) -> &dyn Repository {
+        self.backends[self.primary].as_ref()
+    }
+
+    /// Keeps the first Pokemon of every number, sorted by number.
+    fn merge<E>(
+        &self,
+        fetch: impl Fn(&dyn Repository) -> Result<Vec<Pokemon>, E>,
+    ) -> Result<Vec<Pokemon>, E> {
+        self.backends
+            .iter()
+            .try_fold(BTreeMap::new(), |mut merged, backend| {
+                match fetch(backend.as_ref()) {
+                    Ok(pokemons) => {
+                        for pokemon in pokemons {
+                            merged.entry(pokemon.number.value()).or_insert(pokemon);
+                        }
+                        Ok(merged)
+                    }
+                    Err(err) => Err(err),
+                }
+            })
+            .map(|merged| merged.into_values().collect())
+    }
+
+    fn fetch_everything(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.merge(|backend| backend.fetch_all(Query::new()))
+    }
+}
+
+impl Repository for FederatedRepository {
+    /// Refuses a number stored by any of the backends, as the Pokemon of the
+    /// primary backend could otherwise hide another one or be hidden.
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        match self.fetch_one(number.clone()) {
+            Ok(_) => Err(InsertError::Conflict),
+            Err(FetchOneError::NotFound) => self.primary().insert(number, name, types),
+            Err(FetchOneError::Unknown) => Err(InsertError::Unknown),
+        }
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        pokemons
+            .into_iter()
+            .map(|p| self.insert(p.number, p.name, p.types))
+            .collect()
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        match self.fetch_all_skipping_invalid(query) {
+            Ok((pokemons, _)) => Ok(pokemons),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// An invalid record hides the Pokemons of the same number in the next
+    /// backends, as the record would if it were valid.
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        let mut pokemons: BTreeMap<u16, Pokemon> = BTreeMap::new();
+        let mut invalid: BTreeMap<u16, InvalidRecord> = BTreeMap::new();
+
+        // The backends are read whole, as a Pokemon they filter out may hide
+        // one of the next backends, and the page is only known once merged.
+        for backend in self.backends.iter() {
+            let (valid, records) = match backend.fetch_all_skipping_invalid(Query::new()) {
+                Ok(listing) => listing,
+                _ => return Err(FetchAllError::Unknown),
+            };
+
+            let shadowed = pokemons
+                .keys()
+                .chain(invalid.keys())
+                .copied()
+                .collect::<Vec<u16>>();
+            for pokemon in valid {
+                if !shadowed.contains(&pokemon.number.value()) {
+                    pokemons.insert(pokemon.number.value(), pokemon);
+                }
+            }
+            for record in records {
+                if !shadowed.contains(&record.number) {
+                    invalid.insert(record.number, record);
+                }
+            }
+        }
+
+        let pokemons = select(pokemons.into_values(), query);
+        Ok((pokemons, invalid.into_values().collect()))
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        self.merge(|backend| backend.fetch_range(from.clone(), to.clone()))
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        let query = Query {
+            pokemon_type: Some(pokemon_type),
+            ..Query::new()
+        };
+        match self.fetch_all(query) {
+            Ok(pokemons) => Ok(pokemons),
+            Err(FetchAllError::Unknown) => Err(FetchByTypeError::Unknown),
+        }
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        match self.fetch_by_name(name) {
+            Ok(_) => Ok(true),
+            Err(FetchByNameError::NotFound) => Ok(false),
+            Err(FetchByNameError::Unknown) => Err(NameExistsError::Unknown),
+        }
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        match self.fetch_everything() {
+            Ok(pokemons) => Ok(pokemons.len() as u32),
+            Err(FetchAllError::Unknown) => Err(CountError::Unknown),
+        }
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        let pokemons = match self.fetch_everything() {
+            Ok(pokemons) => pokemons,
+            Err(FetchAllError::Unknown) => return Err(CountByTypeError::Unknown),
+        };
+
+        Ok(PokemonType::all()
+            .into_iter()
+            .map(|t| {
+                let count = pokemons
+                    .iter()
+                    .filter(|p| p.types.as_slice().contains(&t))
+                    .count();
+                (t, count as u32)
+            })
+            .collect())
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        for backend in self.backends.iter() {
+            match backend.fetch_one(number.clone()) {
+                Err(FetchOneError::NotFound) => continue,
+                res => return res,
+            }
+        }
+        Err(FetchOneError::NotFound)
+    }
+
+    /// A backend may store the name under a number another backend takes
+    /// precedence on, so the name is looked for in the merged Pokemons.
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        let pokemons = match self.fetch_everything() {
+            Ok(pokemons) => pokemons,
+            Err(FetchAllError::Unknown) => return Err(FetchByNameError::Unknown),
+        };
+
+        let name = PokemonName::fold(name.as_str());
+        match pokemons
+            .into_iter()
+            .find(|p| PokemonName::fold(p.name.as_str()) == name)
+        {
+            Some(pokemon) => Ok(pokemon),
+            None => Err(FetchByNameError::NotFound),
+        }
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        self.primary().update_types(number, types)
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        self.primary().update(number, name, types)
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.primary().update_evolutions(number, evolutions)
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.primary().update_abilities(number, abilities)
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.primary().update_measurements(number, height, weight)
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.primary().update_sprite(number, sprite)
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        self.primary().update_class(number, class)
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        self.primary().delete(number)
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        self.primary().delete_by_type(pokemon_type)
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        self.primary().clear()
+    }
+
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        self.merge(|backend| backend.snapshot())
+    }
+
+    fn backend(&self) -> Backend {
+        let kinds = self
+            .backends
+            .iter()
+            .map(|backend| backend.backend().kind)
+            .collect::<Vec<String>>();
+        Backend {
+            kind: kinds.join(","),
+            schema_version: self.primary().backend().schema_version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::pokemon::InMemoryRepository;
+
+    /// Pikachu is in both backends, as a Fire Pokemon in the shared one.
+    fn federated() -> (
+        FederatedRepository,
+        Arc<InMemoryRepository>,
+        Arc<InMemoryRepository>,
+    ) {
+        let private = Arc::new(InMemoryRepository::new());
+        let shared = Arc::new(InMemoryRepository::new());
+        private
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+            .ok();
+        shared
+            .insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::charmander(),
+            )
+            .ok();
+        shared
+            .insert(
+                PokemonNumber::charmander(),
+                PokemonName::charmander(),
+                PokemonTypes::charmander(),
+            )
+            .ok();
+
+        match FederatedRepository::try_new(vec![private.clone(), shared.clone()], 0) {
+            Ok(repo) => (repo, private, shared),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_refuse_a_primary_backend_which_is_not_configured() {
+        let backends: Vec<Arc<dyn Repository>> = vec![Arc::new(InMemoryRepository::new())];
+
+        assert!(FederatedRepository::try_new(backends, 1).is_err());
+    }
+
+    #[test]
+    fn it_should_read_the_pokemon_of_the_first_backend_storing_it() {
+        let (repo, _, _) = federated();
+
+        match repo.fetch_all(Query::new()).as_deref() {
+            Ok([charmander, pikachu]) => {
+                assert!(charmander.number == PokemonNumber::charmander());
+                assert!(pikachu.number == PokemonNumber::pikachu());
+                assert!(pikachu.types.as_slice() == PokemonTypes::pikachu().as_slice());
+            }
+            _ => unreachable!(),
+        };
+        assert!(matches!(repo.count(), Ok(2)));
+        match repo.fetch_by_type(PokemonType::Fire).as_deref() {
+            Ok([charmander]) => assert!(charmander.number == PokemonNumber::charmander()),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_write_to_the_primary_backend_only() {
+        let (repo, private, shared) = federated();
+
+        repo.delete(PokemonNumber::pikachu()).ok();
+        let res = repo.delete(PokemonNumber::charmander());
+
+        assert!(matches!(res, Err(DeleteError::NotFound)));
+        assert!(matches!(private.count(), Ok(0)));
+        assert!(matches!(shared.count(), Ok(2)));
+        assert!(matches!(
+            repo.fetch_one(PokemonNumber::pikachu()).map(|p| p.types),
+            Ok(types) if types.as_slice() == PokemonTypes::charmander().as_slice()
+        ));
+    }
+
+    #[test]
+    fn it_should_refuse_to_insert_a_number_stored_by_another_backend() {
+        let (repo, private, _) = federated();
+
+        let res = repo.insert(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        );
+
+        assert!(matches!(res, Err(InsertError::Conflict)));
+        assert!(matches!(private.count(), Ok(1)));
+    }
+}
@@ -0,0 +1,375 @@
+use crate::domain::entities::{
+    Evolution, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
+use crate::repositories::pokemon::{
+    sift, Backend, ClearError, CountByTypeError, CountError, DeleteByTypeError, DeleteError,
+    FetchAllError, FetchByNameError, FetchByTypeError, FetchOneError, FetchPageError,
+    FetchRangeError, InMemoryRepository, InsertError, InvalidRecord, NameExistsError,
+    PokemonRecord, Query, Repository, SnapshotError, UpdateError,
+};
+use js_sys::{Array, Promise};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{IdbDatabase, IdbRequest, IdbTransactionMode};
+
+const VERSION: u32 = 1;
+const STORE: &str = "pokemons";
+
+enum Write {
+    Put(u16, String),
+    Delete(u16),
+    Clear,
+}
+
+/// Keeps the Pokemons of a browser in an IndexedDB database, read once when
+/// opened. As IndexedDB can't be waited on from the synchronous calls of a
+/// repository, the Pokemons are served from memory and every write is saved
+/// in the background, in the order of the calls.
+pub struct IndexedDbRepository {
+    name: String,
+    memory: InMemoryRepository,
+}
+
+impl IndexedDbRepository {
+    /// Opens the database of this name, creating it when it doesn't exist.
+    pub async fn open(name: &str) -> Result<Self, ()> {
+        let db = match open(name).await {
+            Ok(db) => db,
+            _ => return Err(()),
+        };
+        let records = match db
+            .transaction_with_str(STORE)
+            .and_then(|transaction| transaction.object_store(STORE))
+            .and_then(|store| store.get_all())
+        {
+            Ok(request) => wait(&request).await,
+            _ => Err(()),
+        };
+        db.close();
+
+        let records = match records {
+            Ok(records) => Array::from(&records),
+            _ => return Err(()),
+        };
+        let (pokemons, invalid) = match sift(records.iter().map(|record| {
+            match record
+                .as_string()
+                .map(|json| serde_json::from_str::<PokemonRecord>(&json))
+            {
+                Some(Ok(record)) => record.decode(),
+                _ => Err(InvalidRecord {
+                    number: 0,
+                    reason: "invalid record",
+                }),
+            }
+        })) {
+            Ok(sifted) => sifted,
+            _ => return Err(()),
+        };
+
+        let memory = InMemoryRepository::new().with_invalid(invalid);
+        if memory.insert_many(pokemons).iter().any(Result::is_err) {
+            return Err(());
+        }
+
+        Ok(Self {
+            name: String::from(name),
+            memory,
+        })
+    }
+
+    fn save(&self, writes: Vec<Write>) {
+        if writes.is_empty() {
+            return;
+        }
+
+        let name = self.name.clone();
+        spawn_local(async move {
+            if save(&name, writes).await.is_err() {
+                web_sys::console::error_1(&JsValue::from_str(
+                    "An error occurred while saving the Pokemons",
+                ));
+            }
+        });
+    }
+
+    fn put<E>(&self, res: Result<Pokemon, E>) -> Result<Pokemon, E> {
+        if let Ok(pokemon) = &res {
+            if let Ok(json) = serde_json::to_string(&PokemonRecord::from(pokemon)) {
+                self.save(vec![Write::Put(pokemon.number.value(), json)]);
+            }
+        }
+        res
+    }
+
+    fn delete_all<E>(&self, res: Result<Vec<Pokemon>, E>) -> Result<Vec<Pokemon>, E> {
+        if let Ok(pokemons) = &res {
+            self.save(
+                pokemons
+                    .iter()
+                    .map(|p| Write::Delete(p.number.value()))
+                    .collect(),
+            );
+        }
+        res
+    }
+}
+
+impl Repository for IndexedDbRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        self.put(self.memory.insert(number, name, types))
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        let res = self.memory.insert_many(pokemons);
+        self.save(
+            res.iter()
+                .flatten()
+                .filter_map(|pokemon| {
+                    serde_json::to_string(&PokemonRecord::from(pokemon))
+                        .ok()
+                        .map(|json| Write::Put(pokemon.number.value(), json))
+                })
+                .collect(),
+        );
+        res
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.memory.fetch_all(query)
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        self.memory.fetch_all_skipping_invalid(query)
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        self.memory.fetch_range(from, to)
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        self.memory.fetch_by_type(pokemon_type)
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        self.memory.name_exists(name)
+    }
+
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        self.memory.fetch_page(offset, limit)
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        self.memory.count()
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        self.memory.count_by_type()
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        self.memory.fetch_one(number)
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        self.memory.fetch_by_name(name)
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        self.put(self.memory.update_types(number, types))
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        self.put(self.memory.update(number, name, types))
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.put(self.memory.update_evolutions(number, evolutions))
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.put(self.memory.update_abilities(number, abilities))
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.put(self.memory.update_measurements(number, height, weight))
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.put(self.memory.update_sprite(number, sprite))
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        self.put(self.memory.update_class(number, class))
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        let res = self.memory.delete(number.clone());
+        if res.is_ok() {
+            self.save(vec![Write::Delete(number.value())]);
+        }
+        res
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        self.delete_all(self.memory.delete_by_type(pokemon_type))
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        let res = self.memory.clear();
+        if res.is_ok() {
+            self.save(vec![Write::Clear]);
+        }
+        res
+    }
+
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        self.memory.snapshot()
+    }
+
+    fn backend(&self) -> Backend {
+        Backend {
+            kind: String::from("indexeddb"),
+            schema_version: Some(VERSION),
+        }
+    }
+}
+
+async fn open(name: &str) -> Result<IdbDatabase, ()> {
+    let request = match web_sys::window().map(|window| window.indexed_db()) {
+        Some(Ok(Some(factory))) => match factory.open_with_u32(name, VERSION) {
+            Ok(request) => request,
+            _ => return Err(()),
+        },
+        _ => return Err(()),
+    };
+
+    let upgraded = request.clone();
+    let on_upgrade_needed = Closure::once_into_js(move || {
+        if let Ok(db) = upgraded.result() {
+            db.unchecked_into::<IdbDatabase>()
+                .create_object_store(STORE)
+                .ok();
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade_needed.unchecked_ref()));
+
+    wait(&request)
+        .await
+        .map(|db| db.unchecked_into::<IdbDatabase>())
+}
+
+/// The writes share a transaction, so that they are all saved or none is.
+async fn save(name: &str, writes: Vec<Write>) -> Result<(), ()> {
+    let db = match open(name).await {
+        Ok(db) => db,
+        _ => return Err(()),
+    };
+    let store = match db
+        .transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite)
+        .and_then(|transaction| transaction.object_store(STORE))
+    {
+        Ok(store) => store,
+        _ => {
+            db.close();
+            return Err(());
+        }
+    };
+
+    // Every request is made before waiting for any, as the transaction
+    // commits once it has no request left.
+    let requests = writes
+        .into_iter()
+        .map(|write| match write {
+            Write::Put(number, json) => {
+                store.put_with_key(&JsValue::from_str(&json), &JsValue::from(number))
+            }
+            Write::Delete(number) => store.delete(&JsValue::from(number)),
+            Write::Clear => store.clear(),
+        })
+        .collect::<Result<Vec<IdbRequest>, JsValue>>();
+
+    let res = match requests {
+        Ok(requests) => {
+            let mut res = Ok(());
+            for request in requests.iter() {
+                if wait(request).await.is_err() {
+                    res = Err(());
+                }
+            }
+            res
+        }
+        _ => Err(()),
+    };
+    db.close();
+    res
+}
+
+/// The result of the request, once it succeeded.
+async fn wait(request: &IdbRequest) -> Result<JsValue, ()> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let succeeded = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            resolve
+                .call1(
+                    &JsValue::NULL,
+                    &succeeded.result().unwrap_or(JsValue::UNDEFINED),
+                )
+                .ok();
+        });
+        let on_error = Closure::once_into_js(move || {
+            reject.call0(&JsValue::NULL).ok();
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+
+    match JsFuture::from(promise).await {
+        Ok(result) => Ok(result),
+        _ => Err(()),
+    }
+}
@@ -1 +1,21 @@
+pub mod cancellable;
+pub mod change;
+pub mod cry;
+#[cfg(feature = "server")]
+pub mod diagnostics;
+pub mod federated;
+#[cfg(feature = "wasm")]
+pub mod indexed_db;
+pub mod moves;
+#[cfg(feature = "server")]
+pub mod outbox;
+#[cfg(feature = "server")]
+pub mod plugin;
 pub mod pokemon;
+pub mod read_mode;
+pub mod unit_of_work;
+pub mod usage;
+pub mod webhook;
+pub mod wishlist;
+#[cfg(feature = "server")]
+pub mod write_queue;
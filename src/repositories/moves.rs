@@ -0,0 +1,508 @@
+#[cfg(feature = "server")]
+use crate::domain::entities::Move;
+use crate::domain::entities::{LearnedMove, PokemonNumber};
+#[cfg(feature = "server")]
+use crate::repositories::read_mode::ReadMode;
+#[cfg(feature = "server")]
+use rusqlite::{params, Connection, Error::SqliteFailure, OpenFlags};
+use std::sync::Mutex;
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum DeleteError {
+    NotFound,
+    Unknown,
+}
+
+/// The learnsets of the Pokemons. A move is shared by every Pokemon learning
+/// it, so that its details are the same for all of them.
+pub trait Repository: Send + Sync {
+    /// Adds the move to the learnset of the Pokemon, its details replacing
+    /// those of the move of the same name, if any.
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        learned: LearnedMove,
+    ) -> Result<LearnedMove, InsertError>;
+
+    /// The learnset of the Pokemon, ordered by level and then by name.
+    fn fetch_all(&self, number: PokemonNumber) -> Result<Vec<LearnedMove>, FetchAllError>;
+
+    /// Removes the move from the learnset of the Pokemon, names ignoring case.
+    fn delete(&self, number: PokemonNumber, name: &str) -> Result<(), DeleteError>;
+}
+
+pub struct InMemoryRepository {
+    error: bool,
+    learnsets: Mutex<Vec<(PokemonNumber, LearnedMove)>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let learnsets: Mutex<Vec<(PokemonNumber, LearnedMove)>> = Mutex::new(vec![]);
+        Self {
+            error: false,
+            learnsets,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        learned: LearnedMove,
+    ) -> Result<LearnedMove, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let mut lock = match self.learnsets.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if lock
+            .iter()
+            .any(|(n, l)| *n == number && l.details.is(&learned.details.name))
+        {
+            return Err(InsertError::Conflict);
+        }
+
+        for (_, l) in lock.iter_mut() {
+            if l.details.is(&learned.details.name) {
+                l.details = learned.details.clone();
+            }
+        }
+        lock.push((number, learned.clone()));
+        Ok(learned)
+    }
+
+    fn fetch_all(&self, number: PokemonNumber) -> Result<Vec<LearnedMove>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let lock = match self.learnsets.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut learnset = lock
+            .iter()
+            .filter(|(n, _)| *n == number)
+            .map(|(_, l)| l.clone())
+            .collect::<Vec<LearnedMove>>();
+        learnset.sort_by(|a, b| {
+            a.level.cmp(&b.level).then_with(|| {
+                a.details
+                    .name
+                    .to_lowercase()
+                    .cmp(&b.details.name.to_lowercase())
+            })
+        });
+        Ok(learnset)
+    }
+
+    fn delete(&self, number: PokemonNumber, name: &str) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let mut lock = match self.learnsets.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let index = match lock
+            .iter()
+            .position(|(n, l)| *n == number && l.details.is(name))
+        {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
+        };
+
+        lock.remove(index);
+        Ok(())
+    }
+}
+
+/// Stores the moves in a `moves` table and the learnsets in a `learnsets`
+/// one, both created when missing. A learnset goes along with its Pokemon.
+#[cfg(feature = "server")]
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+#[cfg(feature = "server")]
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match Self::migrate(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    fn migrate(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "pragma foreign_keys = 1;
+            create table if not exists moves (
+                name text primary key collate nocase,
+                type text not null,
+                power integer,
+                accuracy integer,
+                pp integer not null
+            );
+            create table if not exists learnsets (
+                pokemon_number integer not null references pokemons(number) on delete cascade,
+                move_name text not null collate nocase references moves(name) on delete cascade,
+                level integer not null,
+                primary key (pokemon_number, move_name)
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        let connection = match Connection::open_in_memory() {
+            Ok(connection) => connection,
+            _ => panic!("Error while opening the in-memory database"),
+        };
+
+        match connection
+            .execute_batch(
+                "create table pokemons (number integer primary key, name text not null);",
+            )
+            .map_err(|_| ())
+            .and_then(|()| Self::migrate(&connection))
+        {
+            Ok(()) => Self {
+                connection: Mutex::new(connection),
+            },
+            _ => panic!("Error while creating the in-memory database"),
+        }
+    }
+
+    fn learned_move_from_row(
+        name: String,
+        move_type: String,
+        power: Option<u8>,
+        accuracy: Option<u8>,
+        pp: u8,
+        level: u8,
+    ) -> Result<LearnedMove, ()> {
+        match Move::try_new(name, move_type, power, accuracy, pp) {
+            Ok(details) => LearnedMove::try_new(details, level),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for SqliteRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        learned: LearnedMove,
+    ) -> Result<LearnedMove, InsertError> {
+        let mut lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let transaction = match lock.transaction() {
+            Ok(transaction) => transaction,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let details = &learned.details;
+        if transaction
+            .execute(
+                "insert into moves (name, type, power, accuracy, pp) values (?, ?, ?, ?, ?)
+                on conflict (name) do update set
+                type = excluded.type,
+                power = excluded.power,
+                accuracy = excluded.accuracy,
+                pp = excluded.pp",
+                params![
+                    details.name,
+                    details.move_type.as_str(),
+                    details.power,
+                    details.accuracy,
+                    details.pp
+                ],
+            )
+            .is_err()
+        {
+            return Err(InsertError::Unknown);
+        }
+
+        match transaction.execute(
+            "insert into learnsets (pokemon_number, move_name, level)
+            select ?, name, ? from moves where name = ?",
+            params![number.value(), learned.level, details.name],
+        ) {
+            Ok(_) => {}
+            Err(SqliteFailure(_, Some(message)))
+                if message.starts_with("UNIQUE constraint failed: learnsets.") =>
+            {
+                return Err(InsertError::Conflict)
+            }
+            _ => return Err(InsertError::Unknown),
+        };
+
+        match transaction.commit() {
+            Ok(()) => Ok(learned),
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self, number: PokemonNumber) -> Result<Vec<LearnedMove>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select m.name, m.type, m.power, m.accuracy, m.pp, l.level
+            from learnsets l join moves m on m.name = l.move_name
+            where l.pokemon_number = ? order by l.level, m.name",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut rows = match stmt.query([number.value()]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut decoded = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, String>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, Option<u8>>(2),
+                row.get::<usize, Option<u8>>(3),
+                row.get::<usize, u8>(4),
+                row.get::<usize, u8>(5),
+            ) {
+                (Ok(name), Ok(move_type), Ok(power), Ok(accuracy), Ok(pp), Ok(level)) => decoded
+                    .push(
+                        Self::learned_move_from_row(
+                            name.clone(),
+                            move_type,
+                            power,
+                            accuracy,
+                            pp,
+                            level,
+                        )
+                        .map_err(|()| name),
+                    ),
+                _ => return Err(FetchAllError::Unknown),
+            };
+        }
+
+        match ReadMode::current().sift(decoded) {
+            Ok((learnset, invalid)) => {
+                for name in invalid {
+                    eprintln!(
+                        "Skipped the stored move {} of Pokemon #{}: invalid move",
+                        name,
+                        number.value()
+                    );
+                }
+                Ok(learnset)
+            }
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber, name: &str) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.execute(
+            "delete from learnsets where pokemon_number = ? and move_name = ?",
+            params![number.value(), name.trim()],
+        ) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Move;
+    use std::sync::Arc;
+
+    // Every backend must pass these, the sqlite one on a database holding
+    // Pikachu and Charmander.
+    fn repositories() -> Vec<Arc<dyn Repository>> {
+        vec![
+            Arc::new(InMemoryRepository::new()),
+            #[cfg(feature = "server")]
+            Arc::new(sqlite()),
+        ]
+    }
+
+    #[cfg(feature = "server")]
+    fn sqlite() -> SqliteRepository {
+        let repo = SqliteRepository::in_memory();
+        if let Ok(lock) = repo.connection.lock() {
+            lock.execute_batch(
+                "insert into pokemons (number, name) values (25, 'Pikachu'), (4, 'Charmander');",
+            )
+            .ok();
+        }
+        repo
+    }
+
+    fn learned(details: Move, level: u8) -> LearnedMove {
+        match LearnedMove::try_new(details, level) {
+            Ok(learned) => learned,
+            _ => unreachable!(),
+        }
+    }
+
+    fn names(repo: &Arc<dyn Repository>, number: PokemonNumber) -> Vec<(String, u8)> {
+        match repo.fetch_all(number) {
+            Ok(learnset) => learnset
+                .into_iter()
+                .map(|l| (l.details.name, l.level))
+                .collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_order_a_learnset_by_level_and_then_by_name() {
+        for repo in repositories() {
+            for (details, level) in [
+                (Move::thunderbolt(), 26),
+                (Move::growl(), 1),
+                (
+                    Move {
+                        name: String::from("Agility"),
+                        ..Move::thunderbolt()
+                    },
+                    26,
+                ),
+            ] {
+                assert!(repo
+                    .insert(PokemonNumber::pikachu(), learned(details, level))
+                    .is_ok());
+            }
+
+            assert_eq!(
+                names(&repo, PokemonNumber::pikachu()),
+                vec![
+                    (String::from("Growl"), 1),
+                    (String::from("Agility"), 26),
+                    (String::from("Thunderbolt"), 26),
+                ]
+            );
+            assert!(names(&repo, PokemonNumber::charmander()).is_empty());
+        }
+    }
+
+    #[test]
+    fn it_should_refuse_a_move_the_pokemon_already_learns() {
+        for repo in repositories() {
+            assert!(repo
+                .insert(PokemonNumber::pikachu(), learned(Move::thunderbolt(), 26))
+                .is_ok());
+
+            let res = repo.insert(
+                PokemonNumber::pikachu(),
+                learned(
+                    Move {
+                        name: String::from("THUNDERBOLT"),
+                        ..Move::thunderbolt()
+                    },
+                    30,
+                ),
+            );
+
+            assert!(matches!(res, Err(InsertError::Conflict)));
+        }
+    }
+
+    #[test]
+    fn it_should_share_the_details_of_a_move_between_pokemons() {
+        for repo in repositories() {
+            assert!(repo
+                .insert(PokemonNumber::pikachu(), learned(Move::thunderbolt(), 26))
+                .is_ok());
+            assert!(repo
+                .insert(
+                    PokemonNumber::charmander(),
+                    learned(
+                        Move {
+                            power: Some(95),
+                            ..Move::thunderbolt()
+                        },
+                        40
+                    )
+                )
+                .is_ok());
+
+            match repo.fetch_all(PokemonNumber::pikachu()) {
+                Ok(learnset) => assert_eq!(learnset[0].details.power, Some(95)),
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_remove_a_move_from_a_single_learnset() {
+        for repo in repositories() {
+            for number in [PokemonNumber::pikachu(), PokemonNumber::charmander()] {
+                assert!(repo
+                    .insert(number, learned(Move::thunderbolt(), 26))
+                    .is_ok());
+            }
+
+            assert!(repo.delete(PokemonNumber::pikachu(), "Thunderbolt").is_ok());
+            assert!(matches!(
+                repo.delete(PokemonNumber::pikachu(), "Thunderbolt"),
+                Err(DeleteError::NotFound)
+            ));
+
+            assert!(names(&repo, PokemonNumber::pikachu()).is_empty());
+            assert_eq!(names(&repo, PokemonNumber::charmander()).len(), 1);
+        }
+    }
+}
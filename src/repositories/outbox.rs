@@ -0,0 +1,274 @@
+use crate::domain::entities::{Pokemon, PokemonNumber, WebhookEvent};
+use rusqlite::{params, Connection, OpenFlags};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// An event stored along with the write it tells about, waiting to be
+/// delivered.
+pub struct Message {
+    pub id: u64,
+    pub event: WebhookEvent,
+    pub data: serde_json::Value,
+}
+
+pub enum PushError {
+    Unknown,
+}
+
+pub enum FetchPendingError {
+    Unknown,
+}
+
+pub enum RemoveError {
+    Unknown,
+}
+
+pub trait Repository: Send + Sync {
+    fn push(&self, event: WebhookEvent, data: serde_json::Value) -> Result<(), PushError>;
+
+    /// The oldest events, at most `limit` of them, to `remove` once
+    /// delivered.
+    fn fetch_pending(&self, limit: u32) -> Result<Vec<Message>, FetchPendingError>;
+
+    fn remove(&self, id: u64) -> Result<(), RemoveError>;
+}
+
+/// The data of the events about a Pokemon, as `PokedexService` publishes
+/// them.
+pub fn pokemon_data(pokemon: &Pokemon) -> serde_json::Value {
+    serde_json::json!({
+        "number": pokemon.number.value(),
+        "name": pokemon.name.as_str(),
+        "types": Vec::<String>::from(pokemon.types.clone()),
+    })
+}
+
+pub fn deleted_data(number: &PokemonNumber) -> serde_json::Value {
+    serde_json::json!({ "number": number.value() })
+}
+
+pub struct InMemoryRepository {
+    messages: Mutex<(u64, VecDeque<Message>)>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self {
+            messages: Mutex::new((0, VecDeque::new())),
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn push(&self, event: WebhookEvent, data: serde_json::Value) -> Result<(), PushError> {
+        let mut lock = match self.messages.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PushError::Unknown),
+        };
+
+        let (next_id, messages) = &mut *lock;
+        *next_id += 1;
+        messages.push_back(Message {
+            id: *next_id,
+            event,
+            data,
+        });
+        Ok(())
+    }
+
+    fn fetch_pending(&self, limit: u32) -> Result<Vec<Message>, FetchPendingError> {
+        match self.messages.lock() {
+            Ok(lock) => Ok(lock
+                .1
+                .iter()
+                .take(limit as usize)
+                .map(|message| Message {
+                    id: message.id,
+                    event: message.event,
+                    data: message.data.clone(),
+                })
+                .collect::<Vec<Message>>()),
+            _ => Err(FetchPendingError::Unknown),
+        }
+    }
+
+    fn remove(&self, id: u64) -> Result<(), RemoveError> {
+        match self.messages.lock() {
+            Ok(mut lock) => {
+                lock.1.retain(|message| message.id != id);
+                Ok(())
+            }
+            _ => Err(RemoveError::Unknown),
+        }
+    }
+}
+
+/// Reads the `outbox` table the sqlite Pokemon repository writes into when
+/// built `with_outbox`, through a connection of its own.
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match create_table(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        match Connection::open_in_memory() {
+            Ok(connection) if create_table(&connection).is_ok() => Self {
+                connection: Mutex::new(connection),
+            },
+            _ => panic!("Error while opening the in-memory database"),
+        }
+    }
+}
+
+impl Repository for SqliteRepository {
+    fn push(&self, event: WebhookEvent, data: serde_json::Value) -> Result<(), PushError> {
+        match self.connection.lock() {
+            Ok(lock) => match insert_row(&lock, event, &data) {
+                Ok(()) => Ok(()),
+                _ => Err(PushError::Unknown),
+            },
+            _ => Err(PushError::Unknown),
+        }
+    }
+
+    fn fetch_pending(&self, limit: u32) -> Result<Vec<Message>, FetchPendingError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchPendingError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select id, event, data from outbox order by id limit ?")
+        {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchPendingError::Unknown),
+        };
+
+        let mut rows = match stmt.query(params![limit]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchPendingError::Unknown),
+        };
+
+        let mut messages = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, u64>(0),
+                row.get::<usize, String>(1).map(WebhookEvent::try_from),
+                row.get::<usize, String>(2)
+                    .map(|data| serde_json::from_str::<serde_json::Value>(&data)),
+            ) {
+                (Ok(id), Ok(Ok(event)), Ok(Ok(data))) => messages.push(Message { id, event, data }),
+                _ => return Err(FetchPendingError::Unknown),
+            }
+        }
+
+        Ok(messages)
+    }
+
+    fn remove(&self, id: u64) -> Result<(), RemoveError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RemoveError::Unknown),
+        };
+
+        match lock.execute("delete from outbox where id = ?", params![id]) {
+            Ok(_) => Ok(()),
+            _ => Err(RemoveError::Unknown),
+        }
+    }
+}
+
+pub(crate) fn create_table(connection: &Connection) -> Result<(), ()> {
+    match connection.execute_batch(
+        "create table if not exists outbox (
+            id integer primary key autoincrement,
+            event text not null,
+            data text not null
+        )",
+    ) {
+        Ok(()) => Ok(()),
+        _ => Err(()),
+    }
+}
+
+/// Stores the event through `connection`, so that it belongs to the
+/// transaction the connection is in, if any.
+pub(crate) fn insert_row(
+    connection: &Connection,
+    event: WebhookEvent,
+    data: &serde_json::Value,
+) -> Result<(), ()> {
+    match connection.execute(
+        "insert into outbox (event, data) values (?, ?)",
+        params![String::from(event), data.to_string()],
+    ) {
+        Ok(_) => Ok(()),
+        _ => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonTypes};
+
+    #[test]
+    fn it_should_give_the_events_back_in_order() {
+        let outboxes: Vec<Box<dyn Repository>> = vec![
+            Box::new(InMemoryRepository::new()),
+            Box::new(SqliteRepository::in_memory()),
+        ];
+
+        for outbox in outboxes {
+            outbox
+                .push(
+                    WebhookEvent::PokemonCreated,
+                    pokemon_data(&Pokemon::new(
+                        PokemonNumber::pikachu(),
+                        PokemonName::pikachu(),
+                        PokemonTypes::pikachu(),
+                    )),
+                )
+                .ok();
+            outbox
+                .push(
+                    WebhookEvent::PokemonDeleted,
+                    deleted_data(&PokemonNumber::pikachu()),
+                )
+                .ok();
+
+            let id = match outbox.fetch_pending(10).as_deref() {
+                Ok([created, deleted]) => {
+                    assert!(matches!(created.event, WebhookEvent::PokemonCreated));
+                    assert_eq!(created.data["name"], "Pikachu");
+                    assert_eq!(created.data["types"][0], "Electric");
+                    assert!(matches!(deleted.event, WebhookEvent::PokemonDeleted));
+                    assert_eq!(deleted.data["number"], 25);
+                    created.id
+                }
+                _ => unreachable!(),
+            };
+            outbox.remove(id).ok();
+            assert!(matches!(
+                outbox.fetch_pending(10).as_deref(),
+                Ok([deleted]) if matches!(deleted.event, WebhookEvent::PokemonDeleted)
+            ));
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use crate::domain::entities::{
+    Evolution, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
+use crate::repositories::pokemon::{
+    Backend, ClearError, CountByTypeError, CountError, DeleteByTypeError, DeleteError,
+    FetchAllError, FetchByNameError, FetchByTypeError, FetchOneError, FetchPageError,
+    FetchRangeError, InsertError, InvalidRecord, NameExistsError, Query, Repository, SnapshotError,
+    TransactionError, UpdateError,
+};
+use libloading::Library;
+
+/// Version of this crate, a plugin must have been built against the same one.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Builds the backend from the string given to `--backend-config`.
+pub type Constructor = fn(&str) -> Result<Box<dyn Repository>, ()>;
+
+/// What a plugin exports under the `POKEDEX_BACKEND` symbol, see
+/// `declare_backend!`.
+pub struct Declaration {
+    pub version: &'static str,
+    pub constructor: Constructor,
+}
+
+/// Exports a backend from a `cdylib` crate so that `--backend-plugin` can
+/// load it:
+///
+/// ```ignore
+/// fn connect(config: &str) -> Result<Box<dyn Repository>, ()> { ... }
+///
+/// pokedex::declare_backend!(connect);
+/// ```
+///
+/// Trait objects cross the library boundary as they are, so the plugin and
+/// the binary must be built with the same compiler and the same version of
+/// this crate.
+#[macro_export]
+macro_rules! declare_backend {
+    ($constructor:path) => {
+        #[no_mangle]
+        pub static POKEDEX_BACKEND: $crate::repositories::plugin::Declaration =
+            $crate::repositories::plugin::Declaration {
+                version: $crate::repositories::plugin::VERSION,
+                constructor: $constructor,
+            };
+    };
+}
+
+/// A backend loaded from a shared library. The library stays loaded for as
+/// long as the backend lives.
+pub struct PluginRepository {
+    inner: Box<dyn Repository>,
+    _library: Library,
+}
+
+impl PluginRepository {
+    pub fn try_load(path: &str, config: &str) -> Result<Self, ()> {
+        // Safety: loading runs the library initializers and trusts the type
+        // of the exported symbol, which `declare_backend!` guarantees.
+        let library = match unsafe { Library::new(path) } {
+            Ok(library) => library,
+            _ => return Err(()),
+        };
+        let declaration = match unsafe { library.get::<*const Declaration>(b"POKEDEX_BACKEND\0") } {
+            Ok(symbol) => unsafe { &**symbol },
+            _ => return Err(()),
+        };
+
+        if declaration.version != VERSION {
+            return Err(());
+        }
+
+        match (declaration.constructor)(config) {
+            Ok(inner) => Ok(Self {
+                inner,
+                _library: library,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Repository for PluginRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        self.inner.insert(number, name, types)
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        self.inner.insert_many(pokemons)
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.inner.fetch_all(query)
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        self.inner.fetch_all_skipping_invalid(query)
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        self.inner.fetch_range(from, to)
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        self.inner.fetch_by_type(pokemon_type)
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        self.inner.name_exists(name)
+    }
+
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        self.inner.fetch_page(offset, limit)
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        self.inner.count()
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        self.inner.count_by_type()
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        self.inner.fetch_one(number)
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        self.inner.fetch_by_name(name)
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        self.inner.update_types(number, types)
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        self.inner.update(number, name, types)
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.inner.update_evolutions(number, evolutions)
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.inner.update_abilities(number, abilities)
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.inner.update_measurements(number, height, weight)
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        self.inner.update_sprite(number, sprite)
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        self.inner.update_class(number, class)
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        self.inner.delete(number)
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        self.inner.delete_by_type(pokemon_type)
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        self.inner.clear()
+    }
+
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        self.inner.snapshot()
+    }
+
+    fn begin(&self) -> Result<(), TransactionError> {
+        self.inner.begin()
+    }
+
+    fn commit(&self) -> Result<(), TransactionError> {
+        self.inner.commit()
+    }
+
+    fn rollback(&self) -> Result<(), TransactionError> {
+        self.inner.rollback()
+    }
+
+    fn backend(&self) -> Backend {
+        Backend {
+            kind: String::from("plugin"),
+            schema_version: self.inner.backend().schema_version,
+        }
+    }
+}
@@ -1,7 +1,75 @@
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+#[cfg(feature = "server")]
+use crate::clock::now;
+#[cfg(any(feature = "server", feature = "wasm"))]
+use crate::domain::entities::Ability;
+#[cfg(feature = "server")]
+use crate::domain::entities::{ChangeKind, WebhookEvent};
+use crate::domain::entities::{
+    Evolution, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
+#[cfg(feature = "server")]
+use crate::repositories::change;
+#[cfg(feature = "server")]
+use crate::repositories::outbox;
+#[cfg(any(feature = "server", feature = "wasm"))]
+use crate::repositories::read_mode::ReadMode;
+#[cfg(feature = "server")]
+use crate::repositories::write_queue::{self, Write};
+#[cfg(feature = "server")]
+use crate::trace;
+#[cfg(feature = "server")]
+use rusqlite::functions::FunctionFlags;
+#[cfg(feature = "server")]
 use rusqlite::{params, params_from_iter, Connection, Error::SqliteFailure, OpenFlags};
-use serde::Deserialize;
-use std::sync::{Mutex, MutexGuard};
+#[cfg(feature = "server")]
+use serde::de::{DeserializeOwned, IgnoredAny};
+#[cfg(any(feature = "server", feature = "wasm"))]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "server")]
+use std::sync::{Condvar, MutexGuard};
+#[cfg(feature = "server")]
+use std::thread::{self, ThreadId};
+
+#[derive(Clone, Copy)]
+pub enum SortBy {
+    Number,
+    Name,
+    /// By the name of the first type.
+    Type,
+}
+
+/// What `fetch_all` returns and in which order. The name filter matches a
+/// case-insensitive substring, names are sorted case-insensitively and ties
+/// are always broken by ascending number.
+pub struct Query {
+    pub sort_by: SortBy,
+    pub descending: bool,
+    pub name: Option<String>,
+    pub pokemon_type: Option<PokemonType>,
+    pub class: Option<PokemonClass>,
+    /// How many of the sorted and filtered Pokemons to skip, then how many
+    /// to return at most.
+    pub offset: u32,
+    pub limit: Option<u32>,
+}
+
+impl Query {
+    pub fn new() -> Self {
+        Self {
+            sort_by: SortBy::Number,
+            descending: false,
+            name: None,
+            pokemon_type: None,
+            class: None,
+            offset: 0,
+            limit: None,
+        }
+    }
+}
 
 pub enum InsertError {
     Conflict,
@@ -12,16 +80,93 @@ pub enum FetchAllError {
     Unknown,
 }
 
+pub enum FetchRangeError {
+    Unknown,
+}
+
+pub enum FetchByTypeError {
+    Unknown,
+}
+
+pub enum NameExistsError {
+    Unknown,
+}
+
+pub enum FetchPageError {
+    Unknown,
+}
+
+pub enum CountError {
+    Unknown,
+}
+
+pub enum CountByTypeError {
+    Unknown,
+}
+
 pub enum FetchOneError {
     NotFound,
     Unknown,
 }
 
+pub enum FetchByNameError {
+    NotFound,
+    Unknown,
+}
+
+pub enum UpdateError {
+    NotFound,
+    Unknown,
+}
+
 pub enum DeleteError {
     NotFound,
     Unknown,
 }
 
+pub enum DeleteByTypeError {
+    Unknown,
+}
+
+pub enum ClearError {
+    Unknown,
+}
+
+pub enum SnapshotError {
+    Unknown,
+}
+
+/// A stored record which isn't a valid Pokemon.
+#[derive(Clone)]
+pub struct InvalidRecord {
+    pub number: u16,
+    pub reason: &'static str,
+}
+
+pub enum TransactionError {
+    Unsupported,
+    Unknown,
+}
+
+/// What a repository keeps the Pokemons in, as `/health` reports it.
+pub struct Backend {
+    /// `memory`, `sqlite`, `airtable`, `http`, `indexeddb` or `plugin`, the
+    /// remote and the local ones joined by a `+` when mirrored, the federated
+    /// ones by a `,` in order of precedence.
+    pub kind: String,
+    /// The migrations the storage went through, for backends having some.
+    pub schema_version: Option<u32>,
+}
+
+impl Backend {
+    fn new(kind: &str) -> Self {
+        Self {
+            kind: String::from(kind),
+            schema_version: None,
+        }
+    }
+}
+
 pub trait Repository: Send + Sync {
     fn insert(
         &self,
@@ -30,15 +175,472 @@ pub trait Repository: Send + Sync {
         types: PokemonTypes,
     ) -> Result<Pokemon, InsertError>;
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError>;
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>>;
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError>;
+
+    /// Lists like `fetch_all`, along with the invalid records a lenient
+    /// `ReadMode` left out. Backends which can't store such records leave
+    /// none out.
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        match self.fetch_all(query) {
+            Ok(pokemons) => Ok((pokemons, vec![])),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError>;
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError>;
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError>;
+
+    /// At most `limit` Pokemons sorted by number, after skipping the first
+    /// `offset` ones. Backends without such reads list everything.
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        match self.fetch_all(Query::new()) {
+            Ok(pokemons) => Ok(pokemons
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect()),
+            Err(FetchAllError::Unknown) => Err(FetchPageError::Unknown),
+        }
+    }
+
+    fn count(&self) -> Result<u32, CountError>;
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError>;
 
     fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError>;
 
+    /// Ignores case and accents. The lowest number wins when several names match.
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError>;
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError>;
+
+    /// Replaces both the name and the types of the Pokemon.
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError>;
+
+    /// Replaces what the Pokemon evolves into.
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError>;
+
+    /// Replaces the abilities of the Pokemon, none removing them.
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError>;
+
+    /// Replaces the height and the weight of the Pokemon, none removing
+    /// them.
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError>;
+
+    /// Replaces the sprite URL of the Pokemon, none removing it.
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError>;
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError>;
+
     fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError>;
+
+    /// Deletes every Pokemon having this type at once and returns them,
+    /// sorted by number.
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError>;
+
+    /// Deletes every Pokemon and returns them, sorted by number. The rows
+    /// which aren't valid Pokemons are deleted too, without being returned.
+    /// Only the `clear_pokemons` use case calls it, once confirmed.
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError>;
+
+    /// Every Pokemon as of a single point in time, sorted by number, even
+    /// while other clients write. Backends without such reads return a
+    /// single listing.
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        match self.fetch_all(Query::new()) {
+            Ok(pokemons) => Ok(pokemons),
+            Err(FetchAllError::Unknown) => Err(SnapshotError::Unknown),
+        }
+    }
+
+    /// Starts a transaction holding the next calls of the current thread
+    /// until `commit` or `rollback`. Other threads wait for it to end.
+    /// See `unit_of_work::run` for backends without transactions.
+    fn begin(&self) -> Result<(), TransactionError> {
+        Err(TransactionError::Unsupported)
+    }
+
+    fn commit(&self) -> Result<(), TransactionError> {
+        Err(TransactionError::Unsupported)
+    }
+
+    fn rollback(&self) -> Result<(), TransactionError> {
+        Err(TransactionError::Unsupported)
+    }
+
+    /// The same storage without a cancellation refusing the calls, for the
+    /// undoing of a unit of work which must go on once cancelled.
+    fn uncancellable(&self) -> Option<Arc<dyn Repository>> {
+        None
+    }
+
+    fn backend(&self) -> Backend {
+        Backend::new("unknown")
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+/// An evolution as the backends store it, a row of its own in sqlite and
+/// JSON for the other ones.
+#[derive(Deserialize, Serialize)]
+struct EvolutionRecord {
+    target: u16,
+    method: String,
+    level: Option<u8>,
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+impl From<&Evolution> for EvolutionRecord {
+    fn from(evolution: &Evolution) -> Self {
+        Self {
+            target: evolution.target.value(),
+            method: String::from(evolution.method),
+            level: evolution.level,
+        }
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+/// An ability as the backends store it, with no ability at all standing
+/// for abilities not set yet.
+#[derive(Deserialize, Serialize)]
+struct AbilityRecord {
+    name: String,
+    #[serde(default)]
+    hidden: bool,
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+impl From<&Ability> for AbilityRecord {
+    fn from(ability: &Ability) -> Self {
+        Self {
+            name: ability.name.clone(),
+            hidden: ability.hidden,
+        }
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+fn ability_records(abilities: &Option<PokemonAbilities>) -> Vec<AbilityRecord> {
+    abilities
+        .as_ref()
+        .map(|abilities| {
+            abilities
+                .as_slice()
+                .iter()
+                .map(AbilityRecord::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+/// The height in meters and the weight in kilograms as the backends store
+/// them, none standing for a measurement not set yet.
+#[derive(Default)]
+struct MeasurementsRecord {
+    height: Option<f64>,
+    weight: Option<f64>,
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+impl MeasurementsRecord {
+    fn new(height: Option<PokemonHeight>, weight: Option<PokemonWeight>) -> Self {
+        Self {
+            height: height.map(f64::from),
+            weight: weight.map(f64::from),
+        }
+    }
+
+    fn decode(self) -> Result<(Option<PokemonHeight>, Option<PokemonWeight>), ()> {
+        match (
+            self.height.map(PokemonHeight::try_from).transpose(),
+            self.weight.map(PokemonWeight::try_from).transpose(),
+        ) {
+            (Ok(height), Ok(weight)) => Ok((height, weight)),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A whole Pokemon as a single JSON document, for the browser storage and
+/// the change log.
+#[cfg(any(feature = "server", feature = "wasm"))]
+#[derive(Deserialize, Serialize)]
+pub(super) struct PokemonRecord {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    #[serde(default)]
+    evolutions: Vec<EvolutionRecord>,
+    #[serde(default)]
+    abilities: Vec<AbilityRecord>,
+    #[serde(default)]
+    height: Option<f64>,
+    #[serde(default)]
+    weight: Option<f64>,
+    #[serde(default)]
+    sprite: Option<String>,
+    #[serde(default)]
+    class: Option<String>,
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+impl From<&Pokemon> for PokemonRecord {
+    fn from(pokemon: &Pokemon) -> Self {
+        let measurements = MeasurementsRecord::new(pokemon.height, pokemon.weight);
+        Self {
+            number: pokemon.number.value(),
+            name: String::from(pokemon.name.as_str()),
+            types: Vec::<String>::from(pokemon.types.clone()),
+            evolutions: pokemon
+                .evolutions
+                .iter()
+                .map(EvolutionRecord::from)
+                .collect(),
+            abilities: ability_records(&pokemon.abilities),
+            height: measurements.height,
+            weight: measurements.weight,
+            sprite: sprite_record(&pokemon.sprite),
+            class: class_record(pokemon.class),
+        }
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+impl PokemonRecord {
+    pub(super) fn decode(self) -> Result<Pokemon, InvalidRecord> {
+        decode(
+            self.number,
+            self.name,
+            self.types,
+            self.evolutions,
+            self.abilities,
+            MeasurementsRecord {
+                height: self.height,
+                weight: self.weight,
+            },
+            self.sprite,
+            self.class,
+        )
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+fn sprite_record(sprite: &Option<PokemonSprite>) -> Option<String> {
+    sprite.as_ref().map(|sprite| String::from(sprite.as_str()))
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+// The normal Pokemons are stored without a class, as they were before there
+// was one.
+fn class_record(class: PokemonClass) -> Option<String> {
+    match class {
+        PokemonClass::Normal => None,
+        class => Some(String::from(class)),
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+fn decode_class(class: Option<String>) -> Result<PokemonClass, ()> {
+    match class {
+        Some(class) => PokemonClass::try_from(class),
+        None => Ok(PokemonClass::Normal),
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+// Stored records are checked field by field, so that an invalid one tells
+// what is wrong with it.
+#[allow(clippy::too_many_arguments)]
+fn decode(
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    evolutions: Vec<EvolutionRecord>,
+    abilities: Vec<AbilityRecord>,
+    measurements: MeasurementsRecord,
+    sprite: Option<String>,
+    class: Option<String>,
+) -> Result<Pokemon, InvalidRecord> {
+    let invalid = |reason| InvalidRecord { number, reason };
+    match (
+        PokemonNumber::try_from(number),
+        PokemonName::try_from(name),
+        PokemonTypes::try_from(types),
+        decode_evolutions(evolutions),
+        decode_abilities(abilities),
+        measurements.decode(),
+        sprite.map(PokemonSprite::try_from).transpose(),
+        decode_class(class),
+    ) {
+        (
+            Ok(number),
+            Ok(name),
+            Ok(types),
+            Ok(evolutions),
+            Ok(abilities),
+            Ok((height, weight)),
+            Ok(sprite),
+            Ok(class),
+        ) => Ok(Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class)),
+        (Err(()), _, _, _, _, _, _, _) => Err(invalid("invalid number")),
+        (_, Err(()), _, _, _, _, _, _) => Err(invalid("invalid name")),
+        (_, _, Err(()), _, _, _, _, _) => Err(invalid("invalid types")),
+        (_, _, _, Err(()), _, _, _, _) => Err(invalid("invalid evolutions")),
+        (_, _, _, _, Err(()), _, _, _) => Err(invalid("invalid abilities")),
+        (_, _, _, _, _, Err(()), _, _) => Err(invalid("invalid measurements")),
+        (_, _, _, _, _, _, Err(()), _) => Err(invalid("invalid sprite")),
+        (_, _, _, _, _, _, _, Err(())) => Err(invalid("invalid class")),
+    }
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+fn decode_evolutions(evolutions: Vec<EvolutionRecord>) -> Result<Vec<Evolution>, ()> {
+    evolutions
+        .into_iter()
+        .map(|e| Evolution::try_new(e.target, e.method, e.level))
+        .collect()
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+fn decode_abilities(abilities: Vec<AbilityRecord>) -> Result<Option<PokemonAbilities>, ()> {
+    if abilities.is_empty() {
+        return Ok(None);
+    }
+
+    abilities
+        .into_iter()
+        .map(|a| Ability::try_new(a.name, a.hidden))
+        .collect::<Result<Vec<Ability>, ()>>()
+        .and_then(PokemonAbilities::try_from)
+        .map(Some)
+}
+
+#[cfg(any(feature = "server", feature = "wasm"))]
+// Applies the read mode to decoded records, logging the ones left out.
+pub(super) fn sift<T>(
+    records: impl IntoIterator<Item = Result<T, InvalidRecord>>,
+) -> Result<(Vec<T>, Vec<InvalidRecord>), ()> {
+    let (valid, invalid) = match ReadMode::current().sift(records) {
+        Ok(sifted) => sifted,
+        Err(()) => return Err(()),
+    };
+
+    for record in invalid.iter() {
+        eprintln!(
+            "Skipped the stored Pokemon #{}: {}",
+            record.number, record.reason
+        );
+    }
+
+    Ok((valid, invalid))
+}
+
+/// Filters, sorts and pages as `Query` tells, for the backends which can't
+/// do it themselves.
+pub(super) fn select(pokemons: impl IntoIterator<Item = Pokemon>, query: Query) -> Vec<Pokemon> {
+    let name = query.name.map(|name| name.to_lowercase());
+    let mut pokemons = pokemons
+        .into_iter()
+        .filter(|p| match &name {
+            Some(name) => p.name.as_str().to_lowercase().contains(name),
+            None => true,
+        })
+        .filter(|p| match &query.pokemon_type {
+            Some(pokemon_type) => p.types.as_slice().contains(pokemon_type),
+            None => true,
+        })
+        .filter(|p| match query.class {
+            Some(class) => p.class == class,
+            None => true,
+        })
+        .collect::<Vec<Pokemon>>();
+
+    sort(&mut pokemons, query.sort_by, query.descending);
+    pokemons
+        .into_iter()
+        .skip(query.offset as usize)
+        .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect::<Vec<Pokemon>>()
+}
+
+fn sort(pokemons: &mut [Pokemon], sort_by: SortBy, descending: bool) {
+    pokemons.sort_by(|a, b| {
+        let ordering = match sort_by {
+            SortBy::Number => a.number.cmp(&b.number),
+            SortBy::Name => a
+                .name
+                .as_str()
+                .to_lowercase()
+                .cmp(&b.name.as_str().to_lowercase()),
+            SortBy::Type => a
+                .types
+                .as_slice()
+                .first()
+                .map(PokemonType::as_str)
+                .cmp(&b.types.as_slice().first().map(PokemonType::as_str)),
+        };
+        let ordering = if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        ordering.then_with(|| a.number.cmp(&b.number))
+    });
 }
 
 pub struct InMemoryRepository {
     error: bool,
+    // Records left out of a copy of another backend.
+    invalid: Vec<InvalidRecord>,
     pokemons: Mutex<Vec<Pokemon>>,
 }
 
@@ -47,6 +649,7 @@ impl InMemoryRepository {
         let pokemons: Mutex<Vec<Pokemon>> = Mutex::new(vec![]);
         Self {
             error: false,
+            invalid: vec![],
             pokemons,
         }
     }
@@ -58,6 +661,13 @@ impl InMemoryRepository {
             ..self
         }
     }
+
+    /// Pretends to store these invalid records along with the Pokemons, or
+    /// holds the ones of a copy of another backend.
+    #[cfg(any(test, feature = "wasm"))]
+    pub fn with_invalid(self, invalid: Vec<InvalidRecord>) -> Self {
+        Self { invalid, ..self }
+    }
 }
 
 impl Repository for InMemoryRepository {
@@ -85,7 +695,37 @@ impl Repository for InMemoryRepository {
         Ok(pokemon)
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        if self.error {
+            return pokemons
+                .into_iter()
+                .map(|_| Err(InsertError::Unknown))
+                .collect();
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => {
+                return pokemons
+                    .into_iter()
+                    .map(|_| Err(InsertError::Unknown))
+                    .collect()
+            }
+        };
+
+        pokemons
+            .into_iter()
+            .map(|pokemon| {
+                if lock.iter().any(|p| p.number == pokemon.number) {
+                    return Err(InsertError::Conflict);
+                }
+                lock.push(pokemon.clone());
+                Ok(pokemon)
+            })
+            .collect()
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
         if self.error {
             return Err(FetchAllError::Unknown);
         }
@@ -95,85 +735,557 @@ impl Repository for InMemoryRepository {
             _ => return Err(FetchAllError::Unknown),
         };
 
-        let mut pokemons = lock.to_vec();
-        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
-        Ok(pokemons)
+        Ok(select(lock.iter().cloned(), query))
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        match self.fetch_all(query) {
+            Ok(pokemons) => Ok((pokemons, self.invalid.clone())),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
         if self.error {
-            return Err(FetchOneError::Unknown);
+            return Err(FetchRangeError::Unknown);
         }
 
         let lock = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(FetchOneError::Unknown),
+            _ => return Err(FetchRangeError::Unknown),
         };
 
-        match lock.iter().find(|p| p.number == number) {
-            Some(pokemon) => Ok(pokemon.clone()),
-            None => Err(FetchOneError::NotFound),
-        }
+        let mut pokemons = lock
+            .iter()
+            .filter(|p| p.number >= from && p.number <= to)
+            .cloned()
+            .collect::<Vec<Pokemon>>();
+        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(pokemons)
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
         if self.error {
-            return Err(DeleteError::Unknown);
+            return Err(FetchByTypeError::Unknown);
         }
 
-        let mut lock = match self.pokemons.lock() {
+        let lock = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(DeleteError::Unknown),
+            _ => return Err(FetchByTypeError::Unknown),
         };
 
-        let index = match lock.iter().position(|p| p.number == number) {
-            Some(index) => index,
-            None => return Err(DeleteError::NotFound),
+        let mut pokemons = lock
+            .iter()
+            .filter(|p| p.types.as_slice().contains(&pokemon_type))
+            .cloned()
+            .collect::<Vec<Pokemon>>();
+        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(pokemons)
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        if self.error {
+            return Err(NameExistsError::Unknown);
+        }
+
+        let lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(NameExistsError::Unknown),
         };
 
-        lock.remove(index);
-        Ok(())
+        let name = String::from(name).to_lowercase();
+        Ok(lock.iter().any(|p| p.name.as_str().to_lowercase() == name))
     }
-}
 
-pub struct AirtableRepository {
-    url: String,
-    auth_header: String,
-}
+    fn count(&self) -> Result<u32, CountError> {
+        if self.error {
+            return Err(CountError::Unknown);
+        }
 
-impl AirtableRepository {
-    pub fn try_new(api_key: &str, workspace_id: &str) -> Result<Self, ()> {
-        let url = format!("https://api.airtable.com/v0/{}/pokemons", workspace_id);
-        let auth_header = format!("Bearer {}", api_key);
+        match self.pokemons.lock() {
+            Ok(lock) => Ok(lock.len() as u32),
+            _ => Err(CountError::Unknown),
+        }
+    }
 
-        if let Err(_) = ureq::get(&url).set("Authorization", &auth_header).call() {
-            return Err(());
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        if self.error {
+            return Err(CountByTypeError::Unknown);
         }
 
-        Ok(Self { url, auth_header })
+        let lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(CountByTypeError::Unknown),
+        };
+
+        Ok(count_types(lock.iter().map(|p| &p.types)))
     }
 
-    fn fetch_pokemon_rows(&self, number: Option<u16>) -> Result<AirtableJson, ()> {
-        let url = match number {
-            Some(number) => format!("{}?filterByFormula=number%3D{}", self.url, number),
-            None => format!("{}?sort%5B0%5D%5Bfield%5D=number", self.url),
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.iter().find(|p| p.number == number) {
+            Some(pokemon) => Ok(pokemon.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        if self.error {
+            return Err(FetchByNameError::Unknown);
+        }
+
+        let lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchByNameError::Unknown),
+        };
+
+        let name = PokemonName::fold(name.as_str());
+        match lock
+            .iter()
+            .filter(|p| PokemonName::fold(p.name.as_str()) == name)
+            .min_by(|a, b| a.number.cmp(&b.number))
+        {
+            Some(pokemon) => Ok(pokemon.clone()),
+            None => Err(FetchByNameError::NotFound),
+        }
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match lock.iter_mut().find(|p| p.number == number) {
+            Some(pokemon) => {
+                pokemon.types = types;
+                Ok(pokemon.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match lock.iter_mut().find(|p| p.number == number) {
+            Some(pokemon) => {
+                pokemon.name = name;
+                pokemon.types = types;
+                Ok(pokemon.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match lock.iter_mut().find(|p| p.number == number) {
+            Some(pokemon) => {
+                pokemon.evolutions = evolutions;
+                Ok(pokemon.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match lock.iter_mut().find(|p| p.number == number) {
+            Some(pokemon) => {
+                pokemon.abilities = abilities;
+                Ok(pokemon.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match lock.iter_mut().find(|p| p.number == number) {
+            Some(pokemon) => {
+                pokemon.height = height;
+                pokemon.weight = weight;
+                Ok(pokemon.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match lock.iter_mut().find(|p| p.number == number) {
+            Some(pokemon) => {
+                pokemon.sprite = sprite;
+                Ok(pokemon.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match lock.iter_mut().find(|p| p.number == number) {
+            Some(pokemon) => {
+                pokemon.class = class;
+                Ok(pokemon.clone())
+            }
+            None => Err(UpdateError::NotFound),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let index = match lock.iter().position(|p| p.number == number) {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
+        };
+
+        lock.remove(index);
+        Ok(())
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        if self.error {
+            return Err(DeleteByTypeError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteByTypeError::Unknown),
+        };
+
+        let (mut deleted, kept) = lock
+            .drain(..)
+            .partition::<Vec<Pokemon>, _>(|p| p.types.as_slice().contains(&pokemon_type));
+        *lock = kept;
+        deleted.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(deleted)
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        if self.error {
+            return Err(ClearError::Unknown);
+        }
+
+        let mut lock = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            _ => return Err(ClearError::Unknown),
+        };
+
+        let mut deleted = lock.drain(..).collect::<Vec<Pokemon>>();
+        deleted.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(deleted)
+    }
+
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        if self.error {
+            return Err(SnapshotError::Unknown);
+        }
+
+        let mut pokemons = match self.pokemons.lock() {
+            Ok(lock) => lock.clone(),
+            _ => return Err(SnapshotError::Unknown),
         };
+        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(pokemons)
+    }
+
+    fn backend(&self) -> Backend {
+        Backend::new("memory")
+    }
+}
+
+fn count_types<'a>(types: impl Iterator<Item = &'a PokemonTypes>) -> Vec<(PokemonType, u32)> {
+    let mut counts: Vec<(PokemonType, u32)> = vec![];
+    for t in types.flat_map(PokemonTypes::as_slice) {
+        match counts.iter_mut().find(|(pt, _)| pt == t) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((t.clone(), 1)),
+        }
+    }
+    counts
+}
+
+#[cfg(feature = "server")]
+pub struct AirtableRepository {
+    url: String,
+    auth_header: String,
+    /// The responses of the record listings, by URL. There is about one URL
+    /// per number and per type, so the cache stays small.
+    cache: Mutex<HashMap<String, CachedResponse>>,
+}
+
+/// A response Airtable sent with an ETag or a Last-Modified date. They are
+/// sent back with the next request of the same URL, and the response is
+/// reused if Airtable answers 304 Not Modified.
+#[cfg(feature = "server")]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+#[cfg(feature = "server")]
+impl AirtableRepository {
+    pub fn try_new(api_key: &str, workspace_id: &str) -> Result<Self, ()> {
+        let url = format!("https://api.airtable.com/v0/{}/pokemons", workspace_id);
+        let auth_header = format!("Bearer {}", api_key);
 
-        let res = match ureq::get(&url)
-            .set("Authorization", &self.auth_header)
+        if ureq::get(&url)
+            .set("Authorization", &auth_header)
             .call()
+            .is_err()
         {
-            Ok(res) => res,
+            return Err(());
+        }
+
+        Ok(Self {
+            url,
+            auth_header,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn fetch_pokemon_rows(&self, number: Option<u16>) -> Result<AirtableJson, ()> {
+        let url = match number {
+            Some(number) => format!("{}?filterByFormula=number%3D{}", self.url, number),
+            None => format!("{}?sort%5B0%5D%5Bfield%5D=number", self.url),
+        };
+
+        self.fetch_rows(&url)
+    }
+
+    fn type_url(&self, pokemon_type: &PokemonType) -> String {
+        format!(
+            "{}?filterByFormula=FIND(%22{}%22%2CARRAYJOIN(types))&sort%5B0%5D%5Bfield%5D=number",
+            self.url,
+            pokemon_type.as_str()
+        )
+    }
+
+    /// Every call is authenticated, and is part of the trace of the request
+    /// being served.
+    fn authorize(&self, req: ureq::Request) -> ureq::Request {
+        trace::propagate(req.set("Authorization", &self.auth_header))
+    }
+
+    // Airtable deletes at most 10 records per request.
+    fn delete_records(&self, ids: &[String]) -> Result<(), ()> {
+        for chunk in ids.chunks(10) {
+            let req = chunk
+                .iter()
+                .fold(self.authorize(ureq::delete(&self.url)), |req, id| {
+                    req.query("records[]", id)
+                });
+            if req.call().is_err() {
+                return Err(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists every record the request matches. Airtable answers with at most
+    /// a page of them, and an `offset` to ask for the next one with.
+    fn list<T: DeserializeOwned>(req: ureq::Request) -> Result<Vec<T>, ()> {
+        let mut records = vec![];
+        let mut offset: Option<String> = None;
+
+        loop {
+            let page_req = match &offset {
+                Some(offset) => req.clone().query("offset", offset),
+                None => req.clone(),
+            };
+            let page = match page_req
+                .call()
+                .map(|res| res.into_json::<AirtablePage<T>>())
+            {
+                Ok(Ok(page)) => page,
+                _ => return Err(()),
+            };
+
+            records.extend(page.records);
+            offset = match page.offset {
+                Some(offset) => Some(offset),
+                None => return Ok(records),
+            };
+        }
+    }
+
+    fn fetch_rows(&self, url: &str) -> Result<AirtableJson, ()> {
+        let body = match self.fetch_cached(url) {
+            Ok(body) => body,
             _ => return Err(()),
         };
 
-        match res.into_json::<AirtableJson>() {
+        match serde_json::from_str::<AirtableJson>(&body) {
             Ok(json) => Ok(json),
             _ => Err(()),
         }
     }
+
+    /// A cached response is only reused once Airtable tells it is still
+    /// fresh, so the writes made elsewhere are seen right away. Airtable
+    /// doesn't send the records again when they haven't changed.
+    fn fetch_cached(&self, url: &str) -> Result<String, ()> {
+        let mut req = self.authorize(ureq::get(url));
+        if let Ok(cache) = self.cache.lock() {
+            if let Some(cached) = cache.get(url) {
+                if let Some(etag) = &cached.etag {
+                    req = req.set("If-None-Match", etag);
+                }
+                if let Some(last_modified) = &cached.last_modified {
+                    req = req.set("If-Modified-Since", last_modified);
+                }
+            }
+        }
+
+        let res = match req.call() {
+            Ok(res) => res,
+            _ => return Err(()),
+        };
+
+        if res.status() == 304 {
+            return match self
+                .cache
+                .lock()
+                .map(|cache| cache.get(url).map(|c| c.body.clone()))
+            {
+                Ok(Some(body)) => Ok(body),
+                _ => Err(()),
+            };
+        }
+
+        let etag = res.header("ETag").map(String::from);
+        let last_modified = res.header("Last-Modified").map(String::from);
+        let body = match res.into_string() {
+            Ok(body) => body,
+            _ => return Err(()),
+        };
+
+        if let Ok(mut cache) = self.cache.lock() {
+            match etag.is_some() || last_modified.is_some() {
+                true => cache.insert(
+                    String::from(url),
+                    CachedResponse {
+                        etag,
+                        last_modified,
+                        body: body.clone(),
+                    },
+                ),
+                false => cache.remove(url),
+            };
+        }
+
+        Ok(body)
+    }
 }
 
+#[cfg(feature = "server")]
 impl Repository for AirtableRepository {
     fn insert(
         &self,
@@ -181,7 +1293,7 @@ impl Repository for AirtableRepository {
         name: PokemonName,
         types: PokemonTypes,
     ) -> Result<Pokemon, InsertError> {
-        let json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
+        let json = match self.fetch_pokemon_rows(Some(number.value())) {
             Ok(json) => json,
             _ => return Err(InsertError::Unknown),
         };
@@ -193,314 +1305,4394 @@ impl Repository for AirtableRepository {
         let body = ureq::json!({
             "records": [{
                 "fields": {
-                    "number": u16::from(number.clone()),
-                    "name": String::from(name.clone()),
-                    "types": Vec::<String>::from(types.clone()),
+                    "number": number.value(),
+                    "name": name.as_str(),
+                    "types": types.as_slice().iter().map(PokemonType::as_str).collect::<Vec<&str>>(),
                 },
             }],
         });
 
-        if let Err(_) = ureq::post(&self.url)
-            .set("Authorization", &self.auth_header)
-            .send_json(body)
-        {
-            return Err(InsertError::Unknown);
+        if self
+            .authorize(ureq::post(&self.url))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(InsertError::Unknown);
+        }
+
+        Ok(Pokemon::new(number, name, types))
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        pokemons
+            .into_iter()
+            .map(|p| self.insert(p.number, p.name, p.types))
+            .collect()
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        match self.fetch_all_skipping_invalid(query) {
+            Ok((pokemons, _)) => Ok(pokemons),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        let mut formulas = vec![];
+        if let Some(name) = query.name {
+            formulas.push(format!(
+                "FIND(LOWER('{}'),LOWER({{name}}))",
+                name.replace('\'', "\\'")
+            ));
+        }
+        if let Some(pokemon_type) = query.pokemon_type {
+            formulas.push(format!(
+                "FIND('{}',ARRAYJOIN(types))",
+                pokemon_type.as_str()
+            ));
+        }
+        match query.class {
+            Some(PokemonClass::Normal) => formulas.push(String::from("{class}=BLANK()")),
+            Some(class) => formulas.push(format!("{{class}}='{}'", class.as_str())),
+            None => {}
+        }
+
+        let direction = if query.descending { "desc" } else { "asc" };
+
+        let mut req = self
+            .authorize(ureq::get(&self.url))
+            .query("sort[0][direction]", direction);
+        req = match query.sort_by {
+            SortBy::Number => req.query("sort[0][field]", "number"),
+            SortBy::Name => req
+                .query("sort[0][field]", "name")
+                .query("sort[1][field]", "number"),
+            // Airtable sorts a multiple select by the order of its options,
+            // so every record is listed and sorted here.
+            SortBy::Type => req,
+        };
+        if !formulas.is_empty() {
+            req = req.query("filterByFormula", &format!("AND({})", formulas.join(",")));
+        }
+        // Airtable can't skip records, so the skipped ones are listed too,
+        // page after page.
+        if let (Some(limit), false) = (query.limit, matches!(query.sort_by, SortBy::Type)) {
+            req = req.query(
+                "maxRecords",
+                &query.offset.saturating_add(limit).to_string(),
+            );
+        }
+
+        let records = match Self::list::<AirtableRecord>(req) {
+            Ok(records) => records,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let (mut pokemons, invalid) = match sift(records.into_iter().map(AirtableRecord::decode)) {
+            Ok(listing) => listing,
+            _ => return Err(FetchAllError::Unknown),
+        };
+        if let SortBy::Type = query.sort_by {
+            sort(&mut pokemons, query.sort_by, query.descending);
+        }
+
+        let pokemons = pokemons
+            .into_iter()
+            .skip(query.offset as usize)
+            .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect::<Vec<Pokemon>>();
+        Ok((pokemons, invalid))
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        let url = format!(
+            "{}?filterByFormula=AND(number%3E%3D{}%2Cnumber%3C%3D{})&sort%5B0%5D%5Bfield%5D=number",
+            self.url,
+            u16::from(from),
+            u16::from(to)
+        );
+
+        let json = match self.fetch_rows(&url) {
+            Ok(json) => json,
+            _ => return Err(FetchRangeError::Unknown),
+        };
+
+        match sift(json.records.into_iter().map(AirtableRecord::decode)) {
+            Ok((pokemons, _)) => Ok(pokemons),
+            _ => Err(FetchRangeError::Unknown),
+        }
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        let json = match self.fetch_rows(&self.type_url(&pokemon_type)) {
+            Ok(json) => json,
+            _ => return Err(FetchByTypeError::Unknown),
+        };
+
+        match sift(json.records.into_iter().map(AirtableRecord::decode)) {
+            Ok((pokemons, _)) => Ok(pokemons),
+            _ => Err(FetchByTypeError::Unknown),
+        }
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        let formula = format!(
+            "LOWER({{name}})=LOWER('{}')",
+            String::from(name).replace('\'', "\\'")
+        );
+
+        let res = match self
+            .authorize(ureq::get(&self.url))
+            .query("filterByFormula", &formula)
+            .call()
+        {
+            Ok(res) => res,
+            _ => return Err(NameExistsError::Unknown),
+        };
+
+        match res.into_json::<AirtableJson>() {
+            Ok(json) => Ok(!json.records.is_empty()),
+            _ => Err(NameExistsError::Unknown),
+        }
+    }
+
+    // Only the records of the pages before this one and of this one are
+    // listed, following Airtable's own pages, as it can't skip records.
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        let records = match Self::list::<AirtableRecord>(
+            self.authorize(ureq::get(&self.url))
+                .query("sort[0][field]", "number")
+                .query("maxRecords", &offset.saturating_add(limit).to_string()),
+        ) {
+            Ok(records) => records,
+            _ => return Err(FetchPageError::Unknown),
+        };
+
+        let records = records.into_iter().skip(offset as usize);
+
+        match sift(records.map(AirtableRecord::decode)) {
+            Ok((pokemons, _)) => Ok(pokemons),
+            _ => Err(FetchPageError::Unknown),
+        }
+    }
+
+    // Only the numbers are listed, to keep the responses small.
+    fn count(&self) -> Result<u32, CountError> {
+        match Self::list::<IgnoredAny>(
+            self.authorize(ureq::get(&self.url))
+                .query("fields[]", "number"),
+        ) {
+            Ok(records) => Ok(records.len() as u32),
+            _ => Err(CountError::Unknown),
+        }
+    }
+
+    // Airtable has no grouped queries, so the counting happens here.
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        let json = match self.fetch_pokemon_rows(None) {
+            Ok(json) => json,
+            _ => return Err(CountByTypeError::Unknown),
+        };
+
+        match sift(json.records.into_iter().map(AirtableRecord::decode)) {
+            Ok((pokemons, _)) => Ok(count_types(pokemons.iter().map(|p| &p.types))),
+            _ => Err(CountByTypeError::Unknown),
+        }
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(FetchOneError::NotFound);
+        }
+
+        match json.records.remove(0).decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    // Airtable formulas have no accent folding: LOWER() finds names that only
+    // differ by case, and the other ones are folded here.
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        let formula = format!(
+            "LOWER({{name}})=LOWER('{}')",
+            name.as_str().replace('\'', "\\'")
+        );
+
+        let mut json = match self
+            .authorize(ureq::get(&self.url))
+            .query("filterByFormula", &formula)
+            .query("sort[0][field]", "number")
+            .call()
+            .map(|res| res.into_json::<AirtableJson>())
+        {
+            Ok(Ok(json)) => json,
+            _ => return Err(FetchByNameError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            json = match self.fetch_pokemon_rows(None) {
+                Ok(json) => json,
+                _ => return Err(FetchByNameError::Unknown),
+            };
+            let name = PokemonName::fold(name.as_str());
+            json.records
+                .retain(|record| PokemonName::fold(&record.fields.name) == name);
+        }
+
+        if json.records.is_empty() {
+            return Err(FetchByNameError::NotFound);
+        }
+
+        match json.records.remove(0).decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(FetchByNameError::Unknown),
+        }
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let mut record = json.records.remove(0);
+
+        let body = ureq::json!({
+            "fields": {
+                "types": types.as_slice().iter().map(PokemonType::as_str).collect::<Vec<&str>>(),
+            },
+        });
+
+        if self
+            .authorize(ureq::request(
+                "PATCH",
+                &format!("{}/{}", self.url, record.id),
+            ))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        record.fields.types = Vec::<String>::from(types);
+        match record.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let mut record = json.records.remove(0);
+
+        let body = ureq::json!({
+            "fields": {
+                "name": name.as_str(),
+                "types": types.as_slice().iter().map(PokemonType::as_str).collect::<Vec<&str>>(),
+            },
+        });
+
+        if self
+            .authorize(ureq::request(
+                "PATCH",
+                &format!("{}/{}", self.url, record.id),
+            ))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        record.fields.name = String::from(name);
+        record.fields.types = Vec::<String>::from(types);
+        match record.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let mut record = json.records.remove(0);
+
+        let encoded = match serde_json::to_string(
+            &evolutions
+                .iter()
+                .map(EvolutionRecord::from)
+                .collect::<Vec<EvolutionRecord>>(),
+        ) {
+            Ok(encoded) => encoded,
+            _ => return Err(UpdateError::Unknown),
+        };
+        let body = ureq::json!({
+            "fields": {
+                "evolutions": encoded,
+            },
+        });
+
+        if self
+            .authorize(ureq::request(
+                "PATCH",
+                &format!("{}/{}", self.url, record.id),
+            ))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        record.fields.evolutions = Some(encoded);
+        match record.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let mut record = json.records.remove(0);
+
+        let encoded = match serde_json::to_string(&ability_records(&abilities)) {
+            Ok(encoded) => encoded,
+            _ => return Err(UpdateError::Unknown),
+        };
+        let body = ureq::json!({
+            "fields": {
+                "abilities": encoded,
+            },
+        });
+
+        if self
+            .authorize(ureq::request(
+                "PATCH",
+                &format!("{}/{}", self.url, record.id),
+            ))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        record.fields.abilities = Some(encoded);
+        match record.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    // A null clears a number field.
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let mut record = json.records.remove(0);
+
+        let measurements = MeasurementsRecord::new(height, weight);
+        let body = ureq::json!({
+            "fields": {
+                "height": measurements.height,
+                "weight": measurements.weight,
+            },
+        });
+
+        if self
+            .authorize(ureq::request(
+                "PATCH",
+                &format!("{}/{}", self.url, record.id),
+            ))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        record.fields.height = measurements.height;
+        record.fields.weight = measurements.weight;
+        match record.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    // Like the number fields, a null clears the URL field.
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let mut record = json.records.remove(0);
+
+        let sprite = sprite_record(&sprite);
+        let body = ureq::json!({
+            "fields": {
+                "sprite": sprite,
+            },
+        });
+
+        if self
+            .authorize(ureq::request(
+                "PATCH",
+                &format!("{}/{}", self.url, record.id),
+            ))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        record.fields.sprite = sprite;
+        match record.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    // A null empties the single select, which is read back as normal.
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let mut record = json.records.remove(0);
+
+        let class = class_record(class);
+        let body = ureq::json!({
+            "fields": {
+                "class": class,
+            },
+        });
+
+        if self
+            .authorize(ureq::request(
+                "PATCH",
+                &format!("{}/{}", self.url, record.id),
+            ))
+            .send_json(body)
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        record.fields.class = class;
+        match record.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        let mut json = match self.fetch_pokemon_rows(Some(number.value())) {
+            Ok(json) => json,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Err(DeleteError::NotFound);
+        }
+
+        let record = json.records.remove(0);
+
+        match self
+            .authorize(ureq::delete(&format!("{}/{}", self.url, record.id)))
+            .call()
+        {
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+
+    // Every page of the type is listed before anything is deleted.
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        let records = match Self::list::<AirtableRecord>(
+            self.authorize(ureq::get(&self.type_url(&pokemon_type))),
+        ) {
+            Ok(records) => records,
+            _ => return Err(DeleteByTypeError::Unknown),
+        };
+
+        let (pokemons, ids): (Vec<Pokemon>, Vec<String>) =
+            match sift(records.into_iter().map(|record| {
+                let id = record.id.clone();
+                record.decode().map(|pokemon| (pokemon, id))
+            })) {
+                Ok((deleted, _)) => deleted.into_iter().unzip(),
+                _ => return Err(DeleteByTypeError::Unknown),
+            };
+
+        match self.delete_records(&ids) {
+            Ok(()) => Ok(pokemons),
+            _ => Err(DeleteByTypeError::Unknown),
+        }
+    }
+
+    // A listing returns at most a page of records: the deletion goes on
+    // until a listing comes back empty.
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        let mut pokemons = vec![];
+
+        loop {
+            let json = match self.fetch_pokemon_rows(None) {
+                Ok(json) => json,
+                _ => return Err(ClearError::Unknown),
+            };
+
+            if json.records.is_empty() {
+                break;
+            }
+
+            let mut ids = vec![];
+
+            for record in json.records.into_iter() {
+                ids.push(record.id.clone());
+                if let Ok(pokemon) = record.decode() {
+                    pokemons.push(pokemon);
+                }
+            }
+
+            if self.delete_records(&ids).is_err() {
+                return Err(ClearError::Unknown);
+            }
+        }
+
+        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(pokemons)
+    }
+
+    fn backend(&self) -> Backend {
+        Backend::new("airtable")
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct AirtableJson {
+    records: Vec<AirtableRecord>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct AirtablePage<T> {
+    records: Vec<T>,
+    offset: Option<String>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct AirtableRecord {
+    id: String,
+    fields: AirtableFields,
+}
+
+#[cfg(feature = "server")]
+impl AirtableRecord {
+    fn decode(self) -> Result<Pokemon, InvalidRecord> {
+        let number = self.fields.number;
+        let invalid = |reason| InvalidRecord { number, reason };
+        let (evolutions, abilities) = match (
+            json_field::<EvolutionRecord>(&self.fields.evolutions),
+            json_field::<AbilityRecord>(&self.fields.abilities),
+        ) {
+            (Ok(evolutions), Ok(abilities)) => (evolutions, abilities),
+            (Err(()), _) => return Err(invalid("invalid evolutions")),
+            (_, Err(())) => return Err(invalid("invalid abilities")),
+        };
+        decode(
+            number,
+            self.fields.name,
+            self.fields.types,
+            evolutions,
+            abilities,
+            MeasurementsRecord {
+                height: self.fields.height,
+                weight: self.fields.weight,
+            },
+            self.fields.sprite,
+            self.fields.class,
+        )
+    }
+}
+
+#[cfg(feature = "server")]
+// The lists are kept as JSON in long text fields, one left empty holding
+// nothing.
+fn json_field<T: DeserializeOwned>(field: &Option<String>) -> Result<Vec<T>, ()> {
+    match field.as_deref().map(str::trim) {
+        None | Some("") => Ok(vec![]),
+        Some(json) => serde_json::from_str(json).map_err(|_| ()),
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct AirtableFields {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    evolutions: Option<String>,
+    abilities: Option<String>,
+    /// Number fields in meters and kilograms.
+    height: Option<f64>,
+    weight: Option<f64>,
+    /// A URL field.
+    sprite: Option<String>,
+    /// A single select field, empty for the normal Pokemons.
+    class: Option<String>,
+}
+
+#[cfg(feature = "server")]
+/// Bumped along with each migration in `SqliteRepository::migrate`.
+const SCHEMA_VERSION: u32 = 7;
+
+#[cfg(feature = "server")]
+/// Transactions started by `begin` belong to a thread: `owner` keeps the
+/// calls of the other threads waiting until `released`.
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+    owner: Mutex<Option<ThreadId>>,
+    released: Condvar,
+    outbox: bool,
+}
+
+#[cfg(feature = "server")]
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match (
+            Self::migrate(&connection),
+            connection.execute("pragma foreign_keys = 1", []),
+            Self::register_functions(&connection),
+        ) {
+            (Ok(()), Ok(_), Ok(())) => Ok(Self::from_connection(connection)),
+            _ => Err(()),
+        }
+    }
+
+    /// Brings the database to `SCHEMA_VERSION`, kept in its `user_version`
+    /// so that `/health` tells which migrations a deployment went through.
+    fn migrate(connection: &Connection) -> Result<(), ()> {
+        if Self::migrate_types_cascade(connection).is_err()
+            || Self::migrate_evolutions(connection).is_err()
+            || Self::migrate_abilities(connection).is_err()
+            || Self::migrate_measurements(connection).is_err()
+            || outbox::create_table(connection).is_err()
+            || Self::migrate_sprites(connection).is_err()
+            || Self::migrate_classes(connection).is_err()
+        {
+            return Err(());
+        }
+
+        match connection.execute_batch(&format!("pragma user_version = {}", SCHEMA_VERSION)) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    /// Databases created before types were deleted along with their Pokemon
+    /// get their types table rebuilt with `on delete cascade`. Foreign keys
+    /// must still be off, so that orphaned rows are kept for `remove_orphans`.
+    fn migrate_types_cascade(connection: &Connection) -> Result<(), ()> {
+        match connection.query_row(
+            "select
+                (select count(*) from sqlite_master where type = 'table' and name = 'types'),
+                (select count(*) from pragma_foreign_key_list('types')
+                where \"table\" = 'pokemons' and on_delete = 'CASCADE')",
+            [],
+            |row| Ok((row.get::<usize, u32>(0)?, row.get::<usize, u32>(1)?)),
+        ) {
+            Ok((0, _)) | Ok((_, 1..)) => return Ok(()),
+            Ok(_) => {}
+            _ => return Err(()),
+        };
+
+        match connection.execute_batch(
+            "begin;
+            create table types_cascade (
+                pokemon_number integer not null references pokemons(number) on delete cascade,
+                name text not null
+            );
+            insert into types_cascade select pokemon_number, name from types;
+            drop table types;
+            alter table types_cascade rename to types;
+            commit;",
+        ) {
+            Ok(()) => Ok(()),
+            _ => {
+                connection.execute_batch("rollback").ok();
+                Err(())
+            }
+        }
+    }
+
+    /// The evolutions came along with the second version. Their target may
+    /// not be stored yet, so only the evolving Pokemon is a foreign key.
+    fn migrate_evolutions(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists evolutions (
+                pokemon_number integer not null references pokemons(number) on delete cascade,
+                target integer not null,
+                method text not null,
+                level integer
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    /// The abilities came along with the third version, in the order they
+    /// were given.
+    fn migrate_abilities(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists abilities (
+                pokemon_number integer not null references pokemons(number) on delete cascade,
+                name text not null,
+                hidden integer not null
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    /// The heights and the weights came along with the fourth version, in
+    /// meters and kilograms.
+    fn migrate_measurements(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists measurements (
+                pokemon_number integer primary key references pokemons(number) on delete cascade,
+                height real,
+                weight real
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    /// The sprites came along with the sixth version, after the outbox.
+    fn migrate_sprites(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists sprites (
+                pokemon_number integer primary key references pokemons(number) on delete cascade,
+                url text not null
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    /// The classes came along with the seventh version. The normal Pokemons
+    /// have no row.
+    fn migrate_classes(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists classes (
+                pokemon_number integer primary key references pokemons(number) on delete cascade,
+                name text not null
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    /// Deletes the types rows pointing at missing Pokemons, left by databases
+    /// written without foreign keys, and tells how many there were.
+    pub fn remove_orphans(&self) -> Result<usize, ()> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(()),
+        };
+
+        match lock.execute(
+            "delete from types where pokemon_number not in (select number from pokemons)",
+            [],
+        ) {
+            Ok(removed) => Ok(removed),
+            _ => Err(()),
+        }
+    }
+
+    fn from_connection(connection: Connection) -> Self {
+        Self {
+            connection: Mutex::new(connection),
+            owner: Mutex::new(None),
+            released: Condvar::new(),
+            outbox: false,
+        }
+    }
+
+    /// Every write also stores the events it makes in the `outbox` table,
+    /// within the same transaction, for `crate::outbox` to deliver them.
+    pub fn with_outbox(self) -> Self {
+        Self {
+            outbox: true,
+            ..self
+        }
+    }
+
+    fn enqueue(
+        &self,
+        connection: &Connection,
+        event: WebhookEvent,
+        data: serde_json::Value,
+    ) -> Result<(), ()> {
+        match self.outbox {
+            true => outbox::insert_row(connection, event, &data),
+            false => Ok(()),
+        }
+    }
+
+    /// Waits for the transaction of another thread to end, if any.
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, ()> {
+        let current = thread::current().id();
+        let owner = match self.owner.lock() {
+            Ok(owner) => owner,
+            _ => return Err(()),
+        };
+        let owner = match self
+            .released
+            .wait_while(owner, |owner| owner.is_some_and(|id| id != current))
+        {
+            Ok(owner) => owner,
+            _ => return Err(()),
+        };
+        let lock = self.connection.lock().map_err(|_| ());
+        drop(owner);
+        lock
+    }
+
+    fn release(&self) {
+        if let Ok(mut owner) = self.owner.lock() {
+            *owner = None;
+        }
+        self.released.notify_all();
+    }
+
+    fn end(&self, statement: &str) -> Result<(), TransactionError> {
+        let res = match self.lock() {
+            Ok(lock) => match lock.execute_batch(statement) {
+                Ok(()) => Ok(()),
+                _ => {
+                    // A failed commit leaves the transaction open.
+                    lock.execute_batch("rollback").ok();
+                    Err(TransactionError::Unknown)
+                }
+            },
+            _ => Err(TransactionError::Unknown),
+        };
+        self.release();
+        res
+    }
+
+    // fold(name) compares names without case nor accents.
+    fn register_functions(connection: &Connection) -> Result<(), ()> {
+        match connection.create_scalar_function(
+            "fold",
+            1,
+            FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+            |ctx| ctx.get::<String>(0).map(|name| PokemonName::fold(&name)),
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        let connection = match Connection::open_in_memory() {
+            Ok(connection) => connection,
+            _ => panic!("Error while opening the in-memory database"),
+        };
+
+        match connection.execute_batch(
+            "pragma foreign_keys = 1;
+            create table pokemons (number integer primary key, name text not null);
+            create table types (
+                pokemon_number integer not null references pokemons(number) on delete cascade,
+                name text not null
+            );
+            create table evolutions (
+                pokemon_number integer not null references pokemons(number) on delete cascade,
+                target integer not null,
+                method text not null,
+                level integer
+            );
+            create table abilities (
+                pokemon_number integer not null references pokemons(number) on delete cascade,
+                name text not null,
+                hidden integer not null
+            );
+            create table measurements (
+                pokemon_number integer primary key references pokemons(number) on delete cascade,
+                height real,
+                weight real
+            );
+            create table sprites (
+                pokemon_number integer primary key references pokemons(number) on delete cascade,
+                url text not null
+            );
+            create table classes (
+                pokemon_number integer primary key references pokemons(number) on delete cascade,
+                name text not null
+            );",
+        ) {
+            Ok(_)
+                if Self::register_functions(&connection).is_ok()
+                    && outbox::create_table(&connection).is_ok() =>
+            {
+                Self::from_connection(connection)
+            }
+            _ => panic!("Error while creating the in-memory database"),
+        }
+    }
+
+    fn fetch_pokemon_rows(
+        lock: &MutexGuard<'_, Connection>,
+        from: u16,
+        to: u16,
+    ) -> Result<Vec<(u16, String)>, ()> {
+        let mut stmt = match lock.prepare(
+            "select number, name from pokemons where number between ? and ? order by number",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query([from, to]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut pokemon_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
+                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(pokemon_rows)
+    }
+
+    fn fetch_queried_pokemons(
+        lock: &MutexGuard<'_, Connection>,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), ()> {
+        let pokemon_rows = match Self::fetch_queried_pokemon_rows(lock, query) {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(()),
+        };
+
+        match Self::decode_rows(lock, pokemon_rows) {
+            Ok(decoded) => sift(decoded),
+            _ => Err(()),
+        }
+    }
+
+    fn decode_rows(
+        lock: &MutexGuard<'_, Connection>,
+        pokemon_rows: Vec<(u16, String)>,
+    ) -> Result<Vec<Result<Pokemon, InvalidRecord>>, ()> {
+        let mut decoded = vec![];
+
+        for pokemon_row in pokemon_rows {
+            let (type_rows, evolution_rows, ability_rows, measurement_row, sprite_row, class_row) =
+                match (
+                    Self::fetch_type_rows(lock, pokemon_row.0),
+                    Self::fetch_evolution_rows(lock, pokemon_row.0),
+                    Self::fetch_ability_rows(lock, pokemon_row.0),
+                    Self::fetch_measurement_row(lock, pokemon_row.0),
+                    Self::fetch_sprite_row(lock, pokemon_row.0),
+                    Self::fetch_class_row(lock, pokemon_row.0),
+                ) {
+                    (
+                        Ok(type_rows),
+                        Ok(evolution_rows),
+                        Ok(ability_rows),
+                        Ok(measurement_row),
+                        Ok(sprite_row),
+                        Ok(class_row),
+                    ) => (
+                        type_rows,
+                        evolution_rows,
+                        ability_rows,
+                        measurement_row,
+                        sprite_row,
+                        class_row,
+                    ),
+                    _ => return Err(()),
+                };
+
+            decoded.push(decode(
+                pokemon_row.0,
+                pokemon_row.1,
+                type_rows,
+                evolution_rows,
+                ability_rows,
+                measurement_row,
+                sprite_row,
+                class_row,
+            ));
+        }
+
+        Ok(decoded)
+    }
+
+    fn fetch_queried_pokemon_rows(
+        lock: &MutexGuard<'_, Connection>,
+        query: Query,
+    ) -> Result<Vec<(u16, String)>, ()> {
+        let mut sql = String::from("select number, name from pokemons p where 1 = 1");
+        let mut params = vec![];
+
+        if let Some(name) = query.name {
+            sql.push_str(" and instr(lower(p.name), lower(?)) > 0");
+            params.push(name);
+        }
+        if let Some(pokemon_type) = query.pokemon_type {
+            sql.push_str(
+                " and exists (select 1 from types t where t.pokemon_number = p.number and t.name = ?)",
+            );
+            params.push(String::from(pokemon_type));
+        }
+        if let Some(class) = query.class {
+            sql.push_str(
+                " and coalesce((select c.name from classes c where c.pokemon_number = p.number), 'Normal') = ?",
+            );
+            params.push(String::from(class));
+        }
+
+        let direction = if query.descending { "desc" } else { "asc" };
+        sql.push_str(&match query.sort_by {
+            SortBy::Number => format!(" order by p.number {}", direction),
+            SortBy::Name => format!(" order by p.name collate nocase {}, p.number", direction),
+            SortBy::Type => format!(
+                " order by (select t.name from types t where t.pokemon_number = p.number order by t.rowid limit 1) {}, p.number",
+                direction
+            ),
+        });
+        // A negative limit is no limit at all.
+        sql.push_str(&format!(
+            " limit {} offset {}",
+            query.limit.map_or(-1, i64::from),
+            query.offset
+        ));
+
+        let mut stmt = match lock.prepare(&sql) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query(params_from_iter(params)) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut pokemon_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
+                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(pokemon_rows)
+    }
+
+    fn fetch_page_rows(
+        lock: &MutexGuard<'_, Connection>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<(u16, String)>, ()> {
+        let mut stmt = match lock
+            .prepare("select number, name from pokemons order by number limit ? offset ?")
+        {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query([limit, offset]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut pokemon_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
+                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(pokemon_rows)
+    }
+
+    fn insert_pokemon_rows(connection: &Connection, pokemon: &Pokemon) -> Result<(), InsertError> {
+        match connection.execute(
+            "insert into pokemons (number, name) values (?, ?)",
+            params![pokemon.number.value(), pokemon.name.as_str()],
+        ) {
+            Ok(_) => {}
+            Err(SqliteFailure(_, Some(message)))
+                if message == "UNIQUE constraint failed: pokemons.number" =>
+            {
+                return Err(InsertError::Conflict);
+            }
+            _ => return Err(InsertError::Unknown),
+        };
+
+        for _type in pokemon.types.as_slice() {
+            if connection
+                .execute(
+                    "insert into types (pokemon_number, name) values (?, ?)",
+                    params![pokemon.number.value(), _type.as_str()],
+                )
+                .is_err()
+            {
+                return Err(InsertError::Unknown);
+            }
+        }
+
+        match (
+            Self::insert_evolution_rows(connection, &pokemon.number, &pokemon.evolutions),
+            Self::insert_ability_rows(connection, &pokemon.number, &pokemon.abilities),
+            Self::insert_measurement_row(
+                connection,
+                &pokemon.number,
+                pokemon.height,
+                pokemon.weight,
+            ),
+            Self::insert_sprite_row(connection, &pokemon.number, &pokemon.sprite),
+            Self::insert_class_row(connection, &pokemon.number, pokemon.class),
+        ) {
+            (Ok(()), Ok(()), Ok(()), Ok(()), Ok(())) => Ok(()),
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn insert_evolution_rows(
+        connection: &Connection,
+        number: &PokemonNumber,
+        evolutions: &[Evolution],
+    ) -> Result<(), ()> {
+        for evolution in evolutions {
+            if connection
+                .execute(
+                    "insert into evolutions (pokemon_number, target, method, level)
+                    values (?, ?, ?, ?)",
+                    params![
+                        number.value(),
+                        evolution.target.value(),
+                        String::from(evolution.method),
+                        evolution.level
+                    ],
+                )
+                .is_err()
+            {
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn insert_ability_rows(
+        connection: &Connection,
+        number: &PokemonNumber,
+        abilities: &Option<PokemonAbilities>,
+    ) -> Result<(), ()> {
+        for ability in ability_records(abilities) {
+            if connection
+                .execute(
+                    "insert into abilities (pokemon_number, name, hidden) values (?, ?, ?)",
+                    params![number.value(), ability.name, ability.hidden],
+                )
+                .is_err()
+            {
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+
+    // A Pokemon with neither a height nor a weight has no row.
+    fn insert_measurement_row(
+        connection: &Connection,
+        number: &PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<(), ()> {
+        if height.is_none() && weight.is_none() {
+            return Ok(());
+        }
+
+        let measurements = MeasurementsRecord::new(height, weight);
+        match connection.execute(
+            "insert into measurements (pokemon_number, height, weight) values (?, ?, ?)",
+            params![number.value(), measurements.height, measurements.weight],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    // A Pokemon without a sprite has no row.
+    fn insert_sprite_row(
+        connection: &Connection,
+        number: &PokemonNumber,
+        sprite: &Option<PokemonSprite>,
+    ) -> Result<(), ()> {
+        let url = match sprite {
+            Some(sprite) => sprite.as_str(),
+            None => return Ok(()),
+        };
+
+        match connection.execute(
+            "insert into sprites (pokemon_number, url) values (?, ?)",
+            params![number.value(), url],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    // Neither has a normal Pokemon.
+    fn insert_class_row(
+        connection: &Connection,
+        number: &PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<(), ()> {
+        let name = match class_record(class) {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+
+        match connection.execute(
+            "insert into classes (pokemon_number, name) values (?, ?)",
+            params![number.value(), name],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_pokemons_of_type(
+        lock: &MutexGuard<'_, Connection>,
+        pokemon_type: PokemonType,
+    ) -> Result<Vec<Pokemon>, ()> {
+        let mut stmt = match lock.prepare(
+            "select p.number, p.name from pokemons p
+            join types t on t.pokemon_number = p.number
+            where t.name = ? order by p.number",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query([String::from(pokemon_type)]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut pokemon_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
+                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
+                _ => return Err(()),
+            };
+        }
+
+        match Self::decode_rows(lock, pokemon_rows).map(sift) {
+            Ok(Ok((pokemons, _))) => Ok(pokemons),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_type_rows(lock: &MutexGuard<'_, Connection>, number: u16) -> Result<Vec<String>, ()> {
+        let mut stmt = match lock.prepare("select name from types where pokemon_number = ?") {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query([number]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut type_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match row.get::<usize, String>(0) {
+                Ok(name) => type_rows.push(name),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(type_rows)
+    }
+
+    fn fetch_evolution_rows(
+        connection: &Connection,
+        number: u16,
+    ) -> Result<Vec<EvolutionRecord>, ()> {
+        let mut stmt = match connection.prepare(
+            "select target, method, level from evolutions where pokemon_number = ? order by rowid",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query([number]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut evolution_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, u16>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, Option<u8>>(2),
+            ) {
+                (Ok(target), Ok(method), Ok(level)) => evolution_rows.push(EvolutionRecord {
+                    target,
+                    method,
+                    level,
+                }),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(evolution_rows)
+    }
+
+    /// The evolutions of a Pokemon being updated, which are left as they are.
+    fn fetch_evolutions(connection: &Connection, number: u16) -> Result<Vec<Evolution>, ()> {
+        match Self::fetch_evolution_rows(connection, number) {
+            Ok(evolution_rows) => decode_evolutions(evolution_rows),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_ability_rows(connection: &Connection, number: u16) -> Result<Vec<AbilityRecord>, ()> {
+        let mut stmt = match connection
+            .prepare("select name, hidden from abilities where pokemon_number = ? order by rowid")
+        {
+            Ok(stmt) => stmt,
+            _ => return Err(()),
+        };
+
+        let mut rows = match stmt.query([number]) {
+            Ok(rows) => rows,
+            _ => return Err(()),
+        };
+
+        let mut ability_rows = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (row.get::<usize, String>(0), row.get::<usize, bool>(1)) {
+                (Ok(name), Ok(hidden)) => ability_rows.push(AbilityRecord { name, hidden }),
+                _ => return Err(()),
+            };
+        }
+
+        Ok(ability_rows)
+    }
+
+    /// Like `fetch_evolutions`, for the abilities.
+    fn fetch_abilities(
+        connection: &Connection,
+        number: u16,
+    ) -> Result<Option<PokemonAbilities>, ()> {
+        match Self::fetch_ability_rows(connection, number) {
+            Ok(ability_rows) => decode_abilities(ability_rows),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_measurement_row(
+        connection: &Connection,
+        number: u16,
+    ) -> Result<MeasurementsRecord, ()> {
+        match connection.query_row(
+            "select height, weight from measurements where pokemon_number = ?",
+            [number],
+            |row| {
+                Ok((
+                    row.get::<usize, Option<f64>>(0),
+                    row.get::<usize, Option<f64>>(1),
+                ))
+            },
+        ) {
+            Ok((Ok(height), Ok(weight))) => Ok(MeasurementsRecord { height, weight }),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(MeasurementsRecord::default()),
+            _ => Err(()),
+        }
+    }
+
+    /// Like `fetch_evolutions`, for the height and the weight.
+    fn fetch_measurements(
+        connection: &Connection,
+        number: u16,
+    ) -> Result<(Option<PokemonHeight>, Option<PokemonWeight>), ()> {
+        match Self::fetch_measurement_row(connection, number) {
+            Ok(measurement_row) => measurement_row.decode(),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_sprite_row(connection: &Connection, number: u16) -> Result<Option<String>, ()> {
+        match connection.query_row(
+            "select url from sprites where pokemon_number = ?",
+            [number],
+            |row| row.get::<usize, String>(0),
+        ) {
+            Ok(url) => Ok(Some(url)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            _ => Err(()),
+        }
+    }
+
+    /// Like `fetch_evolutions`, for the sprite.
+    fn fetch_sprite(connection: &Connection, number: u16) -> Result<Option<PokemonSprite>, ()> {
+        match Self::fetch_sprite_row(connection, number) {
+            Ok(sprite_row) => sprite_row.map(PokemonSprite::try_from).transpose(),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_class_row(connection: &Connection, number: u16) -> Result<Option<String>, ()> {
+        match connection.query_row(
+            "select name from classes where pokemon_number = ?",
+            [number],
+            |row| row.get::<usize, String>(0),
+        ) {
+            Ok(name) => Ok(Some(name)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            _ => Err(()),
+        }
+    }
+
+    fn fetch_class(connection: &Connection, number: u16) -> Result<PokemonClass, ()> {
+        match Self::fetch_class_row(connection, number) {
+            Ok(class_row) => decode_class(class_row),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for SqliteRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let pokemon = Pokemon::new(number, name, types);
+
+        match Self::insert_pokemon_rows(&transaction, &pokemon) {
+            Ok(()) => match self.enqueue(
+                &transaction,
+                WebhookEvent::PokemonCreated,
+                outbox::pokemon_data(&pokemon),
+            ) {
+                Ok(()) => match transaction.commit() {
+                    Ok(_) => Ok(pokemon),
+                    _ => Err(InsertError::Unknown),
+                },
+                _ => Err(InsertError::Unknown),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => {
+                return pokemons
+                    .into_iter()
+                    .map(|_| Err(InsertError::Unknown))
+                    .collect()
+            }
+        };
+
+        let mut transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => {
+                return pokemons
+                    .into_iter()
+                    .map(|_| Err(InsertError::Unknown))
+                    .collect()
+            }
+        };
+
+        let mut results = vec![];
+
+        // Each pokemon gets its own savepoint so that a conflict only rolls
+        // back its own rows, not the whole batch.
+        for pokemon in pokemons {
+            let savepoint = match transaction.savepoint() {
+                Ok(savepoint) => savepoint,
+                _ => {
+                    results.push(Err(InsertError::Unknown));
+                    continue;
+                }
+            };
+
+            results.push(match Self::insert_pokemon_rows(&savepoint, &pokemon) {
+                Ok(()) => match self.enqueue(
+                    &savepoint,
+                    WebhookEvent::PokemonCreated,
+                    outbox::pokemon_data(&pokemon),
+                ) {
+                    Ok(()) => match savepoint.commit() {
+                        Ok(_) => Ok(pokemon),
+                        _ => Err(InsertError::Unknown),
+                    },
+                    _ => Err(InsertError::Unknown),
+                },
+                Err(err) => Err(err),
+            });
+        }
+
+        match transaction.commit() {
+            Ok(_) => results,
+            _ => results
+                .into_iter()
+                .map(|_| Err(InsertError::Unknown))
+                .collect(),
+        }
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        match Self::fetch_queried_pokemons(&lock, query) {
+            Ok((pokemons, _)) => Ok(pokemons),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        match Self::fetch_queried_pokemons(&lock, query) {
+            Ok(listing) => Ok(listing),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchRangeError::Unknown),
+        };
+
+        let pokemon_rows = match Self::fetch_pokemon_rows(&lock, u16::from(from), u16::from(to)) {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(FetchRangeError::Unknown),
+        };
+
+        match Self::decode_rows(&lock, pokemon_rows).map(sift) {
+            Ok(Ok((pokemons, _))) => Ok(pokemons),
+            _ => Err(FetchRangeError::Unknown),
+        }
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchByTypeError::Unknown),
+        };
+
+        match Self::fetch_pokemons_of_type(&lock, pokemon_type) {
+            Ok(pokemons) => Ok(pokemons),
+            _ => Err(FetchByTypeError::Unknown),
+        }
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(NameExistsError::Unknown),
+        };
+
+        match lock.query_row(
+            "select exists(select 1 from pokemons where name = ? collate nocase)",
+            [String::from(name)],
+            |row| row.get::<usize, bool>(0),
+        ) {
+            Ok(exists) => Ok(exists),
+            _ => Err(NameExistsError::Unknown),
+        }
+    }
+
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchPageError::Unknown),
+        };
+
+        let pokemon_rows = match Self::fetch_page_rows(&lock, offset, limit) {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(FetchPageError::Unknown),
+        };
+
+        match Self::decode_rows(&lock, pokemon_rows).map(sift) {
+            Ok(Ok((pokemons, _))) => Ok(pokemons),
+            _ => Err(FetchPageError::Unknown),
+        }
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(CountError::Unknown),
+        };
+
+        match lock.query_row("select count(*) from pokemons", [], |row| {
+            row.get::<usize, u32>(0)
+        }) {
+            Ok(count) => Ok(count),
+            _ => Err(CountError::Unknown),
+        }
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(CountByTypeError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select name, count(*) from types group by name") {
+            Ok(stmt) => stmt,
+            _ => return Err(CountByTypeError::Unknown),
+        };
+
+        let mut rows = match stmt.query([]) {
+            Ok(rows) => rows,
+            _ => return Err(CountByTypeError::Unknown),
+        };
+
+        let mut counts = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, String>(0).map(PokemonType::try_from),
+                row.get::<usize, u32>(1),
+            ) {
+                (Ok(Ok(pokemon_type)), Ok(count)) => counts.push((pokemon_type, count)),
+                _ => return Err(CountByTypeError::Unknown),
+            };
+        }
+
+        Ok(counts)
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, number.value(), number.value())
+        {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        if pokemon_rows.is_empty() {
+            return Err(FetchOneError::NotFound);
+        }
+
+        pokemon_rows.truncate(1);
+
+        match Self::decode_rows(&lock, pokemon_rows).map(|mut decoded| decoded.pop()) {
+            Ok(Some(Ok(pokemon))) => Ok(pokemon),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchByNameError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select number, name from pokemons
+            where name = ? collate nocase or fold(name) = ?
+            order by number limit 1",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchByNameError::Unknown),
+        };
+
+        let mut rows = match stmt.query(params![name.as_str(), PokemonName::fold(name.as_str())]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchByNameError::Unknown),
+        };
+
+        let pokemon_row = match rows.next() {
+            Ok(Some(row)) => match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
+                (Ok(number), Ok(name)) => (number, name),
+                _ => return Err(FetchByNameError::Unknown),
+            },
+            Ok(None) => return Err(FetchByNameError::NotFound),
+            _ => return Err(FetchByNameError::Unknown),
+        };
+
+        drop(rows);
+        drop(stmt);
+
+        match Self::decode_rows(&lock, vec![pokemon_row]).map(|mut decoded| decoded.pop()) {
+            Ok(Some(Ok(pokemon))) => Ok(pokemon),
+            _ => Err(FetchByNameError::Unknown),
+        }
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, number.value(), number.value())
+        {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if pokemon_rows.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let (name, evolutions, abilities, (height, weight), sprite, class) = match (
+            PokemonName::try_from(pokemon_rows.remove(0).1),
+            Self::fetch_evolutions(&lock, number.value()),
+            Self::fetch_abilities(&lock, number.value()),
+            Self::fetch_measurements(&lock, number.value()),
+            Self::fetch_sprite(&lock, number.value()),
+            Self::fetch_class(&lock, number.value()),
+        ) {
+            (Ok(name), Ok(evolutions), Ok(abilities), Ok(measurements), Ok(sprite), Ok(class)) => {
+                (name, evolutions, abilities, measurements, sprite, class)
+            }
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from types where pokemon_number = ?",
+                params![number.value()],
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        for _type in types.as_slice() {
+            if transaction
+                .execute(
+                    "insert into types (pokemon_number, name) values (?, ?)",
+                    params![number.value(), _type.as_str()],
+                )
+                .is_err()
+            {
+                return Err(UpdateError::Unknown);
+            }
+        }
+
+        let pokemon = Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class);
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonUpdated,
+                outbox::pokemon_data(&pokemon),
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match Self::fetch_pokemon_rows(&lock, number.value(), number.value()) {
+            Ok(pokemon_rows) if pokemon_rows.is_empty() => return Err(UpdateError::NotFound),
+            Ok(_) => {}
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let (evolutions, abilities, (height, weight), sprite, class) = match (
+            Self::fetch_evolutions(&lock, number.value()),
+            Self::fetch_abilities(&lock, number.value()),
+            Self::fetch_measurements(&lock, number.value()),
+            Self::fetch_sprite(&lock, number.value()),
+            Self::fetch_class(&lock, number.value()),
+        ) {
+            (Ok(evolutions), Ok(abilities), Ok(measurements), Ok(sprite), Ok(class)) => {
+                (evolutions, abilities, measurements, sprite, class)
+            }
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "update pokemons set name = ? where number = ?",
+                params![name.as_str(), number.value()],
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        if transaction
+            .execute(
+                "delete from types where pokemon_number = ?",
+                params![number.value()],
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        for _type in types.as_slice() {
+            if transaction
+                .execute(
+                    "insert into types (pokemon_number, name) values (?, ?)",
+                    params![number.value(), _type.as_str()],
+                )
+                .is_err()
+            {
+                return Err(UpdateError::Unknown);
+            }
+        }
+
+        let pokemon = Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class);
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonUpdated,
+                outbox::pokemon_data(&pokemon),
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, number.value(), number.value())
+        {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if pokemon_rows.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let (name, types, abilities, (height, weight), sprite, class) = match (
+            PokemonName::try_from(pokemon_rows.remove(0).1),
+            Self::fetch_type_rows(&lock, number.value()).map(PokemonTypes::try_from),
+            Self::fetch_abilities(&lock, number.value()),
+            Self::fetch_measurements(&lock, number.value()),
+            Self::fetch_sprite(&lock, number.value()),
+            Self::fetch_class(&lock, number.value()),
+        ) {
+            (Ok(name), Ok(Ok(types)), Ok(abilities), Ok(measurements), Ok(sprite), Ok(class)) => {
+                (name, types, abilities, measurements, sprite, class)
+            }
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from evolutions where pokemon_number = ?",
+                params![number.value()],
+            )
+            .is_err()
+            || Self::insert_evolution_rows(&transaction, &number, &evolutions).is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        let pokemon = Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class);
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonUpdated,
+                outbox::pokemon_data(&pokemon),
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, number.value(), number.value())
+        {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if pokemon_rows.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let (name, types, evolutions, (height, weight), sprite, class) = match (
+            PokemonName::try_from(pokemon_rows.remove(0).1),
+            Self::fetch_type_rows(&lock, number.value()).map(PokemonTypes::try_from),
+            Self::fetch_evolutions(&lock, number.value()),
+            Self::fetch_measurements(&lock, number.value()),
+            Self::fetch_sprite(&lock, number.value()),
+            Self::fetch_class(&lock, number.value()),
+        ) {
+            (Ok(name), Ok(Ok(types)), Ok(evolutions), Ok(measurements), Ok(sprite), Ok(class)) => {
+                (name, types, evolutions, measurements, sprite, class)
+            }
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from abilities where pokemon_number = ?",
+                params![number.value()],
+            )
+            .is_err()
+            || Self::insert_ability_rows(&transaction, &number, &abilities).is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        let pokemon = Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class);
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonUpdated,
+                outbox::pokemon_data(&pokemon),
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, number.value(), number.value())
+        {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if pokemon_rows.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let (name, types, evolutions, abilities, sprite, class) = match (
+            PokemonName::try_from(pokemon_rows.remove(0).1),
+            Self::fetch_type_rows(&lock, number.value()).map(PokemonTypes::try_from),
+            Self::fetch_evolutions(&lock, number.value()),
+            Self::fetch_abilities(&lock, number.value()),
+            Self::fetch_sprite(&lock, number.value()),
+            Self::fetch_class(&lock, number.value()),
+        ) {
+            (Ok(name), Ok(Ok(types)), Ok(evolutions), Ok(abilities), Ok(sprite), Ok(class)) => {
+                (name, types, evolutions, abilities, sprite, class)
+            }
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from measurements where pokemon_number = ?",
+                params![number.value()],
+            )
+            .is_err()
+            || Self::insert_measurement_row(&transaction, &number, height, weight).is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        let pokemon = Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class);
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonUpdated,
+                outbox::pokemon_data(&pokemon),
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, number.value(), number.value())
+        {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if pokemon_rows.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let (name, types, evolutions, abilities, (height, weight), class) = match (
+            PokemonName::try_from(pokemon_rows.remove(0).1),
+            Self::fetch_type_rows(&lock, number.value()).map(PokemonTypes::try_from),
+            Self::fetch_evolutions(&lock, number.value()),
+            Self::fetch_abilities(&lock, number.value()),
+            Self::fetch_measurements(&lock, number.value()),
+            Self::fetch_class(&lock, number.value()),
+        ) {
+            (
+                Ok(name),
+                Ok(Ok(types)),
+                Ok(evolutions),
+                Ok(abilities),
+                Ok(measurements),
+                Ok(class),
+            ) => (name, types, evolutions, abilities, measurements, class),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from sprites where pokemon_number = ?",
+                params![number.value()],
+            )
+            .is_err()
+            || Self::insert_sprite_row(&transaction, &number, &sprite).is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        let pokemon = Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class);
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonUpdated,
+                outbox::pokemon_data(&pokemon),
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, number.value(), number.value())
+        {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if pokemon_rows.is_empty() {
+            return Err(UpdateError::NotFound);
+        }
+
+        let (name, types, evolutions, abilities, (height, weight), sprite) = match (
+            PokemonName::try_from(pokemon_rows.remove(0).1),
+            Self::fetch_type_rows(&lock, number.value()).map(PokemonTypes::try_from),
+            Self::fetch_evolutions(&lock, number.value()),
+            Self::fetch_abilities(&lock, number.value()),
+            Self::fetch_measurements(&lock, number.value()),
+            Self::fetch_sprite(&lock, number.value()),
+        ) {
+            (
+                Ok(name),
+                Ok(Ok(types)),
+                Ok(evolutions),
+                Ok(abilities),
+                Ok(measurements),
+                Ok(sprite),
+            ) => (name, types, evolutions, abilities, measurements, sprite),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from classes where pokemon_number = ?",
+                params![number.value()],
+            )
+            .is_err()
+            || Self::insert_class_row(&transaction, &number, class).is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        let pokemon = Pokemon::new(number, name, types)
+            .with_evolutions(evolutions)
+            .with_abilities(abilities)
+            .with_measurements(height, weight)
+            .with_sprite(sprite)
+            .with_class(class);
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonUpdated,
+                outbox::pokemon_data(&pokemon),
+            )
+            .is_err()
+        {
+            return Err(UpdateError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match transaction.execute(
+            "delete from pokemons where number = ?",
+            params![number.value()],
+        ) {
+            Ok(0) => return Err(DeleteError::NotFound),
+            Ok(_) => {}
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        if self
+            .enqueue(
+                &transaction,
+                WebhookEvent::PokemonDeleted,
+                outbox::deleted_data(&number),
+            )
+            .is_err()
+        {
+            return Err(DeleteError::Unknown);
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteByTypeError::Unknown),
+        };
+
+        let pokemons = match Self::fetch_pokemons_of_type(&lock, pokemon_type.clone()) {
+            Ok(pokemons) => pokemons,
+            _ => return Err(DeleteByTypeError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(DeleteByTypeError::Unknown),
+        };
+
+        if transaction
+            .execute(
+                "delete from pokemons where number in
+                (select pokemon_number from types where name = ?)",
+                params![pokemon_type.as_str()],
+            )
+            .is_err()
+        {
+            return Err(DeleteByTypeError::Unknown);
+        }
+
+        for pokemon in pokemons.iter() {
+            if self
+                .enqueue(
+                    &transaction,
+                    WebhookEvent::PokemonDeleted,
+                    outbox::deleted_data(&pokemon.number),
+                )
+                .is_err()
+            {
+                return Err(DeleteByTypeError::Unknown);
+            }
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemons),
+            _ => Err(DeleteByTypeError::Unknown),
+        }
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        let mut lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(ClearError::Unknown),
+        };
+
+        let pokemon_rows = match Self::fetch_pokemon_rows(&lock, 0, u16::MAX) {
+            Ok(pokemon_rows) => pokemon_rows,
+            _ => return Err(ClearError::Unknown),
+        };
+
+        let pokemons = match Self::decode_rows(&lock, pokemon_rows) {
+            Ok(decoded) => decoded.into_iter().flatten().collect::<Vec<Pokemon>>(),
+            _ => return Err(ClearError::Unknown),
+        };
+
+        let transaction = match lock.savepoint() {
+            Ok(transaction) => transaction,
+            _ => return Err(ClearError::Unknown),
+        };
+
+        if transaction
+            .execute_batch(
+                "delete from classes; delete from sprites; delete from measurements; delete from abilities;
+                delete from evolutions; delete from types; delete from pokemons;",
+            )
+            .is_err()
+        {
+            return Err(ClearError::Unknown);
+        }
+
+        for pokemon in pokemons.iter() {
+            if self
+                .enqueue(
+                    &transaction,
+                    WebhookEvent::PokemonDeleted,
+                    outbox::deleted_data(&pokemon.number),
+                )
+                .is_err()
+            {
+                return Err(ClearError::Unknown);
+            }
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(pokemons),
+            _ => Err(ClearError::Unknown),
+        }
+    }
+
+    // The reads of a transaction see the database as it was at the first
+    // one, whatever the other connections write meanwhile. A savepoint
+    // starts one, or nests in the transaction begun by this thread.
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        let lock = match self.lock() {
+            Ok(lock) => lock,
+            _ => return Err(SnapshotError::Unknown),
+        };
+
+        if lock.execute_batch("savepoint snapshot").is_err() {
+            return Err(SnapshotError::Unknown);
+        }
+
+        let pokemons = Self::fetch_queried_pokemons(&lock, Query::new());
+
+        match (pokemons, lock.execute_batch("release snapshot")) {
+            (Ok((pokemons, _)), Ok(())) => Ok(pokemons),
+            _ => Err(SnapshotError::Unknown),
+        }
+    }
+
+    fn begin(&self) -> Result<(), TransactionError> {
+        let current = thread::current().id();
+        let mut owner = match self.owner.lock() {
+            Ok(owner) => owner,
+            _ => return Err(TransactionError::Unknown),
+        };
+        owner = match self
+            .released
+            .wait_while(owner, |owner| owner.is_some_and(|id| id != current))
+        {
+            Ok(owner) => owner,
+            _ => return Err(TransactionError::Unknown),
+        };
+
+        // Transactions don't nest.
+        if owner.is_some() {
+            return Err(TransactionError::Unknown);
+        }
+
+        match self
+            .connection
+            .lock()
+            .map(|lock| lock.execute_batch("begin immediate"))
+        {
+            Ok(Ok(())) => {
+                *owner = Some(current);
+                Ok(())
+            }
+            _ => Err(TransactionError::Unknown),
+        }
+    }
+
+    fn commit(&self) -> Result<(), TransactionError> {
+        self.end("commit")
+    }
+
+    fn rollback(&self) -> Result<(), TransactionError> {
+        self.end("rollback")
+    }
+
+    fn backend(&self) -> Backend {
+        let schema_version = match self.lock() {
+            Ok(lock) => lock
+                .query_row("pragma user_version", [], |row| row.get::<usize, u32>(0))
+                .ok(),
+            _ => None,
+        };
+        Backend {
+            kind: String::from("sqlite"),
+            schema_version,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+/// Talks to another pokedex server through its api. Only single Pokemons can
+/// be fetched upstream, the other reads filter the whole collection here.
+pub struct HttpRepository {
+    url: String,
+}
+
+#[cfg(feature = "server")]
+impl HttpRepository {
+    pub fn try_new(url: &str) -> Result<Self, ()> {
+        let url = url.trim_end_matches('/').to_string();
+
+        if ureq::get(&format!("{}/health", url)).call().is_err() {
+            return Err(());
+        }
+
+        Ok(Self { url })
+    }
+
+    fn snapshot(&self) -> Result<InMemoryRepository, ()> {
+        let json = match ureq::get(&format!("{}/pokemons", self.url))
+            .call()
+            .map(|res| res.into_json::<Vec<HttpPokemon>>())
+        {
+            Ok(Ok(json)) => json,
+            _ => return Err(()),
+        };
+
+        let (pokemons, invalid) = match sift(json.into_iter().map(HttpPokemon::decode)) {
+            Ok(sifted) => sifted,
+            _ => return Err(()),
+        };
+
+        let repo = InMemoryRepository {
+            invalid,
+            ..InMemoryRepository::new()
+        };
+
+        if repo.insert_many(pokemons).iter().any(Result::is_err) {
+            return Err(());
+        }
+
+        Ok(repo)
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for HttpRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        let body = ureq::json!({
+            "number": number.value(),
+            "name": name.as_str(),
+            "types": types.as_slice().iter().map(PokemonType::as_str).collect::<Vec<&str>>(),
+        });
+
+        match ureq::post(&format!("{}/pokemons", self.url)).send_json(body) {
+            Ok(_) => Ok(Pokemon::new(number, name, types)),
+            Err(ureq::Error::Status(409, _)) => Err(InsertError::Conflict),
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        pokemons
+            .into_iter()
+            .map(|p| self.insert(p.number, p.name, p.types))
+            .collect()
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        match self.snapshot() {
+            Ok(repo) => repo.fetch_all(query),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        match self.snapshot() {
+            Ok(repo) => repo.fetch_all_skipping_invalid(query),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        match self.snapshot() {
+            Ok(repo) => repo.fetch_range(from, to),
+            _ => Err(FetchRangeError::Unknown),
+        }
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        match self.snapshot() {
+            Ok(repo) => repo.fetch_by_type(pokemon_type),
+            _ => Err(FetchByTypeError::Unknown),
+        }
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        match self.snapshot() {
+            Ok(repo) => repo.name_exists(name),
+            _ => Err(NameExistsError::Unknown),
+        }
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        match self.snapshot() {
+            Ok(repo) => repo.count(),
+            _ => Err(CountError::Unknown),
+        }
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        match self.snapshot() {
+            Ok(repo) => repo.count_by_type(),
+            _ => Err(CountByTypeError::Unknown),
+        }
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        let pokemon = match ureq::get(&format!("{}/pokemons/{}", self.url, number.value()))
+            .call()
+            .map(|res| res.into_json::<HttpPokemon>())
+        {
+            Ok(Ok(pokemon)) => pokemon,
+            Err(ureq::Error::Status(404, _)) => return Err(FetchOneError::NotFound),
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match pokemon.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        match self.snapshot() {
+            Ok(repo) => repo.fetch_by_name(name),
+            _ => Err(FetchByNameError::Unknown),
+        }
+    }
+
+    // The api only adds or removes one type at a time: the new types are
+    // added before the old ones are removed, so a Pokemon never has none.
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let pokemon = match self.fetch_one(number.clone()) {
+            Ok(pokemon) => pokemon,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+
+        let url = format!("{}/pokemons/{}/types", self.url, number.value());
+        let old = pokemon.types.as_slice();
+        let new = types.as_slice();
+        let additions = new.iter().filter(|t| !old.contains(t));
+        let removals = old.iter().filter(|t| !new.contains(t));
+
+        for body in additions
+            .map(|t| ureq::json!({ "add": t.as_str() }))
+            .chain(removals.map(|t| ureq::json!({ "remove": t.as_str() })))
+        {
+            match ureq::request("PATCH", &url).send_json(body) {
+                Ok(_) => {}
+                Err(ureq::Error::Status(404, _)) => return Err(UpdateError::NotFound),
+                _ => return Err(UpdateError::Unknown),
+            }
+        }
+
+        Ok(Pokemon { types, ..pokemon })
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let body = ureq::json!({
+            "name": name.as_str(),
+            "types": types.as_slice().iter().map(PokemonType::as_str).collect::<Vec<&str>>(),
+        });
+
+        match ureq::put(&format!("{}/pokemons/{}", self.url, number.value())).send_json(body) {
+            Ok(_) => {}
+            Err(ureq::Error::Status(404, _)) => return Err(UpdateError::NotFound),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        // The remote only answers with the name and the types, the
+        // evolutions and the abilities it kept are read again.
+        match self.fetch_one(number) {
+            Ok(pokemon) => Ok(pokemon),
+            Err(FetchOneError::NotFound) => Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        let body = ureq::json!({
+            "evolutions": evolutions.iter().map(EvolutionRecord::from).collect::<Vec<EvolutionRecord>>(),
+        });
+
+        let pokemon = match ureq::put(&format!(
+            "{}/pokemons/{}/evolutions",
+            self.url,
+            number.value()
+        ))
+        .send_json(body)
+        .map(|res| res.into_json::<HttpPokemon>())
+        {
+            Ok(Ok(pokemon)) => pokemon,
+            Err(ureq::Error::Status(404, _)) => return Err(UpdateError::NotFound),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match pokemon.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        let body = ureq::json!({
+            "abilities": ability_records(&abilities),
+        });
+
+        let pokemon = match ureq::put(&format!(
+            "{}/pokemons/{}/abilities",
+            self.url,
+            number.value()
+        ))
+        .send_json(body)
+        .map(|res| res.into_json::<HttpPokemon>())
+        {
+            Ok(Ok(pokemon)) => pokemon,
+            Err(ureq::Error::Status(404, _)) => return Err(UpdateError::NotFound),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match pokemon.decode() {
+            Ok(pokemon) => Ok(pokemon),
+            _ => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        let measurements = MeasurementsRecord::new(height, weight);
+        let body = ureq::json!({
+            "height": measurements.height,
+            "weight": measurements.weight,
+        });
+
+        match ureq::put(&format!(
+            "{}/pokemons/{}/measurements",
+            self.url,
+            number.value()
+        ))
+        .send_json(body)
+        {
+            Ok(_) => {}
+            Err(ureq::Error::Status(404, _)) => return Err(UpdateError::NotFound),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        // Like `update`, the rest of the Pokemon is read again.
+        match self.fetch_one(number) {
+            Ok(pokemon) => Ok(pokemon),
+            Err(FetchOneError::NotFound) => Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        let body = ureq::json!({
+            "url": sprite_record(&sprite),
+        });
+
+        match ureq::put(&format!("{}/pokemons/{}/sprite", self.url, number.value())).send_json(body)
+        {
+            Ok(_) => {}
+            Err(ureq::Error::Status(404, _)) => return Err(UpdateError::NotFound),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match self.fetch_one(number) {
+            Ok(pokemon) => Ok(pokemon),
+            Err(FetchOneError::NotFound) => Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        let body = ureq::json!({
+            "class": String::from(class),
+        });
+
+        match ureq::put(&format!("{}/pokemons/{}/class", self.url, number.value())).send_json(body)
+        {
+            Ok(_) => {}
+            Err(ureq::Error::Status(404, _)) => return Err(UpdateError::NotFound),
+            _ => return Err(UpdateError::Unknown),
+        };
+
+        match self.fetch_one(number) {
+            Ok(pokemon) => Ok(pokemon),
+            Err(FetchOneError::NotFound) => Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => Err(UpdateError::Unknown),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        match ureq::delete(&format!("{}/pokemons/{}", self.url, number.value())).call() {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(404, _)) => Err(DeleteError::NotFound),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        let json = match ureq::delete(&format!("{}/pokemons", self.url))
+            .query("type", pokemon_type.as_str())
+            .query("confirm", "true")
+            .call()
+            .map(|res| res.into_json::<HttpDeleted>())
+        {
+            Ok(Ok(json)) => json,
+            _ => return Err(DeleteByTypeError::Unknown),
+        };
+
+        match sift(json.pokemons.into_iter().map(HttpPokemon::decode)) {
+            Ok((pokemons, _)) => Ok(pokemons),
+            _ => Err(DeleteByTypeError::Unknown),
+        }
+    }
+
+    // Deleting type by type needs no admin token on the remote.
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        let mut pokemons = vec![];
+
+        for pokemon_type in PokemonType::all() {
+            match self.delete_by_type(pokemon_type) {
+                Ok(deleted) => pokemons.extend(deleted),
+                _ => return Err(ClearError::Unknown),
+            }
+        }
+
+        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(pokemons)
+    }
+
+    fn backend(&self) -> Backend {
+        Backend::new("http")
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct HttpPokemon {
+    number: u16,
+    name: String,
+    types: Vec<String>,
+    // Older servers don't know about evolutions, abilities, measurements,
+    // sprites nor classes.
+    #[serde(default)]
+    evolutions: Vec<EvolutionRecord>,
+    #[serde(default)]
+    abilities: Vec<AbilityRecord>,
+    #[serde(default)]
+    height: Option<f64>,
+    #[serde(default)]
+    weight: Option<f64>,
+    #[serde(default)]
+    sprite: Option<String>,
+    #[serde(default)]
+    class: Option<String>,
+}
+
+#[cfg(feature = "server")]
+impl HttpPokemon {
+    fn decode(self) -> Result<Pokemon, InvalidRecord> {
+        decode(
+            self.number,
+            self.name,
+            self.types,
+            self.evolutions,
+            self.abilities,
+            MeasurementsRecord {
+                height: self.height,
+                weight: self.weight,
+            },
+            self.sprite,
+            self.class,
+        )
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct HttpDeleted {
+    pokemons: Vec<HttpPokemon>,
+}
+
+#[cfg(feature = "server")]
+/// Records every successful mutation of the wrapped repository in the change log.
+/// The changes made during a transaction are held back until it commits.
+pub struct ChangeLoggingRepository {
+    inner: Arc<dyn Repository>,
+    changes: Arc<dyn change::Repository>,
+    pending: Mutex<Option<(ThreadId, Vec<PendingChange>)>>,
+}
+
+#[cfg(feature = "server")]
+struct PendingChange {
+    kind: ChangeKind,
+    number: PokemonNumber,
+    pokemon: Option<Pokemon>,
+    timestamp: u64,
+}
+
+#[cfg(feature = "server")]
+impl ChangeLoggingRepository {
+    pub fn new(inner: Arc<dyn Repository>, changes: Arc<dyn change::Repository>) -> Self {
+        Self {
+            inner,
+            changes,
+            pending: Mutex::new(None),
+        }
+    }
+
+    fn record(&self, kind: ChangeKind, number: PokemonNumber, pokemon: Option<Pokemon>) {
+        let change = PendingChange {
+            kind,
+            number,
+            pokemon,
+            timestamp: now(),
+        };
+        let change = match self.pending.lock() {
+            Ok(mut pending) => match pending.as_mut() {
+                Some((owner, changes)) if *owner == thread::current().id() => {
+                    changes.push(change);
+                    return;
+                }
+                _ => change,
+            },
+            _ => change,
+        };
+        self.log(change);
+    }
+
+    // The write being done already, a change which can't be logged is only
+    // reported.
+    fn log(&self, change: PendingChange) {
+        let number = u16::from(change.number.clone());
+        if self
+            .changes
+            .insert(change.kind, change.number, change.pokemon, change.timestamp)
+            .is_err()
+        {
+            eprintln!("The change of #{} could not be logged", number);
+        }
+    }
+
+    fn take_pending(&self) -> Vec<PendingChange> {
+        match self.pending.lock() {
+            Ok(mut pending) => match pending.take() {
+                Some((_, changes)) => changes,
+                None => vec![],
+            },
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for ChangeLoggingRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        match self.inner.insert(number, name, types) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Created,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        let results = self.inner.insert_many(pokemons);
+        for pokemon in results.iter().flatten() {
+            self.record(
+                ChangeKind::Created,
+                pokemon.number.clone(),
+                Some(pokemon.clone()),
+            );
+        }
+        results
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.inner.fetch_all(query)
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        self.inner.fetch_all_skipping_invalid(query)
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        self.inner.fetch_range(from, to)
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        self.inner.fetch_by_type(pokemon_type)
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        self.inner.name_exists(name)
+    }
+
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        self.inner.fetch_page(offset, limit)
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        self.inner.count()
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        self.inner.count_by_type()
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        self.inner.fetch_one(number)
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        self.inner.fetch_by_name(name)
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        match self.inner.update_types(number, types) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Updated,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        match self.inner.update(number, name, types) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Updated,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        match self.inner.update_evolutions(number, evolutions) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Updated,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        match self.inner.update_abilities(number, abilities) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Updated,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        match self.inner.update_measurements(number, height, weight) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Updated,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        match self.inner.update_sprite(number, sprite) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Updated,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        match self.inner.update_class(number, class) {
+            Ok(pokemon) => {
+                self.record(
+                    ChangeKind::Updated,
+                    pokemon.number.clone(),
+                    Some(pokemon.clone()),
+                );
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        match self.inner.delete(number.clone()) {
+            Ok(()) => {
+                self.record(ChangeKind::Deleted, number, None);
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        match self.inner.delete_by_type(pokemon_type) {
+            Ok(pokemons) => {
+                for pokemon in pokemons.iter() {
+                    self.record(ChangeKind::Deleted, pokemon.number.clone(), None);
+                }
+                Ok(pokemons)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        match self.inner.clear() {
+            Ok(pokemons) => {
+                for pokemon in pokemons.iter() {
+                    self.record(ChangeKind::Deleted, pokemon.number.clone(), None);
+                }
+                Ok(pokemons)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        self.inner.snapshot()
+    }
+
+    fn begin(&self) -> Result<(), TransactionError> {
+        match self.inner.begin() {
+            Ok(()) => {
+                if let Ok(mut pending) = self.pending.lock() {
+                    *pending = Some((thread::current().id(), vec![]));
+                }
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn commit(&self) -> Result<(), TransactionError> {
+        let changes = self.take_pending();
+        match self.inner.commit() {
+            Ok(()) => {
+                for change in changes {
+                    self.log(change);
+                }
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn rollback(&self) -> Result<(), TransactionError> {
+        self.take_pending();
+        self.inner.rollback()
+    }
+
+    fn backend(&self) -> Backend {
+        self.inner.backend()
+    }
+}
+
+#[cfg(feature = "server")]
+/// Serves reads from a local copy, typically a sqlite cache kept up to date
+/// by `mirror::spawn`, while writes go to the remote backend first and are
+/// then applied to the local copy.
+pub struct MirroredRepository {
+    remote: Arc<dyn Repository>,
+    local: Arc<dyn Repository>,
+    queue: Option<Arc<dyn write_queue::Repository>>,
+}
+
+#[cfg(feature = "server")]
+impl MirroredRepository {
+    pub fn new(remote: Arc<dyn Repository>, local: Arc<dyn Repository>) -> Self {
+        Self {
+            remote,
+            local,
+            queue: None,
+        }
+    }
+
+    /// While the remote backend is down, and then until the writes waiting
+    /// in `queue` are replayed by `mirror::spawn`, writes are applied to the
+    /// local copy only and queued.
+    pub fn with_queue(self, queue: Arc<dyn write_queue::Repository>) -> Self {
+        Self {
+            queue: Some(queue),
+            ..self
+        }
+    }
+
+    /// Older writes wait, so the remote backend must not get this one first.
+    fn is_queueing(&self) -> bool {
+        matches!(
+            self.queue.as_ref().map(|queue| queue.depth()),
+            Some(Ok(1..))
+        )
+    }
+
+    fn queue_locally<T, E>(
+        &self,
+        write: Write,
+        local: impl FnOnce() -> Result<T, E>,
+        unknown: E,
+    ) -> Result<T, E> {
+        let queue = match &self.queue {
+            Some(queue) => queue,
+            None => return Err(unknown),
+        };
+
+        match local() {
+            Ok(res) => match queue.push(write) {
+                Ok(()) => Ok(res),
+                _ => Err(unknown),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    fn copy_locally(&self, pokemon: &Pokemon) {
+        self.local.delete(pokemon.number.clone()).ok();
+        self.local.insert_many(vec![pokemon.clone()]);
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for MirroredRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        let res = match self.is_queueing() {
+            true => Err(InsertError::Unknown),
+            false => self
+                .remote
+                .insert(number.clone(), name.clone(), types.clone()),
+        };
+
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
+            }
+            Err(InsertError::Unknown) => self.queue_locally(
+                Write::Insert {
+                    number: number.value(),
+                    name: String::from(name.clone()),
+                    types: Vec::<String>::from(types.clone()),
+                },
+                || self.local.insert(number, name, types),
+                InsertError::Unknown,
+            ),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        let results = match self.is_queueing() {
+            true => pokemons.iter().map(|_| Err(InsertError::Unknown)).collect(),
+            false => self.remote.insert_many(pokemons.clone()),
+        };
+
+        pokemons
+            .into_iter()
+            .zip(results)
+            .map(|(pokemon, res)| match res {
+                Ok(pokemon) => {
+                    self.copy_locally(&pokemon);
+                    Ok(pokemon)
+                }
+                Err(InsertError::Unknown) => self.queue_locally(
+                    Write::Insert {
+                        number: pokemon.number.value(),
+                        name: String::from(pokemon.name.clone()),
+                        types: Vec::<String>::from(pokemon.types.clone()),
+                    },
+                    || {
+                        self.local
+                            .insert(pokemon.number, pokemon.name, pokemon.types)
+                    },
+                    InsertError::Unknown,
+                ),
+                Err(err) => Err(err),
+            })
+            .collect()
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.local.fetch_all(query)
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        self.local.fetch_all_skipping_invalid(query)
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        self.local.fetch_range(from, to)
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        self.local.fetch_by_type(pokemon_type)
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        self.local.name_exists(name)
+    }
+
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        self.local.fetch_page(offset, limit)
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        self.local.count()
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        self.local.count_by_type()
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        self.local.fetch_one(number)
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        self.local.fetch_by_name(name)
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let res = match self.is_queueing() {
+            true => Err(UpdateError::Unknown),
+            false => self.remote.update_types(number.clone(), types.clone()),
+        };
+
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
+            }
+            Err(UpdateError::Unknown) => self.queue_locally(
+                Write::UpdateTypes {
+                    number: number.value(),
+                    types: Vec::<String>::from(types.clone()),
+                },
+                || self.local.update_types(number, types),
+                UpdateError::Unknown,
+            ),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let res = match self.is_queueing() {
+            true => Err(UpdateError::Unknown),
+            false => self
+                .remote
+                .update(number.clone(), name.clone(), types.clone()),
+        };
+
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
+            }
+            Err(UpdateError::Unknown) => self.queue_locally(
+                Write::Update {
+                    number: number.value(),
+                    name: String::from(name.clone()),
+                    types: Vec::<String>::from(types.clone()),
+                },
+                || self.local.update(number, name, types),
+                UpdateError::Unknown,
+            ),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The write queue has no room for evolutions: they wait for the remote
+    /// backend to be back.
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        let res = match self.is_queueing() {
+            true => Err(UpdateError::Unknown),
+            false => self.remote.update_evolutions(number, evolutions),
+        };
+
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Like the evolutions, the abilities wait for the remote backend.
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        let res = match self.is_queueing() {
+            true => Err(UpdateError::Unknown),
+            false => self.remote.update_abilities(number, abilities),
+        };
+
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
         }
-
-        Ok(Pokemon::new(number, name, types))
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
-        let json = match self.fetch_pokemon_rows(None) {
-            Ok(json) => json,
-            _ => return Err(FetchAllError::Unknown),
+    /// Like the evolutions, the measurements wait for the remote backend.
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        let res = match self.is_queueing() {
+            true => Err(UpdateError::Unknown),
+            false => self.remote.update_measurements(number, height, weight),
         };
 
-        let mut pokemons = vec![];
-
-        for record in json.records.into_iter() {
-            match (
-                PokemonNumber::try_from(record.fields.number),
-                PokemonName::try_from(record.fields.name),
-                PokemonTypes::try_from(record.fields.types),
-            ) {
-                (Ok(number), Ok(name), Ok(types)) => {
-                    pokemons.push(Pokemon::new(number, name, types))
-                }
-                _ => return Err(FetchAllError::Unknown),
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
             }
+            Err(err) => Err(err),
         }
-
-        Ok(pokemons)
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
-        let mut json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
-            Ok(json) => json,
-            _ => return Err(FetchOneError::Unknown),
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        let res = match self.is_queueing() {
+            true => Err(UpdateError::Unknown),
+            false => self.remote.update_sprite(number, sprite),
         };
 
-        if json.records.is_empty() {
-            return Err(FetchOneError::NotFound);
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
         }
+    }
 
-        let record = json.records.remove(0);
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        let res = match self.is_queueing() {
+            true => Err(UpdateError::Unknown),
+            false => self.remote.update_class(number, class),
+        };
 
-        match (
-            PokemonNumber::try_from(record.fields.number),
-            PokemonName::try_from(record.fields.name),
-            PokemonTypes::try_from(record.fields.types),
-        ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(FetchOneError::Unknown),
+        match res {
+            Ok(pokemon) => {
+                self.copy_locally(&pokemon);
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
         }
     }
 
     fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
-        let mut json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
-            Ok(json) => json,
-            _ => return Err(DeleteError::Unknown),
+        let res = match self.is_queueing() {
+            true => Err(DeleteError::Unknown),
+            false => self.remote.delete(number.clone()),
         };
 
-        if json.records.is_empty() {
-            return Err(DeleteError::NotFound);
+        match res {
+            Ok(()) => {
+                self.local.delete(number).ok();
+                Ok(())
+            }
+            Err(DeleteError::Unknown) => self.queue_locally(
+                Write::Delete {
+                    number: number.value(),
+                },
+                || self.local.delete(number),
+                DeleteError::Unknown,
+            ),
+            Err(err) => Err(err),
         }
+    }
 
-        let record = json.records.remove(0);
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        let res = match self.is_queueing() {
+            true => Err(DeleteByTypeError::Unknown),
+            false => self.remote.delete_by_type(pokemon_type.clone()),
+        };
 
-        match ureq::delete(&format!("{}/{}", self.url, record.id))
-            .set("Authorization", &self.auth_header)
-            .call()
-        {
-            Ok(_) => Ok(()),
-            _ => Err(DeleteError::Unknown),
+        match res {
+            Ok(pokemons) => {
+                for pokemon in pokemons.iter() {
+                    self.local.delete(pokemon.number.clone()).ok();
+                }
+                Ok(pokemons)
+            }
+            Err(DeleteByTypeError::Unknown) => self.queue_locally(
+                Write::DeleteByType {
+                    pokemon_type: String::from(pokemon_type.as_str()),
+                },
+                || self.local.delete_by_type(pokemon_type),
+                DeleteByTypeError::Unknown,
+            ),
         }
     }
-}
-
-#[derive(Deserialize)]
-struct AirtableJson {
-    records: Vec<AirtableRecord>,
-}
 
-#[derive(Deserialize)]
-struct AirtableRecord {
-    id: String,
-    fields: AirtableFields,
-}
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        let res = match self.is_queueing() {
+            true => Err(ClearError::Unknown),
+            false => self.remote.clear(),
+        };
 
-#[derive(Deserialize)]
-struct AirtableFields {
-    number: u16,
-    name: String,
-    types: Vec<String>,
-}
+        match res {
+            Ok(pokemons) => {
+                self.local.clear().ok();
+                Ok(pokemons)
+            }
+            Err(ClearError::Unknown) => {
+                self.queue_locally(Write::Clear, || self.local.clear(), ClearError::Unknown)
+            }
+        }
+    }
 
-pub struct SqliteRepository {
-    connection: Mutex<Connection>,
+    fn backend(&self) -> Backend {
+        let (remote, local) = (self.remote.backend(), self.local.backend());
+        Backend {
+            kind: format!("{}+{}", remote.kind, local.kind),
+            schema_version: local.schema_version,
+        }
+    }
 }
 
-impl SqliteRepository {
-    pub fn try_new(path: &str) -> Result<Self, ()> {
-        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
-        {
-            Ok(connection) => connection,
-            _ => return Err(()),
-        };
+// Contract tests: every backend that can run offline must return the same
+// pokemons in the same order for a given query.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::EvolutionMethod;
 
-        match connection.execute("pragma foreign_keys = 1", []) {
-            Ok(_) => Ok(Self {
-                connection: Mutex::new(connection),
-            }),
-            _ => Err(()),
+    fn repositories() -> Vec<Box<dyn Repository>> {
+        let repositories: Vec<Box<dyn Repository>> = vec![
+            Box::new(InMemoryRepository::new()),
+            #[cfg(feature = "server")]
+            Box::new(SqliteRepository::in_memory()),
+        ];
+        for repo in repositories.iter() {
+            for (number, name, pokemon_type) in [
+                (25, "Pikachu", "Electric"),
+                (4, "charmander", "Fire"),
+                (125, "Electabuzz", "Electric"),
+                (6, "Charizard", "Fire"),
+            ] {
+                match (
+                    PokemonNumber::try_from(number),
+                    PokemonName::try_from(String::from(name)),
+                    PokemonTypes::try_from(vec![String::from(pokemon_type)]),
+                ) {
+                    (Ok(number), Ok(name), Ok(types)) => {
+                        repo.insert(number, name, types).ok();
+                    }
+                    _ => unreachable!(),
+                }
+            }
         }
+        repositories
     }
 
-    fn fetch_pokemon_rows(
-        lock: &MutexGuard<'_, Connection>,
-        number: Option<u16>,
-    ) -> Result<Vec<(u16, String)>, ()> {
-        let (query, params) = match number {
-            Some(number) => (
-                "select number, name from pokemons where number = ?",
-                vec![number],
-            ),
-            _ => ("select number, name from pokemons", vec![]),
-        };
+    fn numbers(repo: &dyn Repository, query: Query) -> Vec<u16> {
+        match repo.fetch_all(query) {
+            Ok(pokemons) => pokemons.into_iter().map(|p| p.number.value()).collect(),
+            _ => unreachable!(),
+        }
+    }
 
-        let mut stmt = match lock.prepare(query) {
-            Ok(stmt) => stmt,
-            _ => return Err(()),
-        };
+    #[test]
+    fn it_should_sort_by_ascending_number_by_default() {
+        for repo in repositories() {
+            assert_eq!(numbers(repo.as_ref(), Query::new()), vec![4, 6, 25, 125]);
+        }
+    }
 
-        let mut rows = match stmt.query(params_from_iter(params)) {
-            Ok(rows) => rows,
-            _ => return Err(()),
-        };
+    #[test]
+    fn it_should_sort_by_name_ignoring_case() {
+        for repo in repositories() {
+            let query = Query {
+                sort_by: SortBy::Name,
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![6, 4, 125, 25]);
+        }
+    }
 
-        let mut pokemon_rows = vec![];
+    #[test]
+    fn it_should_sort_by_type_then_by_number() {
+        for repo in repositories() {
+            let query = Query {
+                sort_by: SortBy::Type,
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![25, 125, 4, 6]);
 
-        while let Ok(Some(row)) = rows.next() {
-            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
-                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
-                _ => return Err(()),
+            let query = Query {
+                sort_by: SortBy::Type,
+                descending: true,
+                ..Query::new()
             };
+            assert_eq!(numbers(repo.as_ref(), query), vec![4, 6, 25, 125]);
         }
+    }
 
-        Ok(pokemon_rows)
+    #[test]
+    fn it_should_sort_in_descending_order() {
+        for repo in repositories() {
+            let query = Query {
+                descending: true,
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![125, 25, 6, 4]);
+        }
     }
 
-    fn fetch_type_rows(lock: &MutexGuard<'_, Connection>, number: u16) -> Result<Vec<String>, ()> {
-        let mut stmt = match lock.prepare("select name from types where pokemon_number = ?") {
-            Ok(stmt) => stmt,
-            _ => return Err(()),
-        };
+    #[test]
+    fn it_should_filter_by_name_ignoring_case() {
+        for repo in repositories() {
+            let query = Query {
+                name: Some(String::from("CHAR")),
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![4, 6]);
+        }
+    }
 
-        let mut rows = match stmt.query([number]) {
-            Ok(rows) => rows,
-            _ => return Err(()),
-        };
+    #[test]
+    fn it_should_filter_by_type() {
+        for repo in repositories() {
+            let query = Query {
+                pokemon_type: Some(PokemonType::Electric),
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![25, 125]);
+        }
+    }
 
-        let mut type_rows = vec![];
+    #[test]
+    fn it_should_filter_by_class() {
+        for repo in repositories() {
+            repo.update_class(PokemonNumber::charmander(), PokemonClass::Legendary)
+                .ok();
+            let query = Query {
+                class: Some(PokemonClass::Legendary),
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![4]);
 
-        while let Ok(Some(row)) = rows.next() {
-            match row.get::<usize, String>(0) {
-                Ok(name) => type_rows.push(name),
-                _ => return Err(()),
+            let query = Query {
+                class: Some(PokemonClass::Normal),
+                ..Query::new()
             };
+            assert_eq!(numbers(repo.as_ref(), query), vec![6, 25, 125]);
         }
-
-        Ok(type_rows)
     }
-}
 
-impl Repository for SqliteRepository {
-    fn insert(
-        &self,
-        number: PokemonNumber,
-        name: PokemonName,
-        types: PokemonTypes,
-    ) -> Result<Pokemon, InsertError> {
-        let mut lock = match self.connection.lock() {
-            Ok(lock) => lock,
-            _ => return Err(InsertError::Unknown),
-        };
+    #[test]
+    fn it_should_page_through_the_sorted_and_filtered_pokemons() {
+        for repo in repositories() {
+            let query = Query {
+                descending: true,
+                offset: 1,
+                limit: Some(2),
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![25, 6]);
 
-        let transaction = match lock.transaction() {
-            Ok(transaction) => transaction,
-            _ => return Err(InsertError::Unknown),
-        };
+            let query = Query {
+                offset: 3,
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![125]);
+        }
+    }
 
-        match transaction.execute(
-            "insert into pokemons (number, name) values (?, ?)",
-            params![u16::from(number.clone()), String::from(name.clone())],
-        ) {
-            Ok(_) => {}
-            Err(SqliteFailure(_, Some(message))) => {
-                if message == "UNIQUE constraint failed: pokemons.number" {
-                    return Err(InsertError::Conflict);
-                } else {
-                    return Err(InsertError::Unknown);
+    #[test]
+    fn it_should_fetch_by_name_ignoring_case_and_accents() {
+        for repo in repositories() {
+            for name in ["charmander", "CHARMANDER", "Chármander"] {
+                match PokemonName::try_from(String::from(name)).map(|n| repo.fetch_by_name(n)) {
+                    Ok(Ok(pokemon)) => assert_eq!(pokemon.number.value(), 4),
+                    _ => unreachable!(),
                 }
             }
-            _ => return Err(InsertError::Unknown),
-        };
-
-        for _type in Vec::<String>::from(types.clone()) {
-            if let Err(_) = transaction.execute(
-                "insert into types (pokemon_number, name) values (?, ?)",
-                params![u16::from(number.clone()), _type],
-            ) {
-                return Err(InsertError::Unknown);
-            }
         }
+    }
 
-        match transaction.commit() {
-            Ok(_) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(InsertError::Unknown),
+    #[test]
+    fn it_should_combine_filters_and_sorting() {
+        for repo in repositories() {
+            let query = Query {
+                sort_by: SortBy::Name,
+                descending: true,
+                name: Some(String::from("a")),
+                pokemon_type: Some(PokemonType::Fire),
+                ..Query::new()
+            };
+            assert_eq!(numbers(repo.as_ref(), query), vec![4, 6]);
         }
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
-        let lock = match self.connection.lock() {
-            Ok(lock) => lock,
-            _ => return Err(FetchAllError::Unknown),
-        };
+    #[test]
+    fn it_should_update_the_name_and_the_types() {
+        for repo in repositories() {
+            assert!(repo
+                .update(
+                    PokemonNumber::pikachu(),
+                    PokemonName::charmander(),
+                    PokemonTypes::charmander(),
+                )
+                .is_ok());
+            match repo.fetch_one(PokemonNumber::pikachu()) {
+                Ok(pokemon) => {
+                    assert_eq!(String::from(pokemon.name), "Charmander");
+                    assert_eq!(Vec::<String>::from(pokemon.types), vec!["Fire"]);
+                }
+                _ => unreachable!(),
+            };
 
-        let pokemon_rows = match Self::fetch_pokemon_rows(&lock, None) {
-            Ok(pokemon_rows) => pokemon_rows,
-            _ => return Err(FetchAllError::Unknown),
-        };
+            let missing = match PokemonNumber::try_from(150) {
+                Ok(number) => number,
+                _ => unreachable!(),
+            };
+            assert!(matches!(
+                repo.update(missing, PokemonName::pikachu(), PokemonTypes::pikachu()),
+                Err(UpdateError::NotFound)
+            ));
+        }
+    }
 
-        let mut pokemons = vec![];
+    #[test]
+    fn it_should_keep_the_evolutions_until_they_are_replaced() {
+        for repo in repositories() {
+            let evolutions = match (
+                Evolution::try_new(5, String::from("level"), Some(16)),
+                Evolution::try_new(6, String::from("other"), None),
+            ) {
+                (Ok(charmeleon), Ok(charizard)) => vec![charmeleon, charizard],
+                _ => unreachable!(),
+            };
+            assert!(repo
+                .update_evolutions(PokemonNumber::charmander(), evolutions)
+                .is_ok());
+            assert!(repo
+                .update_types(PokemonNumber::charmander(), PokemonTypes::pikachu())
+                .is_ok_and(|pokemon| pokemon.evolutions.len() == 2));
+            match repo.fetch_one(PokemonNumber::charmander()) {
+                Ok(pokemon) => match &pokemon.evolutions[..] {
+                    [charmeleon, charizard] => {
+                        assert_eq!(charmeleon.target.value(), 5);
+                        assert_eq!(charmeleon.level, Some(16));
+                        assert!(charizard.method == EvolutionMethod::Other);
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            };
 
-        for pokemon_row in pokemon_rows {
-            let type_rows = match Self::fetch_type_rows(&lock, pokemon_row.0) {
-                Ok(type_rows) => type_rows,
-                _ => return Err(FetchAllError::Unknown),
+            assert!(repo
+                .update_evolutions(PokemonNumber::charmander(), vec![])
+                .is_ok());
+            assert!(repo
+                .fetch_one(PokemonNumber::charmander())
+                .is_ok_and(|pokemon| pokemon.evolutions.is_empty()));
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_abilities_until_they_are_replaced() {
+        for repo in repositories() {
+            assert!(repo
+                .update_abilities(
+                    PokemonNumber::charmander(),
+                    Some(PokemonAbilities::charmander())
+                )
+                .is_ok());
+            assert!(repo
+                .update(
+                    PokemonNumber::charmander(),
+                    PokemonName::charmander(),
+                    PokemonTypes::pikachu()
+                )
+                .is_ok_and(|pokemon| pokemon.abilities.is_some()));
+            match repo
+                .fetch_one(PokemonNumber::charmander())
+                .map(|pokemon| pokemon.abilities)
+            {
+                Ok(Some(abilities)) => match abilities.as_slice() {
+                    [blaze, solar_power] => {
+                        assert_eq!(blaze.name, "Blaze");
+                        assert!(!blaze.hidden);
+                        assert_eq!(solar_power.name, "Solar Power");
+                        assert!(solar_power.hidden);
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
             };
 
-            let pokemon = match (
-                PokemonNumber::try_from(pokemon_row.0),
-                PokemonName::try_from(pokemon_row.1),
-                PokemonTypes::try_from(type_rows),
-            ) {
-                (Ok(number), Ok(name), Ok(types)) => Pokemon::new(number, name, types),
-                _ => return Err(FetchAllError::Unknown),
+            assert!(repo
+                .update_abilities(PokemonNumber::charmander(), None)
+                .is_ok());
+            assert!(repo
+                .fetch_one(PokemonNumber::charmander())
+                .is_ok_and(|pokemon| pokemon.abilities.is_none()));
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_measurements_until_they_are_replaced() {
+        for repo in repositories() {
+            assert!(repo
+                .update_measurements(
+                    PokemonNumber::charmander(),
+                    Some(PokemonHeight::charmander()),
+                    Some(PokemonWeight::charmander())
+                )
+                .is_ok());
+            assert!(repo
+                .update_types(PokemonNumber::charmander(), PokemonTypes::pikachu())
+                .is_ok_and(|pokemon| pokemon.height.is_some()));
+            match repo.fetch_one(PokemonNumber::charmander()) {
+                Ok(pokemon) => {
+                    assert_eq!(pokemon.height.map(f64::from), Some(0.6));
+                    assert_eq!(pokemon.weight.map(f64::from), Some(8.5));
+                }
+                _ => unreachable!(),
             };
 
-            pokemons.push(pokemon);
+            assert!(repo
+                .update_measurements(
+                    PokemonNumber::charmander(),
+                    None,
+                    Some(PokemonWeight::charmander())
+                )
+                .is_ok());
+            assert!(repo
+                .fetch_one(PokemonNumber::charmander())
+                .is_ok_and(|pokemon| pokemon.height.is_none() && pokemon.weight.is_some()));
         }
+    }
 
-        Ok(pokemons)
+    #[test]
+    fn it_should_keep_the_sprite_until_it_is_replaced() {
+        for repo in repositories() {
+            assert!(repo
+                .update_sprite(PokemonNumber::pikachu(), Some(PokemonSprite::pikachu()))
+                .is_ok());
+            assert!(repo
+                .update(
+                    PokemonNumber::pikachu(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::charmander()
+                )
+                .is_ok_and(|pokemon| pokemon.sprite.is_some()));
+            assert!(repo
+                .fetch_one(PokemonNumber::pikachu())
+                .is_ok_and(|pokemon| pokemon.sprite == Some(PokemonSprite::pikachu())));
+
+            assert!(repo.update_sprite(PokemonNumber::pikachu(), None).is_ok());
+            assert!(repo
+                .fetch_one(PokemonNumber::pikachu())
+                .is_ok_and(|pokemon| pokemon.sprite.is_none()));
+            assert!(matches!(
+                repo.update_sprite(PokemonNumber::bad(), None),
+                Err(UpdateError::NotFound)
+            ));
+        }
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
-        let lock = match self.connection.lock() {
-            Ok(lock) => lock,
-            _ => return Err(FetchOneError::Unknown),
+    #[test]
+    fn it_should_keep_the_class_until_it_is_replaced() {
+        for repo in repositories() {
+            assert!(repo
+                .update_class(PokemonNumber::pikachu(), PokemonClass::Mythical)
+                .is_ok());
+            assert!(repo
+                .update_sprite(PokemonNumber::pikachu(), Some(PokemonSprite::pikachu()))
+                .is_ok_and(|pokemon| pokemon.class == PokemonClass::Mythical));
+            assert!(repo
+                .fetch_one(PokemonNumber::pikachu())
+                .is_ok_and(|pokemon| pokemon.class == PokemonClass::Mythical));
+
+            assert!(repo
+                .update_class(PokemonNumber::pikachu(), PokemonClass::Normal)
+                .is_ok());
+            assert!(repo
+                .fetch_one(PokemonNumber::pikachu())
+                .is_ok_and(|pokemon| pokemon.class == PokemonClass::Normal));
+            assert!(matches!(
+                repo.update_class(PokemonNumber::bad(), PokemonClass::Legendary),
+                Err(UpdateError::NotFound)
+            ));
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn it_should_read_back_a_whole_pokemon_from_its_record() {
+        let pokemon = Pokemon::new(
+            PokemonNumber::charmander(),
+            PokemonName::charmander(),
+            PokemonTypes::charmander(),
+        )
+        .with_abilities(Some(PokemonAbilities::charmander()))
+        .with_measurements(Some(PokemonHeight::charmander()), None)
+        .with_sprite(Some(PokemonSprite::pikachu()))
+        .with_class(PokemonClass::Legendary);
+
+        let json = match serde_json::to_string(&PokemonRecord::from(&pokemon)) {
+            Ok(json) => json,
+            _ => unreachable!(),
+        };
+
+        match serde_json::from_str::<PokemonRecord>(&json).map(PokemonRecord::decode) {
+            Ok(Ok(decoded)) => {
+                assert!(decoded.number == pokemon.number);
+                assert_eq!(decoded.name.as_str(), "Charmander");
+                assert!(decoded.abilities.is_some_and(|a| a.as_slice().len() == 2));
+                assert!(decoded.height == pokemon.height && decoded.weight.is_none());
+                assert!(decoded.sprite == pokemon.sprite);
+                assert!(decoded.class == PokemonClass::Legendary);
+            }
+            _ => unreachable!(),
         };
+    }
 
-        let mut pokemon_rows =
-            match Self::fetch_pokemon_rows(&lock, Some(u16::from(number.clone()))) {
-                Ok(pokemon_rows) => pokemon_rows,
-                _ => return Err(FetchOneError::Unknown),
+    #[test]
+    fn it_should_delete_by_type_and_return_the_deleted_pokemons() {
+        for repo in repositories() {
+            let deleted = match repo.delete_by_type(PokemonType::Fire) {
+                Ok(pokemons) => pokemons
+                    .into_iter()
+                    .map(|p| p.number.value())
+                    .collect::<Vec<u16>>(),
+                _ => unreachable!(),
             };
+            assert_eq!(deleted, vec![4, 6]);
+            assert_eq!(numbers(repo.as_ref(), Query::new()), vec![25, 125]);
+        }
+    }
 
-        if pokemon_rows.is_empty() {
-            return Err(FetchOneError::NotFound);
+    #[test]
+    fn it_should_clear_and_return_every_pokemon() {
+        for repo in repositories() {
+            let deleted = match repo.clear() {
+                Ok(pokemons) => pokemons
+                    .into_iter()
+                    .map(|p| p.number.value())
+                    .collect::<Vec<u16>>(),
+                _ => unreachable!(),
+            };
+            assert_eq!(deleted, vec![4, 6, 25, 125]);
+            assert!(numbers(repo.as_ref(), Query::new()).is_empty());
         }
+    }
+
+    #[test]
+    fn it_should_snapshot_every_pokemon_even_within_a_transaction() {
+        for repo in repositories() {
+            let in_transaction = repo.begin().is_ok();
+            let snapshot = match repo.snapshot() {
+                Ok(pokemons) => pokemons
+                    .into_iter()
+                    .map(|p| p.number.value())
+                    .collect::<Vec<u16>>(),
+                _ => unreachable!(),
+            };
+            if in_transaction {
+                repo.commit().ok();
+            }
+            assert_eq!(snapshot, vec![4, 6, 25, 125]);
+        }
+    }
 
-        let pokemon_row = pokemon_rows.remove(0);
+    #[cfg(feature = "server")]
+    #[test]
+    fn it_should_store_the_events_of_the_committed_writes_in_the_outbox() {
+        let repo = SqliteRepository::in_memory().with_outbox();
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        repo.begin().ok();
+        repo.delete(PokemonNumber::pikachu()).ok();
+        repo.rollback().ok();
+        repo.update_types(PokemonNumber::pikachu(), PokemonTypes::charmander())
+            .ok();
+        repo.delete(PokemonNumber::charmander()).ok();
 
-        let type_rows = match Self::fetch_type_rows(&lock, pokemon_row.0) {
-            Ok(type_rows) => type_rows,
-            _ => return Err(FetchOneError::Unknown),
+        let events = match repo.lock() {
+            Ok(lock) => lock
+                .prepare("select event, data from outbox order by id")
+                .and_then(|mut stmt| {
+                    stmt.query_map([], |row| {
+                        Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?))
+                    })
+                    .and_then(|rows| rows.collect::<Result<Vec<(String, String)>, _>>())
+                }),
+            _ => unreachable!(),
+        };
+        match events.as_deref() {
+            Ok([(created, _), (updated, data)]) => {
+                assert_eq!(created, "pokemon.created");
+                assert_eq!(updated, "pokemon.updated");
+                assert_eq!(data, r#"{"name":"Pikachu","number":25,"types":["Fire"]}"#);
+            }
+            _ => unreachable!(),
         };
+    }
 
-        match (
-            PokemonNumber::try_from(pokemon_row.0),
-            PokemonName::try_from(pokemon_row.1),
-            PokemonTypes::try_from(type_rows),
-        ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(FetchOneError::Unknown),
-        }
+    #[cfg(feature = "server")]
+    #[test]
+    fn it_should_migrate_the_types_to_cascade_and_keep_the_orphans_for_removal() {
+        let connection = match Connection::open_in_memory() {
+            Ok(connection) => connection,
+            _ => unreachable!(),
+        };
+        connection
+            .execute_batch(
+                "create table pokemons (number integer primary key, name text not null);
+                create table types (pokemon_number integer not null, name text not null);
+                insert into pokemons values (25, 'Pikachu'), (4, 'Charmander');
+                insert into types values (25, 'Electric'), (4, 'Fire'), (6, 'Fire');",
+            )
+            .ok();
+        assert!(SqliteRepository::migrate(&connection).is_ok());
+        assert!(SqliteRepository::migrate(&connection).is_ok());
+        connection.execute("pragma foreign_keys = 1", []).ok();
+        SqliteRepository::register_functions(&connection).ok();
+        let repo = SqliteRepository::from_connection(connection);
+
+        assert_eq!(repo.backend().schema_version, Some(SCHEMA_VERSION));
+        assert!(matches!(repo.remove_orphans(), Ok(1)));
+        assert!(matches!(repo.remove_orphans(), Ok(0)));
+        repo.delete(PokemonNumber::pikachu()).ok();
+        let types = match repo.lock() {
+            Ok(lock) => lock.query_row("select count(*) from types", [], |row| {
+                row.get::<usize, u32>(0)
+            }),
+            _ => unreachable!(),
+        };
+        assert!(matches!(types, Ok(1)));
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
-        let lock = match self.connection.lock() {
-            Ok(lock) => lock,
-            _ => return Err(DeleteError::Unknown),
+    #[cfg(feature = "server")]
+    #[test]
+    fn it_should_leave_the_invalid_rows_out_and_report_them() {
+        let repo = SqliteRepository::in_memory();
+        match repo.lock() {
+            Ok(lock) => lock
+                .execute_batch(
+                    "insert into pokemons values (25, 'Pikachu'), (4, '');
+                    insert into types values (25, 'Electric'), (4, 'Fire');",
+                )
+                .ok(),
+            _ => unreachable!(),
         };
 
-        match lock.execute(
-            "delete from pokemons where number = ?",
-            params![u16::from(number)],
-        ) {
-            Ok(0) => Err(DeleteError::NotFound),
-            Ok(_) => Ok(()),
-            _ => Err(DeleteError::Unknown),
+        assert_eq!(numbers(&repo, Query::new()), vec![25]);
+        match repo.fetch_all_skipping_invalid(Query::new()) {
+            Ok((pokemons, invalid)) => {
+                assert_eq!(pokemons.len(), 1);
+                assert_eq!(invalid.len(), 1);
+                assert_eq!(invalid[0].number, 4);
+                assert_eq!(invalid[0].reason, "invalid name");
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_fetch_a_page_sorted_by_number() {
+        for repo in repositories() {
+            let page = match repo.fetch_page(1, 2) {
+                Ok(pokemons) => pokemons
+                    .into_iter()
+                    .map(|p| p.number.value())
+                    .collect::<Vec<u16>>(),
+                _ => unreachable!(),
+            };
+            assert_eq!(page, vec![6, 25]);
+            assert!(matches!(repo.fetch_page(4, 2).map(|p| p.len()), Ok(0)));
         }
     }
 }
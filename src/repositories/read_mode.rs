@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// What a listing does with a stored record which isn't valid: `Strict`
+/// fails the whole listing, for integrity checks, while `Lenient` leaves
+/// the record out and carries on.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ReadMode {
+    Strict,
+    Lenient,
+}
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+impl ReadMode {
+    /// `Lenient` unless changed by `set`.
+    pub fn current() -> Self {
+        match STRICT.load(Ordering::Relaxed) {
+            true => Self::Strict,
+            false => Self::Lenient,
+        }
+    }
+
+    /// Meant to be called once at startup, before any record is read.
+    pub fn set(mode: Self) {
+        STRICT.store(mode == Self::Strict, Ordering::Relaxed);
+    }
+
+    /// Puts the valid records apart from the invalid ones, unless strict
+    /// where the first invalid record fails the whole listing.
+    pub fn sift<T, E>(
+        self,
+        records: impl IntoIterator<Item = Result<T, E>>,
+    ) -> Result<(Vec<T>, Vec<E>), ()> {
+        let mut valid = vec![];
+        let mut invalid = vec![];
+
+        for record in records {
+            match (record, self) {
+                (Ok(record), _) => valid.push(record),
+                (Err(_), Self::Strict) => return Err(()),
+                (Err(record), Self::Lenient) => invalid.push(record),
+            }
+        }
+
+        Ok((valid, invalid))
+    }
+}
+
+impl TryFrom<&str> for ReadMode {
+    type Error = ();
+
+    fn try_from(mode: &str) -> Result<Self, Self::Error> {
+        match mode {
+            "strict" => Ok(Self::Strict),
+            "lenient" => Ok(Self::Lenient),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Result<u16, u16>> {
+        vec![Ok(25), Err(0), Ok(4)]
+    }
+
+    #[test]
+    fn it_should_fail_on_the_first_invalid_record_when_strict() {
+        assert!(ReadMode::Strict.sift(records()).is_err());
+    }
+
+    #[test]
+    fn it_should_put_the_invalid_records_apart_when_lenient() {
+        match ReadMode::Lenient.sift(records()) {
+            Ok((valid, invalid)) => {
+                assert_eq!(valid, vec![25, 4]);
+                assert_eq!(invalid, vec![0]);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
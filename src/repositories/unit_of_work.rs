@@ -0,0 +1,507 @@
+use crate::domain::entities::{
+    Evolution, Pokemon, PokemonAbilities, PokemonClass, PokemonHeight, PokemonName, PokemonNumber,
+    PokemonSprite, PokemonType, PokemonTypes, PokemonWeight,
+};
+use crate::repositories::pokemon::{
+    Backend, ClearError, CountByTypeError, CountError, DeleteByTypeError, DeleteError,
+    FetchAllError, FetchByNameError, FetchByTypeError, FetchOneError, FetchPageError,
+    FetchRangeError, InsertError, InvalidRecord, NameExistsError, Query, Repository, SnapshotError,
+    TransactionError, UpdateError,
+};
+use std::sync::{Arc, Mutex};
+
+/// Runs the work as a whole: in a transaction on the backends supporting
+/// them, otherwise by undoing what the work did when it fails. Undoing is
+/// best effort: other clients may see or change the Pokemons meanwhile.
+/// A transaction which can't be committed fails with `unknown`.
+pub fn run<T, E>(
+    repo: Arc<dyn Repository>,
+    work: impl FnOnce(Arc<dyn Repository>) -> Result<T, E>,
+    unknown: E,
+) -> Result<T, E> {
+    match repo.begin() {
+        Ok(()) => match work(repo.clone()) {
+            Ok(value) => match repo.commit() {
+                Ok(()) => Ok(value),
+                Err(_) => Err(unknown),
+            },
+            Err(err) => {
+                repo.rollback().ok();
+                Err(err)
+            }
+        },
+        Err(TransactionError::Unsupported) => {
+            let compensating = Arc::new(CompensatingRepository::new(repo));
+            match work(compensating.clone()) {
+                Ok(value) => Ok(value),
+                Err(err) => {
+                    compensating.compensate();
+                    Err(err)
+                }
+            }
+        }
+        Err(TransactionError::Unknown) => Err(unknown),
+    }
+}
+
+enum Undo {
+    Delete(PokemonNumber),
+    Insert(Pokemon),
+    UpdateTypes(PokemonNumber, PokemonTypes),
+    Update(Pokemon),
+    UpdateEvolutions(PokemonNumber, Vec<Evolution>),
+    UpdateAbilities(PokemonNumber, Option<PokemonAbilities>),
+    UpdateMeasurements(PokemonNumber, Option<PokemonHeight>, Option<PokemonWeight>),
+    UpdateSprite(PokemonNumber, Option<PokemonSprite>),
+    UpdateClass(PokemonNumber, PokemonClass),
+}
+
+/// Remembers how to undo each successful write of the wrapped repository.
+/// The undos go on when the work failed because it was cancelled.
+struct CompensatingRepository {
+    inner: Arc<dyn Repository>,
+    undoing: Arc<dyn Repository>,
+    undos: Mutex<Vec<Undo>>,
+}
+
+impl CompensatingRepository {
+    fn new(inner: Arc<dyn Repository>) -> Self {
+        let undoing = match inner.uncancellable() {
+            Some(undoing) => undoing,
+            None => inner.clone(),
+        };
+        Self {
+            inner,
+            undoing,
+            undos: Mutex::new(vec![]),
+        }
+    }
+
+    fn remember(&self, undo: Undo) {
+        if let Ok(mut undos) = self.undos.lock() {
+            undos.push(undo);
+        }
+    }
+
+    /// Undoes the writes, the latest first.
+    fn compensate(&self) {
+        let undos = match self.undos.lock() {
+            Ok(mut undos) => std::mem::take(&mut *undos),
+            _ => return,
+        };
+
+        for undo in undos.into_iter().rev() {
+            let undone = match undo {
+                Undo::Delete(number) => self.undoing.delete(number).is_ok(),
+                Undo::Insert(pokemon) => self
+                    .undoing
+                    .insert_many(vec![pokemon])
+                    .iter()
+                    .all(Result::is_ok),
+                Undo::UpdateTypes(number, types) => {
+                    self.undoing.update_types(number, types).is_ok()
+                }
+                Undo::Update(pokemon) => self
+                    .undoing
+                    .update(pokemon.number, pokemon.name, pokemon.types)
+                    .is_ok(),
+                Undo::UpdateEvolutions(number, evolutions) => {
+                    self.undoing.update_evolutions(number, evolutions).is_ok()
+                }
+                Undo::UpdateAbilities(number, abilities) => {
+                    self.undoing.update_abilities(number, abilities).is_ok()
+                }
+                Undo::UpdateMeasurements(number, height, weight) => self
+                    .undoing
+                    .update_measurements(number, height, weight)
+                    .is_ok(),
+                Undo::UpdateSprite(number, sprite) => {
+                    self.undoing.update_sprite(number, sprite).is_ok()
+                }
+                Undo::UpdateClass(number, class) => {
+                    self.undoing.update_class(number, class).is_ok()
+                }
+            };
+            if !undone {
+                eprintln!("A write could not be undone after a failed unit of work");
+            }
+        }
+    }
+}
+
+impl Repository for CompensatingRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, InsertError> {
+        match self.inner.insert(number, name, types) {
+            Ok(pokemon) => {
+                self.remember(Undo::Delete(pokemon.number.clone()));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn insert_many(&self, pokemons: Vec<Pokemon>) -> Vec<Result<Pokemon, InsertError>> {
+        let results = self.inner.insert_many(pokemons);
+        for pokemon in results.iter().flatten() {
+            self.remember(Undo::Delete(pokemon.number.clone()));
+        }
+        results
+    }
+
+    fn fetch_all(&self, query: Query) -> Result<Vec<Pokemon>, FetchAllError> {
+        self.inner.fetch_all(query)
+    }
+
+    fn fetch_all_skipping_invalid(
+        &self,
+        query: Query,
+    ) -> Result<(Vec<Pokemon>, Vec<InvalidRecord>), FetchAllError> {
+        self.inner.fetch_all_skipping_invalid(query)
+    }
+
+    fn fetch_range(
+        &self,
+        from: PokemonNumber,
+        to: PokemonNumber,
+    ) -> Result<Vec<Pokemon>, FetchRangeError> {
+        self.inner.fetch_range(from, to)
+    }
+
+    fn fetch_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, FetchByTypeError> {
+        self.inner.fetch_by_type(pokemon_type)
+    }
+
+    fn name_exists(&self, name: PokemonName) -> Result<bool, NameExistsError> {
+        self.inner.name_exists(name)
+    }
+
+    fn fetch_page(&self, offset: u32, limit: u32) -> Result<Vec<Pokemon>, FetchPageError> {
+        self.inner.fetch_page(offset, limit)
+    }
+
+    fn count(&self) -> Result<u32, CountError> {
+        self.inner.count()
+    }
+
+    fn count_by_type(&self) -> Result<Vec<(PokemonType, u32)>, CountByTypeError> {
+        self.inner.count_by_type()
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+        self.inner.fetch_one(number)
+    }
+
+    fn fetch_by_name(&self, name: PokemonName) -> Result<Pokemon, FetchByNameError> {
+        self.inner.fetch_by_name(name)
+    }
+
+    fn update_types(
+        &self,
+        number: PokemonNumber,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+        match self.inner.update_types(number, types) {
+            Ok(pokemon) => {
+                self.remember(Undo::UpdateTypes(previous.number, previous.types));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+    ) -> Result<Pokemon, UpdateError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+        match self.inner.update(number, name, types) {
+            Ok(pokemon) => {
+                self.remember(Undo::Update(previous));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_evolutions(
+        &self,
+        number: PokemonNumber,
+        evolutions: Vec<Evolution>,
+    ) -> Result<Pokemon, UpdateError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+        match self.inner.update_evolutions(number, evolutions) {
+            Ok(pokemon) => {
+                self.remember(Undo::UpdateEvolutions(previous.number, previous.evolutions));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: Option<PokemonAbilities>,
+    ) -> Result<Pokemon, UpdateError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+        match self.inner.update_abilities(number, abilities) {
+            Ok(pokemon) => {
+                self.remember(Undo::UpdateAbilities(previous.number, previous.abilities));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_measurements(
+        &self,
+        number: PokemonNumber,
+        height: Option<PokemonHeight>,
+        weight: Option<PokemonWeight>,
+    ) -> Result<Pokemon, UpdateError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+        match self.inner.update_measurements(number, height, weight) {
+            Ok(pokemon) => {
+                self.remember(Undo::UpdateMeasurements(
+                    previous.number,
+                    previous.height,
+                    previous.weight,
+                ));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_sprite(
+        &self,
+        number: PokemonNumber,
+        sprite: Option<PokemonSprite>,
+    ) -> Result<Pokemon, UpdateError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+        match self.inner.update_sprite(number, sprite) {
+            Ok(pokemon) => {
+                self.remember(Undo::UpdateSprite(previous.number, previous.sprite));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn update_class(
+        &self,
+        number: PokemonNumber,
+        class: PokemonClass,
+    ) -> Result<Pokemon, UpdateError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(UpdateError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(UpdateError::Unknown),
+        };
+        match self.inner.update_class(number, class) {
+            Ok(pokemon) => {
+                self.remember(Undo::UpdateClass(previous.number, previous.class));
+                Ok(pokemon)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        let previous = match self.inner.fetch_one(number.clone()) {
+            Ok(previous) => previous,
+            Err(FetchOneError::NotFound) => return Err(DeleteError::NotFound),
+            Err(FetchOneError::Unknown) => return Err(DeleteError::Unknown),
+        };
+        match self.inner.delete(number) {
+            Ok(()) => {
+                self.remember(Undo::Insert(previous));
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn delete_by_type(&self, pokemon_type: PokemonType) -> Result<Vec<Pokemon>, DeleteByTypeError> {
+        match self.inner.delete_by_type(pokemon_type) {
+            Ok(pokemons) => {
+                for pokemon in pokemons.iter() {
+                    self.remember(Undo::Insert(pokemon.clone()));
+                }
+                Ok(pokemons)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn clear(&self) -> Result<Vec<Pokemon>, ClearError> {
+        match self.inner.clear() {
+            Ok(pokemons) => {
+                for pokemon in pokemons.iter() {
+                    self.remember(Undo::Insert(pokemon.clone()));
+                }
+                Ok(pokemons)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn snapshot(&self) -> Result<Vec<Pokemon>, SnapshotError> {
+        self.inner.snapshot()
+    }
+
+    fn backend(&self) -> Backend {
+        self.inner.backend()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancellation::Cancellation;
+    use crate::repositories::cancellable::CancellableRepository;
+    use crate::repositories::pokemon::InMemoryRepository;
+    #[cfg(feature = "server")]
+    use crate::repositories::pokemon::SqliteRepository;
+
+    fn repositories() -> Vec<Arc<dyn Repository>> {
+        let repositories: Vec<Arc<dyn Repository>> = vec![
+            Arc::new(InMemoryRepository::new()),
+            #[cfg(feature = "server")]
+            Arc::new(SqliteRepository::in_memory()),
+        ];
+        for repo in repositories.iter() {
+            repo.insert(
+                PokemonNumber::pikachu(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+            )
+            .ok();
+        }
+        repositories
+    }
+
+    fn numbers(repo: &dyn Repository) -> Vec<u16> {
+        match repo.fetch_all(Query::new()) {
+            Ok(pokemons) => pokemons.into_iter().map(|p| p.number.value()).collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_keep_the_writes_of_a_successful_work() {
+        for repo in repositories() {
+            let res = run(
+                repo.clone(),
+                |repo| match repo.insert(
+                    PokemonNumber::charmander(),
+                    PokemonName::charmander(),
+                    PokemonTypes::charmander(),
+                ) {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(()),
+                },
+                (),
+            );
+
+            assert!(res.is_ok());
+            assert_eq!(numbers(repo.as_ref()), vec![4, 25]);
+        }
+    }
+
+    #[test]
+    fn it_should_undo_every_write_of_a_failed_work() {
+        for repo in repositories() {
+            let res = run(
+                repo.clone(),
+                |repo| {
+                    repo.insert(
+                        PokemonNumber::charmander(),
+                        PokemonName::charmander(),
+                        PokemonTypes::charmander(),
+                    )
+                    .ok();
+                    repo.update_types(PokemonNumber::pikachu(), PokemonTypes::charmander())
+                        .ok();
+                    repo.delete(PokemonNumber::pikachu()).ok();
+                    Err::<(), ()>(())
+                },
+                (),
+            );
+
+            assert!(res.is_err());
+            assert_eq!(numbers(repo.as_ref()), vec![25]);
+            match repo.fetch_one(PokemonNumber::pikachu()) {
+                Ok(pokemon) => assert_eq!(Vec::<String>::from(pokemon.types), vec!["Electric"]),
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_undo_the_writes_of_a_cancelled_work() {
+        let inner = Arc::new(InMemoryRepository::new());
+        let cancellation = Cancellation::new();
+        let repo = Arc::new(CancellableRepository::new(
+            inner.clone(),
+            cancellation.clone(),
+        ));
+
+        let res = run(
+            repo,
+            |repo| {
+                repo.insert(
+                    PokemonNumber::charmander(),
+                    PokemonName::charmander(),
+                    PokemonTypes::charmander(),
+                )
+                .ok();
+                cancellation.cancel();
+                match repo.insert(
+                    PokemonNumber::pikachu(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                ) {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err(()),
+                }
+            },
+            (),
+        );
+
+        assert!(res.is_err());
+        assert!(numbers(inner.as_ref()).is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+use crate::domain::entities::ApiKey;
+#[cfg(feature = "server")]
+use rusqlite::{params, Connection, OpenFlags};
+#[cfg(feature = "server")]
+use serde::Deserialize;
+use std::collections::HashMap;
+#[cfg(feature = "server")]
+use std::sync::Arc;
+use std::sync::Mutex;
+
+pub enum IncrementError {
+    Unknown,
+}
+
+pub enum FetchError {
+    Unknown,
+}
+
+pub trait Repository: Send + Sync {
+    fn increment(&self, key: ApiKey, day: u64) -> Result<u32, IncrementError>;
+
+    fn fetch(&self, key: ApiKey, day: u64) -> Result<u32, FetchError>;
+}
+
+pub struct InMemoryRepository {
+    error: bool,
+    usages: Mutex<HashMap<(String, u64), u32>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let usages: Mutex<HashMap<(String, u64), u32>> = Mutex::new(HashMap::new());
+        Self {
+            error: false,
+            usages,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn increment(&self, key: ApiKey, day: u64) -> Result<u32, IncrementError> {
+        if self.error {
+            return Err(IncrementError::Unknown);
+        }
+
+        let mut lock = match self.usages.lock() {
+            Ok(lock) => lock,
+            _ => return Err(IncrementError::Unknown),
+        };
+
+        let count = lock.entry((String::from(key), day)).or_insert(0);
+        *count += 1;
+        Ok(*count)
+    }
+
+    fn fetch(&self, key: ApiKey, day: u64) -> Result<u32, FetchError> {
+        if self.error {
+            return Err(FetchError::Unknown);
+        }
+
+        let lock = match self.usages.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        Ok(*lock.get(&(String::from(key), day)).unwrap_or(&0))
+    }
+}
+
+#[cfg(feature = "server")]
+pub struct AirtableRepository {
+    url: String,
+    auth_header: String,
+    /// Airtable can't increment a count: it is read then written back, one
+    /// request of a key at a time. Only the issued keys get here.
+    locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+#[cfg(feature = "server")]
+impl AirtableRepository {
+    pub fn try_new(api_key: &str, workspace_id: &str) -> Result<Self, ()> {
+        let url = format!("https://api.airtable.com/v0/{}/usages", workspace_id);
+        let auth_header = format!("Bearer {}", api_key);
+
+        if ureq::get(&url)
+            .set("Authorization", &auth_header)
+            .call()
+            .is_err()
+        {
+            return Err(());
+        }
+
+        Ok(Self {
+            url,
+            auth_header,
+            locks: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn fetch_usage_rows(&self, key: &str, day: u64) -> Result<AirtableJson, ()> {
+        let formula = format!(
+            "AND({{key}}='{}',{{day}}={})",
+            key.replace('\'', "\\'"),
+            day
+        );
+
+        let res = match ureq::get(&self.url)
+            .set("Authorization", &self.auth_header)
+            .query("filterByFormula", &formula)
+            .call()
+        {
+            Ok(res) => res,
+            _ => return Err(()),
+        };
+
+        match res.into_json::<AirtableJson>() {
+            Ok(json) => Ok(json),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for AirtableRepository {
+    fn increment(&self, key: ApiKey, day: u64) -> Result<u32, IncrementError> {
+        let key = String::from(key);
+
+        let lock = match self.locks.lock() {
+            Ok(mut locks) => locks.entry(key.clone()).or_default().clone(),
+            _ => return Err(IncrementError::Unknown),
+        };
+        let _guard = match lock.lock() {
+            Ok(guard) => guard,
+            _ => return Err(IncrementError::Unknown),
+        };
+
+        let mut json = match self.fetch_usage_rows(&key, day) {
+            Ok(json) => json,
+            _ => return Err(IncrementError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            let body = ureq::json!({
+                "records": [{
+                    "fields": {
+                        "key": key,
+                        "day": day,
+                        "count": 1,
+                    },
+                }],
+            });
+
+            return match ureq::post(&self.url)
+                .set("Authorization", &self.auth_header)
+                .send_json(body)
+            {
+                Ok(_) => Ok(1),
+                _ => Err(IncrementError::Unknown),
+            };
+        }
+
+        let record = json.records.remove(0);
+        let count = record.fields.count + 1;
+
+        let body = ureq::json!({
+            "fields": {
+                "count": count,
+            },
+        });
+
+        match ureq::request("PATCH", &format!("{}/{}", self.url, record.id))
+            .set("Authorization", &self.auth_header)
+            .send_json(body)
+        {
+            Ok(_) => Ok(count),
+            _ => Err(IncrementError::Unknown),
+        }
+    }
+
+    fn fetch(&self, key: ApiKey, day: u64) -> Result<u32, FetchError> {
+        let mut json = match self.fetch_usage_rows(&String::from(key), day) {
+            Ok(json) => json,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        if json.records.is_empty() {
+            return Ok(0);
+        }
+
+        Ok(json.records.remove(0).fields.count)
+    }
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct AirtableJson {
+    records: Vec<AirtableRecord>,
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct AirtableRecord {
+    id: String,
+    fields: AirtableFields,
+}
+
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct AirtableFields {
+    count: u32,
+}
+
+#[cfg(feature = "server")]
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+#[cfg(feature = "server")]
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match Self::migrate(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    /// One row per key and day, `day` counting the days since the unix
+    /// epoch.
+    fn migrate(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists usages (
+                key text not null,
+                day integer not null,
+                count integer not null,
+                primary key (key, day)
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for SqliteRepository {
+    fn increment(&self, key: ApiKey, day: u64) -> Result<u32, IncrementError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(IncrementError::Unknown),
+        };
+
+        let key = String::from(key);
+
+        if lock
+            .execute(
+                "insert into usages (key, day, count) values (?, ?, 1)
+                on conflict (key, day) do update set count = count + 1",
+                params![key, day],
+            )
+            .is_err()
+        {
+            return Err(IncrementError::Unknown);
+        }
+
+        match lock.query_row(
+            "select count from usages where key = ? and day = ?",
+            params![key, day],
+            |row| row.get::<usize, u32>(0),
+        ) {
+            Ok(count) => Ok(count),
+            _ => Err(IncrementError::Unknown),
+        }
+    }
+
+    fn fetch(&self, key: ApiKey, day: u64) -> Result<u32, FetchError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select count from usages where key = ? and day = ?") {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        let mut rows = match stmt.query(params![String::from(key), day]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchError::Unknown),
+        };
+
+        match rows.next() {
+            Ok(Some(row)) => match row.get::<usize, u32>(0) {
+                Ok(count) => Ok(count),
+                _ => Err(FetchError::Unknown),
+            },
+            Ok(None) => Ok(0),
+            _ => Err(FetchError::Unknown),
+        }
+    }
+}
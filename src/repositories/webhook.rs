@@ -0,0 +1,458 @@
+#[cfg(feature = "server")]
+use crate::domain::entities::WebhookEvent;
+use crate::domain::entities::{Delivery, Webhook, WebhookId, WebhookSecret, WebhookUrl};
+#[cfg(feature = "server")]
+use crate::repositories::read_mode::ReadMode;
+#[cfg(feature = "server")]
+use rusqlite::{params, Connection, OpenFlags};
+use std::sync::Mutex;
+
+pub enum InsertError {
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchOneError {
+    NotFound,
+    Unknown,
+}
+
+pub enum DeleteError {
+    NotFound,
+    Unknown,
+}
+
+pub enum InsertDeliveryError {
+    Unknown,
+}
+
+pub enum FetchDeliveriesError {
+    Unknown,
+}
+
+pub enum TrimDeliveriesError {
+    Unknown,
+}
+
+pub trait Repository: Send + Sync {
+    fn insert(&self, url: WebhookUrl, secret: WebhookSecret) -> Result<Webhook, InsertError>;
+
+    fn fetch_all(&self) -> Result<Vec<Webhook>, FetchAllError>;
+
+    fn fetch_one(&self, id: WebhookId) -> Result<Webhook, FetchOneError>;
+
+    fn delete(&self, id: WebhookId) -> Result<(), DeleteError>;
+
+    fn insert_delivery(&self, delivery: Delivery) -> Result<(), InsertDeliveryError>;
+
+    fn fetch_deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>, FetchDeliveriesError>;
+
+    /// Removes the deliveries made before `timestamp`, and tells how many
+    /// are or, with `dry_run`, would be removed.
+    fn trim_deliveries(&self, timestamp: u64, dry_run: bool) -> Result<u32, TrimDeliveriesError>;
+}
+
+pub struct InMemoryRepository {
+    error: bool,
+    webhooks: Mutex<Vec<Webhook>>,
+    deliveries: Mutex<Vec<Delivery>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let webhooks: Mutex<Vec<Webhook>> = Mutex::new(vec![]);
+        let deliveries: Mutex<Vec<Delivery>> = Mutex::new(vec![]);
+        Self {
+            error: false,
+            webhooks,
+            deliveries,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(&self, url: WebhookUrl, secret: WebhookSecret) -> Result<Webhook, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let mut lock = match self.webhooks.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let next_id = lock.iter().map(|w| u32::from(w.id)).max().unwrap_or(0) + 1;
+        let id = match WebhookId::try_from(next_id) {
+            Ok(id) => id,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        let webhook = Webhook::new(id, url, secret);
+        lock.push(webhook.clone());
+        Ok(webhook)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Webhook>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        match self.webhooks.lock() {
+            Ok(lock) => Ok(lock.to_vec()),
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_one(&self, id: WebhookId) -> Result<Webhook, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.webhooks.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.iter().find(|w| w.id == id) {
+            Some(webhook) => Ok(webhook.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn delete(&self, id: WebhookId) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let mut lock = match self.webhooks.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let index = match lock.iter().position(|w| w.id == id) {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
+        };
+
+        lock.remove(index);
+        Ok(())
+    }
+
+    fn insert_delivery(&self, delivery: Delivery) -> Result<(), InsertDeliveryError> {
+        if self.error {
+            return Err(InsertDeliveryError::Unknown);
+        }
+
+        match self.deliveries.lock() {
+            Ok(mut lock) => {
+                lock.push(delivery);
+                Ok(())
+            }
+            _ => Err(InsertDeliveryError::Unknown),
+        }
+    }
+
+    fn fetch_deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>, FetchDeliveriesError> {
+        if self.error {
+            return Err(FetchDeliveriesError::Unknown);
+        }
+
+        match self.deliveries.lock() {
+            Ok(lock) => Ok(lock
+                .iter()
+                .filter(|d| d.webhook_id == id)
+                .cloned()
+                .collect::<Vec<Delivery>>()),
+            _ => Err(FetchDeliveriesError::Unknown),
+        }
+    }
+
+    fn trim_deliveries(&self, timestamp: u64, dry_run: bool) -> Result<u32, TrimDeliveriesError> {
+        if self.error {
+            return Err(TrimDeliveriesError::Unknown);
+        }
+
+        let mut lock = match self.deliveries.lock() {
+            Ok(lock) => lock,
+            _ => return Err(TrimDeliveriesError::Unknown),
+        };
+
+        let trimmed = lock.iter().filter(|d| d.timestamp < timestamp).count();
+        if !dry_run {
+            lock.retain(|d| d.timestamp >= timestamp);
+        }
+        Ok(trimmed as u32)
+    }
+}
+
+#[cfg(feature = "server")]
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+#[cfg(feature = "server")]
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match Self::migrate(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    /// The deliveries go along with their webhook.
+    fn migrate(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "pragma foreign_keys = 1;
+            create table if not exists webhooks (
+                id integer primary key autoincrement,
+                url text not null,
+                secret text not null
+            );
+            create table if not exists deliveries (
+                webhook_id integer not null references webhooks(id) on delete cascade,
+                event text not null,
+                attempt integer not null,
+                status_code integer,
+                timestamp integer not null
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    fn webhook_from_row(id: u32, url: String, secret: String) -> Result<Webhook, ()> {
+        match (
+            WebhookId::try_from(id),
+            WebhookUrl::try_from(url),
+            WebhookSecret::try_from(secret),
+        ) {
+            (Ok(id), Ok(url), Ok(secret)) => Ok(Webhook::new(id, url, secret)),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for SqliteRepository {
+    fn insert(&self, url: WebhookUrl, secret: WebhookSecret) -> Result<Webhook, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if lock
+            .execute(
+                "insert into webhooks (url, secret) values (?, ?)",
+                params![String::from(url.clone()), String::from(secret.clone())],
+            )
+            .is_err()
+        {
+            return Err(InsertError::Unknown);
+        }
+
+        match u32::try_from(lock.last_insert_rowid()).map(WebhookId::try_from) {
+            Ok(Ok(id)) => Ok(Webhook::new(id, url, secret)),
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Webhook>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select id, url, secret from webhooks order by id") {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut rows = match stmt.query([]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut decoded = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, u32>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, String>(2),
+            ) {
+                (Ok(id), Ok(url), Ok(secret)) => {
+                    decoded.push(Self::webhook_from_row(id, url, secret).map_err(|()| id))
+                }
+                _ => return Err(FetchAllError::Unknown),
+            };
+        }
+
+        match ReadMode::current().sift(decoded) {
+            Ok((webhooks, invalid)) => {
+                for id in invalid {
+                    eprintln!("Skipped the stored webhook {}: invalid webhook", id);
+                }
+                Ok(webhooks)
+            }
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_one(&self, id: WebhookId) -> Result<Webhook, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select id, url, secret from webhooks where id = ?") {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut rows = match stmt.query([u32::from(id)]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match rows.next() {
+            Ok(Some(row)) => match (
+                row.get::<usize, u32>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, String>(2),
+            ) {
+                (Ok(id), Ok(url), Ok(secret)) => match Self::webhook_from_row(id, url, secret) {
+                    Ok(webhook) => Ok(webhook),
+                    _ => Err(FetchOneError::Unknown),
+                },
+                _ => Err(FetchOneError::Unknown),
+            },
+            Ok(None) => Err(FetchOneError::NotFound),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn delete(&self, id: WebhookId) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.execute("delete from webhooks where id = ?", params![u32::from(id)]) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+
+    fn insert_delivery(&self, delivery: Delivery) -> Result<(), InsertDeliveryError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertDeliveryError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into deliveries (webhook_id, event, attempt, status_code, timestamp)
+            values (?, ?, ?, ?, ?)",
+            params![
+                u32::from(delivery.webhook_id),
+                String::from(delivery.event),
+                delivery.attempt,
+                delivery.status_code,
+                delivery.timestamp,
+            ],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(InsertDeliveryError::Unknown),
+        }
+    }
+
+    fn fetch_deliveries(&self, id: WebhookId) -> Result<Vec<Delivery>, FetchDeliveriesError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchDeliveriesError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare(
+            "select event, attempt, status_code, timestamp from deliveries
+            where webhook_id = ? order by rowid",
+        ) {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchDeliveriesError::Unknown),
+        };
+
+        let mut rows = match stmt.query([u32::from(id)]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchDeliveriesError::Unknown),
+        };
+
+        let mut deliveries = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, String>(0).map(WebhookEvent::try_from),
+                row.get::<usize, u32>(1),
+                row.get::<usize, Option<u16>>(2),
+                row.get::<usize, u64>(3),
+            ) {
+                (Ok(Ok(event)), Ok(attempt), Ok(status_code), Ok(timestamp)) => {
+                    deliveries.push(Delivery {
+                        webhook_id: id,
+                        event,
+                        attempt,
+                        status_code,
+                        timestamp,
+                    })
+                }
+                _ => return Err(FetchDeliveriesError::Unknown),
+            };
+        }
+
+        Ok(deliveries)
+    }
+
+    fn trim_deliveries(&self, timestamp: u64, dry_run: bool) -> Result<u32, TrimDeliveriesError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(TrimDeliveriesError::Unknown),
+        };
+
+        let res = match dry_run {
+            true => lock.query_row(
+                "select count(*) from deliveries where timestamp < ?",
+                params![timestamp],
+                |row| row.get::<usize, u32>(0),
+            ),
+            false => lock
+                .execute(
+                    "delete from deliveries where timestamp < ?",
+                    params![timestamp],
+                )
+                .map(|trimmed| trimmed as u32),
+        };
+
+        match res {
+            Ok(trimmed) => Ok(trimmed),
+            _ => Err(TrimDeliveriesError::Unknown),
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::domain::entities::{PokemonName, PokemonNumber, Wish, WishNote};
+#[cfg(feature = "server")]
+use crate::repositories::read_mode::ReadMode;
+#[cfg(feature = "server")]
+use rusqlite::{params, Connection, Error::SqliteFailure, OpenFlags};
+use std::sync::Mutex;
+
+pub enum InsertError {
+    Conflict,
+    Unknown,
+}
+
+pub enum FetchAllError {
+    Unknown,
+}
+
+pub enum FetchOneError {
+    NotFound,
+    Unknown,
+}
+
+pub enum DeleteError {
+    NotFound,
+    Unknown,
+}
+
+pub trait Repository: Send + Sync {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        note: Option<WishNote>,
+    ) -> Result<Wish, InsertError>;
+
+    fn fetch_all(&self) -> Result<Vec<Wish>, FetchAllError>;
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Wish, FetchOneError>;
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError>;
+}
+
+pub struct InMemoryRepository {
+    error: bool,
+    wishes: Mutex<Vec<Wish>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        let wishes: Mutex<Vec<Wish>> = Mutex::new(vec![]);
+        Self {
+            error: false,
+            wishes,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_error(self) -> Self {
+        Self {
+            error: true,
+            ..self
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        note: Option<WishNote>,
+    ) -> Result<Wish, InsertError> {
+        if self.error {
+            return Err(InsertError::Unknown);
+        }
+
+        let mut lock = match self.wishes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        if lock.iter().any(|wish| wish.number == number) {
+            return Err(InsertError::Conflict);
+        }
+
+        let wish = Wish::new(number, name, note);
+        lock.push(wish.clone());
+        Ok(wish)
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Wish>, FetchAllError> {
+        if self.error {
+            return Err(FetchAllError::Unknown);
+        }
+
+        let lock = match self.wishes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut wishes = lock.to_vec();
+        wishes.sort_by(|a, b| a.number.cmp(&b.number));
+        Ok(wishes)
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Wish, FetchOneError> {
+        if self.error {
+            return Err(FetchOneError::Unknown);
+        }
+
+        let lock = match self.wishes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match lock.iter().find(|w| w.number == number) {
+            Some(wish) => Ok(wish.clone()),
+            None => Err(FetchOneError::NotFound),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        if self.error {
+            return Err(DeleteError::Unknown);
+        }
+
+        let mut lock = match self.wishes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        let index = match lock.iter().position(|w| w.number == number) {
+            Some(index) => index,
+            None => return Err(DeleteError::NotFound),
+        };
+
+        lock.remove(index);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+#[cfg(feature = "server")]
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match Self::migrate(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    /// The wished Pokemons aren't stored yet, so the number is no foreign
+    /// key.
+    fn migrate(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists wishes (
+                number integer primary key,
+                name text not null,
+                note text
+            );",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+
+    fn wish_from_row(number: u16, name: String, note: Option<String>) -> Result<Wish, ()> {
+        let note = match note.map(WishNote::try_from) {
+            Some(Ok(note)) => Some(note),
+            Some(Err(())) => return Err(()),
+            None => None,
+        };
+
+        match (PokemonNumber::try_from(number), PokemonName::try_from(name)) {
+            (Ok(number), Ok(name)) => Ok(Wish::new(number, name, note)),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Repository for SqliteRepository {
+    fn insert(
+        &self,
+        number: PokemonNumber,
+        name: PokemonName,
+        note: Option<WishNote>,
+    ) -> Result<Wish, InsertError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(InsertError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into wishes (number, name, note) values (?, ?, ?)",
+            params![
+                number.value(),
+                name.as_str(),
+                note.clone().map(String::from)
+            ],
+        ) {
+            Ok(_) => Ok(Wish::new(number, name, note)),
+            Err(SqliteFailure(_, Some(message)))
+                if message == "UNIQUE constraint failed: wishes.number" =>
+            {
+                Err(InsertError::Conflict)
+            }
+            _ => Err(InsertError::Unknown),
+        }
+    }
+
+    fn fetch_all(&self) -> Result<Vec<Wish>, FetchAllError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select number, name, note from wishes order by number") {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut rows = match stmt.query([]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchAllError::Unknown),
+        };
+
+        let mut decoded = vec![];
+
+        while let Ok(Some(row)) = rows.next() {
+            match (
+                row.get::<usize, u16>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, Option<String>>(2),
+            ) {
+                (Ok(number), Ok(name), Ok(note)) => {
+                    decoded.push(Self::wish_from_row(number, name, note).map_err(|()| number))
+                }
+                _ => return Err(FetchAllError::Unknown),
+            };
+        }
+
+        match ReadMode::current().sift(decoded) {
+            Ok((wishes, invalid)) => {
+                for number in invalid {
+                    eprintln!("Skipped the stored wish #{}: invalid wish", number);
+                }
+                Ok(wishes)
+            }
+            _ => Err(FetchAllError::Unknown),
+        }
+    }
+
+    fn fetch_one(&self, number: PokemonNumber) -> Result<Wish, FetchOneError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select number, name, note from wishes where number = ?")
+        {
+            Ok(stmt) => stmt,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        let mut rows = match stmt.query([number.value()]) {
+            Ok(rows) => rows,
+            _ => return Err(FetchOneError::Unknown),
+        };
+
+        match rows.next() {
+            Ok(Some(row)) => match (
+                row.get::<usize, u16>(0),
+                row.get::<usize, String>(1),
+                row.get::<usize, Option<String>>(2),
+            ) {
+                (Ok(number), Ok(name), Ok(note)) => match Self::wish_from_row(number, name, note) {
+                    Ok(wish) => Ok(wish),
+                    _ => Err(FetchOneError::Unknown),
+                },
+                _ => Err(FetchOneError::Unknown),
+            },
+            Ok(None) => Err(FetchOneError::NotFound),
+            _ => Err(FetchOneError::Unknown),
+        }
+    }
+
+    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DeleteError::Unknown),
+        };
+
+        match lock.execute(
+            "delete from wishes where number = ?",
+            params![number.value()],
+        ) {
+            Ok(0) => Err(DeleteError::NotFound),
+            Ok(_) => Ok(()),
+            _ => Err(DeleteError::Unknown),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use rusqlite::{params, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+/// A write the remote backend could not take, kept to be replayed in order
+/// once it is back.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Write {
+    Insert {
+        number: u16,
+        name: String,
+        types: Vec<String>,
+    },
+    UpdateTypes {
+        number: u16,
+        types: Vec<String>,
+    },
+    Update {
+        number: u16,
+        name: String,
+        types: Vec<String>,
+    },
+    Delete {
+        number: u16,
+    },
+    DeleteByType {
+        pokemon_type: String,
+    },
+    Clear,
+}
+
+impl fmt::Display for Write {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Insert { number, name, .. } => write!(f, "insert #{} {}", number, name),
+            Self::UpdateTypes { number, types } => {
+                write!(f, "set the types of #{} to {}", number, types.join(", "))
+            }
+            Self::Update {
+                number,
+                name,
+                types,
+            } => write!(
+                f,
+                "set #{} to {} of types {}",
+                number,
+                name,
+                types.join(", ")
+            ),
+            Self::Delete { number } => write!(f, "delete #{}", number),
+            Self::DeleteByType { pokemon_type } => {
+                write!(f, "delete the {} Pokemons", pokemon_type)
+            }
+            Self::Clear => write!(f, "delete every Pokemon"),
+        }
+    }
+}
+
+pub enum PushError {
+    Unknown,
+}
+
+pub enum PeekError {
+    Unknown,
+}
+
+pub enum RemoveError {
+    Unknown,
+}
+
+pub enum DepthError {
+    Unknown,
+}
+
+pub trait Repository: Send + Sync {
+    fn push(&self, write: Write) -> Result<(), PushError>;
+
+    /// The oldest write, with the id to `remove` it once replayed.
+    fn peek(&self) -> Result<Option<(u64, Write)>, PeekError>;
+
+    fn remove(&self, id: u64) -> Result<(), RemoveError>;
+
+    fn depth(&self) -> Result<u32, DepthError>;
+}
+
+pub struct InMemoryRepository {
+    writes: Mutex<(u64, VecDeque<(u64, Write)>)>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self {
+            writes: Mutex::new((0, VecDeque::new())),
+        }
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn push(&self, write: Write) -> Result<(), PushError> {
+        let mut lock = match self.writes.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PushError::Unknown),
+        };
+
+        let (next_id, writes) = &mut *lock;
+        *next_id += 1;
+        writes.push_back((*next_id, write));
+        Ok(())
+    }
+
+    fn peek(&self) -> Result<Option<(u64, Write)>, PeekError> {
+        match self.writes.lock() {
+            Ok(lock) => Ok(lock.1.front().cloned()),
+            _ => Err(PeekError::Unknown),
+        }
+    }
+
+    fn remove(&self, id: u64) -> Result<(), RemoveError> {
+        match self.writes.lock() {
+            Ok(mut lock) => {
+                lock.1.retain(|(write_id, _)| *write_id != id);
+                Ok(())
+            }
+            _ => Err(RemoveError::Unknown),
+        }
+    }
+
+    fn depth(&self) -> Result<u32, DepthError> {
+        match self.writes.lock() {
+            Ok(lock) => Ok(lock.1.len() as u32),
+            _ => Err(DepthError::Unknown),
+        }
+    }
+}
+
+/// Keeps the writes in the `queued_writes` table, created when missing, so
+/// that they survive a restart of the server.
+pub struct SqliteRepository {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteRepository {
+    pub fn try_new(path: &str) -> Result<Self, ()> {
+        let connection = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            Ok(connection) => connection,
+            _ => return Err(()),
+        };
+
+        match Self::create_table(&connection) {
+            Ok(()) => Ok(Self {
+                connection: Mutex::new(connection),
+            }),
+            _ => Err(()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        match Connection::open_in_memory() {
+            Ok(connection) if Self::create_table(&connection).is_ok() => Self {
+                connection: Mutex::new(connection),
+            },
+            _ => panic!("Error while opening the in-memory database"),
+        }
+    }
+
+    fn create_table(connection: &Connection) -> Result<(), ()> {
+        match connection.execute_batch(
+            "create table if not exists queued_writes (
+                id integer primary key autoincrement,
+                write text not null
+            )",
+        ) {
+            Ok(()) => Ok(()),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Repository for SqliteRepository {
+    fn push(&self, write: Write) -> Result<(), PushError> {
+        let write = match serde_json::to_string(&write) {
+            Ok(write) => write,
+            _ => return Err(PushError::Unknown),
+        };
+
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PushError::Unknown),
+        };
+
+        match lock.execute(
+            "insert into queued_writes (write) values (?)",
+            params![write],
+        ) {
+            Ok(_) => Ok(()),
+            _ => Err(PushError::Unknown),
+        }
+    }
+
+    fn peek(&self) -> Result<Option<(u64, Write)>, PeekError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(PeekError::Unknown),
+        };
+
+        let mut stmt = match lock.prepare("select id, write from queued_writes order by id limit 1")
+        {
+            Ok(stmt) => stmt,
+            _ => return Err(PeekError::Unknown),
+        };
+
+        let mut rows = match stmt.query([]) {
+            Ok(rows) => rows,
+            _ => return Err(PeekError::Unknown),
+        };
+
+        match rows.next() {
+            Ok(Some(row)) => match (row.get::<usize, u64>(0), row.get::<usize, String>(1)) {
+                (Ok(id), Ok(write)) => match serde_json::from_str::<Write>(&write) {
+                    Ok(write) => Ok(Some((id, write))),
+                    _ => Err(PeekError::Unknown),
+                },
+                _ => Err(PeekError::Unknown),
+            },
+            Ok(None) => Ok(None),
+            _ => Err(PeekError::Unknown),
+        }
+    }
+
+    fn remove(&self, id: u64) -> Result<(), RemoveError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(RemoveError::Unknown),
+        };
+
+        match lock.execute("delete from queued_writes where id = ?", params![id]) {
+            Ok(_) => Ok(()),
+            _ => Err(RemoveError::Unknown),
+        }
+    }
+
+    fn depth(&self) -> Result<u32, DepthError> {
+        let lock = match self.connection.lock() {
+            Ok(lock) => lock,
+            _ => return Err(DepthError::Unknown),
+        };
+
+        match lock.query_row("select count(*) from queued_writes", [], |row| {
+            row.get::<usize, u32>(0)
+        }) {
+            Ok(depth) => Ok(depth),
+            _ => Err(DepthError::Unknown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_give_the_writes_back_in_order() {
+        let queues: Vec<Box<dyn Repository>> = vec![
+            Box::new(InMemoryRepository::new()),
+            Box::new(SqliteRepository::in_memory()),
+        ];
+
+        for queue in queues {
+            queue.push(Write::Delete { number: 25 }).ok();
+            queue.push(Write::Clear).ok();
+            assert!(matches!(queue.depth(), Ok(2)));
+
+            match queue.peek() {
+                Ok(Some((id, Write::Delete { number: 25 }))) => {
+                    queue.remove(id).ok();
+                }
+                _ => unreachable!(),
+            };
+            assert!(matches!(queue.peek(), Ok(Some((_, Write::Clear)))));
+            assert!(matches!(queue.depth(), Ok(1)));
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::clock::{now, parse_period};
+use crate::domain::clean_up;
+use crate::repositories::{change, webhook};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How long the records are kept, in seconds. `None` keeps them forever.
+#[derive(Clone, Copy)]
+pub struct Policy {
+    changes: Option<u64>,
+    deliveries: Option<u64>,
+}
+
+impl Policy {
+    /// Takes the periods as days or hours, such as `90d` or `12h`.
+    pub fn try_new(changes: Option<&str>, deliveries: Option<&str>) -> Result<Self, ()> {
+        match (
+            changes.map(parse_period).transpose(),
+            deliveries.map(parse_period).transpose(),
+        ) {
+            (Ok(changes), Ok(deliveries)) => Ok(Self {
+                changes,
+                deliveries,
+            }),
+            _ => Err(()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_none() && self.deliveries.is_none()
+    }
+
+    /// The cut-offs of the policy as of now.
+    pub fn request(&self, dry_run: bool) -> clean_up::Request {
+        let now = now();
+        clean_up::Request {
+            changes_before: self.changes.map(|period| now.saturating_sub(period)),
+            deliveries_before: self.deliveries.map(|period| now.saturating_sub(period)),
+            dry_run,
+        }
+    }
+}
+
+/// Applies the policy once, then again every `interval` in the background,
+/// logging what was removed.
+pub fn spawn(
+    policy: Policy,
+    change_repo: Arc<dyn change::Repository>,
+    webhook_repo: Arc<dyn webhook::Repository>,
+    interval: Duration,
+) {
+    run(policy, change_repo.clone(), webhook_repo.clone());
+
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        run(policy, change_repo.clone(), webhook_repo.clone());
+    });
+}
+
+fn run(
+    policy: Policy,
+    change_repo: Arc<dyn change::Repository>,
+    webhook_repo: Arc<dyn webhook::Repository>,
+) {
+    match clean_up::execute(change_repo, webhook_repo, policy.request(false)) {
+        Ok(clean_up::Response {
+            changes: 0,
+            deliveries: 0,
+        }) => {}
+        Ok(res) => eprintln!(
+            "Cleanup removed {} change(s) and {} webhook delivery(ies)",
+            res.changes, res.deliveries
+        ),
+        Err(clean_up::Error::Unknown) => eprintln!("Cleanup failed, it will be retried"),
+    }
+}
@@ -0,0 +1,337 @@
+use crate::domain::entities::WebhookEvent;
+use crate::domain::hooks::{Validator, Write};
+use crate::notifiers::Subscriber;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope, AST};
+use serde::Deserialize;
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// When the script running on this thread has to stop, as the engine is
+    /// shared by the threads.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The hooks file, such as
+/// `{ "validators": ["no_digits.rhai"], "subscribers": ["log.rhai"] }`.
+/// The paths are relative to the file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default)]
+    validators: Vec<String>,
+    #[serde(default)]
+    subscribers: Vec<String>,
+    #[serde(default)]
+    limits: Limits,
+}
+
+/// What a single run of a script may use. Rhai has no notion of memory, so
+/// the size of its strings, arrays and maps is what is bounded.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    pub timeout_ms: u64,
+    pub max_operations: u64,
+    pub max_size: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 50,
+            max_operations: 100_000,
+            max_size: 10_000,
+        }
+    }
+}
+
+struct Script {
+    path: String,
+    ast: AST,
+}
+
+/// Rhai scripts run before the writes to validate them, or after every
+/// event. The scripts can't reach the file system nor the network.
+///
+/// A validator sees `action` (create, update or patch), `number`, `name`
+/// and `types`, the last two being `()` when a patch leaves them as they
+/// are. It refuses the write with `throw "a reason"`, or by failing in any
+/// other way, such as running out of its limits.
+///
+/// A subscriber sees `event`, such as pokemon.created, and its `data`. It
+/// runs on its own thread, so a slow one doesn't hold the request back.
+pub struct Scripts {
+    engine: Arc<Engine>,
+    timeout: Duration,
+    validators: Vec<Script>,
+    subscribers: Arc<Vec<Script>>,
+}
+
+impl Scripts {
+    pub fn try_load(path: &str) -> Result<Self, ()> {
+        let config = match fs::read_to_string(path).map(|c| serde_json::from_str::<Config>(&c)) {
+            Ok(Ok(config)) => config,
+            _ => return Err(()),
+        };
+        let dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+        let read = |paths: Vec<String>| {
+            paths
+                .into_iter()
+                .map(|p| match fs::read_to_string(dir.join(&p)) {
+                    Ok(source) => Ok((p, source)),
+                    _ => {
+                        eprintln!("The script {} can't be read", p);
+                        Err(())
+                    }
+                })
+                .collect::<Result<Vec<(String, String)>, ()>>()
+        };
+
+        match (read(config.validators), read(config.subscribers)) {
+            (Ok(validators), Ok(subscribers)) => {
+                Self::try_new(config.limits, validators, subscribers)
+            }
+            _ => Err(()),
+        }
+    }
+
+    /// The scripts are given as their path, for the logs, and their source.
+    pub fn try_new(
+        limits: Limits,
+        validators: Vec<(String, String)>,
+        subscribers: Vec<(String, String)>,
+    ) -> Result<Self, ()> {
+        let engine = engine(&limits);
+        let compile = |scripts: Vec<(String, String)>| {
+            scripts
+                .into_iter()
+                .map(|(path, source)| match engine.compile(source) {
+                    Ok(ast) => Ok(Script { path, ast }),
+                    Err(err) => {
+                        eprintln!("The script {} doesn't compile: {}", path, err);
+                        Err(())
+                    }
+                })
+                .collect::<Result<Vec<Script>, ()>>()
+        };
+
+        match (compile(validators), compile(subscribers)) {
+            (Ok(validators), Ok(subscribers)) => Ok(Self {
+                engine: Arc::new(engine),
+                timeout: Duration::from_millis(limits.timeout_ms),
+                validators,
+                subscribers: Arc::new(subscribers),
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Validator for Scripts {
+    fn validate(&self, write: &Write) -> Result<(), String> {
+        for script in self.validators.iter() {
+            let mut scope = Scope::new();
+            scope.push("action", String::from(write.action));
+            scope.push("number", write.number as i64);
+            scope.push_dynamic(
+                "name",
+                write.name.clone().map_or(Dynamic::UNIT, Dynamic::from),
+            );
+            scope.push_dynamic(
+                "types",
+                write.types.clone().map_or(Dynamic::UNIT, |types| {
+                    Dynamic::from_array(types.into_iter().map(Dynamic::from).collect::<Array>())
+                }),
+            );
+
+            if let Err(err) = run(&self.engine, self.timeout, &mut scope, &script.ast) {
+                return Err(format!("{} ({})", reason(*err), script.path));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Subscriber for Scripts {
+    fn receive(&self, event: WebhookEvent, data: &serde_json::Value) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+
+        let data = match rhai::serde::to_dynamic(data) {
+            Ok(data) => data,
+            _ => return,
+        };
+        let engine = self.engine.clone();
+        let timeout = self.timeout;
+        let scripts = self.subscribers.clone();
+        thread::spawn(move || {
+            for script in scripts.iter() {
+                let mut scope = Scope::new();
+                scope.push("event", String::from(event));
+                scope.push_dynamic("data", data.clone());
+
+                if let Err(err) = run(&engine, timeout, &mut scope, &script.ast) {
+                    eprintln!(
+                        "The script {} failed on {}: {}",
+                        script.path,
+                        String::from(event),
+                        reason(*err)
+                    );
+                }
+            }
+        });
+    }
+}
+
+fn engine(limits: &Limits) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(limits.max_operations)
+        .set_max_string_size(limits.max_size)
+        .set_max_array_size(limits.max_size)
+        .set_max_map_size(limits.max_size)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .on_progress(|_| {
+            DEADLINE.with(|deadline| match deadline.get() {
+                Some(deadline) if Instant::now() > deadline => Some(Dynamic::UNIT),
+                _ => None,
+            })
+        })
+        // stdout may be the JSON output of the CLI.
+        .on_print(|text| eprintln!("{}", text))
+        .on_debug(|text, _, _| eprintln!("{}", text));
+    engine
+}
+
+fn run(
+    engine: &Engine,
+    timeout: Duration,
+    scope: &mut Scope,
+    ast: &AST,
+) -> Result<(), Box<EvalAltResult>> {
+    DEADLINE.with(|deadline| deadline.set(Some(Instant::now() + timeout)));
+    let res = engine.run_ast_with_scope(scope, ast);
+    DEADLINE.with(|deadline| deadline.set(None));
+    res
+}
+
+fn reason(err: EvalAltResult) -> String {
+    match err {
+        EvalAltResult::ErrorRuntime(value, _) if value.is_string() => value.to_string(),
+        EvalAltResult::ErrorTerminated(..) => String::from("timed out"),
+        EvalAltResult::ErrorTooManyOperations(_) => String::from("ran too many operations"),
+        EvalAltResult::ErrorDataTooLarge(..) => String::from("used too much memory"),
+        err => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(source: &str) -> Scripts {
+        match Scripts::try_new(
+            Limits::default(),
+            vec![(String::from("validator.rhai"), String::from(source))],
+            vec![],
+        ) {
+            Ok(scripts) => scripts,
+            _ => unreachable!(),
+        }
+    }
+
+    fn write(name: &str) -> Write {
+        Write {
+            action: "create",
+            number: 25,
+            name: Some(String::from(name)),
+            types: Some(vec![String::from("Electric")]),
+        }
+    }
+
+    #[test]
+    fn it_should_refuse_what_the_script_throws_on() {
+        let scripts = validator(
+            r#"
+            if name != () && name.contains("2") {
+                throw "no digits in the names";
+            }
+            "#,
+        );
+
+        assert!(scripts.validate(&write("Pikachu")).is_ok());
+        assert_eq!(
+            scripts.validate(&write("Pikachu2")),
+            Err(String::from("no digits in the names (validator.rhai)"))
+        );
+    }
+
+    #[test]
+    fn it_should_see_the_fields_a_patch_leaves_as_unit() {
+        let scripts = validator(r#"if types != () { throw "types given"; }"#);
+
+        let res = scripts.validate(&Write {
+            action: "patch",
+            number: 25,
+            name: None,
+            types: None,
+        });
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn it_should_stop_a_script_running_out_of_its_limits() {
+        let scripts = validator("loop {}");
+        let hungry = validator(r#"let s = "a"; loop { s += s; }"#);
+
+        assert_eq!(
+            scripts.validate(&write("Pikachu")),
+            Err(String::from("ran too many operations (validator.rhai)"))
+        );
+        assert_eq!(
+            hungry.validate(&write("Pikachu")),
+            Err(String::from("used too much memory (validator.rhai)"))
+        );
+    }
+
+    #[test]
+    fn it_should_stop_a_script_running_for_too_long() {
+        let limits = Limits {
+            timeout_ms: 1,
+            max_operations: u64::MAX,
+            ..Limits::default()
+        };
+        let scripts = match Scripts::try_new(
+            limits,
+            vec![(String::from("slow.rhai"), String::from("loop {}"))],
+            vec![],
+        ) {
+            Ok(scripts) => scripts,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            scripts.validate(&write("Pikachu")),
+            Err(String::from("timed out (slow.rhai)"))
+        );
+    }
+
+    #[test]
+    fn it_should_refuse_to_load_a_script_which_does_not_compile() {
+        let res = Scripts::try_new(
+            Limits::default(),
+            vec![],
+            vec![(String::from("broken.rhai"), String::from("if {"))],
+        );
+
+        assert!(res.is_err());
+    }
+}
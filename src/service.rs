@@ -0,0 +1,1127 @@
+use crate::cancellation::Cancellation;
+use crate::domain::entities::{PokemonNumber, WebhookEvent};
+use crate::domain::hooks::{Validator, Write};
+use crate::domain::middleware::{Middleware, Pipeline};
+use crate::domain::validate_pokemon::FieldError;
+use crate::domain::{
+    add_move, check_name, clear_pokemons, create_pokemon, delete_pokemon, delete_pokemons_by_type,
+    fetch_all_pokemons, fetch_index, fetch_moves, fetch_pokemon, fetch_pokemon_by_name,
+    fetch_pokemon_cry, fetch_pokemon_page, fetch_pokemon_range, fetch_pokemons_by_type,
+    fetch_progress, fetch_type_stats, ingest_pokemons, patch_pokemon, promote_wish, remove_move,
+    replace_pokemon, update_pokemon, update_pokemon_abilities, update_pokemon_class,
+    update_pokemon_cry, update_pokemon_evolutions, update_pokemon_measurements,
+    update_pokemon_sprite, update_pokemon_types, update_pokemons,
+};
+use crate::notifiers::EventBus;
+use crate::repositories::cancellable::CancellableRepository;
+use crate::repositories::cry::Repository as CryRepository;
+use crate::repositories::moves::Repository as MovesRepository;
+use crate::repositories::pokemon::Repository;
+use crate::repositories::wishlist::Repository as WishlistRepository;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct Pokemon<'a> {
+    number: u16,
+    name: &'a str,
+    types: &'a [String],
+}
+
+#[derive(Serialize)]
+struct Deleted {
+    number: u16,
+}
+
+/// The one entry point of the api and the CLI to the use cases on Pokemons.
+/// Each method runs its use case and then what comes with it, such as
+/// publishing the event of a successful write.
+pub struct PokedexService {
+    repo: Arc<dyn Repository>,
+    events: Option<Arc<EventBus>>,
+    validators: Vec<Arc<dyn Validator>>,
+    pipeline: Pipeline,
+    cries: Option<Arc<dyn CryRepository>>,
+}
+
+impl PokedexService {
+    pub fn new(repo: Arc<dyn Repository>) -> Self {
+        Self {
+            repo,
+            events: None,
+            validators: vec![],
+            pipeline: Pipeline::new(vec![]),
+            cries: None,
+        }
+    }
+
+    pub fn with_events(self, events: Arc<EventBus>) -> Self {
+        Self {
+            events: Some(events),
+            ..self
+        }
+    }
+
+    /// The cries of the Pokemons, deleted along with them.
+    pub fn with_cries(self, cries: Arc<dyn CryRepository>) -> Self {
+        Self {
+            cries: Some(cries),
+            ..self
+        }
+    }
+
+    /// Validators run before creating, updating or patching a Pokemon, in
+    /// the order they were added.
+    pub fn with_validator(mut self, validator: Arc<dyn Validator>) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Middlewares run around every use case, in the order they were added.
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.pipeline.push(middleware);
+        self
+    }
+
+    /// The same service for the work of a single request: its use cases stop
+    /// calling the repository once `cancellation` is cancelled.
+    pub fn with_cancellation(&self, cancellation: Cancellation) -> Self {
+        Self {
+            repo: Arc::new(CancellableRepository::new(self.repo.clone(), cancellation)),
+            events: self.events.clone(),
+            validators: self.validators.clone(),
+            pipeline: self.pipeline.clone(),
+            cries: self.cries.clone(),
+        }
+    }
+
+    /// For the jobs working on the storage itself, such as exports.
+    pub fn repo(&self) -> Arc<dyn Repository> {
+        self.repo.clone()
+    }
+
+    pub fn check_name(
+        &self,
+        req: check_name::Request,
+    ) -> Result<check_name::Response, check_name::Error> {
+        self.pipeline.run(
+            "check_name",
+            || check_name::execute(self.repo.clone(), req),
+            check_name::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_all_pokemons(
+        &self,
+        req: fetch_all_pokemons::Request,
+    ) -> Result<fetch_all_pokemons::Response, fetch_all_pokemons::Error> {
+        self.pipeline.run(
+            "fetch_all_pokemons",
+            || fetch_all_pokemons::execute(self.repo.clone(), req),
+            fetch_all_pokemons::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_index(&self) -> Result<Vec<fetch_index::Response>, fetch_index::Error> {
+        self.pipeline.run(
+            "fetch_index",
+            || fetch_index::execute(self.repo.clone()),
+            fetch_index::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_moves(
+        &self,
+        moves_repo: Arc<dyn MovesRepository>,
+        req: fetch_moves::Request,
+    ) -> Result<Vec<fetch_moves::LearnedMove>, fetch_moves::Error> {
+        self.pipeline.run(
+            "fetch_moves",
+            || fetch_moves::execute(moves_repo, self.repo.clone(), req),
+            fetch_moves::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_pokemon(
+        &self,
+        req: fetch_pokemon::Request,
+    ) -> Result<fetch_pokemon::Response, fetch_pokemon::Error> {
+        self.pipeline.run(
+            "fetch_pokemon",
+            || fetch_pokemon::execute(self.repo.clone(), req),
+            fetch_pokemon::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_pokemon_cry(
+        &self,
+        cry_repo: Arc<dyn CryRepository>,
+        req: fetch_pokemon_cry::Request,
+    ) -> Result<fetch_pokemon_cry::Response, fetch_pokemon_cry::Error> {
+        self.pipeline.run(
+            "fetch_pokemon_cry",
+            || fetch_pokemon_cry::execute(cry_repo, self.repo.clone(), req),
+            fetch_pokemon_cry::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_pokemon_by_name(
+        &self,
+        req: fetch_pokemon_by_name::Request,
+    ) -> Result<fetch_pokemon_by_name::Response, fetch_pokemon_by_name::Error> {
+        self.pipeline.run(
+            "fetch_pokemon_by_name",
+            || fetch_pokemon_by_name::execute(self.repo.clone(), req),
+            fetch_pokemon_by_name::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_pokemon_page(
+        &self,
+        req: fetch_pokemon_page::Request,
+    ) -> Result<fetch_pokemon_page::Response, fetch_pokemon_page::Error> {
+        self.pipeline.run(
+            "fetch_pokemon_page",
+            || fetch_pokemon_page::execute(self.repo.clone(), req),
+            fetch_pokemon_page::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_pokemon_range(
+        &self,
+        req: fetch_pokemon_range::Request,
+    ) -> Result<Vec<fetch_pokemon_range::Response>, fetch_pokemon_range::Error> {
+        self.pipeline.run(
+            "fetch_pokemon_range",
+            || fetch_pokemon_range::execute(self.repo.clone(), req),
+            fetch_pokemon_range::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_pokemons_by_type(
+        &self,
+        req: fetch_pokemons_by_type::Request,
+    ) -> Result<Vec<fetch_pokemons_by_type::Response>, fetch_pokemons_by_type::Error> {
+        self.pipeline.run(
+            "fetch_pokemons_by_type",
+            || fetch_pokemons_by_type::execute(self.repo.clone(), req),
+            fetch_pokemons_by_type::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_progress(
+        &self,
+        req: fetch_progress::Request,
+    ) -> Result<fetch_progress::Response, fetch_progress::Error> {
+        self.pipeline.run(
+            "fetch_progress",
+            || fetch_progress::execute(self.repo.clone(), req),
+            fetch_progress::Error::Unknown,
+        )
+    }
+
+    pub fn fetch_type_stats(&self) -> Result<fetch_type_stats::Response, fetch_type_stats::Error> {
+        self.pipeline.run(
+            "fetch_type_stats",
+            || fetch_type_stats::execute(self.repo.clone()),
+            fetch_type_stats::Error::Unknown,
+        )
+    }
+
+    pub fn create_pokemon(
+        &self,
+        req: create_pokemon::Request,
+    ) -> Result<create_pokemon::Response, create_pokemon::Error> {
+        let res = self.pipeline.run(
+            "create_pokemon",
+            || {
+                let write = Write {
+                    action: "create",
+                    number: req.number,
+                    name: Some(req.name.clone()),
+                    types: Some(req.types.clone()),
+                };
+                match self.validate(write) {
+                    Ok(()) => create_pokemon::execute(self.repo.clone(), req),
+                    Err(errors) => Err(create_pokemon::Error::BadRequest(errors)),
+                }
+            },
+            create_pokemon::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonCreated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn ingest_pokemons(
+        &self,
+        policy: ingest_pokemons::Policy,
+        reqs: Vec<ingest_pokemons::Request>,
+    ) -> Result<ingest_pokemons::Response, ingest_pokemons::Error> {
+        let res = self.pipeline.run(
+            "ingest_pokemons",
+            || {
+                let (reqs, refused) = self.validate_ingested(reqs);
+                match ingest_pokemons::execute(self.repo.clone(), policy, reqs) {
+                    Ok(res) => Ok(with_refused(res, refused)),
+                    Err(err) => Err(err),
+                }
+            },
+            ingest_pokemons::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            for outcome in res.outcomes.iter() {
+                let event = match outcome.status {
+                    ingest_pokemons::Status::Inserted => WebhookEvent::PokemonCreated,
+                    ingest_pokemons::Status::Overwritten => WebhookEvent::PokemonUpdated,
+                    _ => continue,
+                };
+                self.publish(
+                    event,
+                    &Pokemon {
+                        number: outcome.number,
+                        name: &outcome.name,
+                        types: &outcome.types,
+                    },
+                );
+            }
+        }
+        res
+    }
+
+    pub fn patch_pokemon(
+        &self,
+        req: patch_pokemon::Request,
+    ) -> Result<patch_pokemon::Response, patch_pokemon::Error> {
+        let res = self.pipeline.run(
+            "patch_pokemon",
+            || {
+                let write = Write {
+                    action: "patch",
+                    number: req.number,
+                    name: req.name.clone(),
+                    types: req.types.clone(),
+                };
+                match self.validate(write) {
+                    Ok(()) => patch_pokemon::execute(self.repo.clone(), req),
+                    Err(errors) => Err(patch_pokemon::Error::BadRequest(errors)),
+                }
+            },
+            patch_pokemon::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn promote_wish(
+        &self,
+        wishlist_repo: Arc<dyn WishlistRepository>,
+        req: promote_wish::Request,
+    ) -> Result<promote_wish::Response, promote_wish::Error> {
+        let res = self.pipeline.run(
+            "promote_wish",
+            || {
+                let name = PokemonNumber::try_from(req.number)
+                    .ok()
+                    .and_then(|number| wishlist_repo.fetch_one(number).ok())
+                    .map(|wish| String::from(wish.name));
+                let write = Write {
+                    action: "create",
+                    number: req.number,
+                    name,
+                    types: Some(req.types.clone()),
+                };
+                match self.validate(write) {
+                    Ok(()) => promote_wish::execute(wishlist_repo, self.repo.clone(), req),
+                    Err(errors) => Err(promote_wish::Error::BadRequest(errors)),
+                }
+            },
+            promote_wish::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonCreated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn add_move(
+        &self,
+        moves_repo: Arc<dyn MovesRepository>,
+        req: add_move::Request,
+    ) -> Result<fetch_moves::LearnedMove, add_move::Error> {
+        self.pipeline.run(
+            "add_move",
+            || add_move::execute(moves_repo, self.repo.clone(), req),
+            add_move::Error::Unknown,
+        )
+    }
+
+    pub fn remove_move(
+        &self,
+        moves_repo: Arc<dyn MovesRepository>,
+        req: remove_move::Request,
+    ) -> Result<(), remove_move::Error> {
+        self.pipeline.run(
+            "remove_move",
+            || remove_move::execute(moves_repo, req),
+            remove_move::Error::Unknown,
+        )
+    }
+
+    /// Replaces the Pokemon with all the given fields at once, creating it
+    /// when asked to.
+    pub fn replace_pokemon(
+        &self,
+        req: replace_pokemon::Request,
+    ) -> Result<replace_pokemon::Response, replace_pokemon::Error> {
+        let create = req.create;
+        let res = self.pipeline.run(
+            "replace_pokemon",
+            || {
+                let write = Write {
+                    action: if create { "create" } else { "update" },
+                    number: req.number,
+                    name: Some(req.name.clone()),
+                    types: Some(req.types.clone()),
+                };
+                match self.validate(write) {
+                    Ok(()) => replace_pokemon::execute(self.repo.clone(), req),
+                    Err(errors) => Err(replace_pokemon::Error::BadRequest(errors)),
+                }
+            },
+            replace_pokemon::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                match create {
+                    true => WebhookEvent::PokemonCreated,
+                    false => WebhookEvent::PokemonUpdated,
+                },
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemon(
+        &self,
+        req: update_pokemon::Request,
+    ) -> Result<update_pokemon::Response, update_pokemon::Error> {
+        let res = self.pipeline.run(
+            "update_pokemon",
+            || {
+                let write = Write {
+                    action: "update",
+                    number: req.number,
+                    name: Some(req.name.clone()),
+                    types: Some(req.types.clone()),
+                };
+                match self.validate(write) {
+                    Ok(()) => update_pokemon::execute(self.repo.clone(), req),
+                    Err(errors) => Err(update_pokemon::Error::BadRequest(errors)),
+                }
+            },
+            update_pokemon::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemon_types(
+        &self,
+        req: update_pokemon_types::Request,
+    ) -> Result<update_pokemon_types::Response, update_pokemon_types::Error> {
+        let res = self.pipeline.run(
+            "update_pokemon_types",
+            || {
+                let change = match req.operation {
+                    update_pokemon_types::Operation::Add => {
+                        update_pokemons::Change::AddType(req.pokemon_type.clone())
+                    }
+                    update_pokemon_types::Operation::Remove => {
+                        update_pokemons::Change::RemoveType(req.pokemon_type.clone())
+                    }
+                };
+                let stored = match self.validators.is_empty() {
+                    true => None,
+                    false => self.stored_types(req.number),
+                };
+                match self.validate_types(req.number, stored, &change) {
+                    Ok(_) => update_pokemon_types::execute(self.repo.clone(), req),
+                    Err(errors) => Err(update_pokemon_types::Error::BadRequest(errors)),
+                }
+            },
+            update_pokemon_types::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemon_evolutions(
+        &self,
+        req: update_pokemon_evolutions::Request,
+    ) -> Result<update_pokemon_evolutions::Response, update_pokemon_evolutions::Error> {
+        let res = self.pipeline.run(
+            "update_pokemon_evolutions",
+            || update_pokemon_evolutions::execute(self.repo.clone(), req),
+            update_pokemon_evolutions::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemon_cry(
+        &self,
+        cry_repo: Arc<dyn CryRepository>,
+        req: update_pokemon_cry::Request,
+    ) -> Result<update_pokemon_cry::Response, update_pokemon_cry::Error> {
+        self.pipeline.run(
+            "update_pokemon_cry",
+            || update_pokemon_cry::execute(cry_repo, self.repo.clone(), req),
+            update_pokemon_cry::Error::Unknown,
+        )
+    }
+
+    pub fn update_pokemon_abilities(
+        &self,
+        req: update_pokemon_abilities::Request,
+    ) -> Result<update_pokemon_abilities::Response, update_pokemon_abilities::Error> {
+        let res = self.pipeline.run(
+            "update_pokemon_abilities",
+            || update_pokemon_abilities::execute(self.repo.clone(), req),
+            update_pokemon_abilities::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemon_measurements(
+        &self,
+        req: update_pokemon_measurements::Request,
+    ) -> Result<update_pokemon_measurements::Response, update_pokemon_measurements::Error> {
+        let res = self.pipeline.run(
+            "update_pokemon_measurements",
+            || update_pokemon_measurements::execute(self.repo.clone(), req),
+            update_pokemon_measurements::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemon_sprite(
+        &self,
+        req: update_pokemon_sprite::Request,
+    ) -> Result<update_pokemon_sprite::Response, update_pokemon_sprite::Error> {
+        let res = self.pipeline.run(
+            "update_pokemon_sprite",
+            || update_pokemon_sprite::execute(self.repo.clone(), req),
+            update_pokemon_sprite::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemon_class(
+        &self,
+        req: update_pokemon_class::Request,
+    ) -> Result<update_pokemon_class::Response, update_pokemon_class::Error> {
+        let res = self.pipeline.run(
+            "update_pokemon_class",
+            || update_pokemon_class::execute(self.repo.clone(), req),
+            update_pokemon_class::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            self.publish(
+                WebhookEvent::PokemonUpdated,
+                &Pokemon {
+                    number: res.number,
+                    name: &res.name,
+                    types: &res.types,
+                },
+            );
+        }
+        res
+    }
+
+    pub fn update_pokemons(
+        &self,
+        reqs: Vec<update_pokemons::Request>,
+    ) -> Result<Vec<update_pokemons::Outcome>, update_pokemons::Error> {
+        let res = self.pipeline.run(
+            "update_pokemons",
+            || match self.validate_changes(&reqs) {
+                Ok(()) => update_pokemons::execute(self.repo.clone(), reqs),
+                Err(outcomes) => Err(update_pokemons::Error::Rejected(outcomes)),
+            },
+            update_pokemons::Error::Unknown,
+        );
+        if let Ok(outcomes) = &res {
+            for outcome in outcomes {
+                if let update_pokemons::Status::Updated { name, types } = &outcome.status {
+                    self.publish(
+                        WebhookEvent::PokemonUpdated,
+                        &Pokemon {
+                            number: outcome.number,
+                            name,
+                            types,
+                        },
+                    );
+                }
+            }
+        }
+        res
+    }
+
+    pub fn delete_pokemon(
+        &self,
+        req: delete_pokemon::Request,
+    ) -> Result<(), delete_pokemon::Error> {
+        let number = req.number;
+        let res = self.pipeline.run(
+            "delete_pokemon",
+            || delete_pokemon::execute(self.repo.clone(), req),
+            delete_pokemon::Error::Unknown,
+        );
+        if res.is_ok() {
+            self.forget_cry(number);
+            self.publish(WebhookEvent::PokemonDeleted, &Deleted { number });
+        }
+        res
+    }
+
+    pub fn delete_pokemons_by_type(
+        &self,
+        req: delete_pokemons_by_type::Request,
+    ) -> Result<Vec<delete_pokemons_by_type::Response>, delete_pokemons_by_type::Error> {
+        let res = self.pipeline.run(
+            "delete_pokemons_by_type",
+            || delete_pokemons_by_type::execute(self.repo.clone(), req),
+            delete_pokemons_by_type::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            for pokemon in res.iter() {
+                self.forget_cry(pokemon.number);
+                self.publish(
+                    WebhookEvent::PokemonDeleted,
+                    &Deleted {
+                        number: pokemon.number,
+                    },
+                );
+            }
+        }
+        res
+    }
+
+    pub fn clear_pokemons(
+        &self,
+        req: clear_pokemons::Request,
+    ) -> Result<clear_pokemons::Response, clear_pokemons::Error> {
+        let res = self.pipeline.run(
+            "clear_pokemons",
+            || clear_pokemons::execute(self.repo.clone(), req),
+            clear_pokemons::Error::Unknown,
+        );
+        if let Ok(res) = &res {
+            for number in res.numbers.iter() {
+                self.forget_cry(*number);
+                self.publish(WebhookEvent::PokemonDeleted, &Deleted { number: *number });
+            }
+        }
+        res
+    }
+
+    /// The first refusal is logged, the next validators don't run.
+    // A refusal is about the Pokemon as a whole rather than a single field.
+    fn validate(&self, write: Write) -> Result<(), Vec<FieldError>> {
+        for validator in self.validators.iter() {
+            if let Err(reason) = validator.validate(&write) {
+                eprintln!("{} #{} refused: {}", write.action, write.number, reason);
+                return Err(vec![FieldError::new("pokemon", &reason)]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the validators on the types a change leaves the Pokemon with,
+    /// giving them back. Nothing is checked when the Pokemon can't be read,
+    /// the use case telling why.
+    fn validate_types(
+        &self,
+        number: u16,
+        stored: Option<Vec<String>>,
+        change: &update_pokemons::Change,
+    ) -> Result<Option<Vec<String>>, Vec<FieldError>> {
+        let types = match (stored, change) {
+            (_, update_pokemons::Change::SetTypes(types)) => types.clone(),
+            (Some(mut types), update_pokemons::Change::AddType(added)) => {
+                types.push(added.clone());
+                types
+            }
+            (Some(mut types), update_pokemons::Change::RemoveType(removed)) => {
+                types.retain(|t| !t.eq_ignore_ascii_case(removed));
+                types
+            }
+            (None, _) => return Ok(None),
+        };
+        let write = Write {
+            action: "patch",
+            number,
+            name: None,
+            types: Some(types.clone()),
+        };
+        match self.validate(write) {
+            Ok(()) => Ok(Some(types)),
+            Err(errors) => Err(errors),
+        }
+    }
+
+    /// The batch being applied as a whole, a refused change rejects it all,
+    /// the other changes being left out.
+    fn validate_changes(
+        &self,
+        reqs: &[update_pokemons::Request],
+    ) -> Result<(), Vec<update_pokemons::Outcome>> {
+        if self.validators.is_empty() {
+            return Ok(());
+        }
+
+        let mut types = HashMap::new();
+        let mut refusals = vec![];
+        for req in reqs {
+            let stored = match types.get(&req.number) {
+                Some(stored) => Some(Vec::clone(stored)),
+                None => self.stored_types(req.number),
+            };
+            match self.validate_types(req.number, stored, &req.change) {
+                Ok(Some(changed)) => {
+                    types.insert(req.number, changed);
+                    refusals.push(None);
+                }
+                Ok(None) => refusals.push(None),
+                Err(errors) => refusals.push(Some(errors)),
+            }
+        }
+
+        if refusals.iter().all(Option::is_none) {
+            return Ok(());
+        }
+        Err(reqs
+            .iter()
+            .zip(refusals)
+            .map(|(req, refusal)| update_pokemons::Outcome {
+                number: req.number,
+                status: match refusal {
+                    Some(errors) => update_pokemons::Status::Invalid(errors),
+                    None => update_pokemons::Status::NotApplied,
+                },
+            })
+            .collect())
+    }
+
+    /// Splits the Pokemons to ingest into the ones the validators accept
+    /// and the outcomes of the refused ones, along with their positions.
+    fn validate_ingested(
+        &self,
+        reqs: Vec<ingest_pokemons::Request>,
+    ) -> (
+        Vec<ingest_pokemons::Request>,
+        Vec<(usize, ingest_pokemons::Outcome)>,
+    ) {
+        if self.validators.is_empty() {
+            return (reqs, vec![]);
+        }
+
+        let mut accepted = vec![];
+        let mut refused = vec![];
+        for (index, req) in reqs.into_iter().enumerate() {
+            let write = Write {
+                action: match self.stored_types(req.number) {
+                    Some(_) => "update",
+                    None => "create",
+                },
+                number: req.number,
+                name: Some(req.name.clone()),
+                types: Some(req.types.clone()),
+            };
+            match self.validate(write) {
+                Ok(()) => accepted.push(req),
+                Err(errors) => refused.push((
+                    index,
+                    ingest_pokemons::Outcome {
+                        number: req.number,
+                        name: req.name,
+                        types: req.types,
+                        status: ingest_pokemons::Status::Invalid,
+                        errors,
+                    },
+                )),
+            }
+        }
+        (accepted, refused)
+    }
+
+    fn stored_types(&self, number: u16) -> Option<Vec<String>> {
+        match PokemonNumber::try_from(number).map(|number| self.repo.fetch_one(number)) {
+            Ok(Ok(pokemon)) => Some(Vec::<String>::from(pokemon.types)),
+            _ => None,
+        }
+    }
+
+    // The Pokemon being gone already, a cry left behind is only logged.
+    fn forget_cry(&self, number: u16) {
+        if let (Some(cries), Ok(pokemon_number)) = (&self.cries, PokemonNumber::try_from(number)) {
+            if cries.delete(pokemon_number).is_err() {
+                eprintln!("The cry of #{} could not be deleted", number);
+            }
+        }
+    }
+
+    fn publish<T: Serialize>(&self, event: WebhookEvent, data: &T) {
+        if let Some(events) = &self.events {
+            events.notify(event, data);
+        }
+    }
+}
+
+/// Puts the refused Pokemons back among the ingested ones, in the order of
+/// the request.
+fn with_refused(
+    mut res: ingest_pokemons::Response,
+    refused: Vec<(usize, ingest_pokemons::Outcome)>,
+) -> ingest_pokemons::Response {
+    res.invalid += refused.len();
+    for (index, outcome) in refused {
+        res.outcomes.insert(index, outcome);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Cry, PokemonName, PokemonTypes};
+    use crate::notifiers::Subscriber;
+    use crate::repositories::cry;
+    use crate::repositories::pokemon::InMemoryRepository;
+    use std::sync::Mutex;
+
+    struct Recorder(Mutex<Vec<(WebhookEvent, serde_json::Value)>>);
+
+    impl Subscriber for Recorder {
+        fn receive(&self, event: WebhookEvent, data: &serde_json::Value) {
+            if let Ok(mut events) = self.0.lock() {
+                events.push((event, data.clone()));
+            }
+        }
+    }
+
+    struct NoDigits;
+
+    impl Validator for NoDigits {
+        fn validate(&self, write: &Write) -> Result<(), String> {
+            match &write.name {
+                Some(name) if name.chars().any(|c| c.is_ascii_digit()) => {
+                    Err(String::from("digits in the name"))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_not_write_what_a_validator_refuses() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let service = PokedexService::new(repo.clone()).with_validator(Arc::new(NoDigits));
+
+        let refused = service.create_pokemon(create_pokemon::Request {
+            number: 25,
+            name: String::from("Pikachu2"),
+            types: vec![String::from("Electric")],
+        });
+        let accepted = service.create_pokemon(create_pokemon::Request {
+            number: 25,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+        });
+
+        match refused {
+            Err(create_pokemon::Error::BadRequest(errors)) => {
+                assert_eq!(errors[0].message, "digits in the name")
+            }
+            _ => unreachable!(),
+        };
+        assert!(accepted.is_ok());
+        assert!(matches!(repo.count(), Ok(1)));
+    }
+
+    struct NoFire;
+
+    impl Validator for NoFire {
+        fn validate(&self, write: &Write) -> Result<(), String> {
+            match &write.types {
+                Some(types) if types.iter().any(|t| t.eq_ignore_ascii_case("fire")) => {
+                    Err(String::from("no fire"))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_run_the_validators_on_every_write() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let service = PokedexService::new(repo.clone()).with_validator(Arc::new(NoFire));
+
+        let added = service.update_pokemon_types(update_pokemon_types::Request {
+            number: 25,
+            operation: update_pokemon_types::Operation::Add,
+            pokemon_type: String::from("Fire"),
+        });
+        let batch = service.update_pokemons(vec![
+            update_pokemons::Request {
+                number: 25,
+                change: update_pokemons::Change::AddType(String::from("Flying")),
+            },
+            update_pokemons::Request {
+                number: 25,
+                change: update_pokemons::Change::AddType(String::from("fire")),
+            },
+        ]);
+        let ingested = service.ingest_pokemons(
+            ingest_pokemons::Policy::Skip,
+            vec![
+                ingest_pokemons::Request {
+                    number: 4,
+                    name: String::from("Charmander"),
+                    types: vec![String::from("Fire")],
+                    details: ingest_pokemons::Details::default(),
+                },
+                ingest_pokemons::Request {
+                    number: 7,
+                    name: String::from("Squirtle"),
+                    types: vec![String::from("Water")],
+                    details: ingest_pokemons::Details::default(),
+                },
+            ],
+        );
+
+        match added {
+            Err(update_pokemon_types::Error::BadRequest(errors)) => {
+                assert_eq!(errors[0].message, "no fire")
+            }
+            _ => unreachable!(),
+        };
+        match batch {
+            Err(update_pokemons::Error::Rejected(outcomes)) => match &outcomes[..] {
+                [update_pokemons::Outcome {
+                    status: update_pokemons::Status::NotApplied,
+                    ..
+                }, update_pokemons::Outcome {
+                    status: update_pokemons::Status::Invalid(_),
+                    ..
+                }] => {}
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        match ingested {
+            Ok(res) => {
+                assert_eq!(res.inserted, 1);
+                assert_eq!(res.invalid, 1);
+                assert_eq!(res.outcomes[0].status, ingest_pokemons::Status::Invalid);
+                assert_eq!(res.outcomes[1].number, 7);
+            }
+            _ => unreachable!(),
+        };
+        match repo.fetch_one(PokemonNumber::pikachu()) {
+            Ok(pokemon) => assert_eq!(Vec::<String>::from(pokemon.types), vec!["Electric"]),
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_publish_the_successful_writes_only() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let recorder = Arc::new(Recorder(Mutex::new(vec![])));
+        let service =
+            PokedexService::new(repo).with_events(Arc::new(EventBus::new(vec![recorder.clone()])));
+
+        service
+            .delete_pokemon(delete_pokemon::Request { number: 25 })
+            .ok();
+        service
+            .delete_pokemon(delete_pokemon::Request { number: 25 })
+            .ok();
+
+        match recorder.0.lock() {
+            Ok(events) => match &events[..] {
+                [(WebhookEvent::PokemonDeleted, data)] => {
+                    assert_eq!(data, &serde_json::json!({ "number": 25 }))
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_publish_each_ingested_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let recorder = Arc::new(Recorder(Mutex::new(vec![])));
+        let service =
+            PokedexService::new(repo).with_events(Arc::new(EventBus::new(vec![recorder.clone()])));
+        let req = |number: u16, name: &str, types: &str| ingest_pokemons::Request {
+            number,
+            name: String::from(name),
+            types: vec![String::from(types)],
+            details: ingest_pokemons::Details::default(),
+        };
+
+        service
+            .ingest_pokemons(
+                ingest_pokemons::Policy::Overwrite,
+                vec![
+                    req(25, "Pikachu", "Electric"),
+                    req(4, "Charmander", "Fire"),
+                    req(0, "Missingno", "Normal"),
+                ],
+            )
+            .ok();
+
+        match recorder.0.lock() {
+            Ok(events) => match &events[..] {
+                [(WebhookEvent::PokemonUpdated, updated), (WebhookEvent::PokemonCreated, created)] =>
+                {
+                    assert_eq!(updated["number"], 25);
+                    assert_eq!(created["name"], "Charmander");
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_delete_the_cry_along_with_the_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+        let cries = Arc::new(cry::InMemoryRepository::new());
+        cries.put(PokemonNumber::pikachu(), Cry::pikachu()).ok();
+        let service = PokedexService::new(repo).with_cries(cries.clone());
+
+        assert!(service
+            .delete_pokemon(delete_pokemon::Request { number: 25 })
+            .is_ok());
+
+        assert!(matches!(
+            cries.fetch(PokemonNumber::pikachu()),
+            Err(cry::FetchError::NotFound)
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::domain::entities::PokemonSprite;
+use std::io::{self, Read};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+/// Bigger images are refused rather than downloaded whole.
+pub const MAX_BYTES: u64 = 2 * 1024 * 1024;
+const REDIRECTS: u32 = 5;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Image {
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Downloads the image at the sprite URL. Only public addresses are
+/// connected to, redirections included, and only images of at most
+/// `MAX_BYTES` are given back.
+pub fn download(url: &str) -> Result<Image, ()> {
+    let agent = ureq::AgentBuilder::new()
+        .redirects(REDIRECTS)
+        .timeout(TIMEOUT)
+        .resolver(resolve)
+        .build();
+
+    let res = match agent.get(url).call() {
+        Ok(res) => res,
+        _ => return Err(()),
+    };
+
+    let content_type = String::from(res.content_type());
+    if !content_type.starts_with("image/") {
+        return Err(());
+    }
+
+    let mut bytes = vec![];
+    match res
+        .into_reader()
+        .take(MAX_BYTES + 1)
+        .read_to_end(&mut bytes)
+    {
+        Ok(read) if read as u64 <= MAX_BYTES => Ok(Image {
+            content_type,
+            bytes,
+        }),
+        _ => Err(()),
+    }
+}
+
+/// Resolves the host to its public addresses only. The check happens when
+/// connecting rather than when the URL is saved, so that a host resolving to
+/// a private address, or redirecting to one, is refused too.
+pub fn resolve(netloc: &str) -> io::Result<Vec<SocketAddr>> {
+    let addrs = netloc
+        .to_socket_addrs()?
+        .filter(|addr| PokemonSprite::is_public(addr.ip()))
+        .collect::<Vec<SocketAddr>>();
+
+    match addrs.is_empty() {
+        true => Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "the host is not a public address",
+        )),
+        false => Ok(addrs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_not_connect_to_private_addresses() {
+        assert!(resolve("127.0.0.1:80").is_err());
+        assert!(resolve("[::1]:80").is_err());
+        assert!(resolve("192.168.1.1:80").is_err());
+        assert!(matches!(resolve("93.184.216.34:80").as_deref(), Ok([_])));
+        assert!(download("http://127.0.0.1:1/25.png").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::env;
+use std::net::TcpListener;
+
+/// The first socket systemd passes, the ones before are stdin, stdout and
+/// stderr.
+#[cfg(unix)]
+const LISTEN_FDS_START: i32 = 3;
+
+/// Takes the socket systemd listens on for us with socket activation, so
+/// that connections wait in its backlog while the server restarts instead of
+/// being refused. Only the first socket is used.
+pub fn listener() -> Option<TcpListener> {
+    let count = listen_fds(
+        env::var("LISTEN_PID").ok().as_deref(),
+        env::var("LISTEN_FDS").ok().as_deref(),
+        std::process::id(),
+    );
+
+    // The children must not believe the sockets are theirs.
+    env::remove_var("LISTEN_PID");
+    env::remove_var("LISTEN_FDS");
+    env::remove_var("LISTEN_FDNAMES");
+
+    match count {
+        0 => None,
+        _ => sys::take_listener(),
+    }
+}
+
+/// Tells systemd about the state of the service, such as `READY=1`, when it
+/// runs with `Type=notify`. Does nothing otherwise.
+pub fn notify(state: &str) {
+    if let Ok(path) = env::var("NOTIFY_SOCKET") {
+        if sys::send(&path, state).is_err() {
+            eprintln!("Cannot notify systemd through {}", path);
+        }
+    }
+}
+
+/// How many sockets were passed to this process, the variables being meant
+/// for the process having the PID `LISTEN_PID` only.
+fn listen_fds(listen_pid: Option<&str>, listen_fds: Option<&str>, pid: u32) -> u32 {
+    match (
+        listen_pid.map(str::parse::<u32>),
+        listen_fds.map(str::parse::<u32>),
+    ) {
+        (Some(Ok(listen_pid)), Some(Ok(count))) if listen_pid == pid => count,
+        _ => 0,
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use super::LISTEN_FDS_START;
+    use std::io;
+    use std::net::TcpListener;
+    use std::os::unix::io::FromRawFd;
+    use std::os::unix::net::UnixDatagram;
+
+    pub fn take_listener() -> Option<TcpListener> {
+        unsafe {
+            libc::fcntl(LISTEN_FDS_START, libc::F_SETFD, libc::FD_CLOEXEC);
+            Some(TcpListener::from_raw_fd(LISTEN_FDS_START))
+        }
+    }
+
+    pub fn send(path: &str, state: &str) -> io::Result<()> {
+        let socket = UnixDatagram::unbound()?;
+        match path.strip_prefix('@') {
+            Some(name) => send_abstract(&socket, name, state),
+            None => socket.send_to(state.as_bytes(), path).map(|_| ()),
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn send_abstract(socket: &UnixDatagram, name: &str, state: &str) -> io::Result<()> {
+        #[cfg(target_os = "android")]
+        use std::os::android::net::SocketAddrExt;
+        #[cfg(target_os = "linux")]
+        use std::os::linux::net::SocketAddrExt;
+        use std::os::unix::net::SocketAddr;
+
+        let addr = SocketAddr::from_abstract_name(name.as_bytes())?;
+        socket.send_to_addr(state.as_bytes(), &addr).map(|_| ())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn send_abstract(_socket: &UnixDatagram, _name: &str, _state: &str) -> io::Result<()> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use std::io;
+    use std::net::TcpListener;
+
+    pub fn take_listener() -> Option<TcpListener> {
+        None
+    }
+
+    pub fn send(_path: &str, _state: &str) -> io::Result<()> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_only_take_the_sockets_meant_for_this_process() {
+        assert_eq!(listen_fds(Some("42"), Some("1"), 42), 1);
+        assert_eq!(listen_fds(Some("41"), Some("1"), 42), 0);
+        assert_eq!(listen_fds(None, Some("1"), 42), 0);
+        assert_eq!(listen_fds(Some("42"), Some("one"), 42), 0);
+    }
+}
@@ -0,0 +1,297 @@
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static SPANS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// The trace of the request the thread is serving.
+    static CURRENT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+/// How the caller sent the trace, which the outbound calls follow.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    TraceContext,
+    B3Single,
+    B3Multi,
+}
+
+/// The trace a request belongs to, with the span the Pokedex opens in it.
+#[derive(Clone, Debug)]
+pub struct Context {
+    trace_id: String,
+    span_id: String,
+    sampled: bool,
+    format: Format,
+}
+
+impl Context {
+    /// Continues the trace of a W3C `traceparent` header, or else of the B3
+    /// headers, and starts a new one when there is none or it is malformed.
+    pub fn from_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let parent = match header("traceparent") {
+            Some(traceparent) => trace_context(traceparent),
+            None => match header("b3") {
+                Some(b3) => b3_single(b3),
+                None => b3_multi(&header),
+            },
+        };
+
+        match parent {
+            Some((trace_id, sampled, format)) => Self {
+                trace_id,
+                span_id: new_id(16),
+                sampled,
+                format,
+            },
+            None => Self {
+                trace_id: new_id(32),
+                span_id: new_id(16),
+                sampled: true,
+                format: Format::TraceContext,
+            },
+        }
+    }
+
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// The headers telling the next service that its span is a child of
+    /// the one of the Pokedex.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        match self.format {
+            Format::TraceContext => vec![(
+                "traceparent",
+                format!(
+                    "00-{}-{}-{}",
+                    self.trace_id,
+                    self.span_id,
+                    if self.sampled { "01" } else { "00" }
+                ),
+            )],
+            Format::B3Single => vec![(
+                "b3",
+                format!(
+                    "{}-{}-{}",
+                    self.trace_id,
+                    self.span_id,
+                    if self.sampled { "1" } else { "0" }
+                ),
+            )],
+            Format::B3Multi => vec![
+                ("X-B3-TraceId", self.trace_id.clone()),
+                ("X-B3-SpanId", self.span_id.clone()),
+                (
+                    "X-B3-Sampled",
+                    String::from(if self.sampled { "1" } else { "0" }),
+                ),
+            ],
+        }
+    }
+}
+
+/// Runs `f` within the trace, which `current` then returns on this thread.
+pub fn scope<T>(context: Context, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(context)));
+    let res = f();
+    CURRENT.with(|current| current.replace(previous));
+    res
+}
+
+pub fn current() -> Option<Context> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Adds the headers of the current trace, if any, to an outbound call.
+pub fn propagate(req: ureq::Request) -> ureq::Request {
+    match current() {
+        Some(context) => context
+            .headers()
+            .into_iter()
+            .fold(req, |req, (name, value)| req.set(name, &value)),
+        None => req,
+    }
+}
+
+/// `version-trace_id-parent_id-flags`, the ids being lowercase hex and not
+/// all zeros.
+fn trace_context(traceparent: &str) -> Option<(String, bool, Format)> {
+    match traceparent.trim().split('-').collect::<Vec<&str>>()[..] {
+        [version, trace_id, parent_id, flags]
+            if is_id(version, &[2])
+                && version != "ff"
+                && is_id(trace_id, &[32])
+                && is_id(parent_id, &[16])
+                && is_id(flags, &[2]) =>
+        {
+            match u8::from_str_radix(flags, 16) {
+                Ok(flags) => Some((String::from(trace_id), flags & 1 == 1, Format::TraceContext)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `trace_id-span_id`, optionally followed by the sampling decision and
+/// the parent span id. A header with only the decision carries no trace.
+fn b3_single(b3: &str) -> Option<(String, bool, Format)> {
+    match b3.trim().split('-').collect::<Vec<&str>>()[..] {
+        [trace_id, span_id, ref rest @ ..]
+            if is_id(trace_id, &[16, 32]) && is_id(span_id, &[16]) && rest.len() <= 2 =>
+        {
+            match rest.first() {
+                None | Some(&"1") | Some(&"d") => {
+                    Some((String::from(trace_id), true, Format::B3Single))
+                }
+                Some(&"0") => Some((String::from(trace_id), false, Format::B3Single)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn b3_multi<'a>(header: &impl Fn(&str) -> Option<&'a str>) -> Option<(String, bool, Format)> {
+    let trace_id = match (header("X-B3-TraceId"), header("X-B3-SpanId")) {
+        (Some(trace_id), Some(span_id)) if is_id(trace_id, &[16, 32]) && is_id(span_id, &[16]) => {
+            trace_id
+        }
+        _ => return None,
+    };
+
+    let sampled = header("X-B3-Flags") == Some("1") || header("X-B3-Sampled") != Some("0");
+    Some((String::from(trace_id), sampled, Format::B3Multi))
+}
+
+/// Lowercase hex of one of the lengths, with at least one non-zero digit
+/// for the ids.
+fn is_id(value: &str, lengths: &[usize]) -> bool {
+    lengths.contains(&value.len())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        && (value.len() == 2 || value.chars().any(|c| c != '0'))
+}
+
+/// Random enough to tell the traces apart, without a random number
+/// generator: the keys of `RandomState` are drawn for every instance.
+fn new_id(length: usize) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let span = SPANS.fetch_add(1, Ordering::Relaxed);
+
+    let mut id = String::new();
+    while id.len() < length {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(nanos);
+        hasher.write_u64(span);
+        id.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    id.truncate(length);
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(headers: &[(&'static str, &'static str)]) -> Context {
+        Context::from_headers(|name| {
+            headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value)
+        })
+    }
+
+    #[test]
+    fn it_should_continue_the_trace_of_a_traceparent_header() {
+        let context = context(&[(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )]);
+
+        assert_eq!(context.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        match &context.headers()[..] {
+            [("traceparent", value)] => {
+                assert!(value.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+                assert!(!value.contains("00f067aa0ba902b7"));
+                assert!(value.ends_with("-01"));
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_continue_the_trace_of_b3_headers_in_the_same_format() {
+        let single = context(&[("b3", "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-0")]);
+        let multi = context(&[
+            ("X-B3-TraceId", "463ac35c9f6413ad"),
+            ("X-B3-SpanId", "a2fb4a1d1a96d312"),
+        ]);
+
+        assert_eq!(single.trace_id(), "80f198ee56343ba864fe8b2a57d3eff7");
+        match &single.headers()[..] {
+            [("b3", value)] => assert!(value.ends_with("-0")),
+            _ => unreachable!(),
+        };
+        assert_eq!(multi.trace_id(), "463ac35c9f6413ad");
+        match &multi.headers()[..] {
+            [("X-B3-TraceId", trace_id), ("X-B3-SpanId", _), ("X-B3-Sampled", sampled)] => {
+                assert_eq!(trace_id, "463ac35c9f6413ad");
+                assert_eq!(sampled, "1");
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_start_a_new_trace_when_the_header_is_malformed() {
+        let context = context(&[(
+            "traceparent",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        )]);
+
+        assert_eq!(context.trace_id().len(), 32);
+        assert_ne!(context.trace_id(), "00000000000000000000000000000000");
+    }
+
+    #[test]
+    fn it_should_only_be_current_within_its_scope() {
+        let context = context(&[]);
+        let trace_id = String::from(context.trace_id());
+
+        let current = scope(context, || current().map(|c| String::from(c.trace_id())));
+
+        assert_eq!(current, Some(trace_id));
+        assert!(super::current().is_none());
+    }
+
+    #[test]
+    fn it_should_propagate_the_current_trace_to_outbound_calls() {
+        let context = context(&[(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00",
+        )]);
+
+        let req = scope(context, || propagate(ureq::get("http://localhost")));
+
+        match req.header("traceparent") {
+            Some(value) => {
+                assert!(value.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+                assert!(value.ends_with("-00"));
+            }
+            None => unreachable!(),
+        };
+        assert!(propagate(ureq::get("http://localhost"))
+            .header("traceparent")
+            .is_none());
+    }
+}
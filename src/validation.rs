@@ -0,0 +1,102 @@
+use crate::domain::fetch_all_pokemons;
+use crate::repositories::pokemon::Repository;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Whether invalid records keep the server from starting.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    Warn,
+    Strict,
+}
+
+impl TryFrom<&str> for Mode {
+    type Error = ();
+
+    fn try_from(mode: &str) -> Result<Self, Self::Error> {
+        match mode {
+            "warn" => Ok(Self::Warn),
+            "strict" => Ok(Self::Strict),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Reads every stored record into the domain value objects, as the limits
+/// given at startup define them, and logs a summary of the invalid ones,
+/// which the listing logs one by one. Tells whether the server may start.
+/// With `--read-mode strict` the first invalid record fails the listing, so
+/// the summary only tells that the records can't be read.
+pub fn run(repo: Arc<dyn Repository>, mode: Mode) -> bool {
+    let req = fetch_all_pokemons::Request {
+        sort_by: fetch_all_pokemons::SortBy::NumberAscending,
+        offset: 0,
+        limit: None,
+        class: None,
+    };
+    let res = match fetch_all_pokemons::execute(repo, req) {
+        Ok(res) => res,
+        Err(fetch_all_pokemons::Error::BadRequest) | Err(fetch_all_pokemons::Error::Unknown) => {
+            eprintln!("The stored records can't be read to be validated");
+            return mode == Mode::Warn;
+        }
+    };
+
+    if res.warnings.is_empty() {
+        eprintln!("The {} stored Pokemon(s) are valid", res.pokemons.len());
+        return true;
+    }
+
+    let mut reasons = BTreeMap::<&str, usize>::new();
+    for warning in res.warnings.iter() {
+        *reasons.entry(&warning.reason).or_default() += 1;
+    }
+    eprintln!(
+        "{} of the {} stored record(s) are invalid: {}",
+        res.warnings.len(),
+        res.pokemons.len() + res.warnings.len(),
+        reasons
+            .iter()
+            .map(|(reason, count)| format!("{} {}", count, reason))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    mode == Mode::Warn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::repositories::pokemon::{InMemoryRepository, InvalidRecord};
+
+    #[test]
+    fn it_should_refuse_to_start_on_invalid_records_only_when_strict() {
+        let repo = Arc::new(InMemoryRepository::new().with_invalid(vec![InvalidRecord {
+            number: 7,
+            reason: "invalid name",
+        }]));
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+
+        assert!(run(repo.clone(), Mode::Warn));
+        assert!(!run(repo, Mode::Strict));
+    }
+
+    #[test]
+    fn it_should_start_when_every_record_is_valid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+        )
+        .ok();
+
+        assert!(run(repo, Mode::Strict));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::domain::{fetch_type_matchups, fetch_types, validate_pokemon};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize)]
+struct Validation {
+    valid: bool,
+    errors: Vec<FieldError>,
+}
+
+#[derive(Serialize)]
+struct FieldError {
+    field: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct PokemonType {
+    name: String,
+    color: String,
+    emoji: String,
+    description: String,
+    strong_against: Vec<String>,
+    weak_against: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Matchups {
+    defending: Vec<String>,
+    rows: Vec<Row>,
+}
+
+#[derive(Serialize)]
+struct Row {
+    attacking: String,
+    multipliers: Vec<f32>,
+}
+
+/// Checks a Pokemon as the server would, such as before sending a form:
+/// `{ valid, errors: [{ field, message }] }`.
+#[wasm_bindgen(js_name = validatePokemon)]
+pub fn validate_pokemon(number: u16, name: String, types: Vec<String>) -> JsValue {
+    let validate_pokemon::Response { errors } =
+        validate_pokemon::execute(validate_pokemon::Request {
+            number,
+            name,
+            types,
+        });
+    to_js(&Validation {
+        valid: errors.is_empty(),
+        errors: errors
+            .into_iter()
+            .map(|e| FieldError {
+                field: e.field,
+                message: e.message,
+            })
+            .collect(),
+    })
+}
+
+/// The types of a generation, of the latest one when none is given, as
+/// `GET /types` returns them.
+#[wasm_bindgen(js_name = fetchTypes)]
+pub fn fetch_types(generation: Option<u8>) -> Result<JsValue, JsError> {
+    match fetch_types::execute(fetch_types::Request { generation }) {
+        Ok(types) => Ok(to_js(
+            &types
+                .into_iter()
+                .map(|t| PokemonType {
+                    name: t.name,
+                    color: t.color,
+                    emoji: t.emoji,
+                    description: t.description,
+                    strong_against: t.strong_against,
+                    weak_against: t.weak_against,
+                })
+                .collect::<Vec<PokemonType>>(),
+        )),
+        Err(fetch_types::Error::BadRequest) => Err(JsError::new("unknown generation")),
+    }
+}
+
+/// The multipliers of the type chart, `attacking` and `defending` narrowing
+/// it down as in the CLI: `{ defending, rows: [{ attacking, multipliers }] }`.
+#[wasm_bindgen(js_name = fetchTypeMatchups)]
+pub fn fetch_type_matchups(
+    attacking: Option<String>,
+    defending: Option<Vec<String>>,
+    generation: Option<u8>,
+) -> Result<JsValue, JsError> {
+    let req = fetch_type_matchups::Request {
+        attacking,
+        defending,
+        generation,
+    };
+    match fetch_type_matchups::execute(req) {
+        Ok(res) => Ok(to_js(&Matchups {
+            defending: res.defending,
+            rows: res
+                .rows
+                .into_iter()
+                .map(|row| Row {
+                    attacking: row.attacking,
+                    multipliers: row.multipliers,
+                })
+                .collect(),
+        })),
+        Err(fetch_type_matchups::Error::BadRequest) => Err(JsError::new(
+            "unknown generation, or type which isn't one of it",
+        )),
+    }
+}
+
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    match serde_json::to_string(value).map(|json| js_sys::JSON::parse(&json)) {
+        Ok(Ok(value)) => value,
+        _ => JsValue::UNDEFINED,
+    }
+}